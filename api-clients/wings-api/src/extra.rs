@@ -344,6 +344,9 @@ pub enum ScheduleActionInner {
         backup_group_uuid: Option<uuid::Uuid>,
         #[garde(skip)]
         ignored_files: Vec<compact_str::CompactString>,
+        #[garde(skip)]
+        #[serde(default)]
+        include_databases: bool,
 
         #[garde(dive)]
         #[serde(default)]
@@ -380,6 +383,19 @@ pub enum ScheduleActionInner {
         #[serde(default)]
         backup_group_uuid: Option<uuid::Uuid>,
     },
+    CreateDatabaseInstanceSnapshot {
+        #[garde(skip)]
+        ignore_failure: bool,
+
+        #[garde(skip)]
+        database_instance_uuid: uuid::Uuid,
+        #[garde(dive)]
+        name: Option<ScheduleDynamicParameter>,
+
+        #[garde(dive)]
+        #[serde(default)]
+        output_into: Option<ScheduleVariable>,
+    },
     CreateDirectory {
         #[garde(skip)]
         ignore_failure: bool,
@@ -513,41 +529,48 @@ pub enum ScheduleActionInner {
 }
 
 impl ScheduleActionInner {
-    pub fn permission(&self) -> Option<&'static str> {
+    pub fn permissions(&self) -> &'static [&'static str] {
         match self {
-            ScheduleActionInner::Sleep { .. } => None,
-            ScheduleActionInner::Ensure { .. } => None,
-            ScheduleActionInner::If { .. } => None,
-            ScheduleActionInner::ElseIf { .. } => None,
-            ScheduleActionInner::Else => None,
-            ScheduleActionInner::EndIf => None,
-            ScheduleActionInner::Exit { .. } => None,
-            ScheduleActionInner::WaitForState { .. } => None,
-            ScheduleActionInner::Format { .. } => None,
-            ScheduleActionInner::MatchRegex { .. } => None,
-            ScheduleActionInner::WaitForConsoleLine { .. } => Some("control.read-console"),
+            ScheduleActionInner::Sleep { .. } => &[],
+            ScheduleActionInner::Ensure { .. } => &[],
+            ScheduleActionInner::If { .. } => &[],
+            ScheduleActionInner::ElseIf { .. } => &[],
+            ScheduleActionInner::Else => &[],
+            ScheduleActionInner::EndIf => &[],
+            ScheduleActionInner::Exit { .. } => &[],
+            ScheduleActionInner::WaitForState { .. } => &[],
+            ScheduleActionInner::Format { .. } => &[],
+            ScheduleActionInner::MatchRegex { .. } => &[],
+            ScheduleActionInner::WaitForConsoleLine { .. } => &["control.read-console"],
             ScheduleActionInner::SendPower { action, .. } => match action {
-                super::ServerPowerAction::Start => Some("control.start"),
-                super::ServerPowerAction::Stop => Some("control.stop"),
-                super::ServerPowerAction::Restart => Some("control.restart"),
-                super::ServerPowerAction::Kill => Some("control.stop"),
+                super::ServerPowerAction::Start => &["control.start"],
+                super::ServerPowerAction::Stop => &["control.stop"],
+                super::ServerPowerAction::Restart => &["control.restart"],
+                super::ServerPowerAction::Kill => &["control.stop"],
             },
-            ScheduleActionInner::SendCommand { .. } => Some("control.console"),
-            ScheduleActionInner::CreateBackup { .. } => Some("backups.create"),
-            ScheduleActionInner::RestoreBackup { .. } => Some("backups.restore"),
-            ScheduleActionInner::DeleteBackup { .. } => Some("backups.delete"),
-            ScheduleActionInner::MoveBackup { .. } => Some("backups.update"),
-            ScheduleActionInner::CreateDirectory { .. } => Some("files.create"),
-            ScheduleActionInner::WriteFile { .. } => Some("files.update"),
-            ScheduleActionInner::CopyFile { .. } => Some("files.update"),
-            ScheduleActionInner::DeleteFiles { .. } => Some("files.delete"),
-            ScheduleActionInner::RenameFiles { .. } => Some("files.update"),
-            ScheduleActionInner::CompressFiles { .. } => Some("files.archive"),
-            ScheduleActionInner::DecompressFile { .. } => Some("files.archive"),
-            ScheduleActionInner::UpdateStartupVariable { .. } => Some("startup.update"),
-            ScheduleActionInner::UpdateStartupCommand { .. } => Some("startup.command"),
-            ScheduleActionInner::UpdateStartupDockerImage { .. } => Some("startup.docker-image"),
-            ScheduleActionInner::HttpRequest { .. } => None,
+            ScheduleActionInner::SendCommand { .. } => &["control.console"],
+            ScheduleActionInner::CreateBackup {
+                include_databases: true,
+                ..
+            } => &["backups.create", "databases.read"],
+            ScheduleActionInner::CreateBackup { .. } => &["backups.create"],
+            ScheduleActionInner::RestoreBackup { .. } => &["backups.restore"],
+            ScheduleActionInner::DeleteBackup { .. } => &["backups.delete"],
+            ScheduleActionInner::MoveBackup { .. } => &["backups.update"],
+            ScheduleActionInner::CreateDatabaseInstanceSnapshot { .. } => {
+                &["database-instances.snapshots"]
+            }
+            ScheduleActionInner::CreateDirectory { .. } => &["files.create"],
+            ScheduleActionInner::WriteFile { .. } => &["files.update"],
+            ScheduleActionInner::CopyFile { .. } => &["files.update"],
+            ScheduleActionInner::DeleteFiles { .. } => &["files.delete"],
+            ScheduleActionInner::RenameFiles { .. } => &["files.update"],
+            ScheduleActionInner::CompressFiles { .. } => &["files.archive"],
+            ScheduleActionInner::DecompressFile { .. } => &["files.archive"],
+            ScheduleActionInner::UpdateStartupVariable { .. } => &["startup.update"],
+            ScheduleActionInner::UpdateStartupCommand { .. } => &["startup.command"],
+            ScheduleActionInner::UpdateStartupDockerImage { .. } => &["startup.docker-image"],
+            ScheduleActionInner::HttpRequest { .. } => &[],
         }
     }
 }
@@ -789,3 +812,186 @@ pub fn strip_config_paths(value: &mut serde_json::Value) {
         }
     }
 }
+
+// the file trash endpoints are not part of the wings openapi specification yet, so they live
+// here instead of the generated client
+
+/// Sends a msgpack `body` to `endpoint` the same way the generated client does.
+async fn request_msgpack<T: serde::de::DeserializeOwned>(
+    client: &super::client::WingsClient,
+    method: reqwest::Method,
+    endpoint: impl AsRef<str>,
+    body: &impl Serialize,
+) -> Result<T, super::client::ApiHttpError> {
+    let mut bytes = Vec::new();
+    let mut se = rmp_serde::Serializer::new(&mut bytes)
+        .with_struct_map()
+        .with_human_readable();
+    body.serialize(&mut se)
+        .map_err(super::client::ApiHttpError::MsgpackEncode)?;
+
+    let response = client
+        .request_raw(method, endpoint)
+        .header("Accept", "application/msgpack")
+        .header("Content-Type", "application/msgpack")
+        .body(bytes)
+        .send()
+        .await
+        .map_err(super::client::ApiHttpError::Reqwest)?;
+
+    let status = response.status();
+    let data = response
+        .bytes()
+        .await
+        .map_err(super::client::ApiHttpError::Reqwest)?;
+    let mut de = rmp_serde::Deserializer::new(data.as_ref()).with_human_readable();
+
+    if status.is_success() {
+        T::deserialize(&mut de).map_err(super::client::ApiHttpError::MsgpackDecode)
+    } else {
+        Err(super::client::ApiHttpError::Http(
+            status,
+            super::ApiError::deserialize(&mut de).unwrap_or_else(|err| super::ApiError {
+                error: err.to_string().into(),
+            }),
+        ))
+    }
+}
+
+impl super::client::WingsClient {
+    pub async fn post_servers_server_files_trash(
+        &self,
+        server: uuid::Uuid,
+        data: &servers_server_files_trash::post::RequestBody,
+    ) -> Result<servers_server_files_trash::post::Response, super::client::ApiHttpError> {
+        request_msgpack(
+            self,
+            reqwest::Method::POST,
+            format!("/api/servers/{server}/files/trash"),
+            data,
+        )
+        .await
+    }
+
+    pub async fn post_servers_server_files_trash_purge(
+        &self,
+        server: uuid::Uuid,
+        data: &servers_server_files_trash_purge::post::RequestBody,
+    ) -> Result<servers_server_files_trash_purge::post::Response, super::client::ApiHttpError> {
+        request_msgpack(
+            self,
+            reqwest::Method::POST,
+            format!("/api/servers/{server}/files/trash/purge"),
+            data,
+        )
+        .await
+    }
+
+    pub async fn post_servers_server_files_trash_restore(
+        &self,
+        server: uuid::Uuid,
+        data: &servers_server_files_trash_restore::post::RequestBody,
+    ) -> Result<servers_server_files_trash_restore::post::Response, super::client::ApiHttpError>
+    {
+        request_msgpack(
+            self,
+            reqwest::Method::POST,
+            format!("/api/servers/{server}/files/trash/restore"),
+            data,
+        )
+        .await
+    }
+}
+
+pub mod servers_server_files_trash {
+    use crate::*;
+
+    pub mod post {
+        use super::*;
+
+        nestify::nest! {
+            #[derive(Debug, ToSchema, Deserialize, Serialize, Clone)] pub struct RequestBody {
+                #[schema(inline)]
+                pub root: compact_str::CompactString,
+                #[schema(inline)]
+                pub files: Vec<compact_str::CompactString>,
+            }
+        }
+
+        nestify::nest! {
+            #[derive(Debug, ToSchema, Deserialize, Serialize, Clone)] pub struct Response200 {
+                #[schema(inline)]
+                pub trashed: Vec<#[derive(Debug, ToSchema, Deserialize, Serialize, Clone)] pub struct Response200Trashed {
+                    #[schema(inline)]
+                    pub id: uuid::Uuid,
+                    #[schema(inline)]
+                    pub path: compact_str::CompactString,
+                    #[schema(inline)]
+                    pub directory: bool,
+                    #[schema(inline)]
+                    pub size: u64,
+                }>,
+            }
+        }
+
+        pub type Response404 = ApiError;
+
+        pub type Response417 = ApiError;
+
+        pub type Response = Response200;
+    }
+}
+pub mod servers_server_files_trash_purge {
+    use crate::*;
+
+    pub mod post {
+        use super::*;
+
+        nestify::nest! {
+            #[derive(Debug, ToSchema, Deserialize, Serialize, Clone)] pub struct RequestBody {
+                #[schema(inline)]
+                pub ids: Vec<uuid::Uuid>,
+            }
+        }
+
+        nestify::nest! {
+            #[derive(Debug, ToSchema, Deserialize, Serialize, Clone)] pub struct Response200 {
+                #[schema(inline)]
+                pub purged: u64,
+            }
+        }
+
+        pub type Response404 = ApiError;
+
+        pub type Response = Response200;
+    }
+}
+pub mod servers_server_files_trash_restore {
+    use crate::*;
+
+    pub mod post {
+        use super::*;
+
+        nestify::nest! {
+            #[derive(Debug, ToSchema, Deserialize, Serialize, Clone)] pub struct RequestBody {
+                #[schema(inline)]
+                pub ids: Vec<uuid::Uuid>,
+            }
+        }
+
+        nestify::nest! {
+            #[derive(Debug, ToSchema, Deserialize, Serialize, Clone)] pub struct Response200 {
+                #[schema(inline)]
+                pub restored: Vec<uuid::Uuid>,
+            }
+        }
+
+        pub type Response404 = ApiError;
+
+        pub type Response409 = ApiError;
+
+        pub type Response417 = ApiError;
+
+        pub type Response = Response200;
+    }
+}
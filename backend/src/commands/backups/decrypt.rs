@@ -0,0 +1,83 @@
+use clap::{Args, FromArgMatches};
+use colored::Colorize;
+use shared::models::server_backup::encryption::{
+    ArchiveCipher, BackupDecryptor, BackupEncryptionKey,
+};
+use std::path::PathBuf;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[derive(Args)]
+pub struct DecryptArgs {
+    #[arg(help = "the encrypted backup archive to decrypt")]
+    input: PathBuf,
+    #[arg(help = "where to write the decrypted archive to")]
+    output: PathBuf,
+
+    #[arg(
+        long = "key",
+        help = "the base64 backup encryption key exported from the server backups page"
+    )]
+    key: String,
+}
+
+pub struct DecryptCommand;
+
+impl shared::extensions::commands::CliCommand<DecryptArgs> for DecryptCommand {
+    fn get_command(&self, command: clap::Command) -> clap::Command {
+        command
+    }
+
+    fn get_executor(self) -> Box<shared::extensions::commands::ExecutorFunc> {
+        Box::new(|_env, arg_matches| {
+            Box::pin(async move {
+                let args = DecryptArgs::from_arg_matches(&arg_matches)?;
+
+                let key = match BackupEncryptionKey::from_base64(&args.key) {
+                    Ok(key) => key,
+                    Err(err) => {
+                        eprintln!("{} {}", "invalid backup encryption key:".red(), err);
+                        return Ok(1);
+                    }
+                };
+
+                let mut input = tokio::fs::File::open(&args.input).await?;
+                let mut output = tokio::fs::File::create(&args.output).await?;
+                let mut decryptor = BackupDecryptor::new(&key);
+
+                let mut buffer = vec![0; shared::BUFFER_SIZE];
+                loop {
+                    let read = input.read(&mut buffer).await?;
+                    let decrypted = match read {
+                        0 => decryptor.finish(),
+                        read => decryptor.update(&buffer[..read]),
+                    };
+
+                    match decrypted {
+                        Ok(decrypted) => output.write_all(&decrypted).await?,
+                        Err(err) => {
+                            drop(output);
+                            tokio::fs::remove_file(&args.output).await.ok();
+
+                            eprintln!("{} {}", "failed to decrypt backup:".red(), err);
+                            return Ok(1);
+                        }
+                    }
+
+                    if read == 0 {
+                        break;
+                    }
+                }
+
+                output.flush().await?;
+
+                println!(
+                    "{} {}",
+                    "decrypted backup to".green(),
+                    args.output.display().to_string().cyan()
+                );
+
+                Ok(0)
+            })
+        })
+    }
+}
@@ -1,7 +1,13 @@
 use shared::extensions::commands::CliCommandGroupBuilder;
 
+mod decrypt;
 mod s3;
 
 pub fn commands(cli: CliCommandGroupBuilder) -> CliCommandGroupBuilder {
-    cli.add_group("s3", "Manage S3 backups within the Panel.", s3::commands)
+    cli.add_command(
+        "decrypt",
+        "Decrypts a downloaded encrypted backup archive.",
+        decrypt::DecryptCommand,
+    )
+    .add_group("s3", "Manage S3 backups within the Panel.", s3::commands)
 }
@@ -3,8 +3,10 @@ use colored::Colorize;
 use compact_str::ToCompactString;
 use dialoguer::{Input, theme::ColorfulTheme};
 use shared::models::{
-    BaseModel, ByUuid, backup_configuration::BackupConfiguration, server::Server,
-    server_backup::ServerBackup,
+    BaseModel, ByUuid,
+    backup_configuration::BackupConfiguration,
+    server::Server,
+    server_backup::{ServerBackup, ServerBackupRelations},
 };
 use std::io::IsTerminal;
 
@@ -207,7 +209,11 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                         if args.json {
                             imported_backups.push(
                                 backup
-                                    .into_admin_node_api_object(&state, &storage_url_retriever)
+                                    .into_admin_node_api_object(
+                                        &state,
+                                        &storage_url_retriever,
+                                        &ServerBackupRelations::default(),
+                                    )
                                     .await?,
                             );
                         }
@@ -441,6 +441,7 @@ impl shared::extensions::commands::CliCommand<PelicanArgs> for PelicanCommand {
                                     .ok()
                                     .and_then(|v| v.parse::<u64>().ok())
                                     .unwrap_or(1024 * 1024 * 1024),
+                                encryption_enabled: false,
                             });
 
                         backup_configs.encrypt(&database).await?;
@@ -1713,6 +1714,7 @@ impl shared::extensions::commands::CliCommand<PelicanArgs> for PelicanCommand {
                                             .collect::<Vec<_>>(),
                                         foreground: true,
                                         ignore_failure: continue_on_failure,
+                                        include_databases: false,
                                         output_into: None,
                                     })
                                 }
@@ -392,6 +392,7 @@ impl shared::extensions::commands::CliCommand<PterodactylArgs> for PterodactylCo
                                     .ok()
                                     .and_then(|v| v.parse::<u64>().ok())
                                     .unwrap_or(1024 * 1024 * 1024),
+                                encryption_enabled: false,
                             });
 
                         backup_configs.encrypt(&database).await?;
@@ -1600,6 +1601,7 @@ impl shared::extensions::commands::CliCommand<PterodactylArgs> for PterodactylCo
                                             .collect::<Vec<_>>(),
                                         foreground: true,
                                         ignore_failure: continue_on_failure,
+                                        include_databases: false,
                                         output_into: None,
                                     })
                                 }
@@ -304,6 +304,7 @@ impl shared::extensions::commands::CliCommand<CreateArgs> for CreateCommand {
                     description: description.map(|d| d.into()),
                     deployment_enabled,
                     maintenance_enabled,
+                    file_scan_policy: None,
                     public_url: public_url.map(|u| u.into()),
                     url: url.into(),
                     sftp_host: sftp_host.map(|h| h.into()),
@@ -1,7 +1,9 @@
 use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
+mod pin;
 mod update;
+mod versions;
 
 mod get {
     use crate::routes::api::admin::database_agent_templates::_database_agent_template_::GetDatabaseAgentTemplate;
@@ -88,5 +90,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .routes(routes!(get::route))
         .nest("/update", update::router(state))
+        .nest("/pin", pin::router(state))
+        .nest("/versions", versions::router(state))
         .with_state(state.clone())
 }
@@ -0,0 +1,117 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::admin::database_agent_templates::_database_agent_template_::GetDatabaseAgentTemplate;
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            admin_activity::GetAdminActivityLogger,
+            database_agent_template_revision::DatabaseAgentTemplateRevision,
+            server_database_instance::ServerDatabaseInstance, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use std::collections::HashSet;
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(length(min = 1, max = 100))]
+        #[schema(min_items = 1, max_items = 100)]
+        uuids: HashSet<uuid::Uuid>,
+        /// The revision to pin the instances to, or `null` to have them follow the latest
+        /// revision again.
+        #[garde(skip)]
+        version: Option<i32>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        pinned: usize,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "database_agent_template" = uuid::Uuid,
+            description = "The database agent template ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_agent_template: GetDatabaseAgentTemplate,
+        activity_logger: GetAdminActivityLogger,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("database-agent-templates.update")?;
+
+        if let Some(version) = data.version
+            && DatabaseAgentTemplateRevision::resolve_template(
+                &state.database,
+                &database_agent_template,
+                version,
+            )
+            .await?
+            .is_none()
+        {
+            return ApiResponse::error("database agent template revision not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .ok();
+        }
+
+        let mut pinned = 0;
+
+        for instance_uuid in data.uuids {
+            let mut instance = match ServerDatabaseInstance::by_database_agent_template_uuid_uuid(
+                &state.database,
+                database_agent_template.uuid,
+                instance_uuid,
+            )
+            .await?
+            {
+                Some(instance) => instance,
+                None => continue,
+            };
+
+            instance
+                .set_pinned_template_version(&state.database, data.version)
+                .await?;
+            pinned += 1;
+
+            activity_logger
+                .log(
+                    "database-agent-template:instance.pin",
+                    serde_json::json!({
+                        "uuid": instance.uuid,
+                        "template_uuid": database_agent_template.uuid,
+                        "name": instance.name,
+                        "version": data.version,
+                    }),
+                )
+                .await;
+        }
+
+        ApiResponse::new_serialized(Response { pinned }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
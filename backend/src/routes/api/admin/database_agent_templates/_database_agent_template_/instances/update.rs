@@ -10,6 +10,7 @@ mod post {
         ApiError, GetState,
         models::{
             admin_activity::GetAdminActivityLogger,
+            database_agent_template_revision::DatabaseAgentTemplateRevision,
             server_database_instance::ServerDatabaseInstance, user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
@@ -60,8 +61,6 @@ mod post {
             .lock("database_agent_hosts::deployment", Some(30), Some(5))
             .await?;
 
-        let version = database_agent_template.version;
-
         let update_instance = async |instance_uuid: uuid::Uuid| {
             let mut instance = match ServerDatabaseInstance::by_database_agent_template_uuid_uuid(
                 &state.database,
@@ -81,48 +80,18 @@ mod post {
                 return Ok(false);
             }
 
-            let spec = match instance.resolve_spec(Some(&database_agent_template)) {
-                Some(spec) => spec,
+            let template = match DatabaseAgentTemplateRevision::resolve_template(
+                &state.database,
+                &database_agent_template,
+                instance.target_template_version(&database_agent_template),
+            )
+            .await?
+            {
+                Some(template) => template,
                 None => return Ok(false),
             };
 
-            let timezone = instance
-                .server
-                .fetch_cached(&state.database)
-                .await?
-                .timezone
-                .clone();
-
-            let patch_result = async {
-                instance
-                    .database_agent_host
-                    .api_client(&state.database)
-                    .await?
-                    .patch_instances_instance(
-                        instance.uuid,
-                        &db_agent_api::instances_instance::patch::RequestBody {
-                            suspended: None,
-                            memory: Some(spec.memory),
-                            swap: Some(spec.swap),
-                            disk: Some(spec.disk),
-                            io_weight: spec.io_weight.map(i64::from),
-                            cpu: Some(spec.cpu as i64),
-                            image: Some(spec.image),
-                            image_uid: spec.image_uid.map(|uid| uid as u32),
-                            image_gid: spec.image_gid.map(|gid| gid as u32),
-                            volumes: spec.volumes,
-                            socket_path: spec.socket_path,
-                            timezone,
-                            env: Some(spec.env),
-                            cmd: spec.cmd,
-                        },
-                    )
-                    .await?;
-
-                Ok::<_, anyhow::Error>(())
-            };
-
-            if let Err(err) = patch_result.await {
+            if let Err(err) = instance.apply_template(&state, &template).await {
                 tracing::warn!(
                     instance = %instance.uuid,
                     template = %database_agent_template.uuid,
@@ -133,10 +102,6 @@ mod post {
                 return Ok(false);
             }
 
-            instance
-                .set_template_version(&state.database, version)
-                .await?;
-
             activity_logger
                 .log(
                     "database-agent-template:instance.update",
@@ -144,7 +109,7 @@ mod post {
                         "uuid": instance.uuid,
                         "template_uuid": database_agent_template.uuid,
                         "name": instance.name,
-                        "version": version,
+                        "version": template.version,
                     }),
                 )
                 .await;
@@ -173,7 +138,7 @@ mod post {
                     }
 
                     for instance in instances.data {
-                        if instance.template_version.is_some_and(|v| v < version) {
+                        if instance.update_available(Some(&database_agent_template)) {
                             instance_uuids.push(instance.uuid);
                         }
                     }
@@ -0,0 +1,59 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use crate::routes::api::admin::database_agent_templates::_database_agent_template_::GetDatabaseAgentTemplate;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server_database_instance::{ServerDatabaseInstance, TemplateVersionUsage},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        latest_version: i32,
+        versions: Vec<TemplateVersionUsage>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "database_agent_template" = uuid::Uuid,
+            description = "The database agent template ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_agent_template: GetDatabaseAgentTemplate,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("database-agent-templates.read")?;
+
+        let versions =
+            ServerDatabaseInstance::template_version_usage_by_database_agent_template_uuid(
+                &state.database,
+                database_agent_template.uuid,
+            )
+            .await?;
+
+        ApiResponse::new_serialized(Response {
+            latest_version: database_agent_template.version,
+            versions,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
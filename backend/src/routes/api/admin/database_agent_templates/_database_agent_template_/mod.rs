@@ -14,6 +14,8 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod duplicate;
 mod instances;
+mod revisions;
+mod rollouts;
 
 pub type GetDatabaseAgentTemplate = shared::extract::ConsumingExtension<DatabaseAgentTemplate>;
 
@@ -221,6 +223,8 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .routes(routes!(patch::route))
         .nest("/duplicate", duplicate::router(state))
         .nest("/instances", instances::router(state))
+        .nest("/revisions", revisions::router(state))
+        .nest("/rollouts", rollouts::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
         .with_state(state.clone())
 }
@@ -0,0 +1,88 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use crate::routes::api::admin::database_agent_templates::_database_agent_template_::GetDatabaseAgentTemplate;
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoAdminApiObject, Pagination, PaginationParamsWithSearch,
+            database_agent_template_revision::DatabaseAgentTemplateRevision,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        revisions: Pagination<
+            shared::models::database_agent_template_revision::AdminApiDatabaseAgentTemplateRevision,
+        >,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "database_agent_template" = uuid::Uuid,
+            description = "The database agent template ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_agent_template: GetDatabaseAgentTemplate,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("database-agent-templates.read")?;
+
+        let revisions =
+            DatabaseAgentTemplateRevision::by_database_agent_template_uuid_with_pagination(
+                &state.database,
+                database_agent_template.uuid,
+                params.page,
+                params.per_page,
+                params.search.as_deref(),
+            )
+            .await?;
+
+        ApiResponse::new_serialized(Response {
+            revisions: revisions
+                .try_async_map(|revision| revision.into_admin_api_object(&state, ()))
+                .await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,71 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use super::super::GetDatabaseAgentTemplateRollout;
+    use crate::routes::api::admin::database_agent_templates::_database_agent_template_::GetDatabaseAgentTemplate;
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{admin_activity::GetAdminActivityLogger, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "database_agent_template" = uuid::Uuid,
+            description = "The database agent template ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "rollout" = uuid::Uuid,
+            description = "The rollout ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_agent_template: GetDatabaseAgentTemplate,
+        activity_logger: GetAdminActivityLogger,
+        mut rollout: GetDatabaseAgentTemplateRollout,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("database-agent-templates.update")?;
+
+        if !rollout.cancel(&state.database).await? {
+            return ApiResponse::error("rollout has already finished")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        activity_logger
+            .log(
+                "database-agent-template:rollout.cancel",
+                serde_json::json!({
+                    "uuid": rollout.uuid,
+                    "template_uuid": database_agent_template.uuid,
+
+                    "target_version": rollout.target_version,
+                    "updated": rollout.updated,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,116 @@
+use super::State;
+use axum::{
+    extract::{Path, Request},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::{
+    GetState,
+    models::{
+        database_agent_template_rollout::DatabaseAgentTemplateRollout, user::GetPermissionManager,
+    },
+    response::ApiResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod cancel;
+
+pub type GetDatabaseAgentTemplateRollout =
+    shared::extract::ConsumingExtension<DatabaseAgentTemplateRollout>;
+
+pub async fn auth(
+    state: GetState,
+    permissions: GetPermissionManager,
+    database_agent_template: crate::routes::api::admin::database_agent_templates::_database_agent_template_::GetDatabaseAgentTemplate,
+    Path(rollout): Path<Vec<String>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let rollout = match rollout.get(1).map(|s| s.parse::<uuid::Uuid>()) {
+        Some(Ok(id)) => id,
+        _ => {
+            return Ok(ApiResponse::error("invalid rollout uuid")
+                .with_status(StatusCode::BAD_REQUEST)
+                .into_response());
+        }
+    };
+
+    if let Err(err) = permissions.has_admin_permission("database-agent-templates.read") {
+        return Ok(err.into_response());
+    }
+
+    let rollout = DatabaseAgentTemplateRollout::by_database_agent_template_uuid_uuid(
+        &state.database,
+        database_agent_template.uuid,
+        rollout,
+    )
+    .await;
+    let rollout = match rollout {
+        Ok(Some(rollout)) => rollout,
+        Ok(None) => {
+            return Ok(ApiResponse::error("rollout not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .into_response());
+        }
+        Err(err) => return Ok(ApiResponse::from(err).into_response()),
+    };
+
+    req.extensions_mut().insert(database_agent_template.0);
+    req.extensions_mut().insert(rollout);
+
+    Ok(next.run(req).await)
+}
+
+mod get {
+    use super::GetDatabaseAgentTemplateRollout;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{IntoAdminApiObject, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        rollout:
+            shared::models::database_agent_template_rollout::AdminApiDatabaseAgentTemplateRollout,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "database_agent_template" = uuid::Uuid,
+            description = "The database agent template ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "rollout" = uuid::Uuid,
+            description = "The rollout ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        rollout: GetDatabaseAgentTemplateRollout,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("database-agent-templates.read")?;
+
+        ApiResponse::new_serialized(Response {
+            rollout: rollout.0.into_admin_api_object(&state, ()).await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .nest("/cancel", cancel::router(state))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
+        .with_state(state.clone())
+}
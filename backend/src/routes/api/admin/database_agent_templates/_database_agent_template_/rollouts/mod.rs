@@ -0,0 +1,228 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _rollout_;
+
+mod get {
+    use crate::routes::api::admin::database_agent_templates::_database_agent_template_::GetDatabaseAgentTemplate;
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoAdminApiObject, Pagination, PaginationParams,
+            database_agent_template_rollout::DatabaseAgentTemplateRollout,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        rollouts: Pagination<
+            shared::models::database_agent_template_rollout::AdminApiDatabaseAgentTemplateRollout,
+        >,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "database_agent_template" = uuid::Uuid,
+            description = "The database agent template ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_agent_template: GetDatabaseAgentTemplate,
+        Query(params): Query<PaginationParams>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("database-agent-templates.read")?;
+
+        let rollouts =
+            DatabaseAgentTemplateRollout::by_database_agent_template_uuid_with_pagination(
+                &state.database,
+                database_agent_template.uuid,
+                params.page,
+                params.per_page,
+            )
+            .await?;
+
+        ApiResponse::new_serialized(Response {
+            rollouts: rollouts
+                .try_async_map(|rollout| rollout.into_admin_api_object(&state, ()))
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use crate::routes::api::admin::database_agent_templates::_database_agent_template_::GetDatabaseAgentTemplate;
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel, IntoAdminApiObject,
+            admin_activity::GetAdminActivityLogger,
+            database_agent_template_revision::DatabaseAgentTemplateRevision,
+            database_agent_template_rollout::{
+                CreateDatabaseAgentTemplateRolloutOptions, DatabaseAgentTemplateRollout,
+            },
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        /// The revision to roll out, defaults to the latest revision of the template.
+        #[garde(inner(range(min = 1)))]
+        #[schema(minimum = 1)]
+        target_version: Option<i32>,
+        #[garde(range(min = 1, max = 50))]
+        #[schema(minimum = 1, maximum = 50)]
+        batch_size: i32,
+        #[garde(skip)]
+        halt_on_failure: bool,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        rollout:
+            shared::models::database_agent_template_rollout::AdminApiDatabaseAgentTemplateRollout,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "database_agent_template" = uuid::Uuid,
+            description = "The database agent template ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_agent_template: GetDatabaseAgentTemplate,
+        activity_logger: GetAdminActivityLogger,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("database-agent-templates.update")?;
+
+        let target_version = data
+            .target_version
+            .unwrap_or(database_agent_template.version);
+
+        if DatabaseAgentTemplateRevision::resolve_template(
+            &state.database,
+            &database_agent_template,
+            target_version,
+        )
+        .await?
+        .is_none()
+        {
+            return ApiResponse::error("database agent template revision not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .ok();
+        }
+
+        let rollout_lock = state
+            .cache
+            .lock(
+                format!(
+                    "database_agent_templates::{}::rollout",
+                    database_agent_template.uuid
+                ),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        if DatabaseAgentTemplateRollout::count_active_by_database_agent_template_uuid(
+            &state.database,
+            database_agent_template.uuid,
+        )
+        .await?
+            > 0
+        {
+            return ApiResponse::error("database agent template already has an active rollout")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        let rollout = DatabaseAgentTemplateRollout::create(
+            &state,
+            CreateDatabaseAgentTemplateRolloutOptions {
+                database_agent_template_uuid: database_agent_template.uuid,
+                target_version,
+                batch_size: data.batch_size,
+                halt_on_failure: data.halt_on_failure,
+            },
+        )
+        .await?;
+
+        drop(rollout_lock);
+
+        activity_logger
+            .log(
+                "database-agent-template:rollout.create",
+                serde_json::json!({
+                    "uuid": rollout.uuid,
+                    "template_uuid": database_agent_template.uuid,
+
+                    "target_version": rollout.target_version,
+                    "batch_size": rollout.batch_size,
+                    "halt_on_failure": rollout.halt_on_failure,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {
+            rollout: rollout.into_admin_api_object(&state, ()).await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{rollout}", _rollout_::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,145 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use super::super::GetServerDatabase;
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            ByUuid, CreatableModel, IntoAdminApiObject,
+            admin_activity::GetAdminActivityLogger,
+            database_host::DatabaseHost,
+            server_database_migration::{
+                CreateServerDatabaseMigrationOptions, ServerDatabaseMigration,
+            },
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Payload {
+        target_database_host_uuid: uuid::Uuid,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        migration: shared::models::server_database_migration::AdminApiServerDatabaseMigration,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "database_host" = uuid::Uuid,
+            description = "The database host ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_host: crate::routes::api::admin::database_hosts::_database_host_::GetDatabaseHost,
+        activity_logger: GetAdminActivityLogger,
+        database: GetServerDatabase,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("database-hosts.update")?;
+
+        let target_database_host =
+            match DatabaseHost::by_uuid_optional(&state.database, data.target_database_host_uuid)
+                .await?
+            {
+                Some(target_database_host) => target_database_host,
+                None => {
+                    return ApiResponse::error("target database host not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        if target_database_host.uuid == database_host.uuid {
+            return ApiResponse::error("database is already on the target database host")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+        if target_database_host.r#type != database_host.r#type {
+            return ApiResponse::error("target database host has a different database type")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+        if !target_database_host.is_healthy() {
+            return ApiResponse::error("target database host is unreachable")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        let migration_lock = state
+            .cache
+            .lock(
+                format!("server_databases::{}::migration", database.uuid),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        if ServerDatabaseMigration::active_server_database_uuids(&state.database)
+            .await?
+            .contains(&database.uuid)
+        {
+            return ApiResponse::error("database is already being migrated")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        let migration = ServerDatabaseMigration::create(
+            &state,
+            CreateServerDatabaseMigrationOptions {
+                server_database_uuid: database.uuid,
+                source_database_host_uuid: database_host.uuid,
+                target_database_host_uuid: target_database_host.uuid,
+                name: database.name.clone(),
+            },
+        )
+        .await?;
+
+        drop(migration_lock);
+
+        activity_logger
+            .log(
+                "database-host:database.migrate",
+                serde_json::json!({
+                    "uuid": database.uuid,
+                    "database_host_uuid": database_host.uuid,
+                    "target_database_host_uuid": target_database_host.uuid,
+
+                    "name": database.name,
+                    "migration": migration.uuid,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {
+            migration: migration.into_admin_api_object(&state, ()).await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -12,6 +12,8 @@ use shared::{
 };
 use utoipa_axum::{router::OpenApiRouter, routes};
 
+mod migrate;
+
 pub type GetServerDatabase = shared::extract::ConsumingExtension<ServerDatabase>;
 
 pub async fn auth(
@@ -54,6 +56,89 @@ pub async fn auth(
     Ok(next.run(req).await)
 }
 
+mod patch {
+    use super::GetServerDatabase;
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{admin_activity::GetAdminActivityLogger, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "::serde_with::rust::double_option"
+        )]
+        #[garde(inner(inner(range(min = 1))))]
+        #[schema(minimum = 1)]
+        size_limit: Option<Option<u64>>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "database_host" = uuid::Uuid,
+            description = "The database host ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_host: super::super::super::GetDatabaseHost,
+        activity_logger: GetAdminActivityLogger,
+        mut database: GetServerDatabase,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("database-hosts.update")?;
+
+        if let Some(size_limit) = data.size_limit {
+            database
+                .update_size_limit(&state.database, size_limit.map(|s| s as i64))
+                .await?;
+        }
+
+        activity_logger
+            .log(
+                "database-host:database.update",
+                serde_json::json!({
+                    "uuid": database.uuid,
+                    "database_host_uuid": database_host.uuid,
+
+                    "name": database.name,
+                    "size_limit": database.size_limit,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
 mod delete {
     use super::GetServerDatabase;
     use axum::http::StatusCode;
@@ -157,7 +242,9 @@ mod delete {
 
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
+        .routes(routes!(patch::route))
         .routes(routes!(delete::route))
+        .nest("/migrate", migrate::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
         .with_state(state.clone())
 }
@@ -0,0 +1,140 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::admin::database_hosts::_database_host_::GetDatabaseHost;
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            ByUuid, CreatableModel, UpdatableModel,
+            admin_activity::GetAdminActivityLogger,
+            database_host::{DatabaseHost, UpdateDatabaseHostOptions},
+            server_database::ServerDatabase,
+            server_database_migration::{
+                CreateServerDatabaseMigrationOptions, ServerDatabaseMigration,
+            },
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Payload {
+        target_database_host_uuid: uuid::Uuid,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        migrations: usize,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "database_host" = uuid::Uuid,
+            description = "The database host ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        mut database_host: GetDatabaseHost,
+        activity_logger: GetAdminActivityLogger,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("database-hosts.update")?;
+
+        let target_database_host =
+            match DatabaseHost::by_uuid_optional(&state.database, data.target_database_host_uuid)
+                .await?
+            {
+                Some(target_database_host) => target_database_host,
+                None => {
+                    return ApiResponse::error("target database host not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        if target_database_host.uuid == database_host.uuid {
+            return ApiResponse::error("cannot evacuate a database host onto itself")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+        if target_database_host.r#type != database_host.r#type {
+            return ApiResponse::error("target database host has a different database type")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+        if !target_database_host.is_healthy() {
+            return ApiResponse::error("target database host is unreachable")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        // no new databases should land on a host that is being emptied
+        if database_host.deployment_enabled {
+            database_host
+                .update(
+                    &state,
+                    UpdateDatabaseHostOptions {
+                        deployment_enabled: Some(false),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+        }
+
+        let active_migrations =
+            ServerDatabaseMigration::active_server_database_uuids(&state.database).await?;
+
+        let mut migrations = 0;
+        for database in
+            ServerDatabase::all_by_database_host_uuid(&state.database, database_host.uuid).await?
+        {
+            if active_migrations.contains(&database.uuid) {
+                continue;
+            }
+
+            ServerDatabaseMigration::create(
+                &state,
+                CreateServerDatabaseMigrationOptions {
+                    server_database_uuid: database.uuid,
+                    source_database_host_uuid: database_host.uuid,
+                    target_database_host_uuid: target_database_host.uuid,
+                    name: database.name,
+                },
+            )
+            .await?;
+            migrations += 1;
+        }
+
+        activity_logger
+            .log(
+                "database-host:evacuate",
+                serde_json::json!({
+                    "uuid": database_host.uuid,
+                    "target_database_host_uuid": target_database_host.uuid,
+
+                    "migrations": migrations,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response { migrations }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
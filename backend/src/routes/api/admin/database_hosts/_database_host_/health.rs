@@ -0,0 +1,49 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::admin::database_hosts::_database_host_::GetDatabaseHost;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{database_host::DatabaseHostHealth, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        health: DatabaseHostHealth,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "database_host" = uuid::Uuid,
+            description = "The database host ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        mut database_host: GetDatabaseHost,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("database-hosts.test")?;
+
+        let health = database_host.check_health(&state.database).await;
+        database_host
+            .record_health(&state.database, health.clone())
+            .await?;
+
+        ApiResponse::new_serialized(Response { health }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -13,6 +13,9 @@ use shared::{
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod databases;
+mod evacuate;
+mod health;
+mod migrations;
 mod test;
 
 pub type GetDatabaseHost = shared::extract::ConsumingExtension<DatabaseHost>;
@@ -248,6 +251,9 @@ mod patch {
                     "public_port": database_host.public_port,
 
                     "credentials": database_host.credentials,
+
+                    "max_databases": database_host.max_databases,
+                    "max_size": database_host.max_size,
                 }),
             )
             .await;
@@ -261,8 +267,11 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .routes(routes!(get::route))
         .routes(routes!(delete::route))
         .routes(routes!(patch::route))
+        .nest("/health", health::router(state))
         .nest("/test", test::router(state))
         .nest("/databases", databases::router(state))
+        .nest("/evacuate", evacuate::router(state))
+        .nest("/migrations", migrations::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
         .with_state(state.clone())
 }
@@ -130,6 +130,9 @@ mod post {
                     "public_port": database_host.public_port,
 
                     "credentials": database_host.credentials,
+
+                    "max_databases": database_host.max_databases,
+                    "max_size": database_host.max_size,
                 }),
             )
             .await;
@@ -34,6 +34,7 @@ mod oauth_providers;
 mod roles;
 mod servers;
 mod settings;
+mod ssh_certificate_authorities;
 mod stats;
 mod system;
 mod system_backup_policies;
@@ -104,6 +105,10 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         )
         .nest("/oauth-providers", oauth_providers::router(state))
         .nest("/mounts", mounts::router(state))
+        .nest(
+            "/ssh-certificate-authorities",
+            ssh_certificate_authorities::router(state),
+        )
         .nest("/users", users::router(state))
         .nest("/roles", roles::router(state))
         .nest("/extensions", extensions::router(state))
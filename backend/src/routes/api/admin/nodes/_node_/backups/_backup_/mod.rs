@@ -64,7 +64,7 @@ mod get {
     use serde::Serialize;
     use shared::{
         ApiError, GetState,
-        models::{IntoApiObject, user::GetPermissionManager},
+        models::{IntoApiObject, server_backup::ServerBackupRelations, user::GetPermissionManager},
         response::{ApiResponse, ApiResponseResult},
     };
     use utoipa::ToSchema;
@@ -97,8 +97,10 @@ mod get {
     ) -> ApiResponseResult {
         permissions.has_admin_permission("nodes.backups")?;
 
+        let relations = ServerBackupRelations::load(&state.database, &[backup.uuid]).await?;
+
         ApiResponse::new_serialized(Response {
-            backup: backup.0.into_api_object(&state, ()).await?,
+            backup: backup.0.into_api_object(&state, &relations).await?,
         })
         .ok()
     }
@@ -26,6 +26,8 @@ mod post {
         truncate_directory: bool,
         #[serde(default)]
         restore_startup: bool,
+        #[serde(default)]
+        databases: Vec<uuid::Uuid>,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -117,6 +119,7 @@ mod post {
                 shared::models::server_backup::ServerBackupRestoreOptions {
                     truncate_directory: data.truncate_directory,
                     restore_startup: data.restore_startup,
+                    databases: data.databases.clone(),
                 },
             )
             .await
@@ -139,6 +142,7 @@ mod post {
                     "name": backup_name,
                     "truncate_directory": data.truncate_directory,
                     "restore_startup": data.restore_startup,
+                    "databases": data.databases,
                 }),
             )
             .await;
@@ -9,8 +9,10 @@ mod get {
     use shared::{
         ApiError, GetState,
         models::{
-            IntoAdminApiObject, Pagination, PaginationParamsWithSearch, node::GetNode,
-            server_backup::ServerBackup, user::GetPermissionManager,
+            IntoAdminApiObject, Pagination, PaginationParamsWithSearch,
+            node::GetNode,
+            server_backup::{ServerBackup, ServerBackupRelations},
+            user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
     };
@@ -93,11 +95,12 @@ mod get {
         }?;
 
         let storage_url_retriever = state.storage.retrieve_urls().await?;
+        let relations = ServerBackupRelations::load_for(&state.database, &backups.data).await?;
 
         ApiResponse::new_serialized(Response {
             backups: backups
                 .try_async_map(|backup| {
-                    backup.into_admin_api_object(&state, &storage_url_retriever)
+                    backup.into_admin_api_object(&state, (&storage_url_retriever, &relations))
                 })
                 .await?,
         })
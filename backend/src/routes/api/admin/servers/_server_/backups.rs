@@ -7,7 +7,9 @@ mod get {
     use shared::{
         ApiError, GetState,
         models::{
-            Pagination, PaginationParamsWithSearch, server::GetServer, server_backup::ServerBackup,
+            Pagination, PaginationParamsWithSearch,
+            server::GetServer,
+            server_backup::{ServerBackup, ServerBackupRelations},
             user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
@@ -92,11 +94,12 @@ mod get {
         }?;
 
         let storage_url_retriever = state.storage.retrieve_urls().await?;
+        let relations = ServerBackupRelations::load_for(&state.database, &backups.data).await?;
 
         ApiResponse::new_serialized(Response {
             backups: backups
                 .try_async_map(|backup| {
-                    backup.into_admin_node_api_object(&state, &storage_url_retriever)
+                    backup.into_admin_node_api_object(&state, &storage_url_retriever, &relations)
                 })
                 .await?,
         })
@@ -114,6 +114,37 @@ mod put {
         max_database_instance_database_count: Option<u64>,
         #[garde(skip)]
         max_database_instance_user_count: Option<u64>,
+        #[garde(skip)]
+        max_database_saved_query_count: Option<u64>,
+        #[garde(skip)]
+        max_database_instance_snapshot_policy_count: Option<u64>,
+        #[garde(skip)]
+        max_file_share_count: Option<u64>,
+        #[garde(skip)]
+        max_deployment_source_count: Option<u64>,
+
+        #[garde(range(min = 1, max = 3650))]
+        #[schema(minimum = 1, maximum = 3650)]
+        database_query_history_retention_days: Option<u16>,
+        #[garde(range(min = 1))]
+        #[schema(minimum = 1)]
+        database_query_history_retention_count: Option<u64>,
+        #[garde(range(max = 3650))]
+        #[schema(maximum = 3650)]
+        file_trash_retention_days: Option<u16>,
+        #[garde(range(min = 1, max = 3650))]
+        #[schema(minimum = 1, maximum = 3650)]
+        console_log_archive_retention_days: Option<u16>,
+        #[garde(skip)]
+        max_console_log_archive_size: Option<u64>,
+        #[garde(length(chars, min = 1, max = 255))]
+        #[serde(default, with = "::serde_with::rust::double_option")]
+        file_scan_clamd_address: Option<Option<compact_str::CompactString>>,
+        #[garde(skip)]
+        max_file_scan_size: Option<u64>,
+        #[garde(range(min = 1, max = 3650))]
+        #[schema(minimum = 1, maximum = 3650)]
+        file_quarantine_retention_days: Option<u16>,
 
         #[garde(skip)]
         allow_overwriting_custom_docker_image: Option<bool>,
@@ -140,6 +171,8 @@ mod put {
         max_security_key_count: Option<u64>,
         #[garde(skip)]
         max_ssh_key_count: Option<u64>,
+        #[garde(skip)]
+        max_sftp_password_count: Option<u64>,
 
         #[garde(skip)]
         allow_changing_language: Option<bool>,
@@ -173,6 +206,13 @@ mod put {
         #[schema(minimum = 1)]
         #[serde(default, with = "::serde_with::rust::double_option")]
         server_log_retention_count: Option<Option<u64>>,
+        #[garde(range(min = 1, max = 3650))]
+        #[schema(minimum = 1, maximum = 3650)]
+        server_file_log_retention_days: Option<u16>,
+        #[garde(range(min = 1))]
+        #[schema(minimum = 1)]
+        #[serde(default, with = "::serde_with::rust::double_option")]
+        server_file_log_retention_count: Option<Option<u64>>,
 
         #[garde(skip)]
         server_log_admin_activity: Option<bool>,
@@ -331,6 +371,9 @@ mod put {
             if let Some(max_ssh_key_count) = user.max_ssh_key_count {
                 settings.user.max_ssh_key_count = max_ssh_key_count;
             }
+            if let Some(max_sftp_password_count) = user.max_sftp_password_count {
+                settings.user.max_sftp_password_count = max_sftp_password_count;
+            }
             if let Some(allow_changing_language) = user.allow_changing_language {
                 settings.user.allow_changing_language = allow_changing_language;
             }
@@ -390,6 +433,54 @@ mod put {
             {
                 settings.server.max_database_instance_user_count = max_database_instance_user_count;
             }
+            if let Some(max_database_saved_query_count) = server.max_database_saved_query_count {
+                settings.server.max_database_saved_query_count = max_database_saved_query_count;
+            }
+            if let Some(max_database_instance_snapshot_policy_count) =
+                server.max_database_instance_snapshot_policy_count
+            {
+                settings.server.max_database_instance_snapshot_policy_count =
+                    max_database_instance_snapshot_policy_count;
+            }
+            if let Some(max_file_share_count) = server.max_file_share_count {
+                settings.server.max_file_share_count = max_file_share_count;
+            }
+            if let Some(max_deployment_source_count) = server.max_deployment_source_count {
+                settings.server.max_deployment_source_count = max_deployment_source_count;
+            }
+            if let Some(database_query_history_retention_days) =
+                server.database_query_history_retention_days
+            {
+                settings.server.database_query_history_retention_days =
+                    database_query_history_retention_days;
+            }
+            if let Some(database_query_history_retention_count) =
+                server.database_query_history_retention_count
+            {
+                settings.server.database_query_history_retention_count =
+                    database_query_history_retention_count;
+            }
+            if let Some(file_trash_retention_days) = server.file_trash_retention_days {
+                settings.server.file_trash_retention_days = file_trash_retention_days;
+            }
+            if let Some(console_log_archive_retention_days) =
+                server.console_log_archive_retention_days
+            {
+                settings.server.console_log_archive_retention_days =
+                    console_log_archive_retention_days;
+            }
+            if let Some(max_console_log_archive_size) = server.max_console_log_archive_size {
+                settings.server.max_console_log_archive_size = max_console_log_archive_size;
+            }
+            if let Some(file_scan_clamd_address) = server.file_scan_clamd_address {
+                settings.server.file_scan_clamd_address = file_scan_clamd_address;
+            }
+            if let Some(max_file_scan_size) = server.max_file_scan_size {
+                settings.server.max_file_scan_size = max_file_scan_size;
+            }
+            if let Some(file_quarantine_retention_days) = server.file_quarantine_retention_days {
+                settings.server.file_quarantine_retention_days = file_quarantine_retention_days;
+            }
             if let Some(allow_overwriting_custom_docker_image) =
                 server.allow_overwriting_custom_docker_image
             {
@@ -431,6 +522,13 @@ mod put {
             if let Some(server_log_retention_count) = activity.server_log_retention_count {
                 settings.activity.server_log_retention_count = server_log_retention_count;
             }
+            if let Some(server_file_log_retention_days) = activity.server_file_log_retention_days {
+                settings.activity.server_file_log_retention_days = server_file_log_retention_days;
+            }
+            if let Some(server_file_log_retention_count) = activity.server_file_log_retention_count
+            {
+                settings.activity.server_file_log_retention_count = server_file_log_retention_count;
+            }
             if let Some(server_log_admin_activity) = activity.server_log_admin_activity {
                 settings.activity.server_log_admin_activity = server_log_admin_activity;
             }
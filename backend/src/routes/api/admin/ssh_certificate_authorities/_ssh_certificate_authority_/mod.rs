@@ -0,0 +1,225 @@
+use super::State;
+use axum::{
+    extract::{Path, Request},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::{
+    GetState,
+    models::{
+        ByUuid, ssh_certificate_authority::SshCertificateAuthority, user::GetPermissionManager,
+    },
+    response::ApiResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+pub type GetSshCertificateAuthority = shared::extract::ConsumingExtension<SshCertificateAuthority>;
+
+pub async fn auth(
+    state: GetState,
+    permissions: GetPermissionManager,
+    Path(ssh_certificate_authority): Path<Vec<String>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let ssh_certificate_authority = match ssh_certificate_authority
+        .first()
+        .map(|s| s.parse::<uuid::Uuid>())
+    {
+        Some(Ok(id)) => id,
+        _ => {
+            return Ok(ApiResponse::error("invalid ssh certificate authority uuid")
+                .with_status(StatusCode::BAD_REQUEST)
+                .into_response());
+        }
+    };
+
+    if let Err(err) = permissions.has_admin_permission("ssh-certificate-authorities.read") {
+        return Ok(err.into_response());
+    }
+
+    let ssh_certificate_authority =
+        SshCertificateAuthority::by_uuid_optional(&state.database, ssh_certificate_authority).await;
+    let ssh_certificate_authority = match ssh_certificate_authority {
+        Ok(Some(ssh_certificate_authority)) => ssh_certificate_authority,
+        Ok(None) => {
+            return Ok(ApiResponse::error("ssh certificate authority not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .into_response());
+        }
+        Err(err) => return Ok(ApiResponse::from(err).into_response()),
+    };
+
+    req.extensions_mut().insert(ssh_certificate_authority);
+
+    Ok(next.run(req).await)
+}
+
+mod get {
+    use crate::routes::api::admin::ssh_certificate_authorities::_ssh_certificate_authority_::GetSshCertificateAuthority;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{IntoAdminApiObject, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        ssh_certificate_authority:
+            shared::models::ssh_certificate_authority::AdminApiSshCertificateAuthority,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "ssh_certificate_authority" = uuid::Uuid,
+            description = "The SSH certificate authority ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        ssh_certificate_authority: GetSshCertificateAuthority,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("ssh-certificate-authorities.read")?;
+
+        ApiResponse::new_serialized(Response {
+            ssh_certificate_authority: ssh_certificate_authority
+                .0
+                .into_admin_api_object(&state, ())
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod delete {
+    use crate::routes::api::admin::ssh_certificate_authorities::_ssh_certificate_authority_::GetSshCertificateAuthority;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel, admin_activity::GetAdminActivityLogger, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "ssh_certificate_authority" = uuid::Uuid,
+            description = "The SSH certificate authority ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetAdminActivityLogger,
+        ssh_certificate_authority: GetSshCertificateAuthority,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("ssh-certificate-authorities.delete")?;
+
+        ssh_certificate_authority.delete(&state, ()).await?;
+
+        activity_logger
+            .log(
+                "ssh-certificate-authority:delete",
+                serde_json::json!({
+                    "uuid": ssh_certificate_authority.uuid,
+                    "name": ssh_certificate_authority.name,
+                    "fingerprint": ssh_certificate_authority.fingerprint,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+mod patch {
+    use crate::routes::api::admin::ssh_certificate_authorities::_ssh_certificate_authority_::GetSshCertificateAuthority;
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            UpdatableModel, admin_activity::GetAdminActivityLogger,
+            ssh_certificate_authority::UpdateSshCertificateAuthorityOptions,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "ssh_certificate_authority" = uuid::Uuid,
+            description = "The SSH certificate authority ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(UpdateSshCertificateAuthorityOptions))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetAdminActivityLogger,
+        mut ssh_certificate_authority: GetSshCertificateAuthority,
+        shared::Payload(data): shared::Payload<UpdateSshCertificateAuthorityOptions>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("ssh-certificate-authorities.update")?;
+
+        match ssh_certificate_authority.update(&state, data).await {
+            Ok(_) => {}
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("ssh certificate authority with name already exists")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => return ApiResponse::from(err).ok(),
+        }
+
+        activity_logger
+            .log(
+                "ssh-certificate-authority:update",
+                serde_json::json!({
+                    "uuid": ssh_certificate_authority.uuid,
+                    "name": ssh_certificate_authority.name,
+                    "description": ssh_certificate_authority.description,
+                    "fingerprint": ssh_certificate_authority.fingerprint,
+                    "enabled": ssh_certificate_authority.enabled,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(delete::route))
+        .routes(routes!(patch::route))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
+        .with_state(state.clone())
+}
@@ -0,0 +1,155 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _ssh_certificate_authority_;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoAdminApiObject, Pagination, PaginationParamsWithSearch,
+            ssh_certificate_authority::SshCertificateAuthority, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        ssh_certificate_authorities:
+            Pagination<shared::models::ssh_certificate_authority::AdminApiSshCertificateAuthority>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ), params(
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("ssh-certificate-authorities.read")?;
+
+        let ssh_certificate_authorities = SshCertificateAuthority::all_with_pagination(
+            &state.database,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+        )
+        .await?;
+
+        ApiResponse::new_serialized(Response {
+            ssh_certificate_authorities: ssh_certificate_authorities
+                .try_async_map(|ssh_certificate_authority| {
+                    ssh_certificate_authority.into_admin_api_object(&state, ())
+                })
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel, IntoAdminApiObject,
+            admin_activity::GetAdminActivityLogger,
+            ssh_certificate_authority::{
+                CreateSshCertificateAuthorityOptions, SshCertificateAuthority,
+            },
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        ssh_certificate_authority:
+            shared::models::ssh_certificate_authority::AdminApiSshCertificateAuthority,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), request_body = inline(CreateSshCertificateAuthorityOptions))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetAdminActivityLogger,
+        shared::Payload(data): shared::Payload<CreateSshCertificateAuthorityOptions>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("ssh-certificate-authorities.create")?;
+
+        let ssh_certificate_authority = match SshCertificateAuthority::create(&state, data).await {
+            Ok(ssh_certificate_authority) => ssh_certificate_authority,
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error(
+                    "ssh certificate authority with name or fingerprint already exists",
+                )
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+            }
+            Err(err) => return ApiResponse::from(err).ok(),
+        };
+
+        activity_logger
+            .log(
+                "ssh-certificate-authority:create",
+                serde_json::json!({
+                    "uuid": ssh_certificate_authority.uuid,
+                    "name": ssh_certificate_authority.name,
+                    "description": ssh_certificate_authority.description,
+                    "fingerprint": ssh_certificate_authority.fingerprint,
+                    "enabled": ssh_certificate_authority.enabled,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {
+            ssh_certificate_authority: ssh_certificate_authority
+                .into_admin_api_object(&state, ())
+                .await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest(
+            "/{ssh_certificate_authority}",
+            _ssh_certificate_authority_::router(state),
+        )
+        .with_state(state.clone())
+}
@@ -0,0 +1,91 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server_backup::{BackupStorageConsumer, BackupStorageGrouping, ServerBackup},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    fn default_limit() -> i64 {
+        25
+    }
+
+    #[derive(Deserialize, Validate)]
+    pub struct Params {
+        #[garde(skip)]
+        #[serde(default)]
+        group_by: BackupStorageGrouping,
+        #[garde(range(min = 1, max = 100))]
+        #[serde(default = "default_limit")]
+        limit: i64,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        consumers: Vec<BackupStorageConsumer>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+    ), params(
+        (
+            "group_by" = BackupStorageGrouping, Query,
+            description = "What to aggregate backup storage by",
+            example = "server",
+        ),
+        (
+            "limit" = i64, Query,
+            description = "The number of consumers to return",
+            example = "25",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        Query(params): Query<Params>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("stats.read")?;
+
+        let consumers = state
+            .cache
+            .cached(
+                &format!(
+                    "stats::backup_storage::{:?}::{}",
+                    params.group_by, params.limit
+                ),
+                120,
+                || {
+                    ServerBackup::largest_storage_consumers(
+                        &state.database,
+                        params.group_by,
+                        params.limit,
+                    )
+                },
+            )
+            .await?;
+
+        ApiResponse::new_serialized(Response { consumers }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -1,6 +1,7 @@
 use super::State;
 use utoipa_axum::router::OpenApiRouter;
 
+mod backup_storage;
 mod backups;
 mod general;
 
@@ -8,5 +9,6 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .nest("/general", general::router(state))
         .nest("/backups", backups::router(state))
+        .nest("/backup-storage", backup_storage::router(state))
         .with_state(state.clone())
 }
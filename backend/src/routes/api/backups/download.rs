@@ -0,0 +1,88 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use axum::{body::Body, extract::Query, http::StatusCode};
+    use serde::Deserialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            ByUuid,
+            server_backup::{ServerBackup, encryption::BackupDecryptJwt},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Params {
+        token: String,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = String),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "token" = String, Query,
+            description = "The download token handed out by the panel",
+        ),
+    ))]
+    pub async fn route(state: GetState, Query(params): Query<Params>) -> ApiResponseResult {
+        let payload: BackupDecryptJwt = match state.jwt.verify(&params.token) {
+            Ok(payload) => payload,
+            Err(_) => {
+                return ApiResponse::error("invalid download token")
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        };
+
+        if !payload.base.validate(Some("backup-decrypt")) {
+            return ApiResponse::error("invalid download token")
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let backup =
+            match ServerBackup::by_uuid_optional(&state.database, payload.backup_uuid).await? {
+                Some(backup) if backup.deleting.is_none() && backup.deleted.is_none() => backup,
+                _ => {
+                    return ApiResponse::error("backup not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        if !backup.encrypted {
+            return ApiResponse::error("backup is not encrypted")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        let file_name = backup
+            .upload_path
+            .as_deref()
+            .and_then(|path| path.rsplit('/').next())
+            .map(|name| name.trim_end_matches(".enc").to_string())
+            .unwrap_or_else(|| format!("{}.tar.gz", backup.uuid));
+
+        let stream = backup.decrypted_stream(&state).await?;
+
+        ApiResponse::new(Body::from_stream(stream))
+            .with_header("Content-Type", ServerBackup::s3_content_type(&file_name))
+            .with_header(
+                "Content-Disposition",
+                format!("attachment; filename=\"{file_name}\""),
+            )
+            .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,12 @@
+use super::State;
+use utoipa_axum::router::OpenApiRouter;
+
+mod download;
+mod upload_part;
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .nest("/download", download::router(state))
+        .nest("/upload-part", upload_part::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,161 @@
+use super::State;
+use axum::extract::DefaultBodyLimit;
+use utoipa_axum::{
+    router::{OpenApiRouter, UtoipaMethodRouterExt},
+    routes,
+};
+
+mod put {
+    use aws_sdk_s3::primitives::ByteStream;
+    use axum::{extract::Query, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            ByUuid,
+            server_backup::{
+                BackupDisk, ServerBackup,
+                encryption::{self, BackupEncryptionKey, BackupUploadPartJwt},
+            },
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Params {
+        token: String,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(put, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = PAYLOAD_TOO_LARGE, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "token" = String, Query,
+            description = "The part upload token handed out to wings",
+        ),
+    ), request_body = String)]
+    pub async fn route(
+        state: GetState,
+        Query(params): Query<Params>,
+        body: axum::body::Body,
+    ) -> ApiResponseResult {
+        let payload: BackupUploadPartJwt = match state.jwt.verify(&params.token) {
+            Ok(payload) => payload,
+            Err(_) => {
+                return ApiResponse::error("invalid upload token")
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        };
+
+        if !payload.base.validate(Some("backup-upload-part")) {
+            return ApiResponse::error("invalid upload token")
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let backup =
+            match ServerBackup::by_uuid_optional(&state.database, payload.backup_uuid).await? {
+                Some(backup) if backup.completed.is_none() && backup.deleting.is_none() => backup,
+                _ => {
+                    return ApiResponse::error("backup not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        let (Some(server), Some(upload_path)) = (&backup.server, &backup.upload_path) else {
+            return ApiResponse::error("backup does not have an upload in progress")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        };
+
+        if backup.disk != BackupDisk::S3
+            || !backup.encrypted
+            || backup.upload_id.as_deref() != Some(payload.upload_id.as_str())
+        {
+            return ApiResponse::error("backup does not have an upload in progress")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        let Some(backup_configuration) = &backup.backup_configuration else {
+            return ApiResponse::error("backup does not have a backup configuration assigned")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        };
+        let Some(mut s3_configuration) = backup_configuration
+            .fetch_cached(&state.database)
+            .await?
+            .backup_configs
+            .s3
+        else {
+            return ApiResponse::error("S3 configuration not found")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        };
+        s3_configuration.decrypt(&state.database).await?;
+
+        // wings never sends parts larger than the part size it was handed
+        let data = match axum::body::to_bytes(body, s3_configuration.part_size as usize).await {
+            Ok(data) => data,
+            Err(_) => {
+                return ApiResponse::error("part is larger than the configured part size")
+                    .with_status(StatusCode::PAYLOAD_TOO_LARGE)
+                    .ok();
+            }
+        };
+
+        let key = BackupEncryptionKey::for_server(&state.database, server.uuid).await?;
+        // wings only sends a part shorter than the part size at the end of the archive
+        let last = (data.len() as u64) < s3_configuration.part_size;
+        let encrypted = encryption::encrypt_part(&key, payload.part_number, last, &data)?;
+        drop(data);
+
+        let (client, bucket) = s3_configuration.into_client();
+
+        let part = match client
+            .upload_part()
+            .bucket(&*bucket)
+            .key(&**upload_path)
+            .upload_id(&*payload.upload_id)
+            .part_number(payload.part_number as i32)
+            .body(ByteStream::from(encrypted))
+            .send()
+            .await
+        {
+            Ok(part) => part,
+            Err(err) => {
+                tracing::error!(
+                    backup = %backup.uuid,
+                    part = payload.part_number,
+                    "failed to upload encrypted backup part: {:#?}",
+                    err
+                );
+
+                return ApiResponse::error("failed to upload backup part")
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
+            }
+        };
+
+        // wings reports the etag of every part back when completing the backup
+        ApiResponse::new_serialized(Response {})
+            .with_header("ETag", part.e_tag().unwrap_or_default())
+            .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(put::route).layer(DefaultBodyLimit::disable()))
+        .with_state(state.clone())
+}
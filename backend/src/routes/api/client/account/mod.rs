@@ -11,6 +11,7 @@ mod oauth_links;
 mod password;
 mod security_keys;
 mod sessions;
+mod sftp_passwords;
 mod ssh_keys;
 mod two_factor;
 
@@ -80,6 +81,8 @@ mod patch {
         toast_position: Option<UserToastPosition>,
         #[garde(skip)]
         start_on_grouped_servers: Option<bool>,
+        #[garde(skip)]
+        disable_sftp_password_login: Option<bool>,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -127,6 +130,7 @@ mod patch {
                 language: data.language,
                 toast_position: data.toast_position,
                 start_on_grouped_servers: data.start_on_grouped_servers,
+                disable_sftp_password_login: data.disable_sftp_password_login,
                 ..Default::default()
             },
         )
@@ -142,6 +146,7 @@ mod patch {
                     "language": user.language,
                     "toast_position": user.toast_position,
                     "start_on_grouped_servers": user.start_on_grouped_servers,
+                    "disable_sftp_password_login": user.disable_sftp_password_login,
                 }),
             )
             .await;
@@ -164,6 +169,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/command-snippets", command_snippets::router(state))
         .nest("/api-keys", api_keys::router(state))
         .nest("/ssh-keys", ssh_keys::router(state))
+        .nest("/sftp-passwords", sftp_passwords::router(state))
         .nest("/sessions", sessions::router(state))
         .nest("/activity", activity::router(state))
         .with_state(state.clone())
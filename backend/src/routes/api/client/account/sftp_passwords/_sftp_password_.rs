@@ -0,0 +1,154 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod delete {
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel,
+            user::{GetPermissionManager, GetUser},
+            user_activity::GetUserActivityLogger,
+            user_sftp_password::UserSftpPassword,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "sftp_password" = uuid::Uuid,
+            description = "The SFTP password ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        activity_logger: GetUserActivityLogger,
+        Path(sftp_password): Path<uuid::Uuid>,
+    ) -> ApiResponseResult {
+        permissions.has_user_permission("sftp-passwords.delete")?;
+
+        let sftp_password =
+            match UserSftpPassword::by_user_uuid_uuid(&state.database, user.uuid, sftp_password)
+                .await?
+            {
+                Some(sftp_password) => sftp_password,
+                None => {
+                    return ApiResponse::error("sftp password not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        sftp_password.delete(&state, ()).await?;
+
+        activity_logger
+            .log(
+                "sftp-password:delete",
+                serde_json::json!({
+                    "uuid": sftp_password.uuid,
+                    "identifier": sftp_password.password_start,
+                    "name": sftp_password.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+mod patch {
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            UpdatableModel,
+            user::{GetPermissionManager, GetUser},
+            user_activity::GetUserActivityLogger,
+            user_sftp_password::{UpdateUserSftpPasswordOptions, UserSftpPassword},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "sftp_password" = uuid::Uuid,
+            description = "The SFTP password ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(UpdateUserSftpPasswordOptions))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        activity_logger: GetUserActivityLogger,
+        Path(sftp_password): Path<uuid::Uuid>,
+        shared::Payload(data): shared::Payload<UpdateUserSftpPasswordOptions>,
+    ) -> ApiResponseResult {
+        permissions.has_user_permission("sftp-passwords.update")?;
+
+        let mut sftp_password =
+            match UserSftpPassword::by_user_uuid_uuid(&state.database, user.uuid, sftp_password)
+                .await?
+            {
+                Some(sftp_password) => sftp_password,
+                None => {
+                    return ApiResponse::error("sftp password not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        match sftp_password.update(&state, data).await {
+            Ok(_) => {}
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("sftp password with name already exists")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => return ApiResponse::from(err).ok(),
+        }
+
+        activity_logger
+            .log(
+                "sftp-password:update",
+                serde_json::json!({
+                    "uuid": sftp_password.uuid,
+                    "identifier": sftp_password.password_start,
+                    "name": sftp_password.name,
+                    "servers": sftp_password.servers,
+                    "read_only": sftp_password.read_only,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(delete::route))
+        .routes(routes!(patch::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,196 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _sftp_password_;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, Pagination, PaginationParamsWithSearch,
+            user::{GetPermissionManager, GetUser},
+            user_sftp_password::UserSftpPassword,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        sftp_passwords: Pagination<shared::models::user_sftp_password::ApiUserSftpPassword>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ), params(
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_user_permission("sftp-passwords.read")?;
+
+        let sftp_passwords = UserSftpPassword::by_user_uuid_with_pagination(
+            &state.database,
+            user.uuid,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+        )
+        .await?;
+
+        ApiResponse::new_serialized(Response {
+            sftp_passwords: sftp_passwords
+                .try_async_map(|sftp_password| sftp_password.into_api_object(&state, ()))
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel, IntoApiObject,
+            user::{GetPermissionManager, GetUser},
+            user_activity::GetUserActivityLogger,
+            user_sftp_password::{CreateUserSftpPasswordOptions, UserSftpPassword},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(length(chars, min = 3, max = 31))]
+        #[schema(min_length = 3, max_length = 31)]
+        name: compact_str::CompactString,
+
+        #[garde(length(max = 100))]
+        #[schema(max_items = 100)]
+        #[serde(default)]
+        servers: Vec<uuid::Uuid>,
+        #[garde(skip)]
+        #[serde(default)]
+        read_only: bool,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        sftp_password: shared::models::user_sftp_password::ApiUserSftpPassword,
+        password: String,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        activity_logger: GetUserActivityLogger,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_user_permission("sftp-passwords.create")?;
+
+        let sftp_passwords_lock = state
+            .cache
+            .lock(
+                format!("users::{}::sftp_passwords", user.uuid),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        let sftp_passwords =
+            UserSftpPassword::count_by_user_uuid(&state.database, user.uuid).await?;
+        if sftp_passwords >= state.settings.get().await?.user.max_sftp_password_count as i64 {
+            return ApiResponse::error("maximum number of sftp passwords reached")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        let options = CreateUserSftpPasswordOptions {
+            user_uuid: user.uuid,
+            name: data.name,
+            servers: data.servers,
+            read_only: data.read_only,
+        };
+        let (password, sftp_password) = match UserSftpPassword::create(&state, options).await {
+            Ok(result) => result,
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("sftp password with name already exists")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => return ApiResponse::from(err).ok(),
+        };
+
+        drop(sftp_passwords_lock);
+
+        activity_logger
+            .log(
+                "sftp-password:create",
+                serde_json::json!({
+                    "uuid": sftp_password.uuid,
+                    "identifier": sftp_password.password_start,
+                    "name": sftp_password.name,
+                    "servers": sftp_password.servers,
+                    "read_only": sftp_password.read_only,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {
+            sftp_password: sftp_password.into_api_object(&state, ()).await?,
+            password,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{sftp_password}", _sftp_password_::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,129 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, Pagination, PaginationParamsWithSearch,
+            server::GetServer,
+            server_file_event::{
+                ServerFileEvent, ServerFileEventAction, ServerFileEventFilters,
+                ServerFileEventSource,
+            },
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        events: Pagination<shared::models::server_file_event::ApiServerFileEvent>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "user" = Option<uuid::Uuid>, Query,
+            description = "The user ID to filter file events",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "action" = Option<ServerFileEventAction>, Query,
+            description = "The file action to filter file events",
+        ),
+        (
+            "source" = Option<ServerFileEventSource>, Query,
+            description = "Whether to only show changes made via the panel or via SFTP",
+        ),
+        (
+            "path" = Option<String>, Query,
+            description = "Only show changes to this file, or anything inside this directory",
+            example = "/plugins",
+        ),
+        (
+            "start" = Option<chrono::DateTime<chrono::Utc>>, Query,
+            description = "Only show changes made after this time",
+        ),
+        (
+            "end" = Option<chrono::DateTime<chrono::Utc>>, Query,
+            description = "Only show changes made before this time",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        Query(pagination): Query<PaginationParamsWithSearch>,
+        Query(filters): Query<ServerFileEventFilters>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&pagination) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+        if let Err(errors) = shared::utils::validate_data(&filters) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("activity.read-files")?;
+
+        let events = ServerFileEvent::by_server_uuid_with_pagination(
+            &state.database,
+            server.uuid,
+            &filters,
+            pagination.page,
+            pagination.per_page,
+            pagination.search.as_deref(),
+        )
+        .await?;
+
+        let storage_url_retriever = state.storage.retrieve_urls().await?;
+        let can_read_ip = permissions
+            .has_server_permission("activity.read-ip")
+            .is_ok();
+
+        ApiResponse::new_serialized(Response {
+            events: events
+                .try_async_map(|event| {
+                    event.into_api_object(&state, (&storage_url_retriever, can_read_ip))
+                })
+                .await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -1,6 +1,8 @@
 use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
+mod files;
+
 mod get {
     use axum::{extract::Query, http::StatusCode};
     use serde::{Deserialize, Serialize};
@@ -108,5 +110,6 @@ mod get {
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .routes(routes!(get::route))
+        .nest("/files", files::router(state))
         .with_state(state.clone())
 }
@@ -0,0 +1,145 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use crate::routes::api::client::servers::_server_::backups::_backup_::GetServerBackup;
+    use axum::http::StatusCode;
+    use axum_extra::extract::Query;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::{GetServer, GetServerActivityLogger},
+            server_backup::ServerBackup,
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use std::path::Path;
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Params {
+        #[serde(default)]
+        root: String,
+        files: Vec<String>,
+
+        #[serde(default)]
+        directory: bool,
+        #[serde(default)]
+        archive_format: wings_api::StreamableArchiveFormat,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(format = "uri")]
+        url: String,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "backup" = uuid::Uuid,
+            description = "The backup ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "root" = String, Query,
+            description = "The directory inside the backup to download from",
+            example = "/path/to/root",
+        ),
+        (
+            "files" = Vec<String>, Query,
+            description = "The file(s) to download",
+            example = "/path/to/file.txt",
+        ),
+        (
+            "directory" = bool, Query,
+            description = "Whether the file is a directory",
+            example = "false",
+        ),
+        (
+            "archive_format" = wings_api::StreamableArchiveFormat, Query,
+            description = "The format of the archive to download",
+            example = "tar_gz",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        mut server: GetServer,
+        activity_logger: GetServerActivityLogger,
+        backup: GetServerBackup,
+        Query(params): Query<Params>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("backups.download")?;
+
+        backup.ensure_browsable(&server)?;
+
+        if params.files.is_empty() {
+            return ApiResponse::error("no files to download")
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let mut files = Vec::with_capacity(params.files.len());
+        for file in &params.files {
+            if server.is_ignored(Path::new(&params.root).join(file), params.directory) {
+                return ApiResponse::new_serialized(ApiError::new_value(&["file not found"]))
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+
+            files.push(ServerBackup::normalize_path(file)?);
+        }
+
+        let root = backup.browse_path(&params.root)?;
+        let node = server.node.fetch_cached(&state.database).await?;
+
+        let url = node
+            .file_download_url(
+                &state,
+                server.uuid,
+                user.uuid,
+                &root,
+                &files,
+                params.directory,
+                params.archive_format,
+            )
+            .await?;
+
+        activity_logger
+            .log(
+                "server:backup.download-files",
+                serde_json::json!({
+                    "uuid": backup.uuid,
+                    "name": backup.name,
+                    "directory": params.root,
+                    "files": params.files,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {
+            url: url.to_string(),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
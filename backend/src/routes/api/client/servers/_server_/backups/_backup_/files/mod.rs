@@ -0,0 +1,138 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod download;
+
+mod get {
+    use crate::routes::api::client::servers::_server_::backups::_backup_::GetServerBackup;
+    use axum::{extract::Query, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{Pagination, PaginationParams, server::GetServer, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Params {
+        #[serde(default)]
+        directory: compact_str::CompactString,
+        #[serde(default)]
+        sort: wings_api::DirectorySortingMode,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        entries: Pagination<wings_api::DirectoryEntry>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "backup" = uuid::Uuid,
+            description = "The backup ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number for pagination",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "directory" = String, Query,
+            description = "The directory inside the backup to list files from",
+            example = "/",
+        ),
+        (
+            "sort" = wings_api::DirectorySortingMode, Query,
+            description = "The sorting mode to use for the files",
+            example = "name_asc"
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        mut server: GetServer,
+        backup: GetServerBackup,
+        Query(pagination): Query<PaginationParams>,
+        Query(params): Query<Params>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&pagination) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("files.read")?;
+
+        backup.ensure_browsable(&server)?;
+
+        if server.is_ignored(&params.directory, true) {
+            return ApiResponse::error("directory not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .ok();
+        }
+
+        let entries = match server
+            .node
+            .fetch_cached(&state.database)
+            .await?
+            .api_client(&state.database)
+            .await?
+            .get_servers_server_files_list(
+                server.uuid,
+                &wings_api::servers_server_files_list::get::Query {
+                    directory: Some(backup.browse_path(&params.directory)?),
+                    ignored: server.0.subuser_ignored_files,
+                    per_page: Some(pagination.per_page as u64),
+                    page: Some(pagination.page as u64),
+                    sort: Some(params.sort),
+                    ..Default::default()
+                },
+            )
+            .await
+        {
+            Ok(data) => data,
+            Err(wings_api::client::ApiHttpError::Http(StatusCode::NOT_FOUND, err)) => {
+                return ApiResponse::new_serialized(ApiError::new_wings_value(err))
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        ApiResponse::new_serialized(Response {
+            entries: Pagination {
+                total: entries.total as i64,
+                per_page: pagination.per_page,
+                page: pagination.page,
+                data: entries.entries,
+            },
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .nest("/download", download::router(state))
+        .with_state(state.clone())
+}
@@ -14,6 +14,7 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod download;
 mod export;
+mod files;
 mod query;
 mod restore;
 
@@ -62,7 +63,7 @@ mod get {
     use serde::Serialize;
     use shared::{
         ApiError, GetState,
-        models::{IntoApiObject, user::GetPermissionManager},
+        models::{IntoApiObject, server_backup::ServerBackupRelations, user::GetPermissionManager},
         response::{ApiResponse, ApiResponseResult},
     };
     use utoipa::ToSchema;
@@ -95,8 +96,10 @@ mod get {
     ) -> ApiResponseResult {
         permissions.has_server_permission("backups.read")?;
 
+        let relations = ServerBackupRelations::load(&state.database, &[backup.uuid]).await?;
+
         ApiResponse::new_serialized(Response {
-            backup: backup.0.into_api_object(&state, ()).await?,
+            backup: backup.0.into_api_object(&state, &relations).await?,
         })
         .ok()
     }
@@ -292,6 +295,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .routes(routes!(patch::route))
         .nest("/download", download::router(state))
         .nest("/export", export::router(state))
+        .nest("/files", files::router(state))
         .nest("/query", query::router(state))
         .nest("/restore", restore::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
@@ -9,11 +9,13 @@ mod post {
         ApiError, GetState,
         models::{
             server::{GetServer, GetServerActivityLogger, ServerStatus},
-            server_backup::ServerBackupRestoreOptions,
+            server_backup::{ServerBackupPathRestoreOptions, ServerBackupRestoreOptions},
+            server_backup_database::ServerBackupDatabase,
             user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
     };
+    use std::path::Path;
     use utoipa::ToSchema;
 
     #[derive(ToSchema, Deserialize)]
@@ -22,6 +24,13 @@ mod post {
         truncate_directory: bool,
         #[serde(default)]
         restore_startup: bool,
+        #[serde(default)]
+        databases: Vec<uuid::Uuid>,
+
+        #[serde(default)]
+        paths: Vec<compact_str::CompactString>,
+        #[serde(default)]
+        target_directory: Option<compact_str::CompactString>,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -53,6 +62,9 @@ mod post {
         shared::Payload(data): shared::Payload<Payload>,
     ) -> ApiResponseResult {
         permissions.has_server_permission("backups.restore")?;
+        if !data.databases.is_empty() {
+            permissions.has_server_permission("databases.recreate")?;
+        }
 
         if backup.deleting.is_some() {
             return ApiResponse::error("backup is being deleted")
@@ -72,6 +84,30 @@ mod post {
                 .ok();
         }
 
+        if !data.paths.is_empty() {
+            if data.truncate_directory || data.restore_startup {
+                return ApiResponse::error(
+                    "truncate_directory and restore_startup cannot be used when restoring individual paths",
+                )
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+            }
+
+            let target_directory = data.target_directory.as_deref().unwrap_or("/");
+            for path in &data.paths {
+                if server.is_ignored(path.as_str(), false)
+                    || server.is_ignored(
+                        Path::new(target_directory).join(path.trim_start_matches('/')),
+                        false,
+                    )
+                {
+                    return ApiResponse::error("file not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            }
+        }
+
         let mut transaction = state.database.write().begin().await?;
 
         if !server
@@ -85,6 +121,66 @@ mod post {
                 .ok();
         }
 
+        if !data.paths.is_empty() {
+            transaction.commit().await?;
+
+            // wings copies the paths in the foreground, so the server is restored once it returns
+            let restored = async {
+                let databases = backup
+                    .restorable_databases(&state.database, &data.databases)
+                    .await?;
+
+                backup
+                    .restore_paths(
+                        &state,
+                        &server,
+                        ServerBackupPathRestoreOptions {
+                            paths: data.paths.clone(),
+                            target_directory: data.target_directory.clone(),
+                        },
+                    )
+                    .await?;
+
+                Ok::<_, anyhow::Error>(databases)
+            }
+            .await;
+
+            server
+                .try_set_status(
+                    state.database.write(),
+                    Some(ServerStatus::RestoringBackup),
+                    None,
+                )
+                .await?;
+
+            let databases = restored?;
+            if !databases.is_empty() {
+                let state = state.clone();
+                let backup = backup.0.clone();
+                let server_uuid = server.uuid;
+
+                tokio::spawn(async move {
+                    ServerBackupDatabase::restore_all(&state, &backup, server_uuid, databases)
+                        .await;
+                });
+            }
+
+            activity_logger
+                .log(
+                    "server:backup.restore-paths",
+                    serde_json::json!({
+                        "uuid": backup.uuid,
+                        "name": backup.name,
+                        "paths": data.paths,
+                        "target_directory": data.target_directory,
+                        "databases": data.databases,
+                    }),
+                )
+                .await;
+
+            return ApiResponse::new_serialized(Response {}).ok();
+        }
+
         let backup_uuid = backup.uuid;
         let backup_name = backup.name.clone();
 
@@ -98,6 +194,7 @@ mod post {
                 ServerBackupRestoreOptions {
                     truncate_directory: data.truncate_directory,
                     restore_startup: data.restore_startup,
+                    databases: data.databases.clone(),
                 },
             )
             .await
@@ -120,6 +217,7 @@ mod post {
                     "name": backup_name,
                     "truncate_directory": data.truncate_directory,
                     "restore_startup": data.restore_startup,
+                    "databases": data.databases,
                 }),
             )
             .await;
@@ -0,0 +1,69 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::{GetServer, GetServerActivityLogger},
+            server_backup::encryption::{BackupEncryptionKey, CHUNK_SIZE},
+            user::GetUser,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        key: compact_str::CompactString,
+        algorithm: &'static str,
+        chunk_size: usize,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = FORBIDDEN, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        user: GetUser,
+        server: GetServer,
+        activity_logger: GetServerActivityLogger,
+    ) -> ApiResponseResult {
+        if user.uuid != server.owner.uuid {
+            return ApiResponse::error(
+                "only the server owner can export the backup encryption key",
+            )
+            .with_status(StatusCode::FORBIDDEN)
+            .ok();
+        }
+
+        let key = BackupEncryptionKey::for_server(&state.database, server.uuid).await?;
+
+        activity_logger
+            .log("server:backup.export-encryption-key", serde_json::json!({}))
+            .await;
+
+        ApiResponse::new_serialized(Response {
+            key: key.to_base64(),
+            algorithm: "aes-256-gcm",
+            chunk_size: CHUNK_SIZE,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -10,8 +10,10 @@ mod get {
     use shared::{
         ApiError, GetState,
         models::{
-            IntoApiObject, Pagination, PaginationParamsWithSearch, server::GetServer,
-            server_backup::ServerBackup, server_backup_group::ServerBackupGroup,
+            IntoApiObject, Pagination, PaginationParamsWithSearch,
+            server::GetServer,
+            server_backup::{ServerBackup, ServerBackupRelations},
+            server_backup_group::ServerBackupGroup,
             user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
@@ -96,9 +98,11 @@ mod get {
         )
         .await?;
 
+        let relations = ServerBackupRelations::load_for(&state.database, &backups.data).await?;
+
         ApiResponse::new_serialized(Response {
             backups: backups
-                .try_async_map(|backup| backup.into_api_object(&state, ()))
+                .try_async_map(|backup| backup.into_api_object(&state, &relations))
                 .await?,
         })
         .ok()
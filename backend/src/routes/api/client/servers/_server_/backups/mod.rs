@@ -2,9 +2,11 @@ use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod _backup_;
+mod encryption_key;
 mod groups;
 mod system;
 mod unlock;
+mod usage;
 
 mod get {
     use axum::{extract::Query, http::StatusCode};
@@ -12,8 +14,10 @@ mod get {
     use shared::{
         ApiError, GetState,
         models::{
-            IntoApiObject, Pagination, PaginationParamsWithSearch, server::GetServer,
-            server_backup::ServerBackup, user::GetPermissionManager,
+            IntoApiObject, Pagination, PaginationParamsWithSearch,
+            server::GetServer,
+            server_backup::{ServerBackup, ServerBackupRelations},
+            user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
     };
@@ -97,9 +101,11 @@ mod get {
             .await
         }?;
 
+        let relations = ServerBackupRelations::load_for(&state.database, &backups.data).await?;
+
         ApiResponse::new_serialized(Response {
             backups: backups
-                .try_async_map(|backup| backup.into_api_object(&state, ()))
+                .try_async_map(|backup| backup.into_api_object(&state, &relations))
                 .await?,
         })
         .ok()
@@ -115,7 +121,7 @@ mod post {
         models::{
             CreatableModel, IntoApiObject,
             server::{GetServer, GetServerActivityLogger},
-            server_backup::{GroupRotationOutcome, ServerBackup},
+            server_backup::{GroupRotationOutcome, ServerBackup, ServerBackupRelations},
             server_backup_group::ServerBackupGroup,
             user::GetPermissionManager,
         },
@@ -133,6 +139,9 @@ mod post {
 
         #[garde(skip)]
         ignored_files: Vec<compact_str::CompactString>,
+        #[garde(skip)]
+        #[serde(default)]
+        include_databases: bool,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -165,6 +174,9 @@ mod post {
         }
 
         permissions.has_server_permission("backups.create")?;
+        if data.include_databases {
+            permissions.has_server_permission("databases.read")?;
+        }
 
         let backup_group = if let Some(group_uuid) = data.backup_group_uuid {
             match ServerBackupGroup::by_server_uuid_uuid(&state.database, server.uuid, group_uuid)
@@ -199,26 +211,7 @@ mod post {
                 .ok();
         }
 
-        let backups = ServerBackup::count_by_server_uuid(&state.database, server.uuid).await?;
-        if backups >= server.backup_limit as i64 {
-            return ApiResponse::error("maximum number of backups reached")
-                .with_status(StatusCode::EXPECTATION_FAILED)
-                .ok();
-        }
-
-        let ratelimit = state
-            .settings
-            .get_as(|s| s.ratelimits.client_servers_backups_create)
-            .await?;
-        state
-            .cache
-            .ratelimit(
-                "client/servers/backups/create",
-                ratelimit.hits,
-                ratelimit.window_seconds,
-                server.uuid.to_string(),
-            )
-            .await?;
+        ServerBackup::check_create_limits(&state, &server).await?;
 
         let options = shared::models::server_backup::CreateServerBackupOptions {
             server: &server,
@@ -228,6 +221,7 @@ mod post {
             backup_configuration: None,
             ignored_files: data.ignored_files,
             metadata: ServerBackup::generate_metadata(&state, &server).await?,
+            include_databases: data.include_databases,
         };
         let backup = ServerBackup::create(&state, options).await?;
 
@@ -241,12 +235,15 @@ mod post {
                     "name": backup.name,
                     "backup_group_uuid": backup.backup_group_uuid,
                     "ignored_files": backup.ignored_files,
+                    "include_databases": data.include_databases,
                 }),
             )
             .await;
 
         ApiResponse::new_serialized(Response {
-            backup: backup.into_api_object(&state, ()).await?,
+            backup: backup
+                .into_api_object(&state, &ServerBackupRelations::default())
+                .await?,
         })
         .ok()
     }
@@ -256,9 +253,11 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .routes(routes!(get::route))
         .routes(routes!(post::route))
+        .nest("/encryption-key", encryption_key::router(state))
         .nest("/groups", groups::router(state))
         .nest("/system", system::router(state))
         .nest("/unlock", unlock::router(state))
+        .nest("/usage", usage::router(state))
         .nest("/{backup}", _backup_::router(state))
         .with_state(state.clone())
 }
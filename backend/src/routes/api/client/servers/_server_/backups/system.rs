@@ -7,8 +7,10 @@ mod get {
     use shared::{
         ApiError, GetState,
         models::{
-            IntoApiObject, Pagination, PaginationParamsWithSearch, server::GetServer,
-            server_backup::ServerBackup, user::GetPermissionManager,
+            IntoApiObject, Pagination, PaginationParamsWithSearch,
+            server::GetServer,
+            server_backup::{ServerBackup, ServerBackupRelations},
+            user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
     };
@@ -68,9 +70,11 @@ mod get {
         )
         .await?;
 
+        let relations = ServerBackupRelations::load_for(&state.database, &backups.data).await?;
+
         ApiResponse::new_serialized(Response {
             backups: backups
-                .try_async_map(|backup| backup.into_api_object(&state, ()))
+                .try_async_map(|backup| backup.into_api_object(&state, &relations))
                 .await?,
         })
         .ok()
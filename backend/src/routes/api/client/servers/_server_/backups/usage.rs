@@ -0,0 +1,57 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{server::GetServer, server_backup::ServerBackup, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        backups: i64,
+        backup_limit: i32,
+        bytes: i64,
+        bytes_limit: Option<i64>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("backups.read")?;
+
+        let (backups, bytes) = tokio::try_join!(
+            ServerBackup::count_by_server_uuid(&state.database, server.uuid),
+            ServerBackup::storage_usage_by_server_uuid(&state.database, server.uuid),
+        )?;
+
+        ApiResponse::new_serialized(Response {
+            backups,
+            backup_limit: server.backup_limit,
+            bytes,
+            bytes_limit: server.backup_storage_limit,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
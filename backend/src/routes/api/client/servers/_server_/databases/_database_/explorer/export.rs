@@ -0,0 +1,129 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::_server_::databases::_database_::GetServerDatabase;
+    use axum::http::StatusCode;
+    use futures_util::StreamExt;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::GetServerActivityLogger,
+            server_database::{ExportOptions, QUERY_ACTIVITY_LENGTH},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = String),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = REQUEST_TIMEOUT, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(ExportOptions))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database: GetServerDatabase,
+        activity_logger: GetServerActivityLogger,
+        shared::Payload(data): shared::Payload<ExportOptions>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        if data.query.is_some() {
+            permissions.has_server_permission("databases.query-raw")?;
+        } else {
+            permissions.has_server_permission("databases.query")?;
+        }
+
+        if database.database_host.maintenance_enabled {
+            return ApiResponse::error(
+                "cannot export from database while database host is in maintenance mode",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .ok();
+        }
+
+        let source = match data.source() {
+            Ok(source) => source,
+            Err(err) => return ApiResponse::from(err).ok(),
+        };
+
+        let mut database = database.0;
+        let (uuid, name) = (database.uuid, database.name.clone());
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(4);
+
+        tokio::spawn({
+            let state = state.clone();
+            let format = data.format;
+
+            async move {
+                database
+                    .export(&state.database, &source, format, sender)
+                    .await;
+            }
+        });
+
+        // errors before the first chunk, like an unknown table, still get a proper response
+        let first = match receiver.recv().await {
+            Some(Err(err)) => return ApiResponse::from(err).ok(),
+            first => first,
+        };
+
+        activity_logger
+            .log(
+                "server:database.rows-export",
+                serde_json::json!({
+                    "uuid": uuid,
+                    "name": name,
+                    "format": data.format,
+                    "table": data.table,
+                    "query": data
+                        .query
+                        .as_ref()
+                        .map(|query| query.chars().take(QUERY_ACTIVITY_LENGTH).collect::<String>()),
+                }),
+            )
+            .await;
+
+        let chunks = futures_util::stream::iter(first).chain(futures_util::stream::unfold(
+            receiver,
+            |mut receiver| async move { receiver.recv().await.map(|chunk| (chunk, receiver)) },
+        ));
+
+        ApiResponse::new_stream(tokio_util::io::StreamReader::new(chunks.map(|chunk| {
+            chunk
+                .map(std::io::Cursor::new)
+                .map_err(std::io::Error::other)
+        })))
+        .with_header("Content-Type", data.format.content_type())
+        .with_header(
+            "Content-Disposition",
+            format!("attachment; filename=\"{}\"", data.file_name()),
+        )
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
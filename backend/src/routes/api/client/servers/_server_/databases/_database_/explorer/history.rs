@@ -0,0 +1,151 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use crate::routes::api::client::servers::_server_::databases::_database_::GetServerDatabase;
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, Pagination, PaginationParamsWithSearch,
+            server_database_query_history::ServerDatabaseQueryHistory,
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        history: Pagination<
+            shared::models::server_database_query_history::ApiServerDatabaseQueryHistory,
+        >,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        database: GetServerDatabase,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("databases.query-raw")?;
+
+        let history =
+            ServerDatabaseQueryHistory::by_server_database_uuid_user_uuid_with_pagination(
+                &state.database,
+                database.uuid,
+                user.uuid,
+                params.page,
+                params.per_page,
+                params.search.as_deref(),
+            )
+            .await?;
+
+        ApiResponse::new_serialized(Response {
+            history: history
+                .try_async_map(|entry| entry.into_api_object(&state, ()))
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod delete {
+    use crate::routes::api::client::servers::_server_::databases::_database_::GetServerDatabase;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server_database_query_history::ServerDatabaseQueryHistory,
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        deleted: u64,
+    }
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        database: GetServerDatabase,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("databases.query-raw")?;
+
+        let deleted = ServerDatabaseQueryHistory::delete_by_server_database_uuid_user_uuid(
+            &state.database,
+            database.uuid,
+            user.uuid,
+        )
+        .await?;
+
+        ApiResponse::new_serialized(Response { deleted }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,109 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::_server_::databases::_database_::GetServerDatabase;
+    use axum::{extract::Query, http::StatusCode};
+    use futures_util::TryStreamExt;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::GetServerActivityLogger,
+            server_database::{ImportOptions, ImportSummary},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = ImportSummary),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "schema" = Option<String>, Query,
+            description = "The schema of the table to import into",
+        ),
+        (
+            "table" = String, Query,
+            description = "The table to import into",
+        ),
+        (
+            "format" = String, Query,
+            description = "The format of the file, csv or jsonl",
+            example = "csv",
+        ),
+        (
+            "mapping" = Option<String>, Query,
+            description = "Comma separated field:column pairs, fields left out are skipped",
+            example = "id:user_id,name:username",
+        ),
+    ), request_body = Vec<u8>)]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        mut database: GetServerDatabase,
+        activity_logger: GetServerActivityLogger,
+        Query(params): Query<ImportOptions>,
+        body: axum::body::Body,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("databases.edit-rows")?;
+
+        if database.database_host.maintenance_enabled {
+            return ApiResponse::error(
+                "cannot import into database while database host is in maintenance mode",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .ok();
+        }
+
+        let body_reader = tokio_util::io::StreamReader::new(
+            body.into_data_stream().map_err(std::io::Error::other),
+        );
+
+        let summary = match database.import(&state.database, &params, body_reader).await {
+            Ok(summary) => summary,
+            Err(err) => return ApiResponse::from(err).ok(),
+        };
+
+        activity_logger
+            .log(
+                "server:database.rows-import",
+                serde_json::json!({
+                    "uuid": database.uuid,
+                    "name": database.name,
+                    "table": params.table,
+                    "format": params.format,
+                    "imported": summary.imported,
+                    "failed": summary.failed,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(summary).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
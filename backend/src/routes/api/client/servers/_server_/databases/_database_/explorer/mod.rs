@@ -1,15 +1,23 @@
 use super::State;
 use utoipa_axum::router::OpenApiRouter;
 
+mod export;
+mod history;
+mod import;
 mod query;
 mod rows;
+mod saved_queries;
 mod schema;
 mod tables;
 
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
+        .nest("/export", export::router(state))
+        .nest("/history", history::router(state))
+        .nest("/import", import::router(state))
         .nest("/query", query::router(state))
         .nest("/rows", rows::router(state))
+        .nest("/saved-queries", saved_queries::router(state))
         .nest("/schema", schema::router(state))
         .nest("/tables", tables::router(state))
         .with_state(state.clone())
@@ -9,12 +9,16 @@ mod post {
     use shared::{
         ApiError, GetState,
         models::{
+            CreatableModel,
             server::GetServerActivityLogger,
             server_database::{
                 QUERY_ACTIVITY_LENGTH, QUERY_DEFAULT_ROWS, QUERY_MAX_LENGTH, QUERY_MAX_ROWS,
                 QueryResultSet,
             },
-            user::GetPermissionManager,
+            server_database_query_history::{
+                CreateServerDatabaseQueryHistoryOptions, ServerDatabaseQueryHistory,
+            },
+            user::{GetPermissionManager, GetUser},
         },
         response::{ApiResponse, ApiResponseResult},
     };
@@ -71,6 +75,7 @@ mod post {
     pub async fn route(
         state: GetState,
         permissions: GetPermissionManager,
+        user: GetUser,
         mut database: GetServerDatabase,
         activity_logger: GetServerActivityLogger,
         shared::Payload(data): shared::Payload<Payload>,
@@ -91,10 +96,41 @@ mod post {
             .ok();
         }
 
-        let results = match database
+        let start = std::time::Instant::now();
+        let results = database
             .run_query(&state.database, &data.query, data.rows, data.read_only)
-            .await
+            .await;
+
+        let (rows, rows_affected) = results.as_ref().map_or((0, 0), |results| {
+            results
+                .iter()
+                .fold((0, 0), |(rows, rows_affected), result| {
+                    (
+                        rows + result.rows.len() as i64,
+                        rows_affected + result.rows_affected as i64,
+                    )
+                })
+        });
+
+        if let Err(err) = ServerDatabaseQueryHistory::create(
+            &state,
+            CreateServerDatabaseQueryHistoryOptions {
+                server_database_uuid: database.uuid,
+                user_uuid: user.uuid,
+                query: data.query.clone(),
+                read_only: data.read_only,
+                error: results.as_ref().err().map(|err| err.to_string()),
+                rows,
+                rows_affected,
+                duration_ms: start.elapsed().as_millis() as i64,
+            },
+        )
+        .await
         {
+            tracing::warn!(database = %database.uuid, "failed to record query history: {:?}", err);
+        }
+
+        let results = match results {
             Ok(results) => results,
             Err(err) => return ApiResponse::from(err).ok(),
         };
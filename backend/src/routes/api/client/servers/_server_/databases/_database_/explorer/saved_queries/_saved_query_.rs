@@ -0,0 +1,212 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod patch {
+    use crate::routes::api::client::servers::_server_::databases::_database_::GetServerDatabase;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            UpdatableModel,
+            server::GetServerActivityLogger,
+            server_database_saved_query::{
+                ServerDatabaseSavedQuery, UpdateServerDatabaseSavedQueryOptions,
+            },
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = FORBIDDEN, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "saved_query" = uuid::Uuid,
+            description = "The saved query ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(UpdateServerDatabaseSavedQueryOptions))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        database: GetServerDatabase,
+        activity_logger: GetServerActivityLogger,
+        Path((_server, _database, saved_query)): Path<(String, uuid::Uuid, uuid::Uuid)>,
+        shared::Payload(data): shared::Payload<UpdateServerDatabaseSavedQueryOptions>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("databases.query-raw")?;
+
+        let mut saved_query =
+            match ServerDatabaseSavedQuery::by_server_database_uuid_user_uuid_uuid(
+                &state.database,
+                database.uuid,
+                user.uuid,
+                saved_query,
+            )
+            .await?
+            {
+                Some(saved_query) => saved_query,
+                None => {
+                    return ApiResponse::error("saved query not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        if saved_query.user.uuid != user.uuid {
+            return ApiResponse::error("only the creator of a saved query can update it")
+                .with_status(StatusCode::FORBIDDEN)
+                .ok();
+        }
+
+        match saved_query.update(&state, data).await {
+            Ok(_) => {}
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("saved query with name already exists")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => return ApiResponse::from(err).ok(),
+        }
+
+        activity_logger
+            .log(
+                "server:database.saved-query-update",
+                serde_json::json!({
+                    "uuid": database.uuid,
+                    "name": database.name,
+                    "saved_query": saved_query.uuid,
+                    "saved_query_name": saved_query.name,
+                    "shared": saved_query.shared,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+mod delete {
+    use crate::routes::api::client::servers::_server_::databases::_database_::GetServerDatabase;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel,
+            server::GetServerActivityLogger,
+            server_database_saved_query::ServerDatabaseSavedQuery,
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = FORBIDDEN, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "saved_query" = uuid::Uuid,
+            description = "The saved query ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        database: GetServerDatabase,
+        activity_logger: GetServerActivityLogger,
+        Path((_server, _database, saved_query)): Path<(String, uuid::Uuid, uuid::Uuid)>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("databases.query-raw")?;
+
+        let saved_query = match ServerDatabaseSavedQuery::by_server_database_uuid_user_uuid_uuid(
+            &state.database,
+            database.uuid,
+            user.uuid,
+            saved_query,
+        )
+        .await?
+        {
+            Some(saved_query) => saved_query,
+            None => {
+                return ApiResponse::error("saved query not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        if saved_query.user.uuid != user.uuid {
+            return ApiResponse::error("only the creator of a saved query can delete it")
+                .with_status(StatusCode::FORBIDDEN)
+                .ok();
+        }
+
+        saved_query.delete(&state, ()).await?;
+
+        activity_logger
+            .log(
+                "server:database.saved-query-delete",
+                serde_json::json!({
+                    "uuid": database.uuid,
+                    "name": database.name,
+                    "saved_query": saved_query.uuid,
+                    "saved_query_name": saved_query.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(patch::route))
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
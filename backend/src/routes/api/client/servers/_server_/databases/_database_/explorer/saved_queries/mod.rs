@@ -0,0 +1,244 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _saved_query_;
+
+mod get {
+    use crate::routes::api::client::servers::_server_::databases::_database_::GetServerDatabase;
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, Pagination, PaginationParamsWithSearch,
+            server_database_saved_query::ServerDatabaseSavedQuery,
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        saved_queries:
+            Pagination<shared::models::server_database_saved_query::ApiServerDatabaseSavedQuery>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        database: GetServerDatabase,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("databases.query-raw")?;
+
+        let saved_queries =
+            ServerDatabaseSavedQuery::by_server_database_uuid_user_uuid_with_pagination(
+                &state.database,
+                database.uuid,
+                user.uuid,
+                params.page,
+                params.per_page,
+                params.search.as_deref(),
+            )
+            .await?;
+
+        let storage_url_retriever = state.storage.retrieve_urls().await?;
+
+        ApiResponse::new_serialized(Response {
+            saved_queries: saved_queries
+                .try_async_map(|saved_query| {
+                    saved_query.into_api_object(&state, &storage_url_retriever)
+                })
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use crate::routes::api::client::servers::_server_::databases::_database_::GetServerDatabase;
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel, IntoApiObject,
+            server::GetServerActivityLogger,
+            server_database::QUERY_MAX_LENGTH,
+            server_database_saved_query::{
+                CreateServerDatabaseSavedQueryOptions, ServerDatabaseSavedQuery,
+            },
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(length(chars, min = 1, max = 63))]
+        #[schema(min_length = 1, max_length = 63)]
+        name: compact_str::CompactString,
+        #[garde(length(chars, min = 1, max = QUERY_MAX_LENGTH))]
+        #[schema(min_length = 1, max_length = 65535)]
+        query: String,
+        #[garde(skip)]
+        #[serde(default)]
+        shared: bool,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        saved_query: shared::models::server_database_saved_query::ApiServerDatabaseSavedQuery,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        database: GetServerDatabase,
+        activity_logger: GetServerActivityLogger,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("databases.query-raw")?;
+
+        let saved_queries_lock = state
+            .cache
+            .lock(
+                format!("server_databases::{}::saved_queries", database.uuid),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        let saved_queries =
+            ServerDatabaseSavedQuery::count_by_server_database_uuid(&state.database, database.uuid)
+                .await?;
+        let max_saved_queries = state
+            .settings
+            .get()
+            .await?
+            .server
+            .max_database_saved_query_count;
+        if saved_queries >= max_saved_queries as i64 {
+            return ApiResponse::error("maximum number of saved queries reached")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        let options = CreateServerDatabaseSavedQueryOptions {
+            server_database_uuid: database.uuid,
+            user_uuid: user.uuid,
+            name: data.name,
+            query: data.query,
+            shared: data.shared,
+        };
+        let saved_query = match ServerDatabaseSavedQuery::create(&state, options).await {
+            Ok(saved_query) => saved_query,
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("saved query with name already exists")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => return ApiResponse::from(err).ok(),
+        };
+
+        drop(saved_queries_lock);
+
+        activity_logger
+            .log(
+                "server:database.saved-query-create",
+                serde_json::json!({
+                    "uuid": database.uuid,
+                    "name": database.name,
+                    "saved_query": saved_query.uuid,
+                    "saved_query_name": saved_query.name,
+                    "shared": saved_query.shared,
+                }),
+            )
+            .await;
+
+        let storage_url_retriever = state.storage.retrieve_urls().await?;
+
+        ApiResponse::new_serialized(Response {
+            saved_query: saved_query
+                .into_api_object(&state, &storage_url_retriever)
+                .await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{saved_query}", _saved_query_::router(state))
+        .with_state(state.clone())
+}
@@ -2,7 +2,9 @@ use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod columns;
+mod constraints;
 mod delete;
+mod indexes;
 mod rename;
 mod types;
 
@@ -114,7 +116,9 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .routes(routes!(post::route))
         .nest("/columns", columns::router(state))
+        .nest("/constraints", constraints::router(state))
         .nest("/delete", delete::router(state))
+        .nest("/indexes", indexes::router(state))
         .nest("/rename", rename::router(state))
         .nest("/types", types::router(state))
         .with_state(state.clone())
@@ -0,0 +1,102 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod delete {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel, server::GetServerActivityLogger,
+            server_database_access_token::ServerDatabaseAccessToken, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "access_token" = uuid::Uuid,
+            description = "The access token ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetServerActivityLogger,
+        database_instance: GetServerDatabaseInstance,
+        Path((_server, _database_instance, database, access_token)): Path<(
+            String,
+            uuid::Uuid,
+            uuid::Uuid,
+            uuid::Uuid,
+        )>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("database-instances.access-tokens")?;
+
+        let access_token =
+            match ServerDatabaseAccessToken::by_server_database_instance_uuid_instance_database_uuid_uuid(
+                &state.database,
+                database_instance.uuid,
+                database,
+                access_token,
+            )
+            .await?
+            {
+                Some(access_token) => access_token,
+                None => {
+                    return ApiResponse::error("access token not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        access_token.delete(&state, ()).await?;
+
+        activity_logger
+            .log(
+                "server:database-instance.access-token.delete",
+                serde_json::json!({
+                    "uuid": database_instance.uuid,
+                    "name": database_instance.name,
+                    "database_uuid": database,
+                    "access_token_uuid": access_token.uuid,
+                    "access_token_name": access_token.name,
+                    "username": access_token.username,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,248 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _access_token_;
+
+mod get {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{
+        extract::{Path, Query},
+        http::StatusCode,
+    };
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, Pagination, PaginationParamsWithSearch,
+            server_database_access_token::ServerDatabaseAccessToken, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        access_tokens:
+            Pagination<shared::models::server_database_access_token::ApiServerDatabaseAccessToken>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_instance: GetServerDatabaseInstance,
+        Path((_server, _database_instance, database)): Path<(String, uuid::Uuid, uuid::Uuid)>,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("database-instances.access-tokens")?;
+
+        let access_tokens =
+            ServerDatabaseAccessToken::by_server_database_instance_uuid_instance_database_uuid_with_pagination(
+                &state.database,
+                database_instance.uuid,
+                database,
+                params.page,
+                params.per_page,
+                params.search.as_deref(),
+            )
+            .await?;
+
+        ApiResponse::new_serialized(Response {
+            access_tokens: access_tokens
+                .try_async_map(|access_token| access_token.into_api_object(&state, ()))
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{extract::Path, http::StatusCode};
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel, IntoApiObject,
+            server::GetServerActivityLogger,
+            server_database_access_token::{
+                CreateServerDatabaseAccessTokenOptions, MAX_ACCESS_TOKENS_PER_DATABASE,
+                ServerDatabaseAccessToken, ServerDatabaseAccessTokenTarget,
+            },
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(length(chars, min = 1, max = 63))]
+        #[schema(min_length = 1, max_length = 63)]
+        name: compact_str::CompactString,
+        #[garde(custom(shared::models::server_database_access_token::validate_expires))]
+        expires: chrono::DateTime<chrono::Utc>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        access_token: shared::models::server_database_access_token::ApiServerDatabaseAccessToken,
+        password: String,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetServerActivityLogger,
+        database_instance: GetServerDatabaseInstance,
+        Path((_server, _database_instance, database)): Path<(String, uuid::Uuid, uuid::Uuid)>,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("database-instances.access-tokens")?;
+
+        if database_instance.database_agent_host.maintenance_enabled {
+            return ApiResponse::error(
+                "cannot create access tokens while database agent host is in maintenance mode",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .ok();
+        }
+
+        let access_tokens_lock = state
+            .cache
+            .lock(
+                format!("database-instances::{}::users", database_instance.uuid),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        if ServerDatabaseAccessToken::count_by_server_database_instance_uuid_instance_database_uuid(
+            &state.database,
+            database_instance.uuid,
+            database,
+        )
+        .await?
+            >= MAX_ACCESS_TOKENS_PER_DATABASE
+        {
+            return ApiResponse::error("maximum number of access tokens reached")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        let (password, access_token) = ServerDatabaseAccessToken::create(
+            &state,
+            CreateServerDatabaseAccessTokenOptions {
+                target: ServerDatabaseAccessTokenTarget::InstanceDatabase {
+                    instance: &database_instance,
+                    database_uuid: database,
+                },
+                name: data.name,
+                expires: data.expires,
+            },
+        )
+        .await?;
+
+        drop(access_tokens_lock);
+
+        activity_logger
+            .log(
+                "server:database-instance.access-token.create",
+                serde_json::json!({
+                    "uuid": database_instance.uuid,
+                    "name": database_instance.name,
+                    "database_uuid": database,
+                    "access_token_uuid": access_token.uuid,
+                    "access_token_name": access_token.name,
+                    "username": access_token.username,
+                    "expires": access_token.expires.and_utc(),
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {
+            access_token: access_token.into_api_object(&state, ()).await?,
+            password,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{access_token}", _access_token_::router(state))
+        .with_state(state.clone())
+}
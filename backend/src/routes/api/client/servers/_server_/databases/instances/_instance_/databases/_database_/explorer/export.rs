@@ -0,0 +1,129 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{extract::Path, http::StatusCode};
+    use futures_util::StreamExt;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::GetServerActivityLogger,
+            server_database::{ExportOptions, QUERY_ACTIVITY_LENGTH},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = String),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = REQUEST_TIMEOUT, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(ExportOptions))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_instance: GetServerDatabaseInstance,
+        activity_logger: GetServerActivityLogger,
+        Path((_server, _database_instance, database)): Path<(String, uuid::Uuid, uuid::Uuid)>,
+        shared::Payload(data): shared::Payload<ExportOptions>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        if data.query.is_some() {
+            permissions.has_server_permission("database-instances.query-raw")?;
+        } else {
+            permissions.has_server_permission("database-instances.query")?;
+        }
+
+        let source = match data.source() {
+            Ok(source) => source,
+            Err(err) => return ApiResponse::from(err).ok(),
+        };
+
+        let database_instance = database_instance.0;
+        let (uuid, name) = (database_instance.uuid, database_instance.name.clone());
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(4);
+
+        tokio::spawn({
+            let state = state.clone();
+            let format = data.format;
+
+            async move {
+                database_instance
+                    .export_database(&state.database, database, &source, format, sender)
+                    .await;
+            }
+        });
+
+        // errors before the first chunk, like an unknown table, still get a proper response
+        let first = match receiver.recv().await {
+            Some(Err(err)) => return ApiResponse::from(err).ok(),
+            first => first,
+        };
+
+        activity_logger
+            .log(
+                "server:database-instance.database.rows-export",
+                serde_json::json!({
+                    "uuid": uuid,
+                    "name": name,
+                    "database_uuid": database,
+                    "format": data.format,
+                    "table": data.table,
+                    "query": data
+                        .query
+                        .as_ref()
+                        .map(|query| query.chars().take(QUERY_ACTIVITY_LENGTH).collect::<String>()),
+                }),
+            )
+            .await;
+
+        let chunks = futures_util::stream::iter(first).chain(futures_util::stream::unfold(
+            receiver,
+            |mut receiver| async move { receiver.recv().await.map(|chunk| (chunk, receiver)) },
+        ));
+
+        ApiResponse::new_stream(tokio_util::io::StreamReader::new(chunks.map(|chunk| {
+            chunk
+                .map(std::io::Cursor::new)
+                .map_err(std::io::Error::other)
+        })))
+        .with_header("Content-Type", data.format.content_type())
+        .with_header(
+            "Content-Disposition",
+            format!("attachment; filename=\"{}\"", data.file_name()),
+        )
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,115 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{
+        extract::{Path, Query},
+        http::StatusCode,
+    };
+    use futures_util::TryStreamExt;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::GetServerActivityLogger,
+            server_database::{ImportOptions, ImportSummary},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = ImportSummary),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database" = uuid::Uuid,
+            description = "The database ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "schema" = Option<String>, Query,
+            description = "The schema of the table to import into",
+        ),
+        (
+            "table" = String, Query,
+            description = "The table to import into",
+        ),
+        (
+            "format" = String, Query,
+            description = "The format of the file, csv or jsonl",
+            example = "csv",
+        ),
+        (
+            "mapping" = Option<String>, Query,
+            description = "Comma separated field:column pairs, fields left out are skipped",
+            example = "id:user_id,name:username",
+        ),
+    ), request_body = Vec<u8>)]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_instance: GetServerDatabaseInstance,
+        activity_logger: GetServerActivityLogger,
+        Path((_server, _database_instance, database)): Path<(String, uuid::Uuid, uuid::Uuid)>,
+        Query(params): Query<ImportOptions>,
+        body: axum::body::Body,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("database-instances.edit-rows")?;
+
+        let body_reader = tokio_util::io::StreamReader::new(
+            body.into_data_stream().map_err(std::io::Error::other),
+        );
+
+        let summary = match database_instance
+            .import_database(&state.database, database, &params, body_reader)
+            .await
+        {
+            Ok(summary) => summary,
+            Err(err) => return ApiResponse::from(err).ok(),
+        };
+
+        activity_logger
+            .log(
+                "server:database-instance.database.rows-import",
+                serde_json::json!({
+                    "uuid": database_instance.uuid,
+                    "name": database_instance.name,
+                    "database_uuid": database,
+                    "table": params.table,
+                    "format": params.format,
+                    "imported": summary.imported,
+                    "failed": summary.failed,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(summary).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -1,6 +1,8 @@
 use super::State;
 use utoipa_axum::router::OpenApiRouter;
 
+mod export;
+mod import;
 mod query;
 mod rows;
 mod schema;
@@ -8,6 +10,8 @@ mod tables;
 
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
+        .nest("/export", export::router(state))
+        .nest("/import", import::router(state))
         .nest("/query", query::router(state))
         .nest("/rows", rows::router(state))
         .nest("/schema", schema::router(state))
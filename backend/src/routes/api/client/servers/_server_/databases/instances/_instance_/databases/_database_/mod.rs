@@ -1,6 +1,7 @@
 use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
+mod access_tokens;
 mod explorer;
 mod export;
 mod import;
@@ -81,5 +82,6 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/import", import::router(state))
         .nest("/recreate", recreate::router(state))
         .nest("/size", size::router(state))
+        .nest("/access-tokens", access_tokens::router(state))
         .with_state(state.clone())
 }
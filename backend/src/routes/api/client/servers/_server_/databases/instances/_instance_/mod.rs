@@ -22,6 +22,8 @@ mod logs;
 mod operations;
 mod power;
 mod resources;
+mod snapshot_policies;
+mod snapshots;
 mod update;
 mod users;
 mod ws;
@@ -362,6 +364,8 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/operations", operations::router(state))
         .nest("/power", power::router(state))
         .nest("/resources", resources::router(state))
+        .nest("/snapshots", snapshots::router(state))
+        .nest("/snapshot-policies", snapshot_policies::router(state))
         .nest("/update", update::router(state))
         .nest("/ws", ws::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
@@ -0,0 +1,190 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod patch {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            UpdatableModel,
+            server::GetServerActivityLogger,
+            server_database_instance_snapshot_policy::{
+                ServerDatabaseInstanceSnapshotPolicy,
+                UpdateServerDatabaseInstanceSnapshotPolicyOptions,
+            },
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "snapshot_policy" = uuid::Uuid,
+            description = "The snapshot policy ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(UpdateServerDatabaseInstanceSnapshotPolicyOptions))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_instance: GetServerDatabaseInstance,
+        activity_logger: GetServerActivityLogger,
+        Path((_server, _database_instance, snapshot_policy)): Path<(
+            String,
+            uuid::Uuid,
+            uuid::Uuid,
+        )>,
+        shared::Payload(data): shared::Payload<UpdateServerDatabaseInstanceSnapshotPolicyOptions>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("database-instances.snapshots")?;
+
+        let mut snapshot_policy =
+            match ServerDatabaseInstanceSnapshotPolicy::by_server_database_instance_uuid_uuid(
+                &state.database,
+                database_instance.uuid,
+                snapshot_policy,
+            )
+            .await?
+            {
+                Some(snapshot_policy) => snapshot_policy,
+                None => {
+                    return ApiResponse::error("snapshot policy not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        snapshot_policy.update(&state, data).await?;
+
+        activity_logger
+            .log(
+                "server:database-instance.snapshot-policy.update",
+                serde_json::json!({
+                    "uuid": database_instance.uuid,
+                    "name": database_instance.name,
+                    "snapshot_policy_uuid": snapshot_policy.uuid,
+                    "snapshot_policy_name": snapshot_policy.name,
+                    "enabled": snapshot_policy.enabled,
+                    "cron": snapshot_policy.cron.to_string(),
+                    "retention_count": snapshot_policy.retention_count,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+mod delete {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel, server::GetServerActivityLogger,
+            server_database_instance_snapshot_policy::ServerDatabaseInstanceSnapshotPolicy,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "snapshot_policy" = uuid::Uuid,
+            description = "The snapshot policy ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_instance: GetServerDatabaseInstance,
+        activity_logger: GetServerActivityLogger,
+        Path((_server, _database_instance, snapshot_policy)): Path<(
+            String,
+            uuid::Uuid,
+            uuid::Uuid,
+        )>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("database-instances.snapshots")?;
+
+        let snapshot_policy =
+            match ServerDatabaseInstanceSnapshotPolicy::by_server_database_instance_uuid_uuid(
+                &state.database,
+                database_instance.uuid,
+                snapshot_policy,
+            )
+            .await?
+            {
+                Some(snapshot_policy) => snapshot_policy,
+                None => {
+                    return ApiResponse::error("snapshot policy not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        snapshot_policy.delete(&state, ()).await?;
+
+        activity_logger
+            .log(
+                "server:database-instance.snapshot-policy.delete",
+                serde_json::json!({
+                    "uuid": database_instance.uuid,
+                    "name": database_instance.name,
+                    "snapshot_policy_uuid": snapshot_policy.uuid,
+                    "snapshot_policy_name": snapshot_policy.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(patch::route))
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,238 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _snapshot_policy_;
+
+mod get {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, Pagination, PaginationParamsWithSearch,
+            server_database_instance_snapshot_policy::ServerDatabaseInstanceSnapshotPolicy,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        snapshot_policies: Pagination<
+            shared::models::server_database_instance_snapshot_policy::ApiServerDatabaseInstanceSnapshotPolicy,
+        >,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_instance: GetServerDatabaseInstance,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("database-instances.snapshots")?;
+
+        let snapshot_policies =
+            ServerDatabaseInstanceSnapshotPolicy::by_server_database_instance_uuid_with_pagination(
+                &state.database,
+                database_instance.uuid,
+                params.page,
+                params.per_page,
+                params.search.as_deref(),
+            )
+            .await?;
+
+        ApiResponse::new_serialized(Response {
+            snapshot_policies: snapshot_policies
+                .try_async_map(|snapshot_policy| snapshot_policy.into_api_object(&state, ()))
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel, IntoApiObject,
+            server::GetServerActivityLogger,
+            server_database_instance_snapshot_policy::{
+                CreateServerDatabaseInstanceSnapshotPolicyOptions,
+                ServerDatabaseInstanceSnapshotPolicy,
+            },
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(length(chars, min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        name: compact_str::CompactString,
+        #[garde(skip)]
+        enabled: bool,
+        #[garde(skip)]
+        #[schema(value_type = String, example = "0 0 0 * * *")]
+        cron: croner::Cron,
+        #[garde(range(min = 1, max = 100))]
+        #[schema(minimum = 1, maximum = 100)]
+        retention_count: i32,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        snapshot_policy: shared::models::server_database_instance_snapshot_policy::ApiServerDatabaseInstanceSnapshotPolicy,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_instance: GetServerDatabaseInstance,
+        activity_logger: GetServerActivityLogger,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("database-instances.snapshots")?;
+
+        let snapshot_policies_lock = state
+            .cache
+            .lock(
+                format!(
+                    "server_database_instances::{}::snapshot_policies",
+                    database_instance.uuid
+                ),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        let snapshot_policies =
+            ServerDatabaseInstanceSnapshotPolicy::count_by_server_database_instance_uuid(
+                &state.database,
+                database_instance.uuid,
+            )
+            .await?;
+        let max_snapshot_policies = state
+            .settings
+            .get()
+            .await?
+            .server
+            .max_database_instance_snapshot_policy_count;
+        if snapshot_policies >= max_snapshot_policies as i64 {
+            return ApiResponse::error("maximum number of snapshot policies reached")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        let snapshot_policy = ServerDatabaseInstanceSnapshotPolicy::create(
+            &state,
+            CreateServerDatabaseInstanceSnapshotPolicyOptions {
+                server_database_instance_uuid: database_instance.uuid,
+                name: data.name,
+                enabled: data.enabled,
+                cron: data.cron,
+                retention_count: data.retention_count,
+            },
+        )
+        .await?;
+
+        drop(snapshot_policies_lock);
+
+        activity_logger
+            .log(
+                "server:database-instance.snapshot-policy.create",
+                serde_json::json!({
+                    "uuid": database_instance.uuid,
+                    "name": database_instance.name,
+                    "snapshot_policy_uuid": snapshot_policy.uuid,
+                    "snapshot_policy_name": snapshot_policy.name,
+                    "enabled": snapshot_policy.enabled,
+                    "cron": snapshot_policy.cron.to_string(),
+                    "retention_count": snapshot_policy.retention_count,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {
+            snapshot_policy: snapshot_policy.into_api_object(&state, ()).await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{snapshot_policy}", _snapshot_policy_::router(state))
+        .with_state(state.clone())
+}
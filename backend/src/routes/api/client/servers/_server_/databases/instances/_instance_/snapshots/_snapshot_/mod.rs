@@ -0,0 +1,174 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod restore;
+
+mod get {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, server_database_instance_snapshot::ServerDatabaseInstanceSnapshot,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        snapshot:
+            shared::models::server_database_instance_snapshot::ApiServerDatabaseInstanceSnapshot,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "snapshot" = uuid::Uuid,
+            description = "The snapshot ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_instance: GetServerDatabaseInstance,
+        Path((_server, _database_instance, snapshot)): Path<(String, uuid::Uuid, uuid::Uuid)>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("database-instances.snapshots")?;
+
+        let snapshot = match ServerDatabaseInstanceSnapshot::by_server_database_instance_uuid_uuid(
+            &state.database,
+            database_instance.uuid,
+            snapshot,
+        )
+        .await?
+        {
+            Some(snapshot) => snapshot,
+            None => {
+                return ApiResponse::error("snapshot not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        ApiResponse::new_serialized(Response {
+            snapshot: snapshot.into_api_object(&state, ()).await?,
+        })
+        .ok()
+    }
+}
+
+mod delete {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel,
+            server::GetServerActivityLogger,
+            server_database_instance_snapshot::{
+                ServerDatabaseInstanceSnapshot, ServerDatabaseInstanceSnapshotStatus,
+            },
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "snapshot" = uuid::Uuid,
+            description = "The snapshot ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_instance: GetServerDatabaseInstance,
+        activity_logger: GetServerActivityLogger,
+        Path((_server, _database_instance, snapshot)): Path<(String, uuid::Uuid, uuid::Uuid)>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("database-instances.snapshots")?;
+
+        let snapshot = match ServerDatabaseInstanceSnapshot::by_server_database_instance_uuid_uuid(
+            &state.database,
+            database_instance.uuid,
+            snapshot,
+        )
+        .await?
+        {
+            Some(snapshot) => snapshot,
+            None => {
+                return ApiResponse::error("snapshot not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        if snapshot.status() == ServerDatabaseInstanceSnapshotStatus::Pending {
+            return ApiResponse::error("snapshot is still being created")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        snapshot.delete(&state, ()).await?;
+
+        activity_logger
+            .log(
+                "server:database-instance.snapshot.delete",
+                serde_json::json!({
+                    "uuid": database_instance.uuid,
+                    "name": database_instance.name,
+                    "snapshot_uuid": snapshot.uuid,
+                    "snapshot_name": snapshot.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(delete::route))
+        .nest("/restore", restore::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,117 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::{GetServer, GetServerActivityLogger},
+            server_database_instance_snapshot::ServerDatabaseInstanceSnapshot,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "snapshot" = uuid::Uuid,
+            description = "The snapshot ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        database_instance: GetServerDatabaseInstance,
+        activity_logger: GetServerActivityLogger,
+        Path((_server, _database_instance, snapshot)): Path<(String, uuid::Uuid, uuid::Uuid)>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("database-instances.restore-snapshots")?;
+
+        let snapshot = match ServerDatabaseInstanceSnapshot::by_server_database_instance_uuid_uuid(
+            &state.database,
+            database_instance.uuid,
+            snapshot,
+        )
+        .await?
+        {
+            Some(snapshot) => snapshot,
+            None => {
+                return ApiResponse::error("snapshot not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        if database_instance.database_agent_host.maintenance_enabled {
+            return ApiResponse::error(
+                "cannot restore snapshot while database agent host is in maintenance mode",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .ok();
+        }
+
+        if let Err(err) = snapshot.restore(&state, &database_instance).await {
+            if err
+                .downcast_ref::<shared::response::DisplayError>()
+                .is_some()
+            {
+                return ApiResponse::from(err).ok();
+            }
+
+            tracing::error!(
+                server = %server.uuid,
+                snapshot = %snapshot.uuid,
+                "failed to restore database instance snapshot: {:?}",
+                err
+            );
+
+            return ApiResponse::error("failed to restore database instance snapshot")
+                .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+                .ok();
+        }
+
+        activity_logger
+            .log(
+                "server:database-instance.snapshot.restore",
+                serde_json::json!({
+                    "uuid": database_instance.uuid,
+                    "name": database_instance.name,
+                    "snapshot_uuid": snapshot.uuid,
+                    "snapshot_name": snapshot.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
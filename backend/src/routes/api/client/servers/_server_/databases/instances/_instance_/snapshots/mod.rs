@@ -0,0 +1,222 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _snapshot_;
+
+mod get {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, Pagination, PaginationParamsWithSearch,
+            server_database_instance_snapshot::ServerDatabaseInstanceSnapshot,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        snapshots: Pagination<
+            shared::models::server_database_instance_snapshot::ApiServerDatabaseInstanceSnapshot,
+        >,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_instance: GetServerDatabaseInstance,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("database-instances.snapshots")?;
+
+        let snapshots =
+            ServerDatabaseInstanceSnapshot::by_server_database_instance_uuid_with_pagination(
+                &state.database,
+                database_instance.uuid,
+                params.page,
+                params.per_page,
+                params.search.as_deref(),
+            )
+            .await?;
+
+        ApiResponse::new_serialized(Response {
+            snapshots: snapshots
+                .try_async_map(|snapshot| snapshot.into_api_object(&state, ()))
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use crate::routes::api::client::servers::_server_::databases::instances::_instance_::GetServerDatabaseInstance;
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, server::GetServerActivityLogger,
+            server_database_instance_snapshot::ServerDatabaseInstanceSnapshot,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(length(chars, min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        name: Option<compact_str::CompactString>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        snapshot:
+            shared::models::server_database_instance_snapshot::ApiServerDatabaseInstanceSnapshot,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "database_instance" = uuid::Uuid,
+            description = "The database instance ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        database_instance: GetServerDatabaseInstance,
+        activity_logger: GetServerActivityLogger,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("database-instances.snapshots")?;
+
+        let snapshots_lock = state
+            .cache
+            .lock(
+                format!(
+                    "server_database_instances::{}::snapshots",
+                    database_instance.uuid
+                ),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        if ServerDatabaseInstanceSnapshot::count_pending_by_server_database_instance_uuid(
+            &state.database,
+            database_instance.uuid,
+        )
+        .await?
+            > 0
+        {
+            return ApiResponse::error("a snapshot of this database instance is already running")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        let mut snapshot = ServerDatabaseInstanceSnapshot::create_for_instance(
+            &state,
+            &database_instance,
+            None,
+            data.name
+                .unwrap_or_else(ServerDatabaseInstanceSnapshot::default_name),
+        )
+        .await?;
+
+        drop(snapshots_lock);
+
+        activity_logger
+            .log(
+                "server:database-instance.snapshot.create",
+                serde_json::json!({
+                    "uuid": database_instance.uuid,
+                    "name": database_instance.name,
+                    "snapshot_uuid": snapshot.uuid,
+                    "snapshot_name": snapshot.name,
+                }),
+            )
+            .await;
+
+        let response = snapshot.clone().into_api_object(&state, ()).await?;
+
+        let state = state.clone();
+        let database_instance = database_instance.0;
+        tokio::spawn(async move {
+            // failures are recorded on the snapshot itself
+            snapshot.run(&state, &database_instance).await.ok();
+        });
+
+        ApiResponse::new_serialized(Response { snapshot: response }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{snapshot}", _snapshot_::router(state))
+        .with_state(state.clone())
+}
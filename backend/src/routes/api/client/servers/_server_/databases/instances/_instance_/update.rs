@@ -9,6 +9,7 @@ mod post {
         ApiError, GetState,
         models::{
             IntoApiObject,
+            database_agent_template_revision::DatabaseAgentTemplateRevision,
             server::{GetServer, GetServerActivityLogger},
             user::GetPermissionManager,
         },
@@ -80,6 +81,20 @@ mod post {
             .ok();
         }
 
+        let Some(template) = DatabaseAgentTemplateRevision::resolve_template(
+            &state.database,
+            &template,
+            database_instance.target_template_version(&template),
+        )
+        .await?
+        else {
+            return ApiResponse::error(
+                "cannot apply update: pinned database agent template revision no longer exists",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .ok();
+        };
+
         let version = template.version;
         let from_version = database_instance.template_version;
 
@@ -124,7 +139,7 @@ mod post {
         }
 
         database_instance
-            .set_template_version(&state.database, version)
+            .set_template_version(&state.database, Some(version))
             .await?;
 
         activity_logger
@@ -10,6 +10,7 @@ mod put {
         models::{
             server::GetServerActivityLogger,
             server_database_instance::ApiServerDatabaseInstanceUserDatabase,
+            server_database_instance_database_quota::ServerDatabaseInstanceDatabaseQuota,
             user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
@@ -30,6 +31,7 @@ mod put {
         (status = OK, body = inline(Response)),
         (status = UNAUTHORIZED, body = ApiError),
         (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
     ), params(
         (
             "server" = uuid::Uuid,
@@ -67,6 +69,15 @@ mod put {
     ) -> ApiResponseResult {
         permissions.has_server_permission("database-instances.users")?;
 
+        if matches!(data.permission, db_agent_api::DatabasePermission::ReadWrite) {
+            ServerDatabaseInstanceDatabaseQuota::ensure_writable(
+                &state.database,
+                database_instance.uuid,
+                &[database],
+            )
+            .await?;
+        }
+
         let response = database_instance
             .database_agent_host
             .api_client(&state.database)
@@ -15,6 +15,7 @@ mod put {
             server_database_instance::{
                 ApiServerDatabaseInstanceUserDatabase, ServerDatabaseInstanceUserDatabaseGrant,
             },
+            server_database_instance_database_quota::ServerDatabaseInstanceDatabaseQuota,
             user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
@@ -38,6 +39,7 @@ mod put {
         (status = BAD_REQUEST, body = ApiError),
         (status = UNAUTHORIZED, body = ApiError),
         (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
     ), params(
         (
             "server" = uuid::Uuid,
@@ -71,6 +73,23 @@ mod put {
 
         permissions.has_server_permission("database-instances.users")?;
 
+        ServerDatabaseInstanceDatabaseQuota::ensure_writable(
+            &state.database,
+            database_instance.uuid,
+            &data
+                .databases
+                .iter()
+                .filter(|grant| {
+                    matches!(
+                        grant.permission,
+                        db_agent_api::DatabasePermission::ReadWrite
+                    )
+                })
+                .map(|grant| grant.database_uuid)
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+
         let databases: Vec<_> = data
             .databases
             .into_iter()
@@ -73,6 +73,7 @@ mod post {
             server_database_instance::{
                 ApiServerDatabaseInstanceUser, ServerDatabaseInstanceUserDatabaseGrant,
             },
+            server_database_instance_database_quota::ServerDatabaseInstanceDatabaseQuota,
             user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
@@ -100,6 +101,7 @@ mod post {
         (status = BAD_REQUEST, body = ApiError),
         (status = UNAUTHORIZED, body = ApiError),
         (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
     ), params(
         (
             "server" = uuid::Uuid,
@@ -127,6 +129,23 @@ mod post {
 
         permissions.has_server_permission("database-instances.users")?;
 
+        ServerDatabaseInstanceDatabaseQuota::ensure_writable(
+            &state.database,
+            database_instance.uuid,
+            &data
+                .databases
+                .iter()
+                .filter(|grant| {
+                    matches!(
+                        grant.permission,
+                        db_agent_api::DatabasePermission::ReadWrite
+                    )
+                })
+                .map(|grant| grant.database_uuid)
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+
         let api_client = database_instance
             .database_agent_host
             .api_client(&state.database)
@@ -136,6 +136,7 @@ mod post {
         (status = UNAUTHORIZED, body = ApiError),
         (status = NOT_FOUND, body = ApiError),
         (status = CONFLICT, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
     ), params(
         (
             "server" = uuid::Uuid,
@@ -195,6 +196,18 @@ mod post {
             .ok();
         }
 
+        if !database_host.is_healthy() {
+            return ApiResponse::error("cannot create database while database host is unreachable")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        if !database_host.has_capacity(&state.database).await? {
+            return ApiResponse::error("database host has reached its capacity")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
         let options = shared::models::server_database::CreateServerDatabaseOptions {
             server: &server,
             database_host: &database_host,
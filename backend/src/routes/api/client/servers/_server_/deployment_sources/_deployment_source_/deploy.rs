@@ -0,0 +1,115 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::_server_::deployment_sources::_deployment_source_::GetServerDeploymentSource;
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject,
+            server::{GetServer, GetServerActivityLogger},
+            server_deployment::ServerDeployment,
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        deployment: shared::models::server_deployment::ApiServerDeployment,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "deployment_source" = uuid::Uuid,
+            description = "The deployment source ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        mut server: GetServer,
+        activity_logger: GetServerActivityLogger,
+        deployment_source: GetServerDeploymentSource,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("deployments.deploy")?;
+
+        if server.is_ignored(&deployment_source.target_path, true) {
+            return ApiResponse::error("target directory not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .ok();
+        }
+
+        let deployment_lock = state
+            .cache
+            .lock(
+                format!(
+                    "servers::{}::deployment_sources::{}::deploy",
+                    server.uuid, deployment_source.uuid
+                ),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        if ServerDeployment::running_by_server_deployment_source_uuid(
+            &state.database,
+            deployment_source.uuid,
+        )
+        .await?
+        .is_some()
+        {
+            return ApiResponse::error("a deployment is already running for this source")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        let deployment = deployment_source
+            .deploy(&state, Some(user.uuid), false)
+            .await?;
+
+        drop(deployment_lock);
+
+        activity_logger
+            .log(
+                "server:deployment.trigger",
+                serde_json::json!({
+                    "uuid": deployment.uuid,
+                    "deployment_source": deployment_source.uuid,
+                    "name": deployment_source.name,
+                    "webhook": false,
+                }),
+            )
+            .await;
+
+        let storage_url_retriever = state.storage.retrieve_urls().await?;
+
+        ApiResponse::new_serialized(Response {
+            deployment: deployment
+                .into_api_object(&state, &storage_url_retriever)
+                .await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
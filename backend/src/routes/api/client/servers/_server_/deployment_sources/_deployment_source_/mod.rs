@@ -0,0 +1,260 @@
+use super::State;
+use axum::{
+    extract::{Path, Request},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::{
+    GetState,
+    models::{
+        server::GetServer, server_deployment_source::ServerDeploymentSource,
+        user::GetPermissionManager,
+    },
+    response::ApiResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod deploy;
+mod deployments;
+mod webhook_token;
+
+pub type GetServerDeploymentSource = shared::extract::ConsumingExtension<ServerDeploymentSource>;
+
+pub async fn auth(
+    state: GetState,
+    permissions: GetPermissionManager,
+    server: GetServer,
+    Path(deployment_source): Path<Vec<String>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let deployment_source = match deployment_source.get(1).map(|s| s.parse::<uuid::Uuid>()) {
+        Some(Ok(id)) => id,
+        _ => {
+            return Ok(ApiResponse::error("invalid deployment source uuid")
+                .with_status(StatusCode::BAD_REQUEST)
+                .into_response());
+        }
+    };
+
+    if let Err(err) = permissions.has_server_permission("deployments.read") {
+        return Ok(err.into_response());
+    }
+
+    let deployment_source = ServerDeploymentSource::by_server_uuid_uuid(
+        &state.database,
+        server.uuid,
+        deployment_source,
+    )
+    .await;
+    let deployment_source = match deployment_source {
+        Ok(Some(deployment_source)) => deployment_source,
+        Ok(None) => {
+            return Ok(ApiResponse::error("deployment source not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .into_response());
+        }
+        Err(err) => return Ok(ApiResponse::from(err).into_response()),
+    };
+
+    req.extensions_mut().insert(server.0);
+    req.extensions_mut().insert(deployment_source);
+
+    Ok(next.run(req).await)
+}
+
+mod get {
+    use crate::routes::api::client::servers::_server_::deployment_sources::_deployment_source_::GetServerDeploymentSource;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{IntoApiObject, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    pub struct Response {
+        deployment_source: shared::models::server_deployment_source::ApiServerDeploymentSource,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "deployment_source" = uuid::Uuid,
+            description = "The deployment source ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        deployment_source: GetServerDeploymentSource,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("deployments.read")?;
+
+        ApiResponse::new_serialized(Response {
+            deployment_source: deployment_source.0.into_api_object(&state, ()).await?,
+        })
+        .ok()
+    }
+}
+
+mod delete {
+    use crate::routes::api::client::servers::_server_::deployment_sources::_deployment_source_::GetServerDeploymentSource;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{DeletableModel, server::GetServerActivityLogger, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "deployment_source" = uuid::Uuid,
+            description = "The deployment source ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetServerActivityLogger,
+        deployment_source: GetServerDeploymentSource,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("deployments.delete")?;
+
+        deployment_source.delete(&state, ()).await?;
+
+        activity_logger
+            .log(
+                "server:deployment-source.delete",
+                serde_json::json!({
+                    "uuid": deployment_source.uuid,
+                    "name": deployment_source.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+mod patch {
+    use crate::routes::api::client::servers::_server_::deployment_sources::_deployment_source_::GetServerDeploymentSource;
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            UpdatableModel,
+            server::{GetServer, GetServerActivityLogger},
+            server_deployment_source::UpdateServerDeploymentSourceOptions,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "deployment_source" = uuid::Uuid,
+            description = "The deployment source ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(UpdateServerDeploymentSourceOptions))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        mut server: GetServer,
+        activity_logger: GetServerActivityLogger,
+        mut deployment_source: GetServerDeploymentSource,
+        shared::Payload(data): shared::Payload<UpdateServerDeploymentSourceOptions>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("deployments.update")?;
+
+        if let Some(target_path) = &data.target_path
+            && server.is_ignored(target_path, true)
+        {
+            return ApiResponse::error("target directory not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .ok();
+        }
+
+        match deployment_source.update(&state, data).await {
+            Ok(_) => {}
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("deployment source with name already exists")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => return ApiResponse::from(err).ok(),
+        }
+
+        activity_logger
+            .log(
+                "server:deployment-source.update",
+                serde_json::json!({
+                    "uuid": deployment_source.uuid,
+                    "name": deployment_source.name,
+                    "git_repository": deployment_source.git_repository,
+                    "branch": deployment_source.branch,
+                    "subdirectory": deployment_source.subdirectory,
+                    "target_path": deployment_source.target_path,
+                    "pre_deploy_power_action": deployment_source.pre_deploy_power_action,
+                    "post_deploy_power_action": deployment_source.post_deploy_power_action,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(delete::route))
+        .routes(routes!(patch::route))
+        .nest("/deploy", deploy::router(state))
+        .nest("/deployments", deployments::router(state))
+        .nest("/webhook-token", webhook_token::router(state))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
+        .with_state(state.clone())
+}
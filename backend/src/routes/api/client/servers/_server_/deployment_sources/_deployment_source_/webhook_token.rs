@@ -0,0 +1,65 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::_server_::deployment_sources::_deployment_source_::GetServerDeploymentSource;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{server::GetServerActivityLogger, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        webhook_token: String,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "deployment_source" = uuid::Uuid,
+            description = "The deployment source ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetServerActivityLogger,
+        deployment_source: GetServerDeploymentSource,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("deployments.update")?;
+
+        let webhook_token = deployment_source
+            .rotate_webhook_token(&state.database)
+            .await?;
+
+        activity_logger
+            .log(
+                "server:deployment-source.rotate-webhook-token",
+                serde_json::json!({
+                    "uuid": deployment_source.uuid,
+                    "name": deployment_source.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response { webhook_token }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
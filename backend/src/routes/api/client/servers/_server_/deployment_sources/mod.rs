@@ -0,0 +1,238 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _deployment_source_;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, Pagination, PaginationParamsWithSearch, server::GetServer,
+            server_deployment_source::ServerDeploymentSource, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        deployment_sources:
+            Pagination<shared::models::server_deployment_source::ApiServerDeploymentSource>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("deployments.read")?;
+
+        let deployment_sources = ServerDeploymentSource::by_server_uuid_with_pagination(
+            &state.database,
+            server.uuid,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+        )
+        .await?;
+
+        ApiResponse::new_serialized(Response {
+            deployment_sources: deployment_sources
+                .try_async_map(|deployment_source| deployment_source.into_api_object(&state, ()))
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel, IntoApiObject,
+            egg_repository::EggRepositoryCredentials,
+            server::{GetServer, GetServerActivityLogger},
+            server_deployment_source::{ServerDeploymentPowerAction, ServerDeploymentSource},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(length(chars, min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        name: compact_str::CompactString,
+        #[garde(skip)]
+        #[schema(example = "https://github.com/example/repo.git", format = "uri")]
+        git_repository: compact_str::CompactString,
+        #[garde(skip)]
+        branch: Option<compact_str::CompactString>,
+        #[garde(skip)]
+        subdirectory: Option<compact_str::CompactString>,
+        #[garde(skip)]
+        target_path: compact_str::CompactString,
+        #[garde(skip)]
+        credentials: Option<EggRepositoryCredentials>,
+
+        #[garde(skip)]
+        pre_deploy_power_action: Option<ServerDeploymentPowerAction>,
+        #[garde(skip)]
+        post_deploy_power_action: Option<ServerDeploymentPowerAction>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        deployment_source: shared::models::server_deployment_source::ApiServerDeploymentSource,
+        webhook_token: String,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        mut server: GetServer,
+        activity_logger: GetServerActivityLogger,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("deployments.create")?;
+
+        if server.is_ignored(&data.target_path, true) {
+            return ApiResponse::error("target directory not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .ok();
+        }
+
+        let deployment_sources_lock = state
+            .cache
+            .lock(
+                format!("servers::{}::deployment_sources", server.uuid),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        let max_deployment_source_count = state
+            .settings
+            .get_as(|s| s.server.max_deployment_source_count)
+            .await?;
+
+        let deployment_sources =
+            ServerDeploymentSource::count_by_server_uuid(&state.database, server.uuid).await?;
+        if deployment_sources >= max_deployment_source_count as i64 {
+            return ApiResponse::error("maximum number of deployment sources reached")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        let options =
+            shared::models::server_deployment_source::CreateServerDeploymentSourceOptions {
+                server_uuid: server.uuid,
+                name: data.name,
+                git_repository: data.git_repository,
+                branch: data.branch,
+                subdirectory: data.subdirectory,
+                target_path: data.target_path,
+                credentials: data.credentials,
+                pre_deploy_power_action: data.pre_deploy_power_action,
+                post_deploy_power_action: data.post_deploy_power_action,
+            };
+        let (webhook_token, deployment_source) =
+            match ServerDeploymentSource::create(&state, options).await {
+                Ok(result) => result,
+                Err(err) if err.is_unique_violation() => {
+                    return ApiResponse::error("deployment source with name already exists")
+                        .with_status(StatusCode::CONFLICT)
+                        .ok();
+                }
+                Err(err) => return ApiResponse::from(err).ok(),
+            };
+
+        drop(deployment_sources_lock);
+
+        activity_logger
+            .log(
+                "server:deployment-source.create",
+                serde_json::json!({
+                    "uuid": deployment_source.uuid,
+                    "name": deployment_source.name,
+                    "git_repository": deployment_source.git_repository,
+                    "branch": deployment_source.branch,
+                    "subdirectory": deployment_source.subdirectory,
+                    "target_path": deployment_source.target_path,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {
+            deployment_source: deployment_source.into_api_object(&state, ()).await?,
+            webhook_token,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{deployment_source}", _deployment_source_::router(state))
+        .with_state(state.clone())
+}
@@ -7,8 +7,10 @@ mod post {
     use shared::{
         ApiError, GetState,
         models::{
+            CreatableModel,
             server::{GetServer, GetServerActivityLogger},
-            user::GetPermissionManager,
+            server_trashed_file::{CreateServerTrashedFileOptions, ServerTrashedFile},
+            user::{GetPermissionManager, GetUser},
         },
         response::{ApiResponse, ApiResponseResult},
     };
@@ -21,11 +23,15 @@ mod post {
         root: compact_str::CompactString,
 
         files: Vec<compact_str::CompactString>,
+
+        #[serde(default)]
+        permanent: bool,
     }
 
     #[derive(ToSchema, Serialize)]
     struct Response {
         deleted: u64,
+        trashed: bool,
     }
 
     #[utoipa::path(post, path = "/", responses(
@@ -43,18 +49,90 @@ mod post {
     pub async fn route(
         state: GetState,
         permissions: GetPermissionManager,
+        user: GetUser,
         mut server: GetServer,
         activity_logger: GetServerActivityLogger,
         shared::Payload(data): shared::Payload<Payload>,
     ) -> ApiResponseResult {
         permissions.has_server_permission("files.delete")?;
 
-        let files = data
+        let files: Vec<compact_str::CompactString> = data
             .files
             .into_iter()
             .filter(|f| !server.is_ignored(std::path::Path::new(&data.root).join(f), false))
             .collect();
 
+        let settings = state.settings.get().await?;
+        let trash_enabled = settings.server.file_trash_retention_days > 0;
+        drop(settings);
+
+        if trash_enabled && !data.permanent {
+            let request_body = wings_api::servers_server_files_trash::post::RequestBody {
+                root: data.root,
+                files,
+            };
+
+            let data = match server
+                .node
+                .fetch_cached(&state.database)
+                .await?
+                .api_client(&state.database)
+                .await?
+                .post_servers_server_files_trash(server.uuid, &request_body)
+                .await
+            {
+                Ok(data) => data,
+                // the files must not be gone for good unless the user asks for it
+                Err(wings_api::client::ApiHttpError::Http(
+                    StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED,
+                    _,
+                )) => {
+                    return ApiResponse::error(
+                        "the node of this server does not support the file trash, delete the files permanently instead",
+                    )
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
+                }
+                Err(wings_api::client::ApiHttpError::Http(StatusCode::EXPECTATION_FAILED, err)) => {
+                    return ApiResponse::new_serialized(ApiError::new_wings_value(err))
+                        .with_status(StatusCode::EXPECTATION_FAILED)
+                        .ok();
+                }
+                Err(err) => return Err(err.into()),
+            };
+
+            for trashed in &data.trashed {
+                ServerTrashedFile::create(
+                    &state,
+                    CreateServerTrashedFileOptions {
+                        uuid: trashed.id,
+                        server_uuid: server.uuid,
+                        user_uuid: Some(user.uuid),
+                        path: trashed.path.clone(),
+                        directory: trashed.directory,
+                        size: trashed.size as i64,
+                    },
+                )
+                .await?;
+            }
+
+            activity_logger
+                .log(
+                    "server:file.trash",
+                    serde_json::json!({
+                        "directory": request_body.root,
+                        "files": request_body.files.iter().collect::<Vec<_>>(),
+                    }),
+                )
+                .await;
+
+            return ApiResponse::new_serialized(Response {
+                deleted: data.trashed.len() as u64,
+                trashed: true,
+            })
+            .ok();
+        }
+
         let request_body = wings_api::servers_server_files_delete::post::RequestBody {
             root: data.root,
             files,
@@ -95,6 +173,7 @@ mod post {
 
         ApiResponse::new_serialized(Response {
             deleted: data.deleted,
+            trashed: false,
         })
         .ok()
     }
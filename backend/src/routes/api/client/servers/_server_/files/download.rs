@@ -4,18 +4,16 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 mod get {
     use axum::http::StatusCode;
     use axum_extra::extract::Query;
-    use compact_str::ToCompactString;
     use serde::{Deserialize, Serialize};
     use shared::{
         ApiError, GetState,
-        jwt::BasePayload,
         models::{
             server::{GetServer, GetServerActivityLogger},
             user::{GetPermissionManager, GetUser},
         },
         response::{ApiResponse, ApiResponseResult},
     };
-    use std::path::{Path, PathBuf};
+    use std::path::Path;
     use utoipa::ToSchema;
 
     #[derive(ToSchema, Deserialize)]
@@ -86,96 +84,17 @@ mod get {
 
         let node = server.node.fetch_cached(&state.database).await?;
 
-        let url = if params.files.len() == 1 {
-            #[derive(Serialize)]
-            struct FileDownloadJwt {
-                #[serde(flatten)]
-                base: BasePayload,
-
-                file_path: PathBuf,
-                server_uuid: uuid::Uuid,
-                unique_id: uuid::Uuid,
-            }
-
-            let token = node.create_jwt(
-                &state.database,
-                &state.jwt,
-                &FileDownloadJwt {
-                    base: BasePayload {
-                        scope: "file-download".into(),
-                        issuer: "panel".into(),
-                        subject: None,
-                        audience: Vec::new(),
-                        expiration_time: Some(chrono::Utc::now().timestamp() + 900),
-                        not_before: None,
-                        issued_at: Some(chrono::Utc::now().timestamp()),
-                        jwt_id: user.uuid.to_compact_string(),
-                    },
-                    file_path: Path::new(&params.root).join(&params.files[0]),
-                    server_uuid: server.uuid,
-                    unique_id: uuid::Uuid::new_v4(),
-                },
-            )?;
-
-            let mut url = node
-                .public_url(
-                    &state,
-                    if params.directory {
-                        "/download/directory"
-                    } else {
-                        "/download/file"
-                    },
-                )
-                .await?;
-            url.set_query(Some(&format!(
-                "token={}&archive_format={}",
-                urlencoding::encode(&token),
-                params.archive_format
-            )));
-
-            url
-        } else {
-            #[derive(Serialize)]
-            struct FilesDownloadJwt<'a> {
-                #[serde(flatten)]
-                base: BasePayload,
-
-                file_path: &'a str,
-                file_paths: &'a [String],
-                server_uuid: uuid::Uuid,
-                unique_id: uuid::Uuid,
-            }
-
-            let token = node.create_jwt(
-                &state.database,
-                &state.jwt,
-                &FilesDownloadJwt {
-                    base: BasePayload {
-                        scope: "file-download".into(),
-                        issuer: "panel".into(),
-                        subject: None,
-                        audience: Vec::new(),
-                        expiration_time: Some(chrono::Utc::now().timestamp() + 900),
-                        not_before: None,
-                        issued_at: Some(chrono::Utc::now().timestamp()),
-                        jwt_id: user.uuid.to_compact_string(),
-                    },
-                    file_path: &params.root,
-                    file_paths: &params.files,
-                    server_uuid: server.uuid,
-                    unique_id: uuid::Uuid::new_v4(),
-                },
-            )?;
-
-            let mut url = node.public_url(&state, "/download/files").await?;
-            url.set_query(Some(&format!(
-                "token={}&archive_format={}",
-                urlencoding::encode(&token),
-                params.archive_format
-            )));
-
-            url
-        };
+        let url = node
+            .file_download_url(
+                &state,
+                server.uuid,
+                user.uuid,
+                &params.root,
+                &params.files,
+                params.directory,
+                params.archive_format,
+            )
+            .await?;
 
         activity_logger
             .log(
@@ -0,0 +1,273 @@
+use super::State;
+use serde::{Deserialize, Serialize};
+use shared::models::server_file_event::ServerFileEvent;
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+/// How long an edit lock lives without being refreshed by its holder.
+const LOCK_TTL: u64 = 60;
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct FileEditLock {
+    file: compact_str::CompactString,
+    user_uuid: uuid::Uuid,
+    username: compact_str::CompactString,
+
+    acquired: chrono::DateTime<chrono::Utc>,
+    expires: chrono::DateTime<chrono::Utc>,
+}
+
+fn locks_prefix(server_uuid: uuid::Uuid) -> String {
+    format!("servers::{server_uuid}::file_locks::")
+}
+
+fn lock_key(server_uuid: uuid::Uuid, file: &str) -> String {
+    format!(
+        "{}{}",
+        locks_prefix(server_uuid),
+        ServerFileEvent::normalize_path(file)
+    )
+}
+
+async fn get_lock(
+    state: &shared::State,
+    server_uuid: uuid::Uuid,
+    file: &str,
+) -> Result<Option<FileEditLock>, anyhow::Error> {
+    Ok(state
+        .cache
+        .get::<FileEditLock>(&lock_key(server_uuid, file))
+        .await?
+        .filter(|lock| lock.expires > chrono::Utc::now()))
+}
+
+mod get {
+    use super::FileEditLock;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{server::GetServer, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        locks: Vec<FileEditLock>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("files.read")?;
+
+        let now = chrono::Utc::now();
+        let mut locks = Vec::new();
+
+        for key in state.cache.list(&super::locks_prefix(server.uuid)).await? {
+            if let Some(lock) = state.cache.get::<FileEditLock>(&key).await?
+                && lock.expires > now
+            {
+                locks.push(lock);
+            }
+        }
+
+        locks.sort_by(|a, b| a.file.cmp(&b.file));
+
+        ApiResponse::new_serialized(Response { locks }).ok()
+    }
+}
+
+mod post {
+    use super::FileEditLock;
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::GetServer,
+            server_file_event::ServerFileEvent,
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Payload {
+        file: compact_str::CompactString,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        lock: FileEditLock,
+    }
+
+    /// Acquires an edit lock on a file, or refreshes it if the user already holds it.
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        mut server: GetServer,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("files.create")?;
+
+        if server.is_ignored(&data.file, false) {
+            return ApiResponse::error("file not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .ok();
+        }
+
+        let file_locks_lock = state
+            .cache
+            .lock(
+                format!("servers::{}::file_locks", server.uuid),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        let now = chrono::Utc::now();
+        let acquired = match super::get_lock(&state, server.uuid, &data.file).await? {
+            Some(lock) if lock.user_uuid != user.uuid => {
+                return ApiResponse::error(format!(
+                    "file is currently being edited by {}",
+                    lock.username
+                ))
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+            }
+            Some(lock) => lock.acquired,
+            None => now,
+        };
+
+        let lock = FileEditLock {
+            file: ServerFileEvent::normalize_path(&data.file),
+            user_uuid: user.uuid,
+            username: user.username.clone(),
+            acquired,
+            expires: now + chrono::Duration::seconds(super::LOCK_TTL as i64),
+        };
+        state
+            .cache
+            .set(
+                &super::lock_key(server.uuid, &data.file),
+                super::LOCK_TTL,
+                &lock,
+            )
+            .await?;
+
+        drop(file_locks_lock);
+
+        ApiResponse::new_serialized(Response { lock }).ok()
+    }
+}
+
+mod delete {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::GetServer,
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Params {
+        file: compact_str::CompactString,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    /// Releases an edit lock held by the user, locks of other users expire on their own.
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "file" = String, Query,
+            description = "The file to release the edit lock of",
+            example = "/path/to/file.txt",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        server: GetServer,
+        Query(params): Query<Params>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("files.create")?;
+
+        let file_locks_lock = state
+            .cache
+            .lock(
+                format!("servers::{}::file_locks", server.uuid),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        if let Some(lock) = super::get_lock(&state, server.uuid, &params.file).await? {
+            if lock.user_uuid != user.uuid {
+                return ApiResponse::error(format!(
+                    "file is currently being edited by {}",
+                    lock.username
+                ))
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+            }
+
+            state
+                .cache
+                .invalidate(&super::lock_key(server.uuid, &params.file))
+                .await?;
+        }
+
+        drop(file_locks_lock);
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route, post::route, delete::route))
+        .with_state(state.clone())
+}
@@ -15,12 +15,16 @@ mod download;
 mod fingerprint;
 mod largest_directories;
 mod list;
+mod locks;
 mod operations;
 mod pull;
+mod quarantine;
 mod rename;
 mod revisions;
 mod search;
+mod shares;
 mod sqlite_query;
+mod trash;
 mod upload;
 mod write;
 
@@ -29,6 +33,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/list", list::router(state))
         .nest("/contents", contents::router(state))
         .nest("/fingerprint", fingerprint::router(state))
+        .nest("/locks", locks::router(state))
         .nest("/download", download::router(state))
         .nest("/upload", upload::router(state))
         .nest("/rename", rename::router(state))
@@ -48,5 +53,8 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/pull", pull::router(state))
         .nest("/operations", operations::router(state))
         .nest("/revisions", revisions::router(state))
+        .nest("/trash", trash::router(state))
+        .nest("/quarantine", quarantine::router(state))
+        .nest("/shares", shares::router(state))
         .with_state(state.clone())
 }
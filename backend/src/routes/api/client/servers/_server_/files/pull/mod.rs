@@ -5,12 +5,16 @@ mod query;
 
 mod post {
     use axum::http::StatusCode;
+    use compact_str::ToCompactString;
+    use futures_util::TryStreamExt;
     use garde::Validate;
     use serde::{Deserialize, Serialize};
     use shared::{
         ApiError, GetState,
         models::{
+            location::FileScanPolicy,
             server::{GetServer, GetServerActivityLogger},
+            server_quarantined_file::{FileScanReport, FileScanSource, ServerQuarantinedFile},
             user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
@@ -46,6 +50,82 @@ mod post {
         identifier: uuid::Uuid,
     }
 
+    /// Downloads the file on the panel so it can be scanned before it is written to the server,
+    /// returns `None` if it is larger than `max_size`.
+    async fn download(
+        state: &shared::State,
+        data: &Payload,
+        max_size: u64,
+    ) -> Result<Option<(compact_str::CompactString, Vec<u8>)>, anyhow::Error> {
+        let response = shared::net::outbound_client(&state.env)
+            .get(&data.url)
+            .timeout(std::time::Duration::from_secs(300))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "server responded with status {}",
+                response.status()
+            ));
+        }
+
+        if response
+            .content_length()
+            .is_some_and(|length| length > max_size)
+        {
+            return Ok(None);
+        }
+
+        let header_name = if data.use_header {
+            response
+                .headers()
+                .get(reqwest::header::CONTENT_DISPOSITION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| {
+                    value.split(';').find_map(|part| {
+                        part.trim()
+                            .strip_prefix("filename=")
+                            .map(|name| name.trim_matches('"').to_compact_string())
+                    })
+                })
+        } else {
+            None
+        };
+        let url_name: Option<compact_str::CompactString> = response
+            .url()
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                urlencoding::decode(segment).map_or_else(|_| segment.into(), Into::into)
+            });
+
+        let name = data
+            .name
+            .clone()
+            .or(header_name)
+            .or(url_name)
+            .unwrap_or_else(|| "download".into());
+        let name = std::path::Path::new(name.as_str())
+            .file_name()
+            .map(|name| name.to_string_lossy().to_compact_string())
+            .unwrap_or_else(|| "download".into());
+
+        let mut content = Vec::new();
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk) = stream.try_next().await? {
+            if (content.len() + chunk.len()) as u64 > max_size {
+                return Ok(None);
+            }
+
+            content.extend_from_slice(&chunk);
+        }
+
+        Ok(Some((name, content)))
+    }
+
     #[utoipa::path(post, path = "/", responses(
         (status = OK, body = inline(Response)),
         (status = ACCEPTED, body = inline(ResponseAccepted)),
@@ -89,6 +169,138 @@ mod post {
                 .ok();
         }
 
+        let node = server.node.fetch_cached(&state.database).await?;
+
+        if let Some((policy, _, max_size)) =
+            ServerQuarantinedFile::scan_policy(&state, &node).await?
+        {
+            let downloaded = match download(&state, &data, max_size).await {
+                Ok(downloaded) => downloaded,
+                Err(err) => {
+                    return ApiResponse::error(format!("failed to download file: {err}"))
+                        .with_status(StatusCode::EXPECTATION_FAILED)
+                        .ok();
+                }
+            };
+
+            match downloaded {
+                Some((name, content)) => {
+                    let file = std::path::Path::new(data.root.as_str())
+                        .join(name.as_str())
+                        .to_string_lossy()
+                        .to_compact_string();
+
+                    if server.is_ignored(&file, false) {
+                        return ApiResponse::error("root directory not found")
+                            .with_status(StatusCode::NOT_FOUND)
+                            .ok();
+                    }
+
+                    let report = match ServerQuarantinedFile::scan(
+                        &state,
+                        &node,
+                        server.uuid,
+                        Some(activity_logger.user_uuid),
+                        FileScanSource::Pull,
+                        &file,
+                        &content,
+                    )
+                    .await
+                    {
+                        Ok(report) => report,
+                        Err(err) => {
+                            tracing::error!(
+                                server = %server.uuid,
+                                "failed to scan pulled file: {:?}",
+                                err
+                            );
+
+                            return ApiResponse::error(
+                                "unable to scan file, please try again later",
+                            )
+                            .with_status(StatusCode::EXPECTATION_FAILED)
+                            .ok();
+                        }
+                    };
+
+                    if let Some(report) = &report {
+                        activity_logger
+                            .log("server:file.scan", serde_json::to_value(report)?)
+                            .await;
+
+                        if report.is_quarantined() {
+                            return ApiResponse::error(
+                                "file was flagged as malicious and has been quarantined",
+                            )
+                            .with_status(StatusCode::EXPECTATION_FAILED)
+                            .ok();
+                        }
+                    }
+
+                    match node
+                        .api_client(&state.database)
+                        .await?
+                        .post_servers_server_files_write(
+                            server.uuid,
+                            wings_api::client::AsyncRequestReader::new(std::io::Cursor::new(
+                                content,
+                            )),
+                            &wings_api::servers_server_files_write::post::Query {
+                                file: Some(file.clone()),
+                                user: Some(activity_logger.user_uuid),
+                                ..Default::default()
+                            },
+                        )
+                        .await
+                    {
+                        Ok(_) => {}
+                        Err(wings_api::client::ApiHttpError::Http(StatusCode::NOT_FOUND, err)) => {
+                            return ApiResponse::new_serialized(ApiError::new_wings_value(err))
+                                .with_status(StatusCode::NOT_FOUND)
+                                .ok();
+                        }
+                        Err(wings_api::client::ApiHttpError::Http(
+                            StatusCode::EXPECTATION_FAILED,
+                            err,
+                        )) => {
+                            return ApiResponse::new_serialized(ApiError::new_wings_value(err))
+                                .with_status(StatusCode::EXPECTATION_FAILED)
+                                .ok();
+                        }
+                        Err(err) => return Err(err.into()),
+                    }
+
+                    activity_logger
+                        .log(
+                            "server:file.pull",
+                            serde_json::json!({
+                                "directory": data.root,
+                                "url": data.url,
+                            }),
+                        )
+                        .await;
+
+                    return ApiResponse::new_serialized(Response {}).ok();
+                }
+                None if policy == FileScanPolicy::Block => {
+                    return ApiResponse::error("file is too large to be scanned")
+                        .with_status(StatusCode::EXPECTATION_FAILED)
+                        .ok();
+                }
+                None => {
+                    let report = FileScanReport::unscanned(
+                        data.name.as_deref().unwrap_or(data.url.as_str()),
+                        FileScanSource::Pull,
+                        policy,
+                    );
+
+                    activity_logger
+                        .log("server:file.scan", serde_json::to_value(&report)?)
+                        .await;
+                }
+            }
+        }
+
         let request_body = wings_api::servers_server_files_pull::post::RequestBody {
             root: data.root,
             url: data.url.into(),
@@ -97,10 +309,7 @@ mod post {
             foreground: data.foreground,
         };
 
-        let identifier = match server
-            .node
-            .fetch_cached(&state.database)
-            .await?
+        let identifier = match node
             .api_client(&state.database)
             .await?
             .post_servers_server_files_pull(server.uuid, &request_body)
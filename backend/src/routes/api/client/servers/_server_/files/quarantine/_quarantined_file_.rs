@@ -0,0 +1,83 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod delete {
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel,
+            server::{GetServer, GetServerActivityLogger},
+            server_quarantined_file::ServerQuarantinedFile,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "quarantined_file" = uuid::Uuid,
+            description = "The quarantined file ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        activity_logger: GetServerActivityLogger,
+        Path((_server, quarantined_file)): Path<(String, uuid::Uuid)>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("files.delete")?;
+
+        let quarantined_file = match ServerQuarantinedFile::by_server_uuid_uuid(
+            &state.database,
+            server.uuid,
+            quarantined_file,
+        )
+        .await?
+        {
+            Some(quarantined_file) => quarantined_file,
+            None => {
+                return ApiResponse::error("quarantined file not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        quarantined_file.delete(&state, ()).await?;
+
+        activity_logger
+            .log(
+                "server:file.quarantine-delete",
+                serde_json::json!({
+                    "uuid": quarantined_file.uuid,
+                    "file": quarantined_file.path,
+                    "signature": quarantined_file.signature,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,97 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _quarantined_file_;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, Pagination, PaginationParamsWithSearch, server::GetServer,
+            server_quarantined_file::ServerQuarantinedFile, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        retention_days: u16,
+        #[schema(inline)]
+        quarantined_files:
+            Pagination<shared::models::server_quarantined_file::ApiServerQuarantinedFile>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("files.read")?;
+
+        let quarantined_files = ServerQuarantinedFile::by_server_uuid_with_pagination(
+            &state.database,
+            server.uuid,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+        )
+        .await?;
+
+        let retention_days = state
+            .settings
+            .get_as(|s| s.server.file_quarantine_retention_days)
+            .await?;
+
+        let storage_url_retriever = state.storage.retrieve_urls().await?;
+
+        ApiResponse::new_serialized(Response {
+            retention_days,
+            quarantined_files: quarantined_files
+                .try_async_map(|quarantined_file| {
+                    quarantined_file.into_api_object(&state, &storage_url_retriever)
+                })
+                .await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .nest("/{quarantined_file}", _quarantined_file_::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,79 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod delete {
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel,
+            server::{GetServer, GetServerActivityLogger},
+            server_file_share::ServerFileShare,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "share" = uuid::Uuid,
+            description = "The file share ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        activity_logger: GetServerActivityLogger,
+        Path((_server, share)): Path<(String, uuid::Uuid)>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("files.share")?;
+
+        let share = match ServerFileShare::by_server_uuid_uuid(&state.database, server.uuid, share)
+            .await?
+        {
+            Some(share) => share,
+            None => {
+                return ApiResponse::error("file share not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        share.delete(&state, ()).await?;
+
+        activity_logger
+            .log(
+                "server:file.share-delete",
+                serde_json::json!({
+                    "uuid": share.uuid,
+                    "file": share.path,
+                    "directory": share.directory,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,228 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _share_;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, Pagination, PaginationParamsWithSearch, server::GetServer,
+            server_file_share::ServerFileShare, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        shares: Pagination<shared::models::server_file_share::ApiServerFileShare>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("files.share")?;
+
+        let shares = ServerFileShare::by_server_uuid_with_pagination(
+            &state.database,
+            server.uuid,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+        )
+        .await?;
+
+        let storage_url_retriever = state.storage.retrieve_urls().await?;
+
+        ApiResponse::new_serialized(Response {
+            shares: shares
+                .try_async_map(|share| share.into_api_object(&state, &storage_url_retriever))
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel, IntoApiObject,
+            server::{GetServer, GetServerActivityLogger},
+            server_file_share::{CreateServerFileShareOptions, ServerFileShare},
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use std::path::Path;
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(length(chars, min = 1))]
+        #[schema(min_length = 1)]
+        file: compact_str::CompactString,
+        #[garde(skip)]
+        #[serde(default)]
+        directory: bool,
+
+        #[garde(length(chars, min = 1, max = 512))]
+        #[schema(min_length = 1, max_length = 512)]
+        password: Option<compact_str::CompactString>,
+        #[garde(range(min = 1))]
+        #[schema(minimum = 1)]
+        max_downloads: Option<i32>,
+        #[garde(skip)]
+        expires: chrono::DateTime<chrono::Utc>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        share: shared::models::server_file_share::ApiServerFileShare,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        mut server: GetServer,
+        activity_logger: GetServerActivityLogger,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("files.share")?;
+        permissions.has_server_permission("files.read-content")?;
+
+        if data.expires <= chrono::Utc::now() {
+            return ApiResponse::error("expiration must be in the future")
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        if server.is_ignored(Path::new(&data.file), data.directory) {
+            return ApiResponse::new_serialized(ApiError::new_value(&["file not found"]))
+                .with_status(StatusCode::NOT_FOUND)
+                .ok();
+        }
+
+        let shares_lock = state
+            .cache
+            .lock(
+                format!("servers::{}::file-shares", server.uuid),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        let shares = ServerFileShare::count_by_server_uuid(&state.database, server.uuid).await?;
+        if shares >= state.settings.get().await?.server.max_file_share_count as i64 {
+            return ApiResponse::error("maximum number of file shares reached")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        let share = ServerFileShare::create(
+            &state,
+            CreateServerFileShareOptions {
+                server_uuid: server.uuid,
+                user_uuid: Some(user.uuid),
+                path: data.file,
+                directory: data.directory,
+                password: data.password,
+                max_downloads: data.max_downloads,
+                expires: data.expires.naive_utc(),
+            },
+        )
+        .await?;
+
+        drop(shares_lock);
+
+        activity_logger
+            .log(
+                "server:file.share-create",
+                serde_json::json!({
+                    "uuid": share.uuid,
+                    "file": share.path,
+                    "directory": share.directory,
+                    "has_password": share.has_password,
+                    "max_downloads": share.max_downloads,
+                    "expires": share.expires.and_utc(),
+                }),
+            )
+            .await;
+
+        let storage_url_retriever = state.storage.retrieve_urls().await?;
+
+        ApiResponse::new_serialized(Response {
+            share: share
+                .into_api_object(&state, &storage_url_retriever)
+                .await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{share}", _share_::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,188 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod purge;
+mod restore;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, Pagination, PaginationParamsWithSearch, server::GetServer,
+            server_trashed_file::ServerTrashedFile, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        retention_days: u16,
+        #[schema(inline)]
+        trashed_files: Pagination<shared::models::server_trashed_file::ApiServerTrashedFile>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("files.read")?;
+
+        let trashed_files = ServerTrashedFile::by_server_uuid_with_pagination(
+            &state.database,
+            server.uuid,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+        )
+        .await?;
+
+        let settings = state.settings.get().await?;
+        let retention_days = settings.server.file_trash_retention_days;
+        drop(settings);
+
+        let storage_url_retriever = state.storage.retrieve_urls().await?;
+
+        ApiResponse::new_serialized(Response {
+            retention_days,
+            trashed_files: trashed_files
+                .try_async_map(|trashed_file| {
+                    trashed_file.into_api_object(&state, &storage_url_retriever)
+                })
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod delete {
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::{GetServer, GetServerActivityLogger},
+            server_trashed_file::ServerTrashedFile,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        purged: u64,
+    }
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        activity_logger: GetServerActivityLogger,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("files.delete")?;
+
+        let trashed_files = ServerTrashedFile::all_by_server_uuid(&state.database, server.uuid)
+            .await?
+            .into_iter()
+            .map(|trashed_file| trashed_file.uuid)
+            .collect::<Vec<_>>();
+
+        if trashed_files.is_empty() {
+            return ApiResponse::new_serialized(Response { purged: 0 }).ok();
+        }
+
+        let request_body =
+            wings_api::servers_server_files_trash_purge::post::RequestBody { ids: trashed_files };
+
+        let data = match server
+            .node
+            .fetch_cached(&state.database)
+            .await?
+            .api_client(&state.database)
+            .await?
+            .post_servers_server_files_trash_purge(server.uuid, &request_body)
+            .await
+        {
+            Ok(data) => data,
+            Err(wings_api::client::ApiHttpError::Http(
+                StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED,
+                _,
+            )) => {
+                return ApiResponse::error(ServerTrashedFile::UNSUPPORTED_ERROR)
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        ServerTrashedFile::delete_by_uuids(&state.database, &request_body.ids).await?;
+
+        activity_logger
+            .log(
+                "server:file.trash-empty",
+                serde_json::json!({
+                    "purged": data.purged,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {
+            purged: data.purged,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(delete::route))
+        .nest("/restore", restore::router(state))
+        .nest("/purge", purge::router(state))
+        .with_state(state.clone())
+}
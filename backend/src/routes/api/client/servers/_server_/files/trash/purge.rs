@@ -0,0 +1,113 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::{GetServer, GetServerActivityLogger},
+            server_trashed_file::ServerTrashedFile,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Payload {
+        trashed_files: Vec<uuid::Uuid>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        purged: u64,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        activity_logger: GetServerActivityLogger,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("files.delete")?;
+
+        let trashed_files = ServerTrashedFile::by_server_uuid_uuids(
+            &state.database,
+            server.uuid,
+            &data.trashed_files,
+        )
+        .await?;
+        if trashed_files.is_empty() {
+            return ApiResponse::error("trashed files not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .ok();
+        }
+
+        let request_body = wings_api::servers_server_files_trash_purge::post::RequestBody {
+            ids: trashed_files
+                .iter()
+                .map(|trashed_file| trashed_file.uuid)
+                .collect(),
+        };
+
+        let data = match server
+            .node
+            .fetch_cached(&state.database)
+            .await?
+            .api_client(&state.database)
+            .await?
+            .post_servers_server_files_trash_purge(server.uuid, &request_body)
+            .await
+        {
+            Ok(data) => data,
+            Err(wings_api::client::ApiHttpError::Http(
+                StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED,
+                _,
+            )) => {
+                return ApiResponse::error(ServerTrashedFile::UNSUPPORTED_ERROR)
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        ServerTrashedFile::delete_by_uuids(&state.database, &request_body.ids).await?;
+
+        activity_logger
+            .log(
+                "server:file.trash-purge",
+                serde_json::json!({
+                    "files": trashed_files
+                        .iter()
+                        .map(|trashed_file| &trashed_file.path)
+                        .collect::<Vec<_>>(),
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {
+            purged: data.purged,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -9,6 +9,7 @@ mod get {
         jwt::BasePayload,
         models::{
             server::GetServer,
+            server_quarantined_file::{ScannedFileUploadJwt, ServerQuarantinedFile},
             user::{GetPermissionManager, GetUser},
         },
         response::{ApiResponse, ApiResponseResult},
@@ -53,6 +54,43 @@ mod get {
 
         let node = server.node.fetch_cached(&state.database).await?;
 
+        // scanned uploads go through the panel, so they never reach wings unscanned
+        if ServerQuarantinedFile::scan_policy(&state, &node)
+            .await?
+            .is_some()
+        {
+            let token = state.jwt.create(&ScannedFileUploadJwt {
+                base: BasePayload {
+                    scope: "scanned-file-upload".into(),
+                    issuer: "panel".into(),
+                    subject: None,
+                    audience: Vec::new(),
+                    expiration_time: Some(chrono::Utc::now().timestamp() + 900),
+                    not_before: None,
+                    issued_at: Some(chrono::Utc::now().timestamp()),
+                    jwt_id: user.uuid.to_compact_string(),
+                },
+                server_uuid: server.uuid,
+                user_uuid: user.uuid,
+                ignored_files: server.subuser_ignored_files.clone(),
+            })?;
+
+            let mut url = state
+                .settings
+                .get_as(|s| reqwest::Url::parse(&s.app.url))
+                .await??;
+            url.path_segments_mut()
+                .unwrap()
+                .pop_if_empty()
+                .extend(&["api", "file-uploads"]);
+            url.set_query(Some(&format!("token={}", urlencoding::encode(&token))));
+
+            return ApiResponse::new_serialized(Response {
+                url: url.to_string(),
+            })
+            .ok();
+        }
+
         let token = node.create_jwt(
             &state.database,
             &state.jwt,
@@ -9,6 +9,7 @@ mod post {
         ApiError, GetState,
         models::{
             server::{GetServer, GetServerActivityLogger},
+            server_file_event::ServerFileEvent,
             user::{GetPermissionManager, GetUser},
         },
         response::{ApiResponse, ApiResponseResult},
@@ -18,6 +19,9 @@ mod post {
     #[derive(ToSchema, Deserialize)]
     pub struct Params {
         file: compact_str::CompactString,
+
+        fingerprint: Option<compact_str::CompactString>,
+        algorithm: Option<wings_api::Algorithm>,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -27,6 +31,7 @@ mod post {
         (status = OK, body = inline(Response)),
         (status = UNAUTHORIZED, body = ApiError),
         (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
         (status = EXPECTATION_FAILED, body = ApiError),
     ), params(
         (
@@ -39,6 +44,15 @@ mod post {
             description = "The file to write contents to",
             example = "/path/to/file.txt",
         ),
+        (
+            "fingerprint" = Option<String>, Query,
+            description = "The fingerprint of the file when it was loaded, the write is rejected if it has changed since",
+        ),
+        (
+            "algorithm" = Option<wings_api::Algorithm>, Query,
+            description = "The algorithm the fingerprint was generated with, defaults to sha256",
+            example = "sha256",
+        ),
     ), request_body = String)]
     pub async fn route(
         state: GetState,
@@ -68,12 +82,67 @@ mod post {
             .map_err(std::io::Error::other),
         );
 
-        let revision_id = match server
+        let api_client = server
             .node
             .fetch_cached(&state.database)
             .await?
             .api_client(&state.database)
-            .await?
+            .await?;
+
+        // held until the write went through, so two saves of the same file cannot both pass the check
+        let _write_lock = match &params.fingerprint {
+            Some(fingerprint) => {
+                let write_lock = state
+                    .cache
+                    .lock(
+                        format!(
+                            "servers::{}::file_writes::{}",
+                            server.uuid,
+                            ServerFileEvent::normalize_path(&params.file)
+                        ),
+                        Some(30),
+                        Some(10),
+                    )
+                    .await?;
+
+                let current_fingerprint = match api_client
+                    .get_servers_server_files_fingerprints(
+                        server.uuid,
+                        &wings_api::servers_server_files_fingerprints::get::Query {
+                            algorithm: Some(
+                                params.algorithm.unwrap_or(wings_api::Algorithm::Sha256),
+                            ),
+                            files: Some(vec![params.file.clone()]),
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                {
+                    Ok(data) => data.fingerprints.first().map(|(_, hash)| hash.clone()),
+                    Err(wings_api::client::ApiHttpError::Http(StatusCode::NOT_FOUND, _)) => None,
+                    Err(err) => return Err(err.into()),
+                };
+
+                match current_fingerprint {
+                    Some(current_fingerprint) if current_fingerprint == *fingerprint => {}
+                    Some(_) => {
+                        return ApiResponse::error("file has been modified since it was loaded")
+                            .with_status(StatusCode::CONFLICT)
+                            .ok();
+                    }
+                    None => {
+                        return ApiResponse::error("file has been deleted since it was loaded")
+                            .with_status(StatusCode::CONFLICT)
+                            .ok();
+                    }
+                }
+
+                Some(write_lock)
+            }
+            None => None,
+        };
+
+        let revision_id = match api_client
             .post_servers_server_files_write(
                 server.uuid,
                 wings_api::client::AsyncRequestReader::new(body_reader),
@@ -0,0 +1,104 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use futures_util::StreamExt;
+    use serde::Deserialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::{GetServer, GetServerActivityLogger},
+            server_console_log_chunk::ServerConsoleLogChunk,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Params {
+        pub start: Option<chrono::DateTime<chrono::Utc>>,
+        pub end: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = String),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "start" = Option<chrono::DateTime<chrono::Utc>>, Query,
+            description = "Only include console output archived after this time",
+        ),
+        (
+            "end" = Option<chrono::DateTime<chrono::Utc>>, Query,
+            description = "Only include console output archived before this time",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        activity_logger: GetServerActivityLogger,
+        Query(params): Query<Params>,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("control.read-console")?;
+
+        let chunks = ServerConsoleLogChunk::by_server_uuid_range(
+            &state.database,
+            server.uuid,
+            params.start,
+            params.end,
+        )
+        .await?;
+
+        if chunks.is_empty() {
+            return ApiResponse::error("no archived console output in this time range")
+                .with_status(StatusCode::NOT_FOUND)
+                .ok();
+        }
+
+        activity_logger
+            .log(
+                "server:console-archive.download",
+                serde_json::json!({
+                    "start": params.start,
+                    "end": params.end,
+                }),
+            )
+            .await;
+
+        let contents = futures_util::stream::iter(chunks).then(move |chunk| {
+            let state = state.clone();
+
+            async move {
+                let mut content = chunk.content(&state.database).await?;
+                content.push('\n');
+
+                Ok::<_, shared::database::DatabaseError>(std::io::Cursor::new(content))
+            }
+        });
+
+        ApiResponse::new_stream(tokio_util::io::StreamReader::new(
+            contents.map(|content| content.map_err(std::io::Error::other)),
+        ))
+        .with_header("Content-Type", "text/plain")
+        .with_header(
+            "Content-Disposition",
+            format!("attachment; filename=\"console-{}.log\"", server.uuid),
+        )
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
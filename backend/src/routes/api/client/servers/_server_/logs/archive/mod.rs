@@ -0,0 +1,216 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod download;
+mod search;
+
+mod get {
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            IntoApiObject, server::GetServer, server_console_log_archive::ServerConsoleLogArchive,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        archive: Option<shared::models::server_console_log_archive::ApiServerConsoleLogArchive>,
+        retention_days: u16,
+        max_size: u64,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("control.read-console")?;
+
+        let settings = state.settings.get().await?;
+        let retention_days = settings.server.console_log_archive_retention_days;
+        let max_size = settings.server.max_console_log_archive_size;
+        drop(settings);
+
+        let archive =
+            match ServerConsoleLogArchive::by_server_uuid(&state.database, server.uuid).await? {
+                Some(archive) => {
+                    let storage_url_retriever = state.storage.retrieve_urls().await?;
+
+                    Some(
+                        archive
+                            .into_api_object(&state, &storage_url_retriever)
+                            .await?,
+                    )
+                }
+                None => None,
+            };
+
+        ApiResponse::new_serialized(Response {
+            archive,
+            retention_days,
+            max_size,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel, IntoApiObject,
+            server::{GetServer, GetServerActivityLogger},
+            server_console_log_archive::ServerConsoleLogArchive,
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        archive: shared::models::server_console_log_archive::ApiServerConsoleLogArchive,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        server: GetServer,
+        activity_logger: GetServerActivityLogger,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("settings.console-archive")?;
+
+        let options =
+            shared::models::server_console_log_archive::CreateServerConsoleLogArchiveOptions {
+                server_uuid: server.uuid,
+                user_uuid: Some(user.uuid),
+            };
+        let archive = match ServerConsoleLogArchive::create(&state, options).await {
+            Ok(archive) => archive,
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("console archiving is already enabled")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => return ApiResponse::from(err).ok(),
+        };
+
+        activity_logger
+            .log(
+                "server:console-archive.enable",
+                serde_json::json!({
+                    "uuid": archive.uuid,
+                }),
+            )
+            .await;
+
+        let storage_url_retriever = state.storage.retrieve_urls().await?;
+
+        ApiResponse::new_serialized(Response {
+            archive: archive
+                .into_api_object(&state, &storage_url_retriever)
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod delete {
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel,
+            server::{GetServer, GetServerActivityLogger},
+            server_console_log_archive::ServerConsoleLogArchive,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        activity_logger: GetServerActivityLogger,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("settings.console-archive")?;
+
+        let archive =
+            match ServerConsoleLogArchive::by_server_uuid(&state.database, server.uuid).await? {
+                Some(archive) => archive,
+                None => {
+                    return ApiResponse::error("console archiving is not enabled")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        archive.delete(&state, ()).await?;
+
+        activity_logger
+            .log(
+                "server:console-archive.disable",
+                serde_json::json!({
+                    "uuid": archive.uuid,
+                }),
+            )
+            .await;
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .routes(routes!(delete::route))
+        .nest("/search", search::router(state))
+        .nest("/download", download::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,137 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use garde::Validate;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::GetServer, server_console_log_chunk::ServerConsoleLogChunk,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    const MAX_CHUNKS: i64 = 25;
+    const MAX_MATCHES: usize = 500;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Params {
+        #[garde(length(chars, min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        pub query: compact_str::CompactString,
+
+        #[garde(skip)]
+        pub start: Option<chrono::DateTime<chrono::Utc>>,
+        #[garde(skip)]
+        pub end: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Match {
+        chunk: uuid::Uuid,
+        started: chrono::DateTime<chrono::Utc>,
+        ended: chrono::DateTime<chrono::Utc>,
+        line: String,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        matches: Vec<Match>,
+        truncated: bool,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "query" = String, Query,
+            description = "The words every matching console line has to contain",
+        ),
+        (
+            "start" = Option<chrono::DateTime<chrono::Utc>>, Query,
+            description = "Only search console output archived after this time",
+        ),
+        (
+            "end" = Option<chrono::DateTime<chrono::Utc>>, Query,
+            description = "Only search console output archived before this time",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        Query(params): Query<Params>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("control.read-console")?;
+
+        let chunks = ServerConsoleLogChunk::search_by_server_uuid(
+            &state.database,
+            server.uuid,
+            &params.query,
+            params.start,
+            params.end,
+            MAX_CHUNKS,
+        )
+        .await?;
+
+        // the search vector drops punctuation, so lines are matched on the raw words again
+        let terms = params
+            .query
+            .to_lowercase()
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        let mut matches = Vec::new();
+        let mut truncated = chunks.len() as i64 >= MAX_CHUNKS;
+
+        'chunks: for chunk in chunks {
+            let content = chunk.content(&state.database).await?;
+
+            for line in content.lines() {
+                let lowercase = line.to_lowercase();
+                if !terms.iter().all(|term| lowercase.contains(term.as_str())) {
+                    continue;
+                }
+
+                if matches.len() >= MAX_MATCHES {
+                    truncated = true;
+                    break 'chunks;
+                }
+
+                matches.push(Match {
+                    chunk: chunk.uuid,
+                    started: chunk.started.and_utc(),
+                    ended: chunk.ended.and_utc(),
+                    line: line.to_string(),
+                });
+            }
+        }
+
+        ApiResponse::new_serialized(Response { matches, truncated }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
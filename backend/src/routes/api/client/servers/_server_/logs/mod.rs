@@ -1,6 +1,8 @@
 use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
+mod archive;
+
 mod get {
     use axum::{extract::Query, http::StatusCode};
     use garde::Validate;
@@ -77,5 +79,6 @@ mod get {
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .routes(routes!(get::route))
+        .nest("/archive", archive::router(state))
         .with_state(state.clone())
 }
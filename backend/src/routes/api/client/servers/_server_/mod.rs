@@ -8,7 +8,7 @@ use axum::{
 use shared::{
     GetState,
     models::{
-        server::{Server, ServerActivityLogger, ServerStatus},
+        server::{Server, ServerActivityLogger},
         user::{GetAuthMethod, GetPermissionManager, GetUser, GetUserImpersonator},
     },
     response::ApiResponse,
@@ -22,6 +22,7 @@ mod announcements;
 mod backups;
 mod command;
 mod databases;
+mod deployment_sources;
 mod files;
 mod logs;
 mod mounts;
@@ -64,29 +65,12 @@ pub async fn auth(
         "/api/client/servers/{server}/backups/unlock",
     ];
 
-    if !IGNORED_STATUS_PATHS.contains(&matched_path.as_str()) {
-        if server.suspended {
-            if !user.admin {
-                return Ok(ApiResponse::error("server is suspended")
-                    .with_status(StatusCode::CONFLICT)
-                    .into_response());
-            }
-        } else if server.destination_node.is_some() {
-            return Ok(ApiResponse::error("server is being transferred")
-                .with_status(StatusCode::CONFLICT)
-                .into_response());
-        } else if let Some(status) = server.status {
-            let message = match status {
-                ServerStatus::Installing => "server is currently installing",
-                ServerStatus::InstallFailed => "your server has failed its installation process",
-                ServerStatus::RestoringBackup => "server is restoring from a backup",
-                ServerStatus::BackupRestoreFailed => "your server has failed to restore a backup",
-            };
-
-            return Ok(ApiResponse::error(message)
-                .with_status(StatusCode::CONFLICT)
-                .into_response());
-        }
+    if !IGNORED_STATUS_PATHS.contains(&matched_path.as_str())
+        && let Some(reason) = server.unavailable_reason(user.admin)
+    {
+        return Ok(ApiResponse::error(reason)
+            .with_status(StatusCode::CONFLICT)
+            .into_response());
     }
 
     req.extensions_mut().insert(
@@ -166,6 +150,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/databases", databases::router(state))
         .nest("/mounts", mounts::router(state))
         .nest("/schedules", schedules::router(state))
+        .nest("/deployment-sources", deployment_sources::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
         .with_state(state.clone())
 }
@@ -71,8 +71,10 @@ mod patch {
         permissions.has_server_permission("schedules.update")?;
 
         if let Some(action) = &data.action
-            && let Some(permission) = action.permission()
-            && permissions.has_server_permission(permission).is_err()
+            && let Some(permission) = action
+                .permissions()
+                .iter()
+                .find(|permission| permissions.has_server_permission(permission).is_err())
         {
             return ApiResponse::error(format!(
                 "unable to update schedule step that requires permission: {permission}"
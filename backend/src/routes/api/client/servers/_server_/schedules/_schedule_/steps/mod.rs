@@ -130,8 +130,11 @@ mod post {
 
         permissions.has_server_permission("schedules.update")?;
 
-        if let Some(permission) = data.action.permission()
-            && permissions.has_server_permission(permission).is_err()
+        if let Some(permission) = data
+            .action
+            .permissions()
+            .iter()
+            .find(|permission| permissions.has_server_permission(permission).is_err())
         {
             return ApiResponse::error(format!(
                 "unable to create schedule step that requires permission: {permission}"
@@ -95,8 +95,11 @@ mod post {
             .await?;
 
         for (index, schedule_step) in data.steps.iter().take(max_schedule_step_count).enumerate() {
-            if let Some(permission) = schedule_step.action.permission()
-                && permissions.has_server_permission(permission).is_err()
+            if let Some(permission) = schedule_step
+                .action
+                .permissions()
+                .iter()
+                .find(|permission| permissions.has_server_permission(permission).is_err())
             {
                 transaction.rollback().await.ok();
                 return ApiResponse::error(format!(
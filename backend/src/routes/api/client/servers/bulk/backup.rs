@@ -0,0 +1,144 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::bulk::{
+        BulkServerResult, BulkServerSelection, BulkServers, Response,
+    };
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::Deserialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel,
+            server_backup::ServerBackup,
+            user::{GetAuthMethod, GetPermissionManager, GetUser, GetUserImpersonator},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(dive)]
+        #[schema(inline)]
+        servers: BulkServerSelection,
+
+        #[garde(length(chars, min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        name: Option<compact_str::CompactString>,
+        #[garde(skip)]
+        #[serde(default)]
+        ignored_files: Vec<compact_str::CompactString>,
+        #[garde(skip)]
+        #[serde(default)]
+        include_databases: bool,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        user_impersonator: GetUserImpersonator,
+        auth: GetAuthMethod,
+        ip: shared::GetIp,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let BulkServers {
+            servers,
+            mut results,
+        } = BulkServers::resolve(
+            &state,
+            &user,
+            user_impersonator.as_ref(),
+            &permissions,
+            &auth,
+            ip.0,
+            &data.servers,
+        )
+        .await?;
+
+        results.extend(
+            futures_util::future::join_all(servers.into_iter().map(|server| {
+                let state = state.clone();
+                let data = &data;
+
+                async move {
+                    let result: Result<(), anyhow::Error> = async {
+                        server.has_server_permission("backups.create")?;
+                        if data.include_databases {
+                            server.has_server_permission("databases.read")?;
+                        }
+
+                        let backups_lock = state
+                            .cache
+                            .lock(
+                                format!("servers::{}::backups", server.server.uuid),
+                                Some(30),
+                                Some(5),
+                            )
+                            .await?;
+
+                        ServerBackup::check_create_limits(&state, &server.server).await?;
+
+                        let options = shared::models::server_backup::CreateServerBackupOptions {
+                            server: &server.server,
+                            name: data.name.clone().unwrap_or_else(ServerBackup::default_name),
+                            backup_group_uuid: None,
+                            system_backup_policy_uuid: None,
+                            backup_configuration: None,
+                            ignored_files: data.ignored_files.clone(),
+                            metadata: ServerBackup::generate_metadata(&state, &server.server)
+                                .await?,
+                            include_databases: data.include_databases,
+                        };
+                        let backup = ServerBackup::create(&state, options).await?;
+
+                        drop(backups_lock);
+
+                        server
+                            .activity_logger
+                            .log(
+                                "server:backup.create",
+                                serde_json::json!({
+                                    "uuid": backup.uuid,
+                                    "name": backup.name,
+                                    "backup_group_uuid": backup.backup_group_uuid,
+                                    "ignored_files": backup.ignored_files,
+                                    "include_databases": data.include_databases,
+                                    "bulk": true,
+                                }),
+                            )
+                            .await;
+
+                        Ok(())
+                    }
+                    .await;
+
+                    BulkServerResult::new(&server.server, result)
+                }
+            }))
+            .await,
+        );
+
+        ApiResponse::new_serialized(Response { results }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,115 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::bulk::{
+        BulkServerResult, BulkServerSelection, BulkServers, Response,
+    };
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::Deserialize;
+    use shared::{
+        ApiError, GetState,
+        models::user::{GetAuthMethod, GetPermissionManager, GetUser, GetUserImpersonator},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(dive)]
+        #[schema(inline)]
+        servers: BulkServerSelection,
+
+        #[garde(length(chars, min = 1, max = 1024))]
+        #[schema(min_length = 1, max_length = 1024)]
+        command: compact_str::CompactString,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        user_impersonator: GetUserImpersonator,
+        auth: GetAuthMethod,
+        ip: shared::GetIp,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let BulkServers {
+            servers,
+            mut results,
+        } = BulkServers::resolve(
+            &state,
+            &user,
+            user_impersonator.as_ref(),
+            &permissions,
+            &auth,
+            ip.0,
+            &data.servers,
+        )
+        .await?;
+
+        let request_body = wings_api::servers_server_commands::post::RequestBody {
+            commands: vec![data.command],
+        };
+
+        results.extend(
+            futures_util::future::join_all(servers.into_iter().map(|server| {
+                let state = state.clone();
+                let request_body = &request_body;
+
+                async move {
+                    let result: Result<(), anyhow::Error> = async {
+                        server.has_server_permission("control.console")?;
+
+                        server
+                            .server
+                            .node
+                            .fetch_cached(&state.database)
+                            .await?
+                            .api_client(&state.database)
+                            .await?
+                            .post_servers_server_commands(server.server.uuid, request_body)
+                            .await?;
+
+                        server
+                            .activity_logger
+                            .log(
+                                "server:console.command",
+                                serde_json::json!({
+                                    "command": request_body.commands[0],
+                                    "bulk": true,
+                                }),
+                            )
+                            .await;
+
+                        Ok(())
+                    }
+                    .await;
+
+                    BulkServerResult::new(&server.server, result)
+                }
+            }))
+            .await,
+        );
+
+        ApiResponse::new_serialized(Response { results }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
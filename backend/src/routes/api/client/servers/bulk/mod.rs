@@ -0,0 +1,196 @@
+use super::State;
+use axum::http::StatusCode;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use shared::{
+    models::{
+        server::{Server, ServerActivityLogger},
+        user::{AuthMethod, PermissionManager, User, UserImpersonator},
+        user_server_group::UserServerGroup,
+    },
+    response::{ApiResponse, extract_readable_error},
+};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use utoipa_axum::router::OpenApiRouter;
+
+mod backup;
+mod command;
+mod power;
+mod schedule;
+mod write;
+
+pub const MAX_BULK_SERVERS: usize = 100;
+
+#[derive(ToSchema, Validate, Deserialize)]
+pub struct BulkServerSelection {
+    #[garde(skip)]
+    pub server_group_uuid: Option<uuid::Uuid>,
+    #[garde(length(max = MAX_BULK_SERVERS))]
+    #[schema(max_items = 100)]
+    #[serde(default)]
+    pub server_uuids: Vec<uuid::Uuid>,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct BulkServerResult {
+    pub uuid: uuid::Uuid,
+    pub name: Option<compact_str::CompactString>,
+    pub error: Option<String>,
+}
+
+impl BulkServerResult {
+    pub fn new(server: &Server, result: Result<(), anyhow::Error>) -> Self {
+        Self {
+            uuid: server.uuid,
+            name: Some(server.name.clone()),
+            error: result.err().map(|err| {
+                extract_readable_error(&err).map_or_else(|| err.to_string(), |(error, _)| error)
+            }),
+        }
+    }
+
+    fn error(server: &Server, error: &str) -> Self {
+        Self {
+            uuid: server.uuid,
+            name: Some(server.name.clone()),
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// A selected server together with the permissions and activity logger the user would
+/// get when calling the server's own routes.
+pub struct BulkServer {
+    pub server: Server,
+    pub permissions: PermissionManager,
+    pub activity_logger: ServerActivityLogger,
+}
+
+impl BulkServer {
+    pub fn has_server_permission(&self, permission: &str) -> Result<(), anyhow::Error> {
+        self.permissions
+            .has_server_permission(permission)
+            .map_err(|_| {
+                anyhow::anyhow!("you do not have permission to perform this action: {permission}")
+            })
+    }
+}
+
+pub struct BulkServers {
+    pub servers: Vec<BulkServer>,
+    pub results: Vec<BulkServerResult>,
+}
+
+impl BulkServers {
+    /// Resolves the selection to the servers the user can access. Servers that cannot be
+    /// acted on right now (suspended, transferring, installing, ...) or that are not
+    /// accessible at all are reported in `results` right away.
+    pub async fn resolve(
+        state: &shared::State,
+        user: &User,
+        user_impersonator: Option<&UserImpersonator>,
+        permissions: &PermissionManager,
+        auth: &AuthMethod,
+        ip: std::net::IpAddr,
+        selection: &BulkServerSelection,
+    ) -> Result<Self, ApiResponse> {
+        let mut uuids = selection.server_uuids.clone();
+
+        if let Some(server_group_uuid) = selection.server_group_uuid {
+            let server_group = match UserServerGroup::by_user_uuid_uuid(
+                &state.database,
+                user.uuid,
+                server_group_uuid,
+            )
+            .await?
+            {
+                Some(server_group) => server_group,
+                None => {
+                    return Err(ApiResponse::error("server group not found")
+                        .with_status(StatusCode::NOT_FOUND));
+                }
+            };
+
+            uuids.extend(server_group.server_order);
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        uuids.retain(|uuid| seen.insert(*uuid));
+
+        if uuids.is_empty() {
+            return Err(
+                ApiResponse::error("no servers selected").with_status(StatusCode::BAD_REQUEST)
+            );
+        }
+        if uuids.len() > MAX_BULK_SERVERS {
+            return Err(ApiResponse::error(format!(
+                "cannot select more than {MAX_BULK_SERVERS} servers at once"
+            ))
+            .with_status(StatusCode::BAD_REQUEST));
+        }
+
+        let found = Server::by_user_uuids(&state.database, user, &uuids).await?;
+
+        let mut servers = Vec::with_capacity(found.len());
+        let mut results = Vec::new();
+
+        for uuid in uuids
+            .iter()
+            .filter(|uuid| !found.iter().any(|s| s.uuid == **uuid))
+        {
+            results.push(BulkServerResult {
+                uuid: *uuid,
+                name: None,
+                error: Some("server not found".into()),
+            });
+        }
+
+        for server in found {
+            if let Some(reason) = server.unavailable_reason(user.admin) {
+                results.push(BulkServerResult::error(&server, reason));
+                continue;
+            }
+
+            servers.push(BulkServer {
+                permissions: permissions
+                    .clone()
+                    .set_user_server_owner(user.uuid == server.owner.uuid)
+                    .add_subuser_permissions(server.subuser_permissions.clone()),
+                activity_logger: ServerActivityLogger {
+                    state: Arc::clone(state),
+                    server_uuid: server.uuid,
+                    user_uuid: user.uuid,
+                    impersonator_uuid: user_impersonator.map(|i| i.uuid),
+                    user_admin: user.admin,
+                    user_owner: user.uuid == server.owner.uuid,
+                    user_subuser: server.subuser_permissions.is_some(),
+                    api_key_uuid: match auth {
+                        AuthMethod::ApiKey(api_key) => Some(api_key.uuid),
+                        _ => None,
+                    },
+                    ip,
+                },
+                server,
+            });
+        }
+
+        Ok(Self { servers, results })
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct Response {
+    #[schema(inline)]
+    pub results: Vec<BulkServerResult>,
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .nest("/power", power::router(state))
+        .nest("/command", command::router(state))
+        .nest("/backup", backup::router(state))
+        .nest("/schedule", schedule::router(state))
+        .nest("/write", write::router(state))
+        .with_state(state.clone())
+}
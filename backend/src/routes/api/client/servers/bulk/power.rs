@@ -0,0 +1,151 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::bulk::{
+        BulkServerResult, BulkServerSelection, BulkServers, Response,
+    };
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::Deserialize;
+    use shared::{
+        ApiError, GetState,
+        models::user::{GetAuthMethod, GetPermissionManager, GetUser, GetUserImpersonator},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use std::collections::{HashMap, HashSet};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(dive)]
+        #[schema(inline)]
+        servers: BulkServerSelection,
+
+        #[garde(skip)]
+        action: wings_api::ServerPowerAction,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        user_impersonator: GetUserImpersonator,
+        auth: GetAuthMethod,
+        ip: shared::GetIp,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let BulkServers {
+            servers,
+            mut results,
+        } = BulkServers::resolve(
+            &state,
+            &user,
+            user_impersonator.as_ref(),
+            &permissions,
+            &auth,
+            ip.0,
+            &data.servers,
+        )
+        .await?;
+
+        let permission = match data.action {
+            wings_api::ServerPowerAction::Start => "control.start",
+            wings_api::ServerPowerAction::Stop => "control.stop",
+            wings_api::ServerPowerAction::Kill => "control.stop",
+            wings_api::ServerPowerAction::Restart => "control.restart",
+        };
+
+        // wings can apply a power action to many servers at once, so only one request
+        // per node is needed
+        let mut nodes = HashMap::new();
+        for server in servers {
+            if let Err(err) = server.has_server_permission(permission) {
+                results.push(BulkServerResult::new(&server.server, Err(err)));
+                continue;
+            }
+
+            nodes
+                .entry(server.server.node.uuid)
+                .or_insert_with(Vec::new)
+                .push(server);
+        }
+
+        let node_results = futures_util::future::join_all(nodes.into_values().map(|servers| {
+            let state = state.clone();
+
+            async move {
+                let result: Result<(), anyhow::Error> = async {
+                    servers[0]
+                        .server
+                        .node
+                        .fetch_cached(&state.database)
+                        .await?
+                        .api_client(&state.database)
+                        .await?
+                        .post_servers_power(&wings_api::servers_power::post::RequestBody {
+                            servers: wings_api::ServerSelector::Uuids {
+                                uuids: servers
+                                    .iter()
+                                    .map(|server| server.server.uuid)
+                                    .collect::<HashSet<_>>(),
+                            },
+                            action: data.action,
+                            wait_seconds: None,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+                .await;
+
+                (servers, result)
+            }
+        }))
+        .await;
+
+        for (servers, result) in node_results {
+            let error = result.err().map(|err| err.to_string());
+
+            for server in servers {
+                if error.is_none() {
+                    server
+                        .activity_logger
+                        .log(
+                            "server:power.action",
+                            serde_json::json!({
+                                "action": data.action,
+                                "bulk": true,
+                            }),
+                        )
+                        .await;
+                }
+
+                results.push(BulkServerResult {
+                    uuid: server.server.uuid,
+                    name: Some(server.server.name),
+                    error: error.clone(),
+                });
+            }
+        }
+
+        ApiResponse::new_serialized(Response { results }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
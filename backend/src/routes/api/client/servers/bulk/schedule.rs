@@ -0,0 +1,159 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::bulk::{
+        BulkServerResult, BulkServerSelection, BulkServers, Response,
+    };
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::Deserialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server_schedule::ServerSchedule,
+            user::{GetAuthMethod, GetPermissionManager, GetUser, GetUserImpersonator},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(dive)]
+        #[schema(inline)]
+        servers: BulkServerSelection,
+
+        #[garde(length(chars, min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        schedule_name: compact_str::CompactString,
+        #[garde(skip)]
+        #[serde(default)]
+        skip_condition: bool,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        user_impersonator: GetUserImpersonator,
+        auth: GetAuthMethod,
+        ip: shared::GetIp,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let BulkServers {
+            servers,
+            mut results,
+        } = BulkServers::resolve(
+            &state,
+            &user,
+            user_impersonator.as_ref(),
+            &permissions,
+            &auth,
+            ip.0,
+            &data.servers,
+        )
+        .await?;
+
+        results.extend(
+            futures_util::future::join_all(servers.into_iter().map(|server| {
+                let state = state.clone();
+                let data = &data;
+
+                async move {
+                    let result: Result<(), anyhow::Error> = async {
+                        server.has_server_permission("schedules.update")?;
+
+                        if state
+                            .cache
+                            .ratelimit(
+                                format!("client/servers/{}/schedules/trigger", server.server.uuid),
+                                10,
+                                60,
+                                ip.to_string(),
+                            )
+                            .await
+                            .is_err()
+                        {
+                            return Err(anyhow::anyhow!("too many schedules triggered recently"));
+                        }
+
+                        let schedule = ServerSchedule::by_server_uuid_name(
+                            &state.database,
+                            server.server.uuid,
+                            &data.schedule_name,
+                        )
+                        .await?
+                        .ok_or_else(|| anyhow::anyhow!("schedule not found"))?;
+
+                        let node = server.server.node.fetch_cached(&state.database).await?;
+
+                        server
+                            .server
+                            .clone()
+                            .sync(&state.database)
+                            .await
+                            .map_err(|err| {
+                                tracing::error!(
+                                    server = %server.server.uuid,
+                                    "failed to post server sync: {:?}",
+                                    err
+                                );
+
+                                anyhow::anyhow!("failed to send sync signal to server")
+                            })?;
+
+                        node.api_client(&state.database)
+                            .await?
+                            .post_servers_server_schedules_schedule_trigger(
+                                server.server.uuid,
+                                schedule.uuid,
+                                &wings_api::servers_server_schedules_schedule_trigger::post::RequestBody {
+                                    skip_condition: data.skip_condition,
+                                },
+                            )
+                            .await?;
+
+                        server
+                            .activity_logger
+                            .log(
+                                "server:schedule.trigger",
+                                serde_json::json!({
+                                    "uuid": schedule.uuid,
+                                    "name": schedule.name,
+                                    "skip_condition": data.skip_condition,
+                                    "bulk": true,
+                                }),
+                            )
+                            .await;
+
+                        Ok(())
+                    }
+                    .await;
+
+                    BulkServerResult::new(&server.server, result)
+                }
+            }))
+            .await,
+        );
+
+        ApiResponse::new_serialized(Response { results }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
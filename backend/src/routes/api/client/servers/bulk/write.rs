@@ -0,0 +1,141 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::client::servers::bulk::{
+        BulkServerResult, BulkServerSelection, BulkServers, Response,
+    };
+    use axum::http::StatusCode;
+    use garde::Validate;
+    use serde::Deserialize;
+    use shared::{
+        ApiError, GetState,
+        models::user::{GetAuthMethod, GetPermissionManager, GetUser, GetUserImpersonator},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(dive)]
+        #[schema(inline)]
+        servers: BulkServerSelection,
+
+        #[garde(length(chars, min = 1, max = 4096))]
+        #[schema(min_length = 1, max_length = 4096)]
+        file: compact_str::CompactString,
+        #[garde(skip)]
+        content: String,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        user_impersonator: GetUserImpersonator,
+        auth: GetAuthMethod,
+        ip: shared::GetIp,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let max_size = state
+            .settings
+            .get_as(|s| s.server.max_file_manager_view_size)
+            .await?;
+        if data.content.len() as u64 > max_size {
+            return ApiResponse::error("file content is too large")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        let BulkServers {
+            servers,
+            mut results,
+        } = BulkServers::resolve(
+            &state,
+            &user,
+            user_impersonator.as_ref(),
+            &permissions,
+            &auth,
+            ip.0,
+            &data.servers,
+        )
+        .await?;
+
+        results.extend(
+            futures_util::future::join_all(servers.into_iter().map(|mut server| {
+                let state = state.clone();
+                let data = &data;
+                let user_uuid = user.uuid;
+
+                async move {
+                    let result: Result<(), anyhow::Error> = async {
+                        server.has_server_permission("files.create")?;
+
+                        if server.server.is_ignored(&data.file, false) {
+                            return Err(anyhow::anyhow!("file not found"));
+                        }
+
+                        let revision_id = server
+                            .server
+                            .node
+                            .fetch_cached(&state.database)
+                            .await?
+                            .api_client(&state.database)
+                            .await?
+                            .post_servers_server_files_write(
+                                server.server.uuid,
+                                wings_api::client::AsyncRequestReader::new(std::io::Cursor::new(
+                                    data.content.clone().into_bytes(),
+                                )),
+                                &wings_api::servers_server_files_write::post::Query {
+                                    file: Some(data.file.clone()),
+                                    user: Some(user_uuid),
+                                    ..Default::default()
+                                },
+                            )
+                            .await?
+                            .revision_id;
+
+                        server
+                            .activity_logger
+                            .log(
+                                "server:file.write",
+                                serde_json::json!({
+                                    "file": data.file,
+                                    "revision_id": revision_id,
+                                    "bulk": true,
+                                }),
+                            )
+                            .await;
+
+                        Ok(())
+                    }
+                    .await;
+
+                    BulkServerResult::new(&server.server, result)
+                }
+            }))
+            .await,
+        );
+
+        ApiResponse::new_serialized(Response { results }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -2,6 +2,7 @@ use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 pub mod _server_;
+mod bulk;
 mod eggs;
 mod groups;
 mod nodes;
@@ -102,6 +103,7 @@ mod get {
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .routes(routes!(get::route))
+        .nest("/bulk", bulk::router(state))
         .nest("/groups", groups::router(state))
         .nest("/nodes", nodes::router(state))
         .nest("/eggs", eggs::router(state))
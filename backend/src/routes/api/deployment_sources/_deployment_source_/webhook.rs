@@ -0,0 +1,185 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use axum::{
+        body::Bytes,
+        extract::{Path, Query},
+        http::StatusCode,
+    };
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            ByUuid, CreatableModel,
+            server::Server,
+            server_activity::{CreateServerActivityOptions, ServerActivity},
+            server_deployment::ServerDeployment,
+            server_deployment_source::ServerDeploymentSource,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Params {
+        token: String,
+    }
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Payload {
+        #[serde(rename = "ref")]
+        reference: Option<String>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        triggered: bool,
+        deployment: Option<uuid::Uuid>,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "deployment_source" = uuid::Uuid,
+            description = "The deployment source ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "token" = String, Query,
+            description = "The webhook token of the deployment source",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        ip: shared::GetIp,
+        Path(deployment_source): Path<uuid::Uuid>,
+        Query(params): Query<Params>,
+        body: Bytes,
+    ) -> ApiResponseResult {
+        state
+            .cache
+            .ratelimit(
+                format!("deployment-sources/{deployment_source}/webhook"),
+                10,
+                60,
+                ip.to_string(),
+            )
+            .await?;
+
+        let deployment_source =
+            match ServerDeploymentSource::by_uuid_optional(&state.database, deployment_source)
+                .await?
+            {
+                Some(deployment_source) => deployment_source,
+                None => {
+                    return ApiResponse::error("deployment source not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        if !deployment_source
+            .verify_webhook_token(&state.database, &params.token)
+            .await?
+        {
+            return ApiResponse::error("invalid webhook token")
+                .with_status(StatusCode::UNAUTHORIZED)
+                .ok();
+        }
+
+        // git hosts send their full push event here, only the ref is of interest
+        if let Ok(Payload {
+            reference: Some(reference),
+        }) = serde_json::from_slice::<Payload>(&body)
+            && !deployment_source.matches_reference(&reference)
+        {
+            return ApiResponse::new_serialized(Response {
+                triggered: false,
+                deployment: None,
+            })
+            .ok();
+        }
+
+        match Server::by_uuid_optional(&state.database, deployment_source.server_uuid).await? {
+            Some(server) if !server.suspended => {}
+            _ => {
+                return ApiResponse::error("deployment source not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        }
+
+        let deployment_lock = state
+            .cache
+            .lock(
+                format!(
+                    "servers::{}::deployment_sources::{}::deploy",
+                    deployment_source.server_uuid, deployment_source.uuid
+                ),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        if ServerDeployment::running_by_server_deployment_source_uuid(
+            &state.database,
+            deployment_source.uuid,
+        )
+        .await?
+        .is_some()
+        {
+            return ApiResponse::error("a deployment is already running for this source")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        let deployment = deployment_source.deploy(&state, None, true).await?;
+
+        drop(deployment_lock);
+
+        if let Err(err) = ServerActivity::create(
+            &state,
+            CreateServerActivityOptions {
+                server_uuid: deployment_source.server_uuid,
+                user_uuid: None,
+                impersonator_uuid: None,
+                api_key_uuid: None,
+                schedule_uuid: None,
+                event: "server:deployment.trigger".into(),
+                ip: Some(ip.0.into()),
+                data: serde_json::json!({
+                    "uuid": deployment.uuid,
+                    "deployment_source": deployment_source.uuid,
+                    "name": deployment_source.name,
+                    "webhook": true,
+                }),
+                created: None,
+            },
+        )
+        .await
+        {
+            tracing::warn!(
+                deployment_source = %deployment_source.uuid,
+                "failed to log deployment webhook activity: {:?}",
+                err
+            );
+        }
+
+        ApiResponse::new_serialized(Response {
+            triggered: true,
+            deployment: Some(deployment.uuid),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
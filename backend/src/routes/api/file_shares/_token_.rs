@@ -0,0 +1,244 @@
+use super::State;
+use axum::http::StatusCode;
+use shared::{
+    models::{
+        ByUuid, CreatableModel,
+        server::Server,
+        server_activity::{CreateServerActivityOptions, ServerActivity},
+        server_file_share::{ServerFileShare, ServerFileShareJwt},
+    },
+    response::ApiResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+/// Header carrying the password of a protected share on `GET` requests.
+const PASSWORD_HEADER: &str = "X-Share-Password";
+
+/// Resolves a share token into a download url on the node of the shared file, or into the
+/// response explaining why it cannot be downloaded.
+async fn download_url(
+    state: &shared::State,
+    ip: std::net::IpAddr,
+    token: &str,
+    password: Option<&str>,
+    archive_format: wings_api::StreamableArchiveFormat,
+) -> Result<reqwest::Url, ApiResponse> {
+    let payload: ServerFileShareJwt = match state.jwt.verify(token) {
+        Ok(payload) => payload,
+        Err(_) => {
+            return Err(
+                ApiResponse::error("invalid share token").with_status(StatusCode::BAD_REQUEST)
+            );
+        }
+    };
+
+    if !payload.base.validate(Some("file-share")) {
+        return Err(ApiResponse::error("file share has expired").with_status(StatusCode::GONE));
+    }
+
+    // every request may try a password, so guessing is limited per share and client
+    state
+        .cache
+        .ratelimit(
+            format!("file-shares/{}", payload.file_share_uuid),
+            10,
+            60,
+            ip.to_string(),
+        )
+        .await?;
+
+    let mut share =
+        match ServerFileShare::by_uuid_optional(&state.database, payload.file_share_uuid).await? {
+            Some(share) => share,
+            None => {
+                return Err(
+                    ApiResponse::error("file share not found").with_status(StatusCode::NOT_FOUND)
+                );
+            }
+        };
+
+    if !share.verify_password(&state.database, password).await? {
+        return Err(
+            ApiResponse::error("invalid share password").with_status(StatusCode::UNAUTHORIZED)
+        );
+    }
+
+    let server = match Server::by_uuid_optional(&state.database, share.server_uuid).await? {
+        Some(server) if !server.suspended => server,
+        _ => {
+            return Err(
+                ApiResponse::error("file share not found").with_status(StatusCode::NOT_FOUND)
+            );
+        }
+    };
+
+    if !share.claim_download(&state.database).await? {
+        return Err(ApiResponse::error("file share has expired").with_status(StatusCode::GONE));
+    }
+
+    let node = server.node.fetch_cached(&state.database).await?;
+    let url = node
+        .file_download_url(
+            state,
+            server.uuid,
+            share.uuid,
+            "/",
+            &[share.path.as_str()],
+            share.directory,
+            archive_format,
+        )
+        .await?;
+
+    if let Err(err) = ServerActivity::create(
+        state,
+        CreateServerActivityOptions {
+            server_uuid: server.uuid,
+            user_uuid: None,
+            impersonator_uuid: None,
+            api_key_uuid: None,
+            schedule_uuid: None,
+            event: "server:file.share-download".into(),
+            ip: Some(ip.into()),
+            data: serde_json::json!({
+                "uuid": share.uuid,
+                "file": share.path,
+                "directory": share.directory,
+                "downloads": share.downloads,
+            }),
+            created: None,
+        },
+    )
+    .await
+    {
+        tracing::warn!(
+            share = %share.uuid,
+            "failed to log file share download activity: {:?}",
+            err
+        );
+    }
+
+    Ok(url)
+}
+
+mod get {
+    use axum::{
+        body::Body,
+        extract::{Path, Query},
+        http::{HeaderMap, StatusCode},
+    };
+    use serde::Deserialize;
+    use shared::{
+        ApiError, GetState,
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Params {
+        #[serde(default)]
+        archive_format: wings_api::StreamableArchiveFormat,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = TEMPORARY_REDIRECT, body = String),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = GONE, body = ApiError),
+        (status = TOO_MANY_REQUESTS, body = ApiError),
+    ), params(
+        (
+            "token" = String,
+            description = "The share token handed out by the panel",
+        ),
+        (
+            "X-Share-Password" = Option<String>, Header,
+            description = "The password of the share, if it is protected",
+        ),
+        (
+            "archive_format" = wings_api::StreamableArchiveFormat, Query,
+            description = "The format of the archive to download for directories",
+            example = "tar_gz",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        ip: shared::GetIp,
+        headers: HeaderMap,
+        Path(token): Path<String>,
+        Query(params): Query<Params>,
+    ) -> ApiResponseResult {
+        let password = headers
+            .get(super::PASSWORD_HEADER)
+            .and_then(|password| password.to_str().ok());
+
+        let url =
+            super::download_url(&state, ip.0, &token, password, params.archive_format).await?;
+
+        ApiResponse::new(Body::empty())
+            .with_header("Location", url.as_str())
+            .with_status(StatusCode::TEMPORARY_REDIRECT)
+            .ok()
+    }
+}
+
+mod post {
+    use axum::extract::Path;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Payload {
+        password: Option<String>,
+
+        #[serde(default)]
+        archive_format: wings_api::StreamableArchiveFormat,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        url: String,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = GONE, body = ApiError),
+        (status = TOO_MANY_REQUESTS, body = ApiError),
+    ), params(
+        (
+            "token" = String,
+            description = "The share token handed out by the panel",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        ip: shared::GetIp,
+        Path(token): Path<String>,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        let url = super::download_url(
+            &state,
+            ip.0,
+            &token,
+            data.password.as_deref(),
+            data.archive_format,
+        )
+        .await?;
+
+        ApiResponse::new_serialized(Response { url: url.into() }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
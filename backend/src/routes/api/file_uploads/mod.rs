@@ -0,0 +1,266 @@
+use super::State;
+use axum::extract::DefaultBodyLimit;
+use utoipa_axum::{
+    router::{OpenApiRouter, UtoipaMethodRouterExt},
+    routes,
+};
+
+mod post {
+    use axum::{extract::Query, http::StatusCode};
+    use compact_str::ToCompactString;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            ByUuid, CreatableModel,
+            location::FileScanPolicy,
+            server::Server,
+            server_activity::{CreateServerActivityOptions, ServerActivity},
+            server_quarantined_file::{
+                FileScanReport, FileScanSource, ScannedFileUploadJwt, ServerQuarantinedFile,
+            },
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use std::path::{Component, Path};
+    use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+    use utoipa::ToSchema;
+
+    fn default_directory() -> compact_str::CompactString {
+        "/".into()
+    }
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Params {
+        token: String,
+
+        #[serde(default = "default_directory")]
+        directory: compact_str::CompactString,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    /// Uploads every file of the multipart body to the server, scanning each of them before it
+    /// is written. Chunked uploads are not available here, as a file has to be complete to be
+    /// scanned.
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = GONE, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+        (status = PAYLOAD_TOO_LARGE, body = ApiError),
+    ), params(
+        (
+            "token" = String, Query,
+            description = "The upload token handed out by the panel",
+        ),
+        (
+            "directory" = Option<String>, Query,
+            description = "The directory to upload the files to",
+            example = "/path/to/dir",
+        ),
+    ), request_body = String)]
+    pub async fn route(
+        state: GetState,
+        ip: shared::GetIp,
+        Query(params): Query<Params>,
+        mut multipart: axum::extract::Multipart,
+    ) -> ApiResponseResult {
+        let payload: ScannedFileUploadJwt = match state.jwt.verify(&params.token) {
+            Ok(payload) => payload,
+            Err(_) => {
+                return ApiResponse::error("invalid upload token")
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        };
+
+        if !payload.base.validate(Some("scanned-file-upload")) {
+            return ApiResponse::error("upload token has expired")
+                .with_status(StatusCode::GONE)
+                .ok();
+        }
+
+        let mut server =
+            match Server::by_uuid_optional(&state.database, payload.server_uuid).await? {
+                Some(server) if !server.suspended => server,
+                _ => {
+                    return ApiResponse::error("server not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        server.subuser_ignored_files = payload.ignored_files;
+
+        let node = server.node.fetch_cached(&state.database).await?;
+        let Some((policy, _, max_size)) = ServerQuarantinedFile::scan_policy(&state, &node).await?
+        else {
+            return ApiResponse::error("uploaded files are not scanned on this node")
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        };
+
+        let upload_limit = node
+            .fetch_configuration(&state.database)
+            .await?
+            .api
+            .upload_limit
+            * 1024
+            * 1024;
+        let api_client = node.api_client(&state.database).await?;
+
+        while let Some(mut field) = multipart.next_field().await? {
+            let Some(name) = field.file_name().map(|name| name.to_compact_string()) else {
+                return ApiResponse::error("file name not found")
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
+            };
+
+            let relative_path = Path::new(name.trim_start_matches('/'));
+            if name.is_empty()
+                || !relative_path
+                    .components()
+                    .all(|component| matches!(component, Component::Normal(_)))
+            {
+                return ApiResponse::error("invalid file name")
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+
+            let file = Path::new(params.directory.as_str())
+                .join(relative_path)
+                .to_string_lossy()
+                .to_compact_string();
+
+            if server.is_ignored(&file, false) {
+                return ApiResponse::new_serialized(ApiError::new_value(&["file not found"]))
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+
+            // the whole file is spooled to disk up to the upload limit of the node, only files small
+            // enough to be scanned are kept in memory
+            let mut spool = tokio::fs::File::from_std(tempfile::tempfile()?);
+            let mut content = Some(Vec::new());
+            let mut size = 0;
+
+            while let Some(chunk) = field.chunk().await? {
+                size += chunk.len() as u64;
+
+                if size > upload_limit {
+                    return ApiResponse::error("file is larger than the upload limit")
+                        .with_status(StatusCode::PAYLOAD_TOO_LARGE)
+                        .ok();
+                }
+
+                if size > max_size {
+                    if policy == FileScanPolicy::Block {
+                        return ApiResponse::error("file is too large to be scanned")
+                            .with_status(StatusCode::EXPECTATION_FAILED)
+                            .ok();
+                    }
+
+                    content = None;
+                } else if let Some(content) = &mut content {
+                    content.extend_from_slice(&chunk);
+                }
+
+                spool.write_all(&chunk).await?;
+            }
+
+            let report = match content {
+                Some(content) => match ServerQuarantinedFile::scan(
+                    &state,
+                    &node,
+                    server.uuid,
+                    Some(payload.user_uuid),
+                    FileScanSource::Upload,
+                    &file,
+                    &content,
+                )
+                .await
+                {
+                    Ok(report) => report,
+                    Err(err) => {
+                        tracing::error!(
+                            server = %server.uuid,
+                            "failed to scan uploaded file: {:?}",
+                            err
+                        );
+
+                        return ApiResponse::error("unable to scan file, please try again later")
+                            .with_status(StatusCode::EXPECTATION_FAILED)
+                            .ok();
+                    }
+                },
+                None => Some(FileScanReport::unscanned(
+                    file.clone(),
+                    FileScanSource::Upload,
+                    policy,
+                )),
+            };
+
+            if let Some(report) = &report {
+                let options = CreateServerActivityOptions {
+                    server_uuid: server.uuid,
+                    user_uuid: Some(payload.user_uuid),
+                    impersonator_uuid: None,
+                    api_key_uuid: None,
+                    schedule_uuid: None,
+                    event: "server:file.scan".into(),
+                    ip: Some(ip.0.into()),
+                    data: serde_json::to_value(report)?,
+                    created: None,
+                };
+                ServerActivity::create(&state, options).await?;
+
+                if report.is_quarantined() {
+                    return ApiResponse::error(
+                        "file was flagged as malicious and has been quarantined",
+                    )
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
+                }
+            }
+
+            spool.rewind().await?;
+
+            match api_client
+                .post_servers_server_files_write(
+                    server.uuid,
+                    wings_api::client::AsyncRequestReader::new(spool),
+                    &wings_api::servers_server_files_write::post::Query {
+                        file: Some(file),
+                        user: Some(payload.user_uuid),
+                        ..Default::default()
+                    },
+                )
+                .await
+            {
+                Ok(_) => {}
+                Err(wings_api::client::ApiHttpError::Http(StatusCode::NOT_FOUND, err)) => {
+                    return ApiResponse::new_serialized(ApiError::new_wings_value(err))
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+                Err(wings_api::client::ApiHttpError::Http(StatusCode::EXPECTATION_FAILED, err)) => {
+                    return ApiResponse::new_serialized(ApiError::new_wings_value(err))
+                        .with_status(StatusCode::EXPECTATION_FAILED)
+                        .ok();
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route).layer(DefaultBodyLimit::disable()))
+        .with_state(state.clone())
+}
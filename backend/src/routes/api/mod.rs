@@ -6,7 +6,11 @@ use utoipa_axum::router::OpenApiRouter;
 pub mod admin;
 mod announcements;
 pub mod auth;
+mod backups;
 pub mod client;
+mod deployment_sources;
+mod file_shares;
+mod file_uploads;
 mod languages;
 pub mod remote;
 mod settings;
@@ -37,6 +41,10 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/announcements", announcements::router(state))
         .nest("/languages", languages::router(state))
         .nest("/auth", auth::router(state))
+        .nest("/backups", backups::router(state))
+        .nest("/file-shares", file_shares::router(state))
+        .nest("/file-uploads", file_uploads::router(state))
+        .nest("/deployment-sources", deployment_sources::router(state))
         .nest("/client", client::router(state))
         .nest("/admin", admin::router(state))
         .nest("/remote", remote::router(state))
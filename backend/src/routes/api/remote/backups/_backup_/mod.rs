@@ -47,7 +47,10 @@ pub async fn auth(
 
 mod post {
     use crate::routes::api::remote::backups::_backup_::GetBackup;
-    use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+    use aws_sdk_s3::{
+        primitives::ByteStream,
+        types::{CompletedMultipartUpload, CompletedPart},
+    };
     use axum::http::StatusCode;
     use serde::{Deserialize, Serialize};
     use shared::{
@@ -57,7 +60,10 @@ mod post {
             node::GetNode,
             server::Server,
             server_activity::ServerActivity,
-            server_backup::{BackupDisk, ServerBackup, ServerBackupEvent},
+            server_backup::{
+                BackupDisk, ServerBackup, ServerBackupEvent,
+                encryption::{self, BackupEncryptionKey},
+            },
         },
         response::{ApiResponse, ApiResponseResult},
     };
@@ -156,9 +162,87 @@ mod post {
             };
 
             let compression_type = s3_configuration.compression_type;
+            let part_size = s3_configuration.part_size;
             let (client, bucket) = s3_configuration.into_client();
 
-            let file_path = ServerBackup::s3_path(server.uuid, backup.uuid, compression_type);
+            let file_path = match &backup.upload_path {
+                Some(upload_path) => upload_path.clone(),
+                None => ServerBackup::s3_path(server.uuid, backup.uuid, compression_type),
+            };
+
+            // encrypted archives are closed by their short last part, an archive ending on a part
+            // boundary gets an empty closing part, without it the archive counts as truncated
+            let mut closing_part = None;
+            if data.successful && backup.encrypted {
+                let last_part_number = data.parts.iter().map(|p| p.part_number).max();
+
+                let ends_on_part_boundary = match last_part_number {
+                    Some(last_part_number) => match client
+                        .list_parts()
+                        .bucket(bucket.as_str())
+                        .key(&*file_path)
+                        .upload_id(&**upload_id)
+                        .part_number_marker((last_part_number - 1).to_string())
+                        .max_parts(1)
+                        .send()
+                        .await
+                    {
+                        Ok(parts) => parts.parts().first().is_some_and(|part| {
+                            part.size().unwrap_or_default() as u64
+                                == encryption::encrypted_part_size(part_size)
+                        }),
+                        Err(err) => {
+                            tracing::error!(
+                                backup = %backup.uuid,
+                                location = %node.location.name,
+                                "failed to list parts of encrypted backup: {:#?}",
+                                err
+                            );
+
+                            data.successful = false;
+                            false
+                        }
+                    },
+                    None => true,
+                };
+
+                if ends_on_part_boundary {
+                    let part_number = last_part_number.unwrap_or(0) + 1;
+
+                    let key = BackupEncryptionKey::for_server(&state.database, server.uuid).await?;
+                    let encrypted = encryption::encrypt_part(&key, part_number as u16, true, &[])?;
+
+                    match client
+                        .upload_part()
+                        .bucket(bucket.as_str())
+                        .key(&*file_path)
+                        .upload_id(&**upload_id)
+                        .part_number(part_number as i32)
+                        .body(ByteStream::from(encrypted))
+                        .send()
+                        .await
+                    {
+                        Ok(part) => {
+                            closing_part = Some(
+                                CompletedPart::builder()
+                                    .part_number(part_number as i32)
+                                    .set_e_tag(part.e_tag().map(String::from))
+                                    .build(),
+                            );
+                        }
+                        Err(err) => {
+                            tracing::error!(
+                                backup = %backup.uuid,
+                                location = %node.location.name,
+                                "failed to upload closing part of encrypted backup: {:#?}",
+                                err
+                            );
+
+                            data.successful = false;
+                        }
+                    }
+                }
+            }
 
             if data.successful {
                 let completed_parts: Vec<_> = data
@@ -170,6 +254,7 @@ mod post {
                             .e_tag(p.etag)
                             .build()
                     })
+                    .chain(closing_part)
                     .collect();
 
                 let completed_upload = CompletedMultipartUpload::builder()
@@ -179,7 +264,7 @@ mod post {
                 match client
                     .complete_multipart_upload()
                     .bucket(bucket.as_str())
-                    .key(file_path)
+                    .key(&*file_path)
                     .upload_id(&**upload_id)
                     .multipart_upload(completed_upload)
                     .send()
@@ -207,7 +292,7 @@ mod post {
                 match client
                     .abort_multipart_upload()
                     .bucket(bucket.as_str())
-                    .key(file_path)
+                    .key(&*file_path)
                     .upload_id(&**upload_id)
                     .send()
                     .await
@@ -288,6 +373,16 @@ mod post {
             );
         }
 
+        if data.successful
+            && let Err(err) = backup.queue_replication(&state).await
+        {
+            tracing::warn!(
+                backup = %backup.uuid,
+                "failed to queue backup replication: {:#?}",
+                err
+            );
+        }
+
         ServerBackup::get_event_emitter().emit(
             state.0.clone(),
             ServerBackupEvent::CreationCompleted {
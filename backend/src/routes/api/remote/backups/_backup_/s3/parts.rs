@@ -5,13 +5,15 @@ mod get {
     use crate::routes::api::remote::backups::_backup_::GetBackup;
     use aws_sdk_s3::presigning::PresigningConfig;
     use axum::{extract::Query, http::StatusCode};
+    use compact_str::ToCompactString;
     use garde::Validate;
     use serde::{Deserialize, Serialize};
     use shared::{
         ApiError, GetState,
+        jwt::BasePayload,
         models::{
             node::GetNode,
-            server_backup::{BackupDisk, ServerBackup},
+            server_backup::{BackupDisk, ServerBackup, encryption::BackupUploadPartJwt},
         },
         response::{ApiResponse, ApiResponseResult},
     };
@@ -109,13 +111,18 @@ mod get {
         parts.reserve_exact(part_count as usize);
 
         let compression_type = s3_configuration.compression_type;
+        let encryption_enabled = s3_configuration.encryption_enabled;
         let (client, bucket) = s3_configuration.into_client();
 
-        let (file_path, upload_id) = match (backup.0.upload_path, backup.0.upload_id) {
-            (Some(upload_path), Some(upload_id)) => (upload_path, upload_id),
+        let (file_path, upload_id, encrypted) = match (backup.0.upload_path, backup.0.upload_id) {
+            (Some(upload_path), Some(upload_id)) => (upload_path, upload_id, backup.0.encrypted),
             _ => {
-                let file_path = ServerBackup::s3_path(server_uuid, backup.0.uuid, compression_type);
+                let mut file_path =
+                    ServerBackup::s3_path(server_uuid, backup.0.uuid, compression_type);
                 let content_type = ServerBackup::s3_content_type(&file_path);
+                if encryption_enabled {
+                    file_path.push_str(".enc");
+                }
 
                 let multipart = match client
                     .create_multipart_upload()
@@ -157,19 +164,60 @@ mod get {
 
                 sqlx::query!(
                     "UPDATE server_backups
-                    SET upload_id = $1, upload_path = $2
-                    WHERE server_backups.uuid = $3",
+                    SET upload_id = $1, upload_path = $2, encrypted = $3
+                    WHERE server_backups.uuid = $4",
                     upload_id,
                     &file_path,
+                    encryption_enabled,
                     backup.0.uuid
                 )
                 .execute(state.database.write())
                 .await?;
 
-                (file_path, upload_id.into())
+                (file_path, upload_id.into(), encryption_enabled)
             }
         };
 
+        // wings uploads the plain archive, so encrypted parts go through the panel, which
+        // encrypts them before they are forwarded to the bucket
+        if encrypted {
+            let mut url = state
+                .settings
+                .get_as(|s| reqwest::Url::parse(&s.app.url))
+                .await??;
+            url.path_segments_mut()
+                .map_err(|_| anyhow::anyhow!("app url cannot be used as a base url"))?
+                .pop_if_empty()
+                .extend(&["api", "backups", "upload-part"]);
+
+            let now = chrono::Utc::now().timestamp();
+
+            for i in 0..part_count {
+                let token = state.jwt.create(&BackupUploadPartJwt {
+                    base: BasePayload {
+                        scope: "backup-upload-part".into(),
+                        issuer: "panel".into(),
+                        subject: None,
+                        audience: Vec::new(),
+                        expiration_time: Some(now + Duration::from_hours(2).as_secs() as i64),
+                        not_before: None,
+                        issued_at: Some(now),
+                        jwt_id: uuid::Uuid::new_v4().to_compact_string(),
+                    },
+                    backup_uuid: backup.0.uuid,
+                    upload_id: upload_id.clone(),
+                    part_number: params.from_part + i,
+                })?;
+
+                let mut part_url = url.clone();
+                part_url.set_query(Some(&format!("token={}", urlencoding::encode(&token))));
+
+                parts.push(part_url.to_string());
+            }
+
+            return ApiResponse::new_serialized(Response { parts, part_size }).ok();
+        }
+
         let presigning_config = PresigningConfig::expires_in(Duration::from_hours(2))?;
 
         for i in 0..part_count {
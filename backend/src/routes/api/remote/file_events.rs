@@ -0,0 +1,234 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel,
+            location::FileScanPolicy,
+            node::{GetNode, Node},
+            server_activity::{CreateServerActivityOptions, ServerActivity},
+            server_file_event::{ServerFileEvent, ServerFileEventAction, ServerFileEventSource},
+            server_quarantined_file::{
+                FileScanReport, FileScanSource, FileScanVerdict, ServerQuarantinedFile,
+            },
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use std::collections::HashSet;
+    use tokio::io::AsyncReadExt;
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct PayloadFileEvent {
+        server: uuid::Uuid,
+        user: Option<uuid::Uuid>,
+        action: ServerFileEventAction,
+        source: ServerFileEventSource,
+        path: compact_str::CompactString,
+        new_path: Option<compact_str::CompactString>,
+        mode: Option<u32>,
+
+        #[schema(value_type = Option<String>)]
+        ip: Option<std::net::IpAddr>,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    }
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Payload {
+        #[schema(inline)]
+        data: Vec<PayloadFileEvent>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    struct UploadedFile {
+        server: uuid::Uuid,
+        user: Option<uuid::Uuid>,
+        path: compact_str::CompactString,
+        ip: Option<std::net::IpAddr>,
+    }
+
+    /// SFTP uploads go straight to wings, so they can only be scanned once wings reports them.
+    /// Infected files, and files too large to be scanned, are removed from the server again if
+    /// the policy blocks them. Panel uploads are scanned before they are forwarded to wings.
+    async fn scan_uploaded_file(
+        state: &shared::State,
+        node: &Node,
+        file: UploadedFile,
+    ) -> Result<(), anyhow::Error> {
+        let Some((policy, _, max_size)) = ServerQuarantinedFile::scan_policy(state, node).await?
+        else {
+            return Ok(());
+        };
+
+        let api_client = node.api_client(&state.database).await?;
+
+        let report = match api_client
+            .get_servers_server_files_contents(
+                file.server,
+                &wings_api::servers_server_files_contents::get::Query {
+                    file: Some(file.path.clone()),
+                    max_size: Some(max_size),
+                    ..Default::default()
+                },
+            )
+            .await
+        {
+            Ok(mut contents) => {
+                let mut content = Vec::new();
+                contents.read_to_end(&mut content).await?;
+
+                ServerQuarantinedFile::scan(
+                    state,
+                    node,
+                    file.server,
+                    file.user,
+                    FileScanSource::Sftp,
+                    &file.path,
+                    &content,
+                )
+                .await?
+            }
+            Err(wings_api::client::ApiHttpError::Http(StatusCode::PAYLOAD_TOO_LARGE, _)) => Some(
+                FileScanReport::unscanned(file.path.clone(), FileScanSource::Sftp, policy),
+            ),
+            // the file is already gone again, or is a directory
+            Err(wings_api::client::ApiHttpError::Http(
+                StatusCode::NOT_FOUND | StatusCode::EXPECTATION_FAILED,
+                _,
+            )) => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let Some(report) = report else {
+            return Ok(());
+        };
+
+        if report.is_quarantined()
+            || (report.verdict == FileScanVerdict::Unscanned && policy == FileScanPolicy::Block)
+        {
+            api_client
+                .post_servers_server_files_delete(
+                    file.server,
+                    &wings_api::servers_server_files_delete::post::RequestBody {
+                        root: "/".into(),
+                        files: vec![file.path.clone()],
+                    },
+                )
+                .await?;
+        }
+
+        let options = CreateServerActivityOptions {
+            server_uuid: file.server,
+            user_uuid: file.user,
+            impersonator_uuid: None,
+            api_key_uuid: None,
+            schedule_uuid: None,
+            event: "server:file.scan".into(),
+            ip: file.ip.map(|ip| ip.into()),
+            data: serde_json::to_value(&report)?,
+            created: None,
+        };
+        ServerActivity::create(state, options).await?;
+
+        Ok(())
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        node: GetNode,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        let server_uuids: Vec<_> = data.data.iter().map(|event| event.server).collect();
+        let owned_servers: HashSet<_> = sqlx::query!(
+            "SELECT servers.uuid FROM servers
+            WHERE servers.uuid = ANY($1) AND servers.node_uuid = $2",
+            &server_uuids,
+            node.uuid
+        )
+        .fetch_all(state.database.read())
+        .await?
+        .into_iter()
+        .map(|row| row.uuid)
+        .collect();
+
+        let mut uploaded_files = Vec::new();
+        let mut uploaded_paths = HashSet::new();
+
+        for event in data.data {
+            if !owned_servers.contains(&event.server) {
+                continue;
+            }
+
+            if matches!(event.source, ServerFileEventSource::Sftp)
+                && matches!(
+                    event.action,
+                    ServerFileEventAction::Create | ServerFileEventAction::Write
+                )
+                && uploaded_paths.insert((event.server, event.path.clone()))
+            {
+                uploaded_files.push(UploadedFile {
+                    server: event.server,
+                    user: event.user,
+                    path: event.path.clone(),
+                    ip: event.ip,
+                });
+            }
+
+            let options = shared::models::server_file_event::CreateServerFileEventOptions {
+                server_uuid: event.server,
+                user_uuid: event.user,
+                action: event.action,
+                source: event.source,
+                path: event.path,
+                new_path: event.new_path,
+                mode: event.mode.map(|mode| (mode & 0o7777) as i32),
+                ip: event.ip.map(|ip| ip.into()),
+                created: Some(event.timestamp.naive_utc()),
+            };
+            if let Err(err) = ServerFileEvent::create(&state, options).await {
+                tracing::warn!(
+                    server = %event.server,
+                    "failed to log remote file event for server: {:#?}",
+                    err
+                );
+            }
+        }
+
+        if !uploaded_files.is_empty() {
+            let state = state.clone();
+            let node = node.0;
+
+            tokio::spawn(async move {
+                for file in uploaded_files {
+                    let server = file.server;
+
+                    if let Err(err) = scan_uploaded_file(&state, &node, file).await {
+                        tracing::warn!(
+                            server = %server,
+                            "failed to scan uploaded file for server: {:#?}",
+                            err
+                        );
+                    }
+                }
+            });
+        }
+
+        ApiResponse::new_serialized(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
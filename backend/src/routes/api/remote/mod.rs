@@ -10,6 +10,7 @@ use utoipa_axum::router::OpenApiRouter;
 
 mod activity;
 mod backups;
+mod file_events;
 mod schedule;
 pub mod servers;
 mod sftp;
@@ -87,6 +88,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .nest("/sftp", sftp::router(state))
         .nest("/activity", activity::router(state))
+        .nest("/file-events", file_events::router(state))
         .nest("/servers", servers::router(state))
         .nest("/backups", backups::router(state))
         .nest("/schedule", schedule::router(state))
@@ -31,6 +31,9 @@ mod post {
 
         #[garde(skip)]
         ignored_files: Vec<compact_str::CompactString>,
+        #[garde(skip)]
+        #[serde(default)]
+        include_databases: bool,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -136,6 +139,24 @@ mod post {
                 .ok();
         }
 
+        if let Some(backup_storage_limit) = server.backup_storage_limit {
+            while ServerBackup::storage_usage_by_server_uuid(&state.database, server.uuid).await?
+                >= backup_storage_limit
+            {
+                if let Err(err) = ServerBackup::evict_one_by_server_uuid(&state, &server).await {
+                    tracing::error!(
+                        server = %server.uuid,
+                        "failed to delete old backup to free backup storage: {:?}",
+                        err
+                    );
+
+                    return ApiResponse::error("backup storage quota reached")
+                        .with_status(StatusCode::EXPECTATION_FAILED)
+                        .ok();
+                }
+            }
+        }
+
         let ratelimit = state
             .settings
             .get_as(|s| s.ratelimits.client_servers_backups_create)
@@ -158,6 +179,7 @@ mod post {
             backup_configuration: None,
             ignored_files: data.ignored_files,
             metadata: ServerBackup::generate_metadata(&state, &server).await?,
+            include_databases: data.include_databases,
         };
         let backup = ServerBackup::create_raw(&state, options).await?;
 
@@ -177,6 +199,7 @@ mod post {
                     "uuid": backup.uuid,
                     "name": backup.name,
                     "ignored_files": backup.ignored_files,
+                    "include_databases": data.include_databases,
                 }),
                 created: None,
             },
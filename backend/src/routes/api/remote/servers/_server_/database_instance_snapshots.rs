@@ -0,0 +1,164 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use garde::Validate;
+    use reqwest::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            CreatableModel, server::GetServer, server_activity::ServerActivity,
+            server_database_instance::ServerDatabaseInstance,
+            server_database_instance_snapshot::ServerDatabaseInstanceSnapshot,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[garde(skip)]
+        schedule_uuid: Option<uuid::Uuid>,
+
+        #[garde(skip)]
+        database_instance_uuid: uuid::Uuid,
+        #[garde(length(chars, min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        name: Option<compact_str::CompactString>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        uuid: uuid::Uuid,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        server: GetServer,
+        shared::Payload(data): shared::Payload<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::new_serialized(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let database_instance = match ServerDatabaseInstance::by_server_uuid_uuid(
+            &state.database,
+            server.uuid,
+            data.database_instance_uuid,
+        )
+        .await?
+        {
+            Some(database_instance) => database_instance,
+            None => {
+                return ApiResponse::error("database instance not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        if database_instance.database_agent_host.maintenance_enabled {
+            return ApiResponse::error(
+                "cannot create snapshot while database agent host is in maintenance mode",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .ok();
+        }
+
+        let snapshots_lock = state
+            .cache
+            .lock(
+                format!(
+                    "server_database_instances::{}::snapshots",
+                    database_instance.uuid
+                ),
+                Some(30),
+                Some(5),
+            )
+            .await?;
+
+        if ServerDatabaseInstanceSnapshot::count_pending_by_server_database_instance_uuid(
+            &state.database,
+            database_instance.uuid,
+        )
+        .await?
+            > 0
+        {
+            return ApiResponse::error("a snapshot of this database instance is already running")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        let mut snapshot = ServerDatabaseInstanceSnapshot::create_for_instance(
+            &state,
+            &database_instance,
+            None,
+            data.name
+                .unwrap_or_else(ServerDatabaseInstanceSnapshot::default_name),
+        )
+        .await?;
+
+        drop(snapshots_lock);
+
+        if let Err(err) = ServerActivity::create(
+            &state,
+            shared::models::server_activity::CreateServerActivityOptions {
+                server_uuid: server.uuid,
+                user_uuid: None,
+                impersonator_uuid: None,
+                api_key_uuid: None,
+                schedule_uuid: data.schedule_uuid,
+                event: "server:database-instance.snapshot.create".into(),
+                ip: None,
+                data: serde_json::json!({
+                    "uuid": database_instance.uuid,
+                    "name": database_instance.name,
+                    "snapshot_uuid": snapshot.uuid,
+                    "snapshot_name": snapshot.name,
+                }),
+                created: None,
+            },
+        )
+        .await
+        {
+            tracing::warn!(
+                server = %server.uuid,
+                "failed to log database instance snapshot creation activity: {:#?}",
+                err
+            );
+        }
+
+        // the schedule waits for the snapshot so that following steps only run once it is stored
+        if let Err(err) = snapshot.run(&state, &database_instance).await {
+            return ApiResponse::error(format!("failed to create snapshot: {err}"))
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .ok();
+        }
+
+        ApiResponse::new_serialized(Response {
+            uuid: snapshot.uuid,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
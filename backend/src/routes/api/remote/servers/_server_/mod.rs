@@ -13,6 +13,7 @@ use shared::{
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod backups;
+mod database_instance_snapshots;
 mod install;
 mod startup;
 mod transfer;
@@ -77,6 +78,10 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/install", install::router(state))
         .nest("/transfer", transfer::router(state))
         .nest("/backups", backups::router(state))
+        .nest(
+            "/database-instance-snapshots",
+            database_instance_snapshots::router(state),
+        )
         .nest("/startup", startup::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
         .with_state(state.clone())
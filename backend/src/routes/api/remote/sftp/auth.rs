@@ -6,7 +6,16 @@ mod post {
     use serde::{Deserialize, Serialize};
     use shared::{
         ApiError, GetState,
-        models::{node::GetNode, server::Server, user::User},
+        models::{
+            CreatableModel,
+            node::GetNode,
+            server::Server,
+            ssh_certificate_authority::SshCertificateAuthority,
+            user::User,
+            user_activity::{CreateUserActivityOptions, UserActivity},
+            user_security_key::UserSecurityKey,
+            user_sftp_password::{SFTP_PASSWORD_PREFIX, UserSftpPassword},
+        },
         response::{ApiResponse, ApiResponseResult},
     };
     use utoipa::ToSchema;
@@ -23,6 +32,9 @@ mod post {
         r#type: AuthenticationType,
         username: compact_str::CompactString,
         password: String,
+
+        #[serde(default)]
+        ip: Option<compact_str::CompactString>,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -63,16 +75,107 @@ mod post {
                 .ok();
         };
 
+        let ip = data.ip.as_deref().and_then(|ip| {
+            ip.parse::<std::net::SocketAddr>()
+                .map(|addr| addr.ip())
+                .or_else(|_| ip.parse::<std::net::IpAddr>())
+                .ok()
+        });
+
+        let mut sftp_password: Option<UserSftpPassword> = None;
+        let mut ssh_certificate: Option<(SshCertificateAuthority, russh::keys::Certificate)> = None;
         let user = match data.r#type {
+            AuthenticationType::Password if data.password.starts_with(SFTP_PASSWORD_PREFIX) => {
+                match User::by_username_sftp_password(&state.database, user, &data.password).await?
+                {
+                    Some((user, password)) => {
+                        sftp_password = Some(password);
+                        user
+                    }
+                    None => {
+                        return ApiResponse::error("user not found")
+                            .with_status(StatusCode::EXPECTATION_FAILED)
+                            .ok();
+                    }
+                }
+            }
             AuthenticationType::Password => {
-                match User::by_username_password(&state.database, user, &data.password).await? {
+                let user = match User::by_username_password(&state.database, user, &data.password)
+                    .await?
+                {
                     Some(user) => user,
                     None => {
                         return ApiResponse::error("user not found")
                             .with_status(StatusCode::EXPECTATION_FAILED)
                             .ok();
                     }
+                };
+
+                if user.disable_sftp_password_login
+                    && (user.totp_enabled
+                        || UserSecurityKey::count_by_user_uuid(&state.database, user.uuid).await?
+                            > 0)
+                {
+                    return ApiResponse::error(
+                        "password login is disabled for this account, use an sftp app password",
+                    )
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
                 }
+
+                user
+            }
+            AuthenticationType::PublicKey
+                if data
+                    .password
+                    .split_whitespace()
+                    .next()
+                    .is_some_and(|algorithm| algorithm.ends_with("-cert-v01@openssh.com")) =>
+            {
+                let certificate = match russh::keys::Certificate::from_openssh(&data.password) {
+                    Ok(certificate) => certificate,
+                    Err(_) => {
+                        return ApiResponse::error("invalid certificate")
+                            .with_status(StatusCode::EXPECTATION_FAILED)
+                            .ok();
+                    }
+                };
+
+                let authority = match SshCertificateAuthority::by_fingerprint_enabled(
+                    &state.database,
+                    &certificate
+                        .signature_key()
+                        .fingerprint(russh::keys::HashAlg::Sha256)
+                        .to_string(),
+                )
+                .await?
+                {
+                    Some(authority) => authority,
+                    None => {
+                        return ApiResponse::error("certificate authority not trusted")
+                            .with_status(StatusCode::EXPECTATION_FAILED)
+                            .ok();
+                    }
+                };
+
+                if let Err(err) = authority.verify_certificate(&certificate, user, ip) {
+                    return ApiResponse::error(err)
+                        .with_status(StatusCode::EXPECTATION_FAILED)
+                        .ok();
+                }
+
+                let user = match User::by_username(&state.database, user).await? {
+                    Some(user) => user,
+                    None => {
+                        return ApiResponse::error("user not found")
+                            .with_status(StatusCode::EXPECTATION_FAILED)
+                            .ok();
+                    }
+                };
+
+                ssh_certificate = Some((authority, certificate));
+
+                user
             }
             AuthenticationType::PublicKey => {
                 let public_key = match russh::keys::PublicKey::from_openssh(&data.password) {
@@ -103,16 +206,67 @@ mod post {
             }
         };
 
-        if server.node.uuid != node.uuid {
+        if server.node.uuid != node.uuid
+            || sftp_password
+                .as_ref()
+                .is_some_and(|sftp_password| !sftp_password.allows_server(server.uuid))
+        {
             return ApiResponse::error("server not found")
                 .with_status(StatusCode::NOT_FOUND)
                 .ok();
         }
 
+        let mut permissions = server.wings_permissions(&*state.settings.get().await?, &user, None);
+        if let Some(sftp_password) = &sftp_password {
+            sftp_password.restrict_permissions(&mut permissions);
+            sftp_password.update_last_used(&state.database).await;
+        }
+
+        let certificate = ssh_certificate.as_ref().map(|(authority, certificate)| {
+            serde_json::json!({
+                "key_id": certificate.key_id(),
+                "serial": certificate.serial(),
+                "authority": authority.uuid,
+            })
+        });
+
+        if let Err(err) = UserActivity::create(
+            &state,
+            CreateUserActivityOptions {
+                user_uuid: user.uuid,
+                impersonator_uuid: None,
+                api_key_uuid: None,
+                event: "sftp:login".into(),
+                ip: ip.map(|ip| ip.into()),
+                data: serde_json::json!({
+                    "using": match (&data.r#type, &sftp_password, &ssh_certificate) {
+                        (AuthenticationType::PublicKey, _, Some(_)) => "certificate",
+                        (AuthenticationType::PublicKey, _, None) => "public_key",
+                        (AuthenticationType::Password, Some(_), _) => "sftp_password",
+                        (AuthenticationType::Password, None, _) => "password",
+                    },
+                    "sftp_password": sftp_password.as_ref().map(|sftp_password| sftp_password.uuid),
+                    "certificate": certificate,
+                    "server": server.uuid,
+                    "node": node.uuid,
+                    "node_name": node.name,
+                }),
+                created: None,
+            },
+        )
+        .await
+        {
+            tracing::warn!(
+                user = %user.uuid,
+                "failed to log user activity: {:#?}",
+                err
+            );
+        }
+
         ApiResponse::new_serialized(Response {
             user: user.uuid,
             server: server.uuid,
-            permissions: server.wings_permissions(&*state.settings.get().await?, &user, None),
+            permissions,
             ignored_files: server.subuser_ignored_files.as_deref().unwrap_or(&[]),
         })
         .ok()
@@ -38,6 +38,8 @@ mod get {
         max_backup_group_count: u64,
         max_database_instance_database_count: u64,
         max_database_instance_user_count: u64,
+        max_database_saved_query_count: u64,
+        max_database_instance_snapshot_policy_count: u64,
 
         allow_overwriting_custom_docker_image: bool,
         allow_acknowledging_installation_failure: bool,
@@ -115,6 +117,10 @@ mod get {
                     .server
                     .max_database_instance_database_count,
                 max_database_instance_user_count: settings.server.max_database_instance_user_count,
+                max_database_saved_query_count: settings.server.max_database_saved_query_count,
+                max_database_instance_snapshot_policy_count: settings
+                    .server
+                    .max_database_instance_snapshot_policy_count,
 
                 allow_overwriting_custom_docker_image: settings
                     .server
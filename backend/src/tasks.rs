@@ -1,12 +1,39 @@
 use rand::RngExt;
 use shared::models::{
-    ByUuid, CreatableModel, admin_activity::AdminActivity, announcement::Announcement,
-    backup_configuration::BackupConfiguration, egg_configuration::EggConfiguration, node::Node,
-    oauth_provider_mapping::OAuthProviderMapping, server::Server, server_activity::ServerActivity,
-    server_backup::ServerBackup, system_backup_policy::SystemBackupPolicy,
-    user_activity::UserActivity, user_api_key::UserApiKey,
-    user_command_snippet::UserCommandSnippet, user_password_reset::UserPasswordReset,
-    user_security_key::UserSecurityKey, user_server_group::UserServerGroup,
+    ByUuid, CreatableModel, DeletableModel,
+    admin_activity::AdminActivity,
+    announcement::Announcement,
+    backup_configuration::BackupConfiguration,
+    database_agent_template_rollout::DatabaseAgentTemplateRollout,
+    database_host::DatabaseHost,
+    egg_configuration::EggConfiguration,
+    node::Node,
+    oauth_provider_mapping::OAuthProviderMapping,
+    server::Server,
+    server_activity::ServerActivity,
+    server_backup::ServerBackup,
+    server_backup_replica::ServerBackupReplica,
+    server_console_log_archive::ServerConsoleLogArchive,
+    server_console_log_chunk::ServerConsoleLogChunk,
+    server_database::{QuotaEnforcement, ServerDatabase},
+    server_database_access_token::ServerDatabaseAccessToken,
+    server_database_instance::ServerDatabaseInstance,
+    server_database_instance_snapshot::ServerDatabaseInstanceSnapshot,
+    server_database_instance_snapshot_policy::ServerDatabaseInstanceSnapshotPolicy,
+    server_database_migration::ServerDatabaseMigration,
+    server_database_query_history::ServerDatabaseQueryHistory,
+    server_deployment::ServerDeployment,
+    server_file_event::ServerFileEvent,
+    server_file_share::ServerFileShare,
+    server_quarantined_file::ServerQuarantinedFile,
+    server_trashed_file::ServerTrashedFile,
+    system_backup_policy::SystemBackupPolicy,
+    user_activity::UserActivity,
+    user_api_key::UserApiKey,
+    user_command_snippet::UserCommandSnippet,
+    user_password_reset::UserPasswordReset,
+    user_security_key::UserSecurityKey,
+    user_server_group::UserServerGroup,
     user_session::UserSession,
 };
 use std::str::FromStr;
@@ -136,6 +163,109 @@ pub async fn define_background_tasks(
             },
         )
         .await;
+    background_task_builder
+        .add_cron_task(
+            "delete_expired_file_shares",
+            croner::Cron::from_str("0 */30 * * * *").unwrap(),
+            async |state| {
+                let deleted_file_shares = ServerFileShare::delete_expired(&state.database).await?;
+                if deleted_file_shares > 0 {
+                    tracing::info!("deleted {} expired file shares", deleted_file_shares);
+                }
+
+                Ok(())
+            },
+        )
+        .await;
+    background_task_builder
+        .add_cron_task(
+            "cleanup_server_deployments",
+            croner::Cron::from_str("0 */15 * * * *").unwrap(),
+            async |state| {
+                let failed_deployments = ServerDeployment::fail_stale(
+                    &state.database,
+                    chrono::Utc::now() - chrono::Duration::hours(1),
+                )
+                .await?;
+                if failed_deployments > 0 {
+                    tracing::info!("failed {} stale server deployments", failed_deployments);
+                }
+
+                let deleted_deployments =
+                    ServerDeployment::retain_latest_per_source(&state.database, 50).await?;
+                if deleted_deployments > 0 {
+                    tracing::info!("deleted {} old server deployments", deleted_deployments);
+                }
+
+                Ok(())
+            },
+        )
+        .await;
+    background_task_builder
+        .add_cron_task(
+            "collect_console_log_archives",
+            croner::Cron::from_str("0 * * * * *").unwrap(),
+            async |state| {
+                use futures_util::StreamExt;
+
+                let archives = ServerConsoleLogArchive::all_collectable(&state.database).await?;
+
+                let futures = archives.into_iter().map(|mut archive| {
+                    let state = state.clone();
+
+                    async move {
+                        if let Err(err) = archive.collect(&state).await {
+                            tracing::warn!(
+                                server = %archive.server_uuid,
+                                "failed to collect console logs for archive: {err:#?}"
+                            );
+                        }
+                    }
+                });
+                futures_util::stream::iter(futures)
+                    .buffer_unordered(10)
+                    .collect::<()>()
+                    .await;
+
+                Ok(())
+            },
+        )
+        .await;
+    background_task_builder
+        .add_cron_task(
+            "cleanup_console_log_archives",
+            croner::Cron::from_str("0 10 * * * *").unwrap(),
+            async |state| {
+                let settings = state.settings.get().await?;
+                let retention_days = settings.server.console_log_archive_retention_days;
+                let max_size = settings.server.max_console_log_archive_size;
+                drop(settings);
+
+                let expired_chunks = ServerConsoleLogChunk::delete_older_than(
+                    &state.database,
+                    chrono::Utc::now() - chrono::Duration::days(retention_days as i64),
+                )
+                .await?;
+                if expired_chunks > 0 {
+                    tracing::info!("deleted {} expired console log chunks", expired_chunks);
+                }
+
+                let oversized_chunks = ServerConsoleLogChunk::delete_exceeding_size(
+                    &state.database,
+                    max_size.min(i64::MAX as u64) as i64,
+                )
+                .await?;
+                if oversized_chunks > 0 {
+                    tracing::info!(
+                        "deleted {} console log chunks exceeding the archive size limit",
+                        oversized_chunks
+                    );
+                }
+
+                Ok(())
+            },
+        )
+        .await;
     background_task_builder
         .add_cron_task(
             "delete_unconfigured_security_keys",
@@ -366,6 +496,8 @@ pub async fn define_background_tasks(
             let user_retention_count = settings.activity.user_log_retention_count;
             let server_retention_days = settings.activity.server_log_retention_days;
             let server_retention_count = settings.activity.server_log_retention_count;
+            let server_file_retention_days = settings.activity.server_file_log_retention_days;
+            let server_file_retention_count = settings.activity.server_file_log_retention_count;
             drop(settings);
 
             let deleted_admin_activity = AdminActivity::delete_older_than(
@@ -443,9 +575,417 @@ pub async fn define_background_tasks(
                 }
             }
 
+            let deleted_server_file_events = ServerFileEvent::delete_older_than(
+                &state.database,
+                chrono::Utc::now() - chrono::Duration::days(server_file_retention_days as i64),
+            )
+            .await?;
+            if deleted_server_file_events > 0 {
+                tracing::info!(
+                    "deleted {} old server file events",
+                    deleted_server_file_events
+                );
+            }
+
+            if let Some(server_file_retention_count) = server_file_retention_count {
+                let deleted_server_file_events = ServerFileEvent::retain_latest_events_per_server(
+                    &state.database,
+                    server_file_retention_count as i64,
+                )
+                .await?;
+                if deleted_server_file_events > 0 {
+                    tracing::info!(
+                        "deleted {} old server file events to retain latest {} events per server",
+                        deleted_server_file_events,
+                        server_file_retention_count
+                    );
+                }
+            }
+
             Ok(())
         })
         .await;
+    background_task_builder
+        .add_cron_task(
+            "check_database_hosts",
+            croner::Cron::from_str("30 * * * * *").unwrap(),
+            async |state| {
+                let database_hosts = DatabaseHost::all(&state.database).await?;
+
+                let futures = database_hosts.into_iter().map(|mut database_host| {
+                    let state = state.clone();
+
+                    async move {
+                        let was_healthy = database_host.is_healthy();
+                        let health = database_host.check_health(&state.database).await;
+
+                        if was_healthy && !health.healthy {
+                            tracing::warn!(
+                                database_host = %database_host.uuid,
+                                "database host became unreachable: {}",
+                                health.error.as_deref().unwrap_or("unknown error")
+                            );
+                        } else if !was_healthy && health.healthy {
+                            tracing::info!(
+                                database_host = %database_host.uuid,
+                                "database host is reachable again"
+                            );
+                        }
+
+                        if let Err(err) = database_host.record_health(&state.database, health).await
+                        {
+                            tracing::error!(
+                                database_host = %database_host.uuid,
+                                "failed to record database host health: {:?}",
+                                err
+                            );
+                        }
+                    }
+                });
+                futures_util::future::join_all(futures).await;
+
+                Ok(())
+            },
+        )
+        .await;
+    background_task_builder
+        .add_cron_task(
+            "delete_old_database_query_history",
+            croner::Cron::from_str("0 15,45 * * * *").unwrap(),
+            async |state| {
+                let settings = state.settings.get().await?;
+                let retention_days = settings.server.database_query_history_retention_days;
+                let retention_count = settings.server.database_query_history_retention_count;
+                drop(settings);
+
+                let deleted_query_history = ServerDatabaseQueryHistory::delete_older_than(
+                    &state.database,
+                    chrono::Utc::now() - chrono::Duration::days(retention_days as i64),
+                )
+                .await?;
+                if deleted_query_history > 0 {
+                    tracing::info!(
+                        "deleted {} old database query history entries",
+                        deleted_query_history
+                    );
+                }
+
+                let deleted_query_history = ServerDatabaseQueryHistory::retain_latest_per_user(
+                    &state.database,
+                    retention_count as i64,
+                )
+                .await?;
+                if deleted_query_history > 0 {
+                    tracing::info!(
+                        "deleted {} old database query history entries to retain latest {} per user",
+                        deleted_query_history,
+                        retention_count
+                    );
+                }
+
+                Ok(())
+            },
+        )
+        .await;
+    background_task_builder
+        .add_cron_task(
+            "purge_expired_trashed_files",
+            croner::Cron::from_str("0 5 * * * *").unwrap(),
+            async |state| {
+                let settings = state.settings.get().await?;
+                let retention_days = settings.server.file_trash_retention_days;
+                drop(settings);
+
+                let trashed_files = ServerTrashedFile::all_older_than(
+                    &state.database,
+                    chrono::Utc::now() - chrono::Duration::days(retention_days as i64),
+                )
+                .await?;
+
+                let mut servers: std::collections::HashMap<uuid::Uuid, Vec<uuid::Uuid>> =
+                    std::collections::HashMap::new();
+                for trashed_file in trashed_files {
+                    servers
+                        .entry(trashed_file.server_uuid)
+                        .or_default()
+                        .push(trashed_file.uuid);
+                }
+
+                for (server_uuid, ids) in servers {
+                    let server = match Server::by_uuid_optional(&state.database, server_uuid).await
+                    {
+                        Ok(Some(server)) => server,
+                        Ok(None) => continue,
+                        Err(err) => {
+                            tracing::warn!(
+                                server = %server_uuid,
+                                "failed to load server for trash purge: {err:#?}"
+                            );
+                            continue;
+                        }
+                    };
+
+                    let client = match server.node.fetch_cached(&state.database).await {
+                        Ok(node) => match node.api_client(&state.database).await {
+                            Ok(client) => client,
+                            Err(err) => {
+                                tracing::warn!(
+                                    server = %server.uuid,
+                                    "failed to create node api client for trash purge: {err:#?}"
+                                );
+                                continue;
+                            }
+                        },
+                        Err(err) => {
+                            tracing::warn!(
+                                server = %server.uuid,
+                                "failed to load node for trash purge: {err:#?}"
+                            );
+                            continue;
+                        }
+                    };
+
+                    let request_body =
+                        wings_api::servers_server_files_trash_purge::post::RequestBody { ids };
+
+                    if let Err(err) = client
+                        .post_servers_server_files_trash_purge(server.uuid, &request_body)
+                        .await
+                    {
+                        tracing::warn!(
+                            server = %server.uuid,
+                            "failed to purge expired trashed files: {err:#?}"
+                        );
+                        continue;
+                    }
+
+                    let purged =
+                        ServerTrashedFile::delete_by_uuids(&state.database, &request_body.ids)
+                            .await?;
+                    if purged > 0 {
+                        tracing::info!(
+                            server = %server.uuid,
+                            "purged {} expired trashed files",
+                            purged
+                        );
+                    }
+                }
+
+                Ok(())
+            },
+        )
+        .await;
+    background_task_builder
+        .add_cron_task(
+            "purge_expired_quarantined_files",
+            croner::Cron::from_str("0 15 * * * *").unwrap(),
+            async |state| {
+                let retention_days = state
+                    .settings
+                    .get_as(|s| s.server.file_quarantine_retention_days)
+                    .await?;
+
+                let quarantined_files = ServerQuarantinedFile::all_older_than(
+                    &state.database,
+                    chrono::Utc::now() - chrono::Duration::days(retention_days as i64),
+                )
+                .await?;
+
+                for quarantined_file in quarantined_files {
+                    if let Err(err) = quarantined_file.delete(&state, ()).await {
+                        tracing::warn!(
+                            server = %quarantined_file.server_uuid,
+                            "failed to purge expired quarantined file: {err:#?}"
+                        );
+                    }
+                }
+
+                Ok(())
+            },
+        )
+        .await;
+    background_task_builder
+        .add_cron_task(
+            "enforce_database_quotas",
+            croner::Cron::from_str("0 */10 * * * *").unwrap(),
+            async |state| {
+                async fn notify_database_quota(
+                    state: &shared::State,
+                    server: &shared::models::Fetchable<Server>,
+                    template: &str,
+                    database_name: &str,
+                    size: i64,
+                    size_limit: Option<i64>,
+                    percent: i16,
+                ) -> Result<(), anyhow::Error> {
+                    let server = server.fetch_cached(&state.database).await?;
+                    let settings = state.settings.get().await?;
+                    let server_link = format!(
+                        "{}/server/{:08x}/databases",
+                        settings.app.url, server.uuid_short,
+                    );
+                    drop(settings);
+
+                    state
+                        .mail
+                        .send_template(
+                            state,
+                            template,
+                            server.owner.email.clone(),
+                            minijinja::context! {
+                                user => server.owner,
+                                server => server,
+                                database_name => database_name,
+                                usage => human_bytes::human_bytes(size as f64),
+                                limit => human_bytes::human_bytes(
+                                    size_limit.unwrap_or_default() as f64
+                                ),
+                                percent => percent,
+                                server_link => server_link,
+                            },
+                        )
+                        .await;
+
+                    Ok(())
+                }
+
+                let databases = ServerDatabase::all_with_size_limit(&state.database).await?;
+
+                let futures = databases
+                    .into_iter()
+                    .filter(|(database, _)| database.database_host.is_healthy())
+                    .map(|(mut database, size_limit)| {
+                        let state = state.clone();
+
+                        async move {
+                            let enforcement =
+                                match database.enforce_quota(&state.database, size_limit).await {
+                                    Ok(enforcement) => enforcement,
+                                    Err(err) => {
+                                        tracing::error!(
+                                            database = %database.uuid,
+                                            "failed to enforce database quota: {:?}",
+                                            err
+                                        );
+
+                                        return Ok(());
+                                    }
+                                };
+
+                            let (template, percent) = match enforcement {
+                                QuotaEnforcement::Unchanged => return Ok(()),
+                                QuotaEnforcement::Restored => {
+                                    tracing::info!(
+                                        database = %database.uuid,
+                                        "database is below its quota again, restored write access"
+                                    );
+
+                                    return Ok(());
+                                }
+                                QuotaEnforcement::Warning(percent) => {
+                                    ("database_quota_warning", percent)
+                                }
+                                QuotaEnforcement::Exceeded => {
+                                    tracing::info!(
+                                        database = %database.uuid,
+                                        "database exceeded its quota, revoked write access"
+                                    );
+
+                                    ("database_quota_exceeded", 100)
+                                }
+                            };
+
+                            notify_database_quota(
+                                &state,
+                                &database.server,
+                                template,
+                                &database.name,
+                                database.size.unwrap_or_default(),
+                                size_limit,
+                                percent,
+                            )
+                            .await
+                        }
+                    });
+
+                for result in futures_util::future::join_all(futures).await {
+                    if let Err(err) = result {
+                        tracing::error!("failed to notify about database quota: {:?}", err);
+                    }
+                }
+
+                let instances = ServerDatabaseInstance::all_with_size_limit(&state.database).await?;
+
+                let futures = instances.into_iter().map(|(instance, size_limit)| {
+                    let state = state.clone();
+
+                    async move {
+                        let enforcements =
+                            match instance.enforce_quotas(&state.database, size_limit).await {
+                                Ok(enforcements) => enforcements,
+                                Err(err) => {
+                                    tracing::error!(
+                                        instance = %instance.uuid,
+                                        "failed to enforce database instance quotas: {:?}",
+                                        err
+                                    );
+
+                                    return Ok(());
+                                }
+                            };
+
+                        for (quota, enforcement) in enforcements {
+                            let (template, percent) = match enforcement {
+                                QuotaEnforcement::Unchanged => continue,
+                                QuotaEnforcement::Restored => {
+                                    tracing::info!(
+                                        instance = %instance.uuid,
+                                        database = %quota.database_uuid,
+                                        "instance database is below its quota again, restored write access"
+                                    );
+
+                                    continue;
+                                }
+                                QuotaEnforcement::Warning(percent) => {
+                                    ("database_quota_warning", percent)
+                                }
+                                QuotaEnforcement::Exceeded => {
+                                    tracing::info!(
+                                        instance = %instance.uuid,
+                                        database = %quota.database_uuid,
+                                        "instance database exceeded its quota, revoked write access"
+                                    );
+
+                                    ("database_quota_exceeded", 100)
+                                }
+                            };
+
+                            notify_database_quota(
+                                &state,
+                                &instance.server,
+                                template,
+                                &quota.name,
+                                quota.size,
+                                size_limit,
+                                percent,
+                            )
+                            .await?;
+                        }
+
+                        Ok::<_, anyhow::Error>(())
+                    }
+                });
+
+                for result in futures_util::future::join_all(futures).await {
+                    if let Err(err) = result {
+                        tracing::error!("failed to notify about database instance quota: {:?}", err);
+                    }
+                }
+
+                Ok(())
+            },
+        )
+        .await;
     background_task_builder
         .add_cron_task(
             "redispatch_stale_backup_deletions",
@@ -465,6 +1005,227 @@ pub async fn define_background_tasks(
             },
         )
         .await;
+    background_task_builder
+        .add_task("replicate_backups", async |state| {
+            const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+            const CLAIM_LIMIT: i64 = 4;
+
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                let replicas =
+                    match ServerBackupReplica::claim_due(&state.database, CLAIM_LIMIT).await {
+                        Ok(replicas) => replicas,
+                        Err(err) => {
+                            tracing::error!("failed to claim backup replicas: {err:#?}");
+                            continue;
+                        }
+                    };
+
+                for replica in replicas {
+                    let state = state.clone();
+
+                    tokio::spawn(async move {
+                        match replica.replicate(&state).await {
+                            Ok(()) => tracing::info!(
+                                replica = %replica.uuid,
+                                backup = %replica.backup_uuid,
+                                "replicated backup"
+                            ),
+                            Err(err) => {
+                                tracing::error!(
+                                    replica = %replica.uuid,
+                                    backup = %replica.backup_uuid,
+                                    attempts = replica.attempts,
+                                    "failed to replicate backup: {err:#?}"
+                                );
+
+                                if let Err(err) = replica.fail_attempt(&state, &err).await {
+                                    tracing::error!(
+                                        replica = %replica.uuid,
+                                        "failed to record backup replication failure: {err:#?}"
+                                    );
+                                }
+                            }
+                        }
+                    });
+                }
+            }
+        })
+        .await;
+    background_task_builder
+        .add_task("run_database_migrations", async |state| {
+            const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+            const CONCURRENCY_LIMIT: i64 = 2;
+
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                match ServerDatabaseMigration::fail_interrupted(&state.database).await {
+                    Ok(0) => {}
+                    Ok(interrupted) => {
+                        tracing::warn!("failed {} interrupted database migrations", interrupted)
+                    }
+                    Err(err) => {
+                        tracing::error!("failed to fail interrupted database migrations: {err:#?}")
+                    }
+                }
+
+                let migrations = match ServerDatabaseMigration::claim_pending(
+                    &state.database,
+                    CONCURRENCY_LIMIT,
+                )
+                .await
+                {
+                    Ok(migrations) => migrations,
+                    Err(err) => {
+                        tracing::error!("failed to claim database migrations: {err:#?}");
+                        continue;
+                    }
+                };
+
+                for migration in migrations {
+                    let state = state.clone();
+
+                    tokio::spawn(async move {
+                        match migration.run(&state).await {
+                            Ok(()) => tracing::info!(
+                                migration = %migration.uuid,
+                                database = %migration.server_database_uuid,
+                                "migrated database"
+                            ),
+                            Err(err) => tracing::error!(
+                                migration = %migration.uuid,
+                                database = %migration.server_database_uuid,
+                                "failed to migrate database: {err:#?}"
+                            ),
+                        }
+                    });
+                }
+            }
+        })
+        .await;
+    background_task_builder
+        .add_task("run_database_agent_template_rollouts", async |state| {
+            const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                match DatabaseAgentTemplateRollout::fail_interrupted(&state.database).await {
+                    Ok(0) => {}
+                    Ok(interrupted) => tracing::warn!(
+                        "failed {} interrupted database agent template rollouts",
+                        interrupted
+                    ),
+                    Err(err) => tracing::error!(
+                        "failed to fail interrupted database agent template rollouts: {err:#?}"
+                    ),
+                }
+
+                let rollouts =
+                    match DatabaseAgentTemplateRollout::claim_pending(&state.database).await {
+                        Ok(rollouts) => rollouts,
+                        Err(err) => {
+                            tracing::error!(
+                                "failed to claim database agent template rollouts: {err:#?}"
+                            );
+                            continue;
+                        }
+                    };
+
+                for mut rollout in rollouts {
+                    let state = state.clone();
+
+                    tokio::spawn(async move {
+                        match rollout.run(&state).await {
+                            Ok(()) => tracing::info!(
+                                rollout = %rollout.uuid,
+                                template = %rollout.database_agent_template_uuid,
+                                updated = rollout.updated,
+                                failed = rollout.failures.len(),
+                                "finished database agent template rollout"
+                            ),
+                            Err(err) => tracing::error!(
+                                rollout = %rollout.uuid,
+                                template = %rollout.database_agent_template_uuid,
+                                "failed to roll out database agent template: {err:#?}"
+                            ),
+                        }
+                    });
+                }
+            }
+        })
+        .await;
+    background_task_builder
+        .add_cron_task(
+            "expire_database_access_tokens",
+            croner::Cron::from_str("0 * * * * *").unwrap(),
+            async |state| {
+                for access_token in ServerDatabaseAccessToken::all_expired(&state.database).await? {
+                    if let Err(err) = access_token.delete(&state, ()).await {
+                        tracing::warn!(
+                            access_token = %access_token.uuid,
+                            "failed to revoke expired database access token: {:?}",
+                            err
+                        );
+                        continue;
+                    }
+
+                    let (event, uuid) = match access_token.server_database_instance_uuid {
+                        Some(instance_uuid) => (
+                            "server:database-instance.access-token.expire",
+                            instance_uuid,
+                        ),
+                        None => (
+                            "server:database.access-token.expire",
+                            access_token.server_database_uuid.unwrap_or_default(),
+                        ),
+                    };
+
+                    if let Err(err) = ServerActivity::create(
+                        &state,
+                        shared::models::server_activity::CreateServerActivityOptions {
+                            server_uuid: access_token.server.uuid,
+                            user_uuid: None,
+                            impersonator_uuid: None,
+                            api_key_uuid: None,
+                            schedule_uuid: None,
+                            event: event.into(),
+                            ip: None,
+                            data: serde_json::json!({
+                                "uuid": uuid,
+                                "database_uuid": access_token.instance_database_uuid,
+                                "access_token_uuid": access_token.uuid,
+                                "access_token_name": access_token.name,
+                                "username": access_token.username,
+                            }),
+                            created: None,
+                        },
+                    )
+                    .await
+                    {
+                        tracing::warn!(
+                            access_token = %access_token.uuid,
+                            "failed to log database access token expiry: {:?}",
+                            err
+                        );
+                    }
+                }
+
+                Ok(())
+            },
+        )
+        .await;
     background_task_builder
         .add_cron_task(
             "prune_backup_groups",
@@ -660,6 +1421,7 @@ pub async fn define_background_tasks(
                             backup_configuration: Some(backup_configuration),
                             ignored_files: Vec::new(),
                             metadata,
+                            include_databases: policy.include_databases,
                         };
                         let backup = match ServerBackup::create(&state, options).await {
                             Ok(backup) => backup,
@@ -736,4 +1498,178 @@ pub async fn define_background_tasks(
             },
         )
         .await;
+    background_task_builder
+        .add_task("run_database_instance_snapshot_policies", async |state| {
+            const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+                let now = chrono::Utc::now();
+
+                match ServerDatabaseInstanceSnapshot::fail_interrupted(&state.database).await {
+                    Ok(0) => {}
+                    Ok(interrupted) => {
+                        tracing::warn!("failed {} interrupted database instance snapshots", interrupted)
+                    }
+                    Err(err) => {
+                        tracing::error!(
+                            "failed to fail interrupted database instance snapshots: {err:#?}"
+                        )
+                    }
+                }
+
+                let policies =
+                    match ServerDatabaseInstanceSnapshotPolicy::all_enabled(&state.database).await {
+                        Ok(policies) => policies,
+                        Err(err) => {
+                            tracing::error!(
+                                "failed to load database instance snapshot policies: {err:#?}"
+                            );
+                            continue;
+                        }
+                    };
+
+                for mut policy in policies {
+                    if !policy.is_due(now) {
+                        continue;
+                    }
+
+                    let instance = match ServerDatabaseInstance::by_uuid_optional(
+                        &state.database,
+                        policy.server_database_instance_uuid,
+                    )
+                    .await
+                    {
+                        Ok(Some(instance)) => instance,
+                        Ok(None) => continue,
+                        Err(err) => {
+                            tracing::warn!(
+                                database_instance = %policy.server_database_instance_uuid,
+                                "failed to load database instance for snapshot policy: {err:#?}"
+                            );
+                            continue;
+                        }
+                    };
+                    if instance.database_agent_host.maintenance_enabled {
+                        continue;
+                    }
+
+                    match ServerDatabaseInstanceSnapshot::count_pending_by_server_database_instance_uuid(
+                        &state.database,
+                        instance.uuid,
+                    )
+                    .await
+                    {
+                        Ok(0) => {}
+                        Ok(_) => continue,
+                        Err(err) => {
+                            tracing::warn!(
+                                database_instance = %instance.uuid,
+                                "failed to count pending database instance snapshots: {err:#?}"
+                            );
+                            continue;
+                        }
+                    }
+
+                    match policy.claim_run(&state.database).await {
+                        Ok(true) => {}
+                        Ok(false) => continue,
+                        Err(err) => {
+                            tracing::error!(
+                                policy = %policy.uuid,
+                                "failed to claim database instance snapshot policy run: {err:#?}"
+                            );
+                            continue;
+                        }
+                    }
+
+                    let mut snapshot = match ServerDatabaseInstanceSnapshot::create_for_instance(
+                        &state,
+                        &instance,
+                        Some(policy.uuid),
+                        ServerDatabaseInstanceSnapshot::default_name(),
+                    )
+                    .await
+                    {
+                        Ok(snapshot) => snapshot,
+                        Err(err) => {
+                            tracing::warn!(
+                                database_instance = %instance.uuid,
+                                policy = %policy.uuid,
+                                "failed to create database instance snapshot: {err:#?}"
+                            );
+                            continue;
+                        }
+                    };
+
+                    if let Err(err) = ServerActivity::create(
+                        &state,
+                        shared::models::server_activity::CreateServerActivityOptions {
+                            server_uuid: instance.server.uuid,
+                            user_uuid: None,
+                            impersonator_uuid: None,
+                            api_key_uuid: None,
+                            schedule_uuid: None,
+                            event: "server:database-instance.snapshot.create".into(),
+                            ip: None,
+                            data: serde_json::json!({
+                                "source": "snapshot-policy",
+                                "uuid": instance.uuid,
+                                "name": instance.name,
+                                "snapshot_uuid": snapshot.uuid,
+                                "snapshot_name": snapshot.name,
+                                "policy": policy.name,
+                            }),
+                            created: None,
+                        },
+                    )
+                    .await
+                    {
+                        tracing::warn!(
+                            database_instance = %instance.uuid,
+                            "failed to log database instance snapshot activity: {err:#?}"
+                        );
+                    }
+
+                    let state = state.clone();
+
+                    tokio::spawn(async move {
+                        if snapshot.run(&state, &instance).await.is_err() {
+                            return;
+                        }
+
+                        let expired =
+                            match ServerDatabaseInstanceSnapshot::all_expired_by_snapshot_policy_uuid(
+                                &state.database,
+                                policy.uuid,
+                                policy.retention_count,
+                            )
+                            .await
+                            {
+                                Ok(expired) => expired,
+                                Err(err) => {
+                                    tracing::error!(
+                                        policy = %policy.uuid,
+                                        "failed to load expired database instance snapshots: {err:#?}"
+                                    );
+                                    return;
+                                }
+                            };
+
+                        for snapshot in expired {
+                            if let Err(err) = snapshot.delete(&state, ()).await {
+                                tracing::error!(
+                                    snapshot = %snapshot.uuid,
+                                    "failed to delete expired database instance snapshot: {err:#?}"
+                                );
+                            }
+                        }
+                    });
+                }
+            }
+        })
+        .await;
 }
@@ -68,6 +68,7 @@ async fn handle_aio_wings(
                                 shared: false,
                                 backup_disk: shared::models::server_backup::BackupDisk::Local,
                                 backup_configs: Default::default(),
+                                replication_backup_configuration_uuid: None,
                             }).await?
                         }
                     };
@@ -79,6 +80,7 @@ async fn handle_aio_wings(
                             name: "Integrated Location".into(),
                             description: None,
                             flag: None,
+                            file_scan_policy: Default::default(),
                         },
                     )
                     .await?
@@ -110,6 +112,7 @@ async fn handle_aio_wings(
                     description: None,
                     deployment_enabled: true,
                     maintenance_enabled: false,
+                    file_scan_policy: None,
                     public_url: None,
                     url: "http://localhost:64332".into(),
                     sftp_host: None,
@@ -0,0 +1,22 @@
+CREATE TABLE "server_backup_replicas" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"backup_uuid" uuid NOT NULL,
+	"backup_configuration_uuid" uuid,
+	"bytes" bigint DEFAULT 0 NOT NULL,
+	"upload_path" text,
+	"attempts" integer DEFAULT 0 NOT NULL,
+	"error" text,
+	"last_attempt" timestamp,
+	"completed" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "backup_configurations" ADD COLUMN "replication_backup_configuration_uuid" uuid;
+CREATE INDEX "backup_configurations_replication_backup_configuration_uuid_idx" ON "backup_configurations" ("replication_backup_configuration_uuid");
+CREATE INDEX "server_backup_replicas_backup_uuid_idx" ON "server_backup_replicas" ("backup_uuid");
+CREATE INDEX "server_backup_replicas_backup_configuration_uuid_idx" ON "server_backup_replicas" ("backup_configuration_uuid");
+CREATE UNIQUE INDEX "server_backup_replicas_backup_uuid_configuration_uuid_idx" ON "server_backup_replicas" ("backup_uuid","backup_configuration_uuid");
+CREATE INDEX "server_backup_replicas_completed_idx" ON "server_backup_replicas" ("completed");
+ALTER TABLE "backup_configurations" ADD CONSTRAINT "backup_configurations_hFAQlZql8tCp_fkey" FOREIGN KEY ("replication_backup_configuration_uuid") REFERENCES "backup_configurations"("uuid") ON DELETE SET NULL;
+ALTER TABLE "server_backup_replicas" ADD CONSTRAINT "server_backup_replicas_backup_uuid_server_backups_uuid_fkey" FOREIGN KEY ("backup_uuid") REFERENCES "server_backups"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_backup_replicas" ADD CONSTRAINT "server_backup_replicas_ARLZJEGg0CRN_fkey" FOREIGN KEY ("backup_configuration_uuid") REFERENCES "backup_configurations"("uuid") ON DELETE SET NULL;
\ No newline at end of file
@@ -0,0 +1,3 @@
+ALTER TABLE "servers" ADD COLUMN "backup_encryption_key" text;
+ALTER TABLE "server_backups" ADD COLUMN "encrypted" boolean DEFAULT false NOT NULL;
+ALTER TABLE "server_backups" ADD COLUMN "encryption_claimed" timestamp;
\ No newline at end of file
@@ -0,0 +1,21 @@
+CREATE TABLE "server_backup_databases" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"backup_uuid" uuid NOT NULL,
+	"server_database_uuid" uuid,
+	"server_database_instance_uuid" uuid,
+	"instance_database_uuid" uuid,
+	"name" varchar(255) NOT NULL,
+	"encrypted" boolean DEFAULT false NOT NULL,
+	"bytes" bigint DEFAULT 0 NOT NULL,
+	"upload_path" text,
+	"error" text,
+	"completed" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE INDEX "server_backup_databases_backup_uuid_idx" ON "server_backup_databases" ("backup_uuid");
+CREATE INDEX "server_backup_databases_server_database_uuid_idx" ON "server_backup_databases" ("server_database_uuid");
+CREATE INDEX "server_backup_databases_server_database_instance_uuid_idx" ON "server_backup_databases" ("server_database_instance_uuid");
+ALTER TABLE "server_backup_databases" ADD CONSTRAINT "server_backup_databases_backup_uuid_server_backups_uuid_fkey" FOREIGN KEY ("backup_uuid") REFERENCES "server_backups"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_backup_databases" ADD CONSTRAINT "server_backup_databases_mUt7qrWJIrF5_fkey" FOREIGN KEY ("server_database_uuid") REFERENCES "server_databases"("uuid") ON DELETE SET NULL;
+ALTER TABLE "server_backup_databases" ADD CONSTRAINT "server_backup_databases_1zopWWOXFx9i_fkey" FOREIGN KEY ("server_database_instance_uuid") REFERENCES "server_database_instances"("uuid") ON DELETE SET NULL;
\ No newline at end of file
@@ -0,0 +1,31 @@
+CREATE TABLE "server_database_query_history" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_database_uuid" uuid NOT NULL,
+	"user_uuid" uuid NOT NULL,
+	"query" text NOT NULL,
+	"read_only" boolean DEFAULT true NOT NULL,
+	"error" text,
+	"rows" bigint DEFAULT 0 NOT NULL,
+	"rows_affected" bigint DEFAULT 0 NOT NULL,
+	"duration_ms" bigint DEFAULT 0 NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE TABLE "server_database_saved_queries" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_database_uuid" uuid NOT NULL,
+	"user_uuid" uuid NOT NULL,
+	"name" varchar(252) NOT NULL,
+	"query" text NOT NULL,
+	"shared" boolean DEFAULT false NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE INDEX "server_database_query_history_database_user_idx" ON "server_database_query_history" ("server_database_uuid","user_uuid");
+CREATE INDEX "server_database_query_history_created_idx" ON "server_database_query_history" ("created");
+CREATE INDEX "server_database_saved_queries_server_database_uuid_idx" ON "server_database_saved_queries" ("server_database_uuid");
+CREATE UNIQUE INDEX "server_database_saved_queries_database_user_name_idx" ON "server_database_saved_queries" ("server_database_uuid","user_uuid","name");
+ALTER TABLE "server_database_query_history" ADD CONSTRAINT "server_database_query_history_jnRdyexL2XKj_fkey" FOREIGN KEY ("server_database_uuid") REFERENCES "server_databases"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_database_query_history" ADD CONSTRAINT "server_database_query_history_user_uuid_users_uuid_fkey" FOREIGN KEY ("user_uuid") REFERENCES "users"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_database_saved_queries" ADD CONSTRAINT "server_database_saved_queries_AKpe3dSKxovj_fkey" FOREIGN KEY ("server_database_uuid") REFERENCES "server_databases"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_database_saved_queries" ADD CONSTRAINT "server_database_saved_queries_user_uuid_users_uuid_fkey" FOREIGN KEY ("user_uuid") REFERENCES "users"("uuid") ON DELETE CASCADE;
\ No newline at end of file
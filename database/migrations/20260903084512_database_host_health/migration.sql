@@ -0,0 +1,3 @@
+ALTER TABLE "database_hosts" ADD COLUMN "max_databases" integer;
+ALTER TABLE "database_hosts" ADD COLUMN "max_size" bigint;
+ALTER TABLE "database_hosts" ADD COLUMN "health" jsonb;
\ No newline at end of file
@@ -0,0 +1,6 @@
+ALTER TABLE "servers" ADD COLUMN "database_size_limit" bigint;
+ALTER TABLE "server_databases" ADD COLUMN "size_limit" bigint;
+ALTER TABLE "server_databases" ADD COLUMN "size" bigint;
+ALTER TABLE "server_databases" ADD COLUMN "size_checked" timestamp;
+ALTER TABLE "server_databases" ADD COLUMN "quota_exceeded" boolean DEFAULT false NOT NULL;
+ALTER TABLE "server_databases" ADD COLUMN "quota_warning" smallint;
\ No newline at end of file
@@ -0,0 +1,20 @@
+CREATE TABLE "server_database_migrations" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_database_uuid" uuid NOT NULL,
+	"source_database_host_uuid" uuid NOT NULL,
+	"target_database_host_uuid" uuid NOT NULL,
+	"name" varchar(124) NOT NULL,
+	"bytes" bigint DEFAULT 0 NOT NULL,
+	"error" text,
+	"started" timestamp,
+	"heartbeat" timestamp,
+	"completed" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE INDEX "server_database_migrations_server_database_uuid_idx" ON "server_database_migrations" ("server_database_uuid");
+CREATE INDEX "server_database_migrations_source_database_host_uuid_idx" ON "server_database_migrations" ("source_database_host_uuid");
+CREATE INDEX "server_database_migrations_target_database_host_uuid_idx" ON "server_database_migrations" ("target_database_host_uuid");
+ALTER TABLE "server_database_migrations" ADD CONSTRAINT "server_database_migrations_WYbFJRSRde2g_fkey" FOREIGN KEY ("server_database_uuid") REFERENCES "server_databases"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_database_migrations" ADD CONSTRAINT "server_database_migrations_7dCueMMvLASV_fkey" FOREIGN KEY ("source_database_host_uuid") REFERENCES "database_hosts"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_database_migrations" ADD CONSTRAINT "server_database_migrations_qDAfatBKVqei_fkey" FOREIGN KEY ("target_database_host_uuid") REFERENCES "database_hosts"("uuid") ON DELETE CASCADE;
\ No newline at end of file
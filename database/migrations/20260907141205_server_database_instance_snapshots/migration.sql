@@ -0,0 +1,32 @@
+CREATE TABLE "server_database_instance_snapshot_policies" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_database_instance_uuid" uuid NOT NULL,
+	"name" varchar(1020) NOT NULL,
+	"enabled" boolean DEFAULT true NOT NULL,
+	"cron" varchar(255) NOT NULL,
+	"retention_count" integer NOT NULL,
+	"last_run" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE TABLE "server_database_instance_snapshots" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_database_instance_uuid" uuid NOT NULL,
+	"snapshot_policy_uuid" uuid,
+	"backup_configuration_uuid" uuid,
+	"name" varchar(1020) NOT NULL,
+	"encrypted" boolean DEFAULT false NOT NULL,
+	"bytes" bigint DEFAULT 0 NOT NULL,
+	"files" jsonb DEFAULT '[]'::jsonb NOT NULL,
+	"error" text,
+	"completed" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE INDEX "server_database_instance_snapshot_policies_instance_uuid_idx" ON "server_database_instance_snapshot_policies" ("server_database_instance_uuid");
+CREATE INDEX "server_database_instance_snapshots_instance_uuid_idx" ON "server_database_instance_snapshots" ("server_database_instance_uuid");
+CREATE INDEX "server_database_instance_snapshots_snapshot_policy_uuid_idx" ON "server_database_instance_snapshots" ("snapshot_policy_uuid");
+ALTER TABLE "server_database_instance_snapshot_policies" ADD CONSTRAINT "server_database_instance_snapshot_policies_kciHOc2ElYcb_fkey" FOREIGN KEY ("server_database_instance_uuid") REFERENCES "server_database_instances"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_database_instance_snapshots" ADD CONSTRAINT "server_database_instance_snapshots_HqvBpuzp0cTc_fkey" FOREIGN KEY ("server_database_instance_uuid") REFERENCES "server_database_instances"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_database_instance_snapshots" ADD CONSTRAINT "server_database_instance_snapshots_iLUHmKOTbx68_fkey" FOREIGN KEY ("snapshot_policy_uuid") REFERENCES "server_database_instance_snapshot_policies"("uuid") ON DELETE SET NULL;
+ALTER TABLE "server_database_instance_snapshots" ADD CONSTRAINT "server_database_instance_snapshots_j6mYVDQQ0hqR_fkey" FOREIGN KEY ("backup_configuration_uuid") REFERENCES "backup_configurations"("uuid") ON DELETE SET NULL;
\ No newline at end of file
@@ -0,0 +1,46 @@
+CREATE TABLE "database_agent_template_revisions" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"database_agent_template_uuid" uuid NOT NULL,
+	"version" integer NOT NULL,
+	"changelog" text,
+	"spec" json NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE TABLE "database_agent_template_rollouts" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"database_agent_template_uuid" uuid NOT NULL,
+	"target_version" integer NOT NULL,
+	"batch_size" integer NOT NULL,
+	"halt_on_failure" boolean DEFAULT true NOT NULL,
+	"updated" integer DEFAULT 0 NOT NULL,
+	"failures" jsonb DEFAULT '[]'::jsonb NOT NULL,
+	"error" text,
+	"started" timestamp,
+	"heartbeat" timestamp,
+	"completed" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "server_database_instances" ADD COLUMN "pinned_template_version" integer;
+CREATE UNIQUE INDEX "database_agent_template_revisions_template_uuid_version_idx" ON "database_agent_template_revisions" ("database_agent_template_uuid","version");
+CREATE INDEX "database_agent_template_rollouts_template_uuid_idx" ON "database_agent_template_rollouts" ("database_agent_template_uuid");
+ALTER TABLE "database_agent_template_revisions" ADD CONSTRAINT "database_agent_template_revisions_49T4eXdOk1sF_fkey" FOREIGN KEY ("database_agent_template_uuid") REFERENCES "database_agent_templates"("uuid") ON DELETE CASCADE;
+ALTER TABLE "database_agent_template_rollouts" ADD CONSTRAINT "database_agent_template_rollouts_hSfczWuSQalW_fkey" FOREIGN KEY ("database_agent_template_uuid") REFERENCES "database_agent_templates"("uuid") ON DELETE CASCADE;
+
+INSERT INTO "database_agent_template_revisions" ("database_agent_template_uuid", "version", "spec")
+SELECT "uuid", "version", json_build_object(
+	'docker_images', "docker_images",
+	'env', "env",
+	'image_uid', "image_uid",
+	'image_gid', "image_gid",
+	'cmd', "cmd",
+	'volumes', "volumes",
+	'socket_path', "socket_path",
+	'memory', "memory",
+	'swap', "swap",
+	'disk', "disk",
+	'io_weight', "io_weight",
+	'cpu', "cpu"
+)
+FROM "database_agent_templates";
\ No newline at end of file
@@ -0,0 +1,19 @@
+CREATE TABLE "server_database_access_tokens" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_uuid" uuid NOT NULL,
+	"server_database_uuid" uuid,
+	"server_database_instance_uuid" uuid,
+	"instance_database_uuid" uuid,
+	"agent_user_uuid" uuid,
+	"name" varchar(252) NOT NULL,
+	"username" varchar(63) NOT NULL,
+	"expires" timestamp NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE INDEX "server_database_access_tokens_server_database_uuid_idx" ON "server_database_access_tokens" ("server_database_uuid");
+CREATE INDEX "server_database_access_tokens_instance_database_idx" ON "server_database_access_tokens" ("server_database_instance_uuid","instance_database_uuid");
+CREATE INDEX "server_database_access_tokens_expires_idx" ON "server_database_access_tokens" ("expires");
+ALTER TABLE "server_database_access_tokens" ADD CONSTRAINT "server_database_access_tokens_server_uuid_servers_uuid_fkey" FOREIGN KEY ("server_uuid") REFERENCES "servers"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_database_access_tokens" ADD CONSTRAINT "server_database_access_tokens_iNxv7bsq30wg_fkey" FOREIGN KEY ("server_database_uuid") REFERENCES "server_databases"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_database_access_tokens" ADD CONSTRAINT "server_database_access_tokens_UVLQw0LWgUvc_fkey" FOREIGN KEY ("server_database_instance_uuid") REFERENCES "server_database_instances"("uuid") ON DELETE CASCADE;
\ No newline at end of file
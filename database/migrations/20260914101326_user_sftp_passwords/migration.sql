@@ -0,0 +1,17 @@
+CREATE TABLE "user_sftp_passwords" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"user_uuid" uuid NOT NULL,
+	"name" varchar(124) NOT NULL,
+	"password_start" char(16) NOT NULL,
+	"password" text NOT NULL,
+	"servers" uuid[] DEFAULT '{}'::uuid[] NOT NULL,
+	"read_only" boolean DEFAULT false NOT NULL,
+	"last_used" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "users" ADD COLUMN "disable_sftp_password_login" boolean DEFAULT false NOT NULL;
+CREATE INDEX "user_sftp_passwords_user_uuid_idx" ON "user_sftp_passwords" ("user_uuid");
+CREATE UNIQUE INDEX "user_sftp_passwords_user_uuid_name_idx" ON "user_sftp_passwords" ("user_uuid","name");
+CREATE UNIQUE INDEX "user_sftp_passwords_user_uuid_password_start_idx" ON "user_sftp_passwords" ("user_uuid","password_start");
+ALTER TABLE "user_sftp_passwords" ADD CONSTRAINT "user_sftp_passwords_user_uuid_users_uuid_fkey" FOREIGN KEY ("user_uuid") REFERENCES "users"("uuid") ON DELETE CASCADE;
\ No newline at end of file
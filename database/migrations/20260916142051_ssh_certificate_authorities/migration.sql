@@ -0,0 +1,12 @@
+CREATE TABLE "ssh_certificate_authorities" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"name" varchar(1020) NOT NULL,
+	"description" text,
+	"fingerprint" char(50) NOT NULL,
+	"public_key" bytea NOT NULL,
+	"enabled" boolean DEFAULT true NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE UNIQUE INDEX "ssh_certificate_authorities_name_idx" ON "ssh_certificate_authorities" ("name");
+CREATE UNIQUE INDEX "ssh_certificate_authorities_fingerprint_idx" ON "ssh_certificate_authorities" ("fingerprint");
\ No newline at end of file
@@ -0,0 +1,15 @@
+CREATE TABLE "server_trashed_files" (
+	"uuid" uuid PRIMARY KEY,
+	"server_uuid" uuid NOT NULL,
+	"user_uuid" uuid,
+	"path" text NOT NULL,
+	"directory" boolean DEFAULT false NOT NULL,
+	"size" bigint DEFAULT 0 NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE INDEX "server_trashed_files_server_uuid_idx" ON "server_trashed_files" ("server_uuid");
+CREATE INDEX "server_trashed_files_user_uuid_idx" ON "server_trashed_files" ("user_uuid");
+CREATE INDEX "server_trashed_files_created_idx" ON "server_trashed_files" ("created");
+ALTER TABLE "server_trashed_files" ADD CONSTRAINT "server_trashed_files_server_uuid_servers_uuid_fkey" FOREIGN KEY ("server_uuid") REFERENCES "servers"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_trashed_files" ADD CONSTRAINT "server_trashed_files_user_uuid_users_uuid_fkey" FOREIGN KEY ("user_uuid") REFERENCES "users"("uuid") ON DELETE SET NULL;
\ No newline at end of file
@@ -0,0 +1,19 @@
+CREATE TABLE "server_file_shares" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_uuid" uuid NOT NULL,
+	"user_uuid" uuid,
+	"path" text NOT NULL,
+	"directory" boolean DEFAULT false NOT NULL,
+	"password" text,
+	"max_downloads" integer,
+	"downloads" integer DEFAULT 0 NOT NULL,
+	"expires" timestamp NOT NULL,
+	"last_download" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE INDEX "server_file_shares_server_uuid_idx" ON "server_file_shares" ("server_uuid");
+CREATE INDEX "server_file_shares_user_uuid_idx" ON "server_file_shares" ("user_uuid");
+CREATE INDEX "server_file_shares_expires_idx" ON "server_file_shares" ("expires");
+ALTER TABLE "server_file_shares" ADD CONSTRAINT "server_file_shares_server_uuid_servers_uuid_fkey" FOREIGN KEY ("server_uuid") REFERENCES "servers"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_file_shares" ADD CONSTRAINT "server_file_shares_user_uuid_users_uuid_fkey" FOREIGN KEY ("user_uuid") REFERENCES "users"("uuid") ON DELETE SET NULL;
\ No newline at end of file
@@ -0,0 +1,38 @@
+CREATE TYPE "server_deployment_power_action" AS ENUM('START', 'STOP', 'RESTART', 'KILL');
+CREATE TABLE "server_deployment_sources" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_uuid" uuid NOT NULL,
+	"name" varchar(1020) NOT NULL,
+	"git_repository" text NOT NULL,
+	"branch" varchar(1020),
+	"subdirectory" text,
+	"target_path" text DEFAULT '/' NOT NULL,
+	"credentials" jsonb DEFAULT '{"type":"none"}' NOT NULL,
+	"pre_deploy_power_action" "server_deployment_power_action",
+	"post_deploy_power_action" "server_deployment_power_action",
+	"webhook_token" text NOT NULL,
+	"last_deployed" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE TABLE "server_deployments" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_deployment_source_uuid" uuid NOT NULL,
+	"user_uuid" uuid,
+	"webhook" boolean DEFAULT false NOT NULL,
+	"commit_hash" varchar(64),
+	"commit_message" text,
+	"files" integer DEFAULT 0 NOT NULL,
+	"error" text,
+	"completed" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE INDEX "server_deployment_sources_server_uuid_idx" ON "server_deployment_sources" ("server_uuid");
+CREATE UNIQUE INDEX "server_deployment_sources_server_uuid_name_idx" ON "server_deployment_sources" ("server_uuid","name");
+CREATE INDEX "server_deployments_server_deployment_source_uuid_idx" ON "server_deployments" ("server_deployment_source_uuid");
+CREATE INDEX "server_deployments_user_uuid_idx" ON "server_deployments" ("user_uuid");
+CREATE INDEX "server_deployments_created_idx" ON "server_deployments" ("created");
+ALTER TABLE "server_deployment_sources" ADD CONSTRAINT "server_deployment_sources_server_uuid_servers_uuid_fkey" FOREIGN KEY ("server_uuid") REFERENCES "servers"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_deployments" ADD CONSTRAINT "server_deployments_ctq3D4EUkZpg_fkey" FOREIGN KEY ("server_deployment_source_uuid") REFERENCES "server_deployment_sources"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_deployments" ADD CONSTRAINT "server_deployments_user_uuid_users_uuid_fkey" FOREIGN KEY ("user_uuid") REFERENCES "users"("uuid") ON DELETE SET NULL;
\ No newline at end of file
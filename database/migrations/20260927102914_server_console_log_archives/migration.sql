@@ -0,0 +1,29 @@
+CREATE TABLE "server_console_log_archives" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_uuid" uuid NOT NULL,
+	"user_uuid" uuid,
+	"tail" text DEFAULT '' NOT NULL,
+	"last_collected" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE TABLE "server_console_log_chunks" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_uuid" uuid NOT NULL,
+	"lines" integer NOT NULL,
+	"size" bigint NOT NULL,
+	"compressed_size" bigint NOT NULL,
+	"content" bytea NOT NULL,
+	"search_vector" tsvector NOT NULL,
+	"started" timestamp NOT NULL,
+	"ended" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE UNIQUE INDEX "server_console_log_archives_server_uuid_idx" ON "server_console_log_archives" ("server_uuid");
+CREATE INDEX "server_console_log_archives_user_uuid_idx" ON "server_console_log_archives" ("user_uuid");
+CREATE INDEX "server_console_log_chunks_server_uuid_started_idx" ON "server_console_log_chunks" ("server_uuid","started");
+CREATE INDEX "server_console_log_chunks_ended_idx" ON "server_console_log_chunks" ("ended");
+CREATE INDEX "server_console_log_chunks_search_vector_idx" ON "server_console_log_chunks" USING gin ("search_vector");
+ALTER TABLE "server_console_log_archives" ADD CONSTRAINT "server_console_log_archives_server_uuid_servers_uuid_fkey" FOREIGN KEY ("server_uuid") REFERENCES "servers"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_console_log_archives" ADD CONSTRAINT "server_console_log_archives_user_uuid_users_uuid_fkey" FOREIGN KEY ("user_uuid") REFERENCES "users"("uuid") ON DELETE SET NULL;
+ALTER TABLE "server_console_log_chunks" ADD CONSTRAINT "server_console_log_chunks_server_uuid_servers_uuid_fkey" FOREIGN KEY ("server_uuid") REFERENCES "servers"("uuid") ON DELETE CASCADE;
\ No newline at end of file
@@ -0,0 +1,20 @@
+CREATE TYPE "server_file_event_action" AS ENUM('CREATE', 'WRITE', 'RENAME', 'DELETE', 'CHMOD');
+CREATE TYPE "server_file_event_source" AS ENUM('PANEL', 'SFTP');
+CREATE TABLE "server_file_events" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+	"server_uuid" uuid NOT NULL,
+	"user_uuid" uuid,
+	"action" "server_file_event_action" NOT NULL,
+	"source" "server_file_event_source" NOT NULL,
+	"path" text NOT NULL,
+	"new_path" text,
+	"mode" integer,
+	"ip" inet,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE INDEX "server_file_events_server_uuid_created_idx" ON "server_file_events" ("server_uuid","created");
+CREATE INDEX "server_file_events_user_uuid_idx" ON "server_file_events" ("user_uuid");
+CREATE INDEX "server_file_events_created_idx" ON "server_file_events" ("created");
+ALTER TABLE "server_file_events" ADD CONSTRAINT "server_file_events_server_uuid_servers_uuid_fkey" FOREIGN KEY ("server_uuid") REFERENCES "servers"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_file_events" ADD CONSTRAINT "server_file_events_user_uuid_users_uuid_fkey" FOREIGN KEY ("user_uuid") REFERENCES "users"("uuid") ON DELETE SET NULL;
\ No newline at end of file
@@ -0,0 +1,20 @@
+CREATE TYPE "file_scan_policy" AS ENUM('OFF', 'WARN', 'BLOCK');
+CREATE TYPE "file_scan_source" AS ENUM('UPLOAD', 'PULL');
+CREATE TABLE "server_quarantined_files" (
+	"uuid" uuid PRIMARY KEY,
+	"server_uuid" uuid NOT NULL,
+	"user_uuid" uuid,
+	"source" "file_scan_source" NOT NULL,
+	"path" text NOT NULL,
+	"size" bigint DEFAULT 0 NOT NULL,
+	"signature" varchar(1020) NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "locations" ADD COLUMN "file_scan_policy" "file_scan_policy" DEFAULT 'OFF' NOT NULL;
+ALTER TABLE "nodes" ADD COLUMN "file_scan_policy" "file_scan_policy";
+CREATE INDEX "server_quarantined_files_server_uuid_idx" ON "server_quarantined_files" ("server_uuid");
+CREATE INDEX "server_quarantined_files_user_uuid_idx" ON "server_quarantined_files" ("user_uuid");
+CREATE INDEX "server_quarantined_files_created_idx" ON "server_quarantined_files" ("created");
+ALTER TABLE "server_quarantined_files" ADD CONSTRAINT "server_quarantined_files_server_uuid_servers_uuid_fkey" FOREIGN KEY ("server_uuid") REFERENCES "servers"("uuid") ON DELETE CASCADE;
+ALTER TABLE "server_quarantined_files" ADD CONSTRAINT "server_quarantined_files_user_uuid_users_uuid_fkey" FOREIGN KEY ("user_uuid") REFERENCES "users"("uuid") ON DELETE SET NULL;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE "server_backups" DROP COLUMN "encryption_claimed";
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE "system_backup_policies" ADD COLUMN "include_databases" boolean DEFAULT false NOT NULL;
\ No newline at end of file
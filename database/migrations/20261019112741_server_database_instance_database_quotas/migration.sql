@@ -0,0 +1,13 @@
+CREATE TABLE "server_database_instance_database_quotas" (
+	"server_database_instance_uuid" uuid NOT NULL,
+	"database_uuid" uuid NOT NULL,
+	"name" varchar(255) NOT NULL,
+	"size" bigint NOT NULL,
+	"size_checked" timestamp DEFAULT now() NOT NULL,
+	"quota_exceeded" boolean DEFAULT false NOT NULL,
+	"quota_warning" smallint,
+	"revoked_users" uuid[] DEFAULT '{}' NOT NULL,
+	CONSTRAINT "server_database_instance_database_quotas_pk" PRIMARY KEY("server_database_instance_uuid","database_uuid")
+);
+
+ALTER TABLE "server_database_instance_database_quotas" ADD CONSTRAINT "server_database_instance_database_quotas_PaYyEdIQFkua_fkey" FOREIGN KEY ("server_database_instance_uuid") REFERENCES "server_database_instances"("uuid") ON DELETE CASCADE;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE "server_database_migrations" ADD COLUMN "provisioned" timestamp;
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE "server_console_log_archives" ADD COLUMN "collected_lines" bigint DEFAULT 0 NOT NULL;--> statement-breakpoint
+ALTER TABLE "server_console_log_archives" DROP COLUMN "tail";
\ No newline at end of file
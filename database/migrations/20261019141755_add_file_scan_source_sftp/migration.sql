@@ -0,0 +1 @@
+ALTER TYPE "file_scan_source" ADD VALUE 'SFTP';
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE "server_quarantined_files" ADD COLUMN "sha256" varchar(64);
\ No newline at end of file
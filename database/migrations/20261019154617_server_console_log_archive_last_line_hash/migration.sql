@@ -0,0 +1,2 @@
+ALTER TABLE "server_console_log_archives" ADD COLUMN "last_line_hash" varchar(64);--> statement-breakpoint
+ALTER TABLE "server_console_log_archives" ADD COLUMN "stalled" boolean DEFAULT false NOT NULL;
\ No newline at end of file
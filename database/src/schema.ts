@@ -46,6 +46,14 @@ export const bytea = customType<{
   },
 });
 
+export const tsvector = customType<{
+  data: string;
+}>({
+  dataType() {
+    return 'tsvector';
+  },
+});
+
 export const databaseTypeEnum = pgEnum('database_type', ['MYSQL', 'POSTGRES', 'MONGODB']);
 export const databaseAgentTypeEnum = pgEnum('database_agent_type', ['POSTGRES', 'MARIADB', 'MONGODB', 'REDIS']);
 export const serverStatusEnum = pgEnum('server_status', [
@@ -74,6 +82,22 @@ export const userToastPositionEnum = pgEnum('user_toast_position', [
   'BOTTOM_RIGHT',
 ]);
 export const announcementTypeEnum = pgEnum('announcement_type', ['INFO', 'SUCCESS', 'WARNING', 'ERROR']);
+export const serverDeploymentPowerActionEnum = pgEnum('server_deployment_power_action', [
+  'START',
+  'STOP',
+  'RESTART',
+  'KILL',
+]);
+export const serverFileEventActionEnum = pgEnum('server_file_event_action', [
+  'CREATE',
+  'WRITE',
+  'RENAME',
+  'DELETE',
+  'CHMOD',
+]);
+export const serverFileEventSourceEnum = pgEnum('server_file_event_source', ['PANEL', 'SFTP']);
+export const fileScanPolicyEnum = pgEnum('file_scan_policy', ['OFF', 'WARN', 'BLOCK']);
+export const fileScanSourceEnum = pgEnum('file_scan_source', ['UPLOAD', 'PULL', 'SFTP']);
 
 // Tables
 export const settingsTable = pgTable('settings', {
@@ -150,6 +174,7 @@ export const usersTable = pgTable(
     language: varchar({ length: 15 }).default('en').notNull(),
     toast_position: userToastPositionEnum().default('BOTTOM_RIGHT').notNull(),
     start_on_grouped_servers: boolean().default(false).notNull(),
+    disable_sftp_password_login: boolean().default(false).notNull(),
     created: timestamp().defaultNow().notNull(),
   },
   (cols) => [
@@ -296,6 +321,28 @@ export const userSshKeysTable = pgTable(
   ],
 );
 
+export const userSftpPasswordsTable = pgTable(
+  'user_sftp_passwords',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    user_uuid: uuid()
+      .references(() => usersTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    name: varchar({ length: 31 * UTF8_MAX_SCALAR_SIZE }).notNull(),
+    password_start: char({ length: 16 }).notNull(),
+    password: text().notNull(),
+    servers: uuid().array().default([]).notNull(),
+    read_only: boolean().default(false).notNull(),
+    last_used: timestamp(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('user_sftp_passwords_user_uuid_idx').on(cols.user_uuid),
+    uniqueIndex('user_sftp_passwords_user_uuid_name_idx').on(cols.user_uuid, cols.name),
+    uniqueIndex('user_sftp_passwords_user_uuid_password_start_idx').on(cols.user_uuid, cols.password_start),
+  ],
+);
+
 export const userApiKeysTable = pgTable(
   'user_api_keys',
   {
@@ -453,6 +500,23 @@ export const mountsTable = pgTable(
   ],
 );
 
+export const sshCertificateAuthoritiesTable = pgTable(
+  'ssh_certificate_authorities',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    name: varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull(),
+    description: text(),
+    fingerprint: char({ length: 50 }).notNull(),
+    public_key: bytea().notNull(),
+    enabled: boolean().default(true).notNull(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    uniqueIndex('ssh_certificate_authorities_name_idx').on(cols.name),
+    uniqueIndex('ssh_certificate_authorities_fingerprint_idx').on(cols.fingerprint),
+  ],
+);
+
 export const backupConfigurationsTable = pgTable(
   'backup_configurations',
   {
@@ -463,9 +527,17 @@ export const backupConfigurationsTable = pgTable(
     description: text(),
     backup_disk: backupDiskEnum().default('LOCAL').notNull(),
     backup_configs: jsonb().default({}).notNull(),
+    replication_backup_configuration_uuid: uuid().references((): PgColumn => backupConfigurationsTable.uuid, {
+      onDelete: 'set null',
+    }),
     created: timestamp().defaultNow().notNull(),
   },
-  (cols) => [uniqueIndex('backup_configurations_name_idx').on(cols.name)],
+  (cols) => [
+    uniqueIndex('backup_configurations_name_idx').on(cols.name),
+    index('backup_configurations_replication_backup_configuration_uuid_idx').on(
+      cols.replication_backup_configuration_uuid,
+    ),
+  ],
 );
 
 export const systemBackupPoliciesTable = pgTable(
@@ -480,6 +552,7 @@ export const systemBackupPoliciesTable = pgTable(
     retention_count: integer(),
     retention_days: integer(),
     parallelism: integer().default(2).notNull(),
+    include_databases: boolean().default(false).notNull(),
     triggered: timestamp(),
     created: timestamp().defaultNow().notNull(),
   },
@@ -560,6 +633,7 @@ export const locationsTable = pgTable(
     name: varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull(),
     description: text(),
     flag: char({ length: 2 }),
+    file_scan_policy: fileScanPolicyEnum().default('OFF').notNull(),
     created: timestamp().defaultNow().notNull(),
   },
   (cols) => [
@@ -597,6 +671,7 @@ export const nodesTable = pgTable(
     name: varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull(),
     deployment_enabled: boolean().default(false).notNull(),
     maintenance_enabled: boolean().default(false).notNull(),
+    file_scan_policy: fileScanPolicyEnum(),
     description: text(),
     public_url: varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }),
     url: varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull(),
@@ -822,6 +897,9 @@ export const databaseHostsTable = pgTable(
     public_host: varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }),
     public_port: integer(),
     credentials: jsonb().notNull(),
+    max_databases: integer(),
+    max_size: bigint({ mode: 'number' }),
+    health: jsonb(),
     created: timestamp().defaultNow().notNull(),
   },
   (cols) => [uniqueIndex('database_hosts_name_idx').on(cols.name)],
@@ -923,6 +1001,49 @@ export const databaseAgentTemplatesTable = pgTable(
   (cols) => [uniqueIndex('database_agent_templates_name_idx').on(cols.name)],
 );
 
+export const databaseAgentTemplateRevisionsTable = pgTable(
+  'database_agent_template_revisions',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    database_agent_template_uuid: uuid()
+      .references(() => databaseAgentTemplatesTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    version: integer().notNull(),
+    changelog: text(),
+    spec: json().notNull(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    uniqueIndex('database_agent_template_revisions_template_uuid_version_idx').on(
+      cols.database_agent_template_uuid,
+      cols.version,
+    ),
+  ],
+);
+
+export const databaseAgentTemplateRolloutsTable = pgTable(
+  'database_agent_template_rollouts',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    database_agent_template_uuid: uuid()
+      .references(() => databaseAgentTemplatesTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    target_version: integer().notNull(),
+    batch_size: integer().notNull(),
+    halt_on_failure: boolean().default(true).notNull(),
+    updated: integer().default(0).notNull(),
+    failures: jsonb().default([]).notNull(),
+    error: text(),
+    started: timestamp(),
+    heartbeat: timestamp(),
+    completed: timestamp(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('database_agent_template_rollouts_template_uuid_idx').on(cols.database_agent_template_uuid),
+  ],
+);
+
 export const serversTable = pgTable(
   'servers',
   {
@@ -965,7 +1086,10 @@ export const serversTable = pgTable(
     allocation_limit: integer().default(0).notNull(),
     database_limit: integer().default(0).notNull(),
     backup_limit: integer().default(0).notNull(),
+    backup_storage_limit: bigint({ mode: 'number' }),
+    database_size_limit: bigint({ mode: 'number' }),
     schedule_limit: integer().default(0).notNull(),
+    backup_encryption_key: text(),
     created: timestamp().defaultNow().notNull(),
   },
   (cols) => [
@@ -1044,6 +1168,185 @@ export const serverActivitiesTable = pgTable(
   ],
 );
 
+export const serverConsoleLogArchivesTable = pgTable(
+  'server_console_log_archives',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_uuid: uuid()
+      .references(() => serversTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    user_uuid: uuid().references(() => usersTable.uuid, { onDelete: 'set null' }),
+    collected_lines: bigint({ mode: 'number' }).default(0).notNull(),
+    last_line_hash: varchar({ length: 64 }),
+    stalled: boolean().default(false).notNull(),
+    last_collected: timestamp(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    uniqueIndex('server_console_log_archives_server_uuid_idx').on(cols.server_uuid),
+    index('server_console_log_archives_user_uuid_idx').on(cols.user_uuid),
+  ],
+);
+
+export const serverConsoleLogChunksTable = pgTable(
+  'server_console_log_chunks',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_uuid: uuid()
+      .references(() => serversTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    lines: integer().notNull(),
+    size: bigint({ mode: 'number' }).notNull(),
+    compressed_size: bigint({ mode: 'number' }).notNull(),
+    content: bytea().notNull(),
+    search_vector: tsvector().notNull(),
+    started: timestamp().notNull(),
+    ended: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_console_log_chunks_server_uuid_started_idx').on(cols.server_uuid, cols.started),
+    index('server_console_log_chunks_ended_idx').on(cols.ended),
+    index('server_console_log_chunks_search_vector_idx').using('gin', cols.search_vector),
+  ],
+);
+
+export const serverDeploymentSourcesTable = pgTable(
+  'server_deployment_sources',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_uuid: uuid()
+      .references(() => serversTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    name: varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull(),
+    git_repository: text().notNull(),
+    branch: varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }),
+    subdirectory: text(),
+    target_path: text().default('/').notNull(),
+    credentials: jsonb().default({ type: 'none' }).notNull(),
+    pre_deploy_power_action: serverDeploymentPowerActionEnum(),
+    post_deploy_power_action: serverDeploymentPowerActionEnum(),
+    webhook_token: text().notNull(),
+    last_deployed: timestamp(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_deployment_sources_server_uuid_idx').on(cols.server_uuid),
+    uniqueIndex('server_deployment_sources_server_uuid_name_idx').on(cols.server_uuid, cols.name),
+  ],
+);
+
+export const serverDeploymentsTable = pgTable(
+  'server_deployments',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_deployment_source_uuid: uuid()
+      .references(() => serverDeploymentSourcesTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    user_uuid: uuid().references(() => usersTable.uuid, { onDelete: 'set null' }),
+    webhook: boolean().default(false).notNull(),
+    commit_hash: varchar({ length: 64 }),
+    commit_message: text(),
+    files: integer().default(0).notNull(),
+    error: text(),
+    completed: timestamp(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_deployments_server_deployment_source_uuid_idx').on(cols.server_deployment_source_uuid),
+    index('server_deployments_user_uuid_idx').on(cols.user_uuid),
+    index('server_deployments_created_idx').on(cols.created),
+  ],
+);
+
+export const serverFileEventsTable = pgTable(
+  'server_file_events',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_uuid: uuid()
+      .references(() => serversTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    user_uuid: uuid().references(() => usersTable.uuid, { onDelete: 'set null' }),
+    action: serverFileEventActionEnum().notNull(),
+    source: serverFileEventSourceEnum().notNull(),
+    path: text().notNull(),
+    new_path: text(),
+    mode: integer(),
+    ip: inet(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_file_events_server_uuid_created_idx').on(cols.server_uuid, cols.created),
+    index('server_file_events_user_uuid_idx').on(cols.user_uuid),
+    index('server_file_events_created_idx').on(cols.created),
+  ],
+);
+
+export const serverFileSharesTable = pgTable(
+  'server_file_shares',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_uuid: uuid()
+      .references(() => serversTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    user_uuid: uuid().references(() => usersTable.uuid, { onDelete: 'set null' }),
+    path: text().notNull(),
+    directory: boolean().default(false).notNull(),
+    password: text(),
+    max_downloads: integer(),
+    downloads: integer().default(0).notNull(),
+    expires: timestamp().notNull(),
+    last_download: timestamp(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_file_shares_server_uuid_idx').on(cols.server_uuid),
+    index('server_file_shares_user_uuid_idx').on(cols.user_uuid),
+    index('server_file_shares_expires_idx').on(cols.expires),
+  ],
+);
+
+export const serverQuarantinedFilesTable = pgTable(
+  'server_quarantined_files',
+  {
+    uuid: uuid().primaryKey().notNull(),
+    server_uuid: uuid()
+      .references(() => serversTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    user_uuid: uuid().references(() => usersTable.uuid, { onDelete: 'set null' }),
+    source: fileScanSourceEnum().notNull(),
+    path: text().notNull(),
+    size: bigint({ mode: 'number' }).default(0).notNull(),
+    signature: varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull(),
+    sha256: varchar({ length: 64 }),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_quarantined_files_server_uuid_idx').on(cols.server_uuid),
+    index('server_quarantined_files_user_uuid_idx').on(cols.user_uuid),
+    index('server_quarantined_files_created_idx').on(cols.created),
+  ],
+);
+
+export const serverTrashedFilesTable = pgTable(
+  'server_trashed_files',
+  {
+    uuid: uuid().primaryKey().notNull(),
+    server_uuid: uuid()
+      .references(() => serversTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    user_uuid: uuid().references(() => usersTable.uuid, { onDelete: 'set null' }),
+    path: text().notNull(),
+    directory: boolean().default(false).notNull(),
+    size: bigint({ mode: 'number' }).default(0).notNull(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_trashed_files_server_uuid_idx').on(cols.server_uuid),
+    index('server_trashed_files_user_uuid_idx').on(cols.user_uuid),
+    index('server_trashed_files_created_idx').on(cols.created),
+  ],
+);
+
 export const serverVariablesTable = pgTable(
   'server_variables',
   {
@@ -1116,6 +1419,7 @@ export const serverBackupsTable = pgTable(
     streaming: boolean().default(false).notNull(),
     shared: boolean().default(false).notNull(),
     locked: boolean().default(false).notNull(),
+    encrypted: boolean().default(false).notNull(),
     ignored_files: text().array().notNull(),
     checksum: varchar({ length: 255 }),
     bytes: bigint({ mode: 'number' }).default(0).notNull(),
@@ -1141,6 +1445,60 @@ export const serverBackupsTable = pgTable(
   ],
 );
 
+export const serverBackupReplicasTable = pgTable(
+  'server_backup_replicas',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    backup_uuid: uuid()
+      .references(() => serverBackupsTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    backup_configuration_uuid: uuid().references(() => backupConfigurationsTable.uuid, { onDelete: 'set null' }),
+    bytes: bigint({ mode: 'number' }).default(0).notNull(),
+    upload_path: text(),
+    attempts: integer().default(0).notNull(),
+    error: text(),
+    last_attempt: timestamp(),
+    completed: timestamp(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_backup_replicas_backup_uuid_idx').on(cols.backup_uuid),
+    index('server_backup_replicas_backup_configuration_uuid_idx').on(cols.backup_configuration_uuid),
+    uniqueIndex('server_backup_replicas_backup_uuid_configuration_uuid_idx').on(
+      cols.backup_uuid,
+      cols.backup_configuration_uuid,
+    ),
+    index('server_backup_replicas_completed_idx').on(cols.completed),
+  ],
+);
+
+export const serverBackupDatabasesTable = pgTable(
+  'server_backup_databases',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    backup_uuid: uuid()
+      .references(() => serverBackupsTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    server_database_uuid: uuid().references(() => serverDatabasesTable.uuid, { onDelete: 'set null' }),
+    server_database_instance_uuid: uuid().references(() => serverDatabaseInstancesTable.uuid, {
+      onDelete: 'set null',
+    }),
+    instance_database_uuid: uuid(),
+    name: varchar({ length: 255 }).notNull(),
+    encrypted: boolean().default(false).notNull(),
+    bytes: bigint({ mode: 'number' }).default(0).notNull(),
+    upload_path: text(),
+    error: text(),
+    completed: timestamp(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_backup_databases_backup_uuid_idx').on(cols.backup_uuid),
+    index('server_backup_databases_server_database_uuid_idx').on(cols.server_database_uuid),
+    index('server_backup_databases_server_database_instance_uuid_idx').on(cols.server_database_instance_uuid),
+  ],
+);
+
 export const serverDatabasesTable = pgTable(
   'server_databases',
   {
@@ -1155,6 +1513,11 @@ export const serverDatabasesTable = pgTable(
     locked: boolean().default(false).notNull(),
     username: varchar({ length: 20 }).notNull(),
     password: bytea().notNull(),
+    size_limit: bigint({ mode: 'number' }),
+    size: bigint({ mode: 'number' }),
+    size_checked: timestamp(),
+    quota_exceeded: boolean().default(false).notNull(),
+    quota_warning: smallint(),
     created: timestamp().defaultNow().notNull(),
   },
   (cols) => [
@@ -1164,6 +1527,88 @@ export const serverDatabasesTable = pgTable(
   ],
 );
 
+export const serverDatabaseMigrationsTable = pgTable(
+  'server_database_migrations',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_database_uuid: uuid()
+      .references(() => serverDatabasesTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    source_database_host_uuid: uuid()
+      .references(() => databaseHostsTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    target_database_host_uuid: uuid()
+      .references(() => databaseHostsTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    name: varchar({ length: 31 * UTF8_MAX_SCALAR_SIZE }).notNull(),
+    bytes: bigint({ mode: 'number' }).default(0).notNull(),
+    error: text(),
+    started: timestamp(),
+    provisioned: timestamp(),
+    heartbeat: timestamp(),
+    completed: timestamp(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_database_migrations_server_database_uuid_idx').on(cols.server_database_uuid),
+    index('server_database_migrations_source_database_host_uuid_idx').on(
+      cols.source_database_host_uuid,
+    ),
+    index('server_database_migrations_target_database_host_uuid_idx').on(
+      cols.target_database_host_uuid,
+    ),
+  ],
+);
+
+export const serverDatabaseSavedQueriesTable = pgTable(
+  'server_database_saved_queries',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_database_uuid: uuid()
+      .references(() => serverDatabasesTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    user_uuid: uuid()
+      .references(() => usersTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    name: varchar({ length: 63 * UTF8_MAX_SCALAR_SIZE }).notNull(),
+    query: text().notNull(),
+    shared: boolean().default(false).notNull(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_database_saved_queries_server_database_uuid_idx').on(cols.server_database_uuid),
+    uniqueIndex('server_database_saved_queries_database_user_name_idx').on(
+      cols.server_database_uuid,
+      cols.user_uuid,
+      cols.name,
+    ),
+  ],
+);
+
+export const serverDatabaseQueryHistoryTable = pgTable(
+  'server_database_query_history',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_database_uuid: uuid()
+      .references(() => serverDatabasesTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    user_uuid: uuid()
+      .references(() => usersTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    query: text().notNull(),
+    read_only: boolean().default(true).notNull(),
+    error: text(),
+    rows: bigint({ mode: 'number' }).default(0).notNull(),
+    rows_affected: bigint({ mode: 'number' }).default(0).notNull(),
+    duration_ms: bigint({ mode: 'number' }).default(0).notNull(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_database_query_history_database_user_idx').on(cols.server_database_uuid, cols.user_uuid),
+    index('server_database_query_history_created_idx').on(cols.created),
+  ],
+);
+
 export const serverDatabaseInstancesTable = pgTable(
   'server_database_instances',
   {
@@ -1181,6 +1626,7 @@ export const serverDatabaseInstancesTable = pgTable(
     name: varchar({ length: 31 * UTF8_MAX_SCALAR_SIZE }).notNull(),
     locked: boolean().default(false).notNull(),
     template_version: integer(),
+    pinned_template_version: integer(),
     image: text(),
     env: json(),
     memory: bigint({ mode: 'number' }),
@@ -1197,6 +1643,108 @@ export const serverDatabaseInstancesTable = pgTable(
   ],
 );
 
+export const serverDatabaseInstanceDatabaseQuotasTable = pgTable(
+  'server_database_instance_database_quotas',
+  {
+    server_database_instance_uuid: uuid()
+      .references(() => serverDatabaseInstancesTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    database_uuid: uuid().notNull(),
+    name: varchar({ length: 255 }).notNull(),
+    size: bigint({ mode: 'number' }).notNull(),
+    size_checked: timestamp().defaultNow().notNull(),
+    quota_exceeded: boolean().default(false).notNull(),
+    quota_warning: smallint(),
+    revoked_users: uuid().array().default([]).notNull(),
+  },
+  (cols) => [
+    primaryKey({
+      name: 'server_database_instance_database_quotas_pk',
+      columns: [cols.server_database_instance_uuid, cols.database_uuid],
+    }),
+  ],
+);
+
+export const serverDatabaseInstanceSnapshotPoliciesTable = pgTable(
+  'server_database_instance_snapshot_policies',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_database_instance_uuid: uuid()
+      .references(() => serverDatabaseInstancesTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    name: varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull(),
+    enabled: boolean().default(true).notNull(),
+    cron: varchar({ length: 255 }).notNull(),
+    retention_count: integer().notNull(),
+    last_run: timestamp(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_database_instance_snapshot_policies_instance_uuid_idx').on(
+      cols.server_database_instance_uuid,
+    ),
+  ],
+);
+
+export const serverDatabaseInstanceSnapshotsTable = pgTable(
+  'server_database_instance_snapshots',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_database_instance_uuid: uuid()
+      .references(() => serverDatabaseInstancesTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    snapshot_policy_uuid: uuid().references(() => serverDatabaseInstanceSnapshotPoliciesTable.uuid, {
+      onDelete: 'set null',
+    }),
+    backup_configuration_uuid: uuid().references(() => backupConfigurationsTable.uuid, {
+      onDelete: 'set null',
+    }),
+    name: varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull(),
+    encrypted: boolean().default(false).notNull(),
+    bytes: bigint({ mode: 'number' }).default(0).notNull(),
+    files: jsonb().default([]).notNull(),
+    error: text(),
+    completed: timestamp(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_database_instance_snapshots_instance_uuid_idx').on(
+      cols.server_database_instance_uuid,
+    ),
+    index('server_database_instance_snapshots_snapshot_policy_uuid_idx').on(
+      cols.snapshot_policy_uuid,
+    ),
+  ],
+);
+
+export const serverDatabaseAccessTokensTable = pgTable(
+  'server_database_access_tokens',
+  {
+    uuid: uuid().default(sql`gen_random_uuid()`).primaryKey().notNull(),
+    server_uuid: uuid()
+      .references(() => serversTable.uuid, { onDelete: 'cascade' })
+      .notNull(),
+    server_database_uuid: uuid().references(() => serverDatabasesTable.uuid, { onDelete: 'cascade' }),
+    server_database_instance_uuid: uuid().references(() => serverDatabaseInstancesTable.uuid, {
+      onDelete: 'cascade',
+    }),
+    instance_database_uuid: uuid(),
+    agent_user_uuid: uuid(),
+    name: varchar({ length: 63 * UTF8_MAX_SCALAR_SIZE }).notNull(),
+    username: varchar({ length: 63 }).notNull(),
+    expires: timestamp().notNull(),
+    created: timestamp().defaultNow().notNull(),
+  },
+  (cols) => [
+    index('server_database_access_tokens_server_database_uuid_idx').on(cols.server_database_uuid),
+    index('server_database_access_tokens_instance_database_idx').on(
+      cols.server_database_instance_uuid,
+      cols.instance_database_uuid,
+    ),
+    index('server_database_access_tokens_expires_idx').on(cols.expires),
+  ],
+);
+
 export const serverSchedulesTable = pgTable(
   'server_schedules',
   {
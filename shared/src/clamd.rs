@@ -0,0 +1,84 @@
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+const MAX_REPLY_SIZE: usize = 4096;
+const SCAN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    Clean,
+    Infected(compact_str::CompactString),
+}
+
+/// Streams `data` to a clamd daemon using the `INSTREAM` command.
+///
+/// `address` is either a unix socket path (starting with `/`) or a `host:port` tcp address.
+/// Note that clamd rejects streams larger than its own `StreamMaxLength`, which is
+/// reported back as an error.
+pub async fn scan(
+    address: &str,
+    data: impl AsyncRead + Unpin,
+) -> Result<ScanVerdict, anyhow::Error> {
+    tokio::time::timeout(SCAN_TIMEOUT, async {
+        if address.starts_with('/') {
+            #[cfg(unix)]
+            {
+                let stream = tokio::net::UnixStream::connect(address).await?;
+                scan_stream(stream, data).await
+            }
+            #[cfg(not(unix))]
+            {
+                Err(anyhow::anyhow!(
+                    "unix sockets are not supported on this platform"
+                ))
+            }
+        } else {
+            let stream = tokio::net::TcpStream::connect(address).await?;
+            scan_stream(stream, data).await
+        }
+    })
+    .await
+    .map_err(|_| anyhow::anyhow!("timed out while waiting for clamd"))?
+}
+
+async fn scan_stream(
+    mut stream: impl AsyncRead + AsyncWrite + Unpin,
+    mut data: impl AsyncRead + Unpin,
+) -> Result<ScanVerdict, anyhow::Error> {
+    stream.write_all(b"zINSTREAM\0").await?;
+
+    let mut buffer = vec![0; CHUNK_SIZE];
+    loop {
+        let read = data.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+
+        stream.write_all(&(read as u32).to_be_bytes()).await?;
+        stream.write_all(&buffer[..read]).await?;
+    }
+
+    stream.write_all(&0u32.to_be_bytes()).await?;
+    stream.flush().await?;
+
+    let mut reply = Vec::new();
+    (&mut stream)
+        .take(MAX_REPLY_SIZE as u64)
+        .read_to_end(&mut reply)
+        .await?;
+
+    parse_reply(&String::from_utf8_lossy(&reply))
+}
+
+fn parse_reply(reply: &str) -> Result<ScanVerdict, anyhow::Error> {
+    let reply = reply.trim_end_matches(['\0', '\n']).trim();
+    let result = reply.strip_prefix("stream:").unwrap_or(reply).trim();
+
+    if result == "OK" {
+        Ok(ScanVerdict::Clean)
+    } else if let Some(signature) = result.strip_suffix(" FOUND") {
+        Ok(ScanVerdict::Infected(signature.trim().into()))
+    } else {
+        Err(anyhow::anyhow!("unexpected clamd reply: {result}"))
+    }
+}
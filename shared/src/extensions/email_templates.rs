@@ -192,6 +192,33 @@ impl Default for ExtensionEmailTemplateBuilder {
                     default_content: include_str!("../../mails/server_restored.html"),
                     default_enabled: false,
                 },
+                EmailTemplate {
+                    identifier: "database_quota_warning",
+                    available_variables: vec![
+                        "server",
+                        "database_name",
+                        "usage",
+                        "limit",
+                        "percent",
+                        "server_link",
+                    ],
+                    default_subject: "{{ settings.app.name }} - Database Quota Warning",
+                    default_content: include_str!("../../mails/database_quota_warning.html"),
+                    default_enabled: true,
+                },
+                EmailTemplate {
+                    identifier: "database_quota_exceeded",
+                    available_variables: vec![
+                        "server",
+                        "database_name",
+                        "usage",
+                        "limit",
+                        "server_link",
+                    ],
+                    default_subject: "{{ settings.app.name }} - Database Quota Exceeded",
+                    default_content: include_str!("../../mails/database_quota_exceeded.html"),
+                    default_enabled: true,
+                },
             ],
         }
     }
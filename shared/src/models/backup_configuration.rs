@@ -47,6 +47,9 @@ pub struct BackupConfigsS3 {
     pub compression_type: wings_api::CompressionType,
     #[garde(skip)]
     pub part_size: u64,
+    #[garde(skip)]
+    #[serde(default)]
+    pub encryption_enabled: bool,
 }
 
 impl BackupConfigsS3 {
@@ -447,6 +450,8 @@ pub struct BackupConfiguration {
     pub backup_disk: super::server_backup::BackupDisk,
     pub backup_configs: BackupConfigs,
 
+    pub replication_backup_configuration: Option<Fetchable<BackupConfiguration>>,
+
     pub created: chrono::NaiveDateTime,
 
     extension_data: super::ModelExtensionData,
@@ -499,6 +504,10 @@ impl BaseModel for BackupConfiguration {
                 "backup_configurations.backup_configs",
                 compact_str::format_compact!("{prefix}backup_configs"),
             ),
+            (
+                "backup_configurations.replication_backup_configuration_uuid",
+                compact_str::format_compact!("{prefix}replication_backup_configuration_uuid"),
+            ),
             (
                 "backup_configurations.created",
                 compact_str::format_compact!("{prefix}created"),
@@ -524,6 +533,10 @@ impl BaseModel for BackupConfiguration {
                 row.get(compact_str::format_compact!("{prefix}backup_configs").as_str()),
             )
             .unwrap_or_default(),
+            replication_backup_configuration: Self::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}replication_backup_configuration_uuid"),
+            ),
             created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
             extension_data: Self::map_extensions(prefix, row)?,
         })
@@ -607,6 +620,39 @@ impl BackupConfiguration {
 
         Ok(result.rows_affected())
     }
+
+    /// Replicas are copied by the panel itself, so the target has to be an S3 configuration
+    /// other than the source one.
+    async fn validate_replication_target(
+        database: &crate::database::Database,
+        uuid: Option<uuid::Uuid>,
+        replication_backup_configuration_uuid: uuid::Uuid,
+    ) -> Result<(), crate::database::DatabaseError> {
+        if uuid == Some(replication_backup_configuration_uuid) {
+            return Err(anyhow::Error::from(crate::response::DisplayError::new(
+                "backup configuration cannot replicate to itself",
+            ))
+            .into());
+        }
+
+        let replication_backup_configuration =
+            Self::by_uuid_optional_cached(database, replication_backup_configuration_uuid)
+                .await?
+                .ok_or(crate::database::InvalidRelationError(
+                    "replication_backup_configuration",
+                ))?;
+
+        if replication_backup_configuration.backup_disk != super::server_backup::BackupDisk::S3
+            || replication_backup_configuration.backup_configs.s3.is_none()
+        {
+            return Err(anyhow::Error::from(crate::response::DisplayError::new(
+                "replication backup configuration must use the s3 backup disk",
+            ))
+            .into());
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -632,6 +678,9 @@ impl IntoAdminApiObject for BackupConfiguration {
                 shared: self.shared,
                 backup_disk: self.backup_disk,
                 backup_configs: self.backup_configs,
+                replication_backup_configuration_uuid: self
+                    .replication_backup_configuration
+                    .map(|c| c.uuid),
                 created: self.created.and_utc(),
             },
             api_object,
@@ -699,6 +748,8 @@ pub struct CreateBackupConfigurationOptions {
     pub backup_disk: super::server_backup::BackupDisk,
     #[garde(dive)]
     pub backup_configs: BackupConfigs,
+    #[garde(skip)]
+    pub replication_backup_configuration_uuid: Option<uuid::Uuid>,
 }
 
 #[async_trait::async_trait]
@@ -720,6 +771,17 @@ impl CreatableModel for BackupConfiguration {
     ) -> Result<Self, crate::database::DatabaseError> {
         options.validate()?;
 
+        if let Some(replication_backup_configuration_uuid) =
+            options.replication_backup_configuration_uuid
+        {
+            Self::validate_replication_target(
+                &state.database,
+                None,
+                replication_backup_configuration_uuid,
+            )
+            .await?;
+        }
+
         let mut query_builder = InsertQueryBuilder::new("backup_configurations");
 
         Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
@@ -735,6 +797,10 @@ impl CreatableModel for BackupConfiguration {
             .set(
                 "backup_configs",
                 serde_json::to_value(&options.backup_configs)?,
+            )
+            .set(
+                "replication_backup_configuration_uuid",
+                options.replication_backup_configuration_uuid,
             );
 
         let row = query_builder
@@ -771,6 +837,13 @@ pub struct UpdateBackupConfigurationOptions {
     pub backup_disk: Option<super::server_backup::BackupDisk>,
     #[garde(dive)]
     pub backup_configs: Option<BackupConfigs>,
+    #[garde(skip)]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    pub replication_backup_configuration_uuid: Option<Option<uuid::Uuid>>,
 }
 
 #[async_trait::async_trait]
@@ -792,6 +865,17 @@ impl UpdatableModel for BackupConfiguration {
     ) -> Result<(), crate::database::DatabaseError> {
         options.validate()?;
 
+        if let Some(Some(replication_backup_configuration_uuid)) =
+            options.replication_backup_configuration_uuid
+        {
+            Self::validate_replication_target(
+                &state.database,
+                Some(self.uuid),
+                replication_backup_configuration_uuid,
+            )
+            .await?;
+        }
+
         let mut query_builder = UpdateQueryBuilder::new("backup_configurations");
 
         self.run_update_handlers(&mut options, &mut query_builder, state, transaction)
@@ -816,6 +900,10 @@ impl UpdatableModel for BackupConfiguration {
                     None
                 },
             )
+            .set(
+                "replication_backup_configuration_uuid",
+                options.replication_backup_configuration_uuid.as_ref(),
+            )
             .where_eq("uuid", self.uuid);
 
         query_builder.execute(&mut **transaction).await?;
@@ -838,6 +926,12 @@ impl UpdatableModel for BackupConfiguration {
         if let Some(backup_configs) = options.backup_configs {
             self.backup_configs = backup_configs;
         }
+        if let Some(replication_backup_configuration_uuid) =
+            options.replication_backup_configuration_uuid
+        {
+            self.replication_backup_configuration =
+                replication_backup_configuration_uuid.map(Self::get_fetchable);
+        }
 
         self.run_after_update_handlers(state, transaction).await?;
 
@@ -921,6 +1015,12 @@ impl DuplicableModel for BackupConfiguration {
             .set(
                 "backup_configs",
                 serde_json::to_value(&self.backup_configs)?,
+            )
+            .set(
+                "replication_backup_configuration_uuid",
+                self.replication_backup_configuration
+                    .as_ref()
+                    .map(|c| c.uuid),
             );
 
         let row = query_builder
@@ -953,5 +1053,7 @@ pub struct AdminApiBackupConfiguration {
     pub backup_disk: super::server_backup::BackupDisk,
     pub backup_configs: BackupConfigs,
 
+    pub replication_backup_configuration_uuid: Option<uuid::Uuid>,
+
     pub created: chrono::DateTime<chrono::Utc>,
 }
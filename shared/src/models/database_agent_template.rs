@@ -475,6 +475,16 @@ impl CreatableModel for DatabaseAgentTemplate {
             .await?;
         let mut database_agent_template = Self::map(None, &row)?;
 
+        super::database_agent_template_revision::DatabaseAgentTemplateRevision::create_with_transaction(
+            state,
+            super::database_agent_template_revision::CreateDatabaseAgentTemplateRevisionOptions {
+                database_agent_template: &database_agent_template,
+                changelog: None,
+            },
+            transaction,
+        )
+        .await?;
+
         Self::run_after_create_handlers(&mut database_agent_template, &options, state, transaction)
             .await?;
 
@@ -542,6 +552,12 @@ pub struct UpdateDatabaseAgentTemplateOptions {
     #[garde(range(min = 0))]
     #[schema(minimum = 0)]
     pub cpu: Option<i32>,
+
+    /// Recorded on the revision created by this update, ignored if the update does not
+    /// change anything that is applied to instances.
+    #[garde(length(chars, min = 1, max = 4096))]
+    #[schema(min_length = 1, max_length = 4096)]
+    pub changelog: Option<compact_str::CompactString>,
 }
 
 #[async_trait::async_trait]
@@ -668,6 +684,18 @@ impl UpdatableModel for DatabaseAgentTemplate {
             self.cpu = cpu;
         }
 
+        if spec_changed {
+            super::database_agent_template_revision::DatabaseAgentTemplateRevision::create_with_transaction(
+                state,
+                super::database_agent_template_revision::CreateDatabaseAgentTemplateRevisionOptions {
+                    database_agent_template: &*self,
+                    changelog: options.changelog,
+                },
+                transaction,
+            )
+            .await?;
+        }
+
         self.run_after_update_handlers(state, transaction).await?;
 
         Ok(())
@@ -703,7 +731,8 @@ impl DeletableModel for DatabaseAgentTemplate {
                 io_weight = COALESCE(io_weight, $5),
                 cpu = COALESCE(cpu, $6),
                 image = COALESCE(image, $7),
-                template_version = NULL
+                template_version = NULL,
+                pinned_template_version = NULL
             WHERE server_database_instances.database_agent_template_uuid = $1
             "#,
         )
@@ -823,6 +852,16 @@ impl DuplicableModel for DatabaseAgentTemplate {
             .await?;
         let mut database_agent_template = Self::map(None, &row)?;
 
+        super::database_agent_template_revision::DatabaseAgentTemplateRevision::create_with_transaction(
+            state,
+            super::database_agent_template_revision::CreateDatabaseAgentTemplateRevisionOptions {
+                database_agent_template: &database_agent_template,
+                changelog: None,
+            },
+            transaction,
+        )
+        .await?;
+
         self.run_after_duplicate_handlers(
             &mut database_agent_template,
             &options,
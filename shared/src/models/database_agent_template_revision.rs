@@ -0,0 +1,333 @@
+use crate::{
+    models::{InsertQueryBuilder, database_agent_template::DatabaseAgentTemplate},
+    prelude::*,
+};
+use garde::Validate;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+/// The parts of a template that are applied to its instances, as they were at a revision.
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct DatabaseAgentTemplateRevisionSpec {
+    pub docker_images: IndexMap<compact_str::CompactString, compact_str::CompactString>,
+    pub env: IndexMap<compact_str::CompactString, compact_str::CompactString>,
+
+    pub image_uid: i32,
+    pub image_gid: i32,
+    pub cmd: Option<Vec<compact_str::CompactString>>,
+    pub volumes: IndexMap<compact_str::CompactString, compact_str::CompactString>,
+    pub socket_path: compact_str::CompactString,
+
+    pub memory: i64,
+    pub swap: i64,
+    pub disk: i64,
+    pub io_weight: Option<i16>,
+    pub cpu: i32,
+}
+
+impl From<&DatabaseAgentTemplate> for DatabaseAgentTemplateRevisionSpec {
+    fn from(template: &DatabaseAgentTemplate) -> Self {
+        Self {
+            docker_images: template.docker_images.clone(),
+            env: template.env.clone(),
+            image_uid: template.image_uid,
+            image_gid: template.image_gid,
+            cmd: template.cmd.clone(),
+            volumes: template.volumes.clone(),
+            socket_path: template.socket_path.clone(),
+            memory: template.memory,
+            swap: template.swap,
+            disk: template.disk,
+            io_weight: template.io_weight,
+            cpu: template.cpu,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DatabaseAgentTemplateRevision {
+    pub uuid: uuid::Uuid,
+    pub database_agent_template_uuid: uuid::Uuid,
+
+    pub version: i32,
+    pub changelog: Option<compact_str::CompactString>,
+    pub spec: DatabaseAgentTemplateRevisionSpec,
+
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for DatabaseAgentTemplateRevision {
+    const NAME: &'static str = "database_agent_template_revision";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "database_agent_template_revisions.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "database_agent_template_revisions.database_agent_template_uuid",
+                compact_str::format_compact!("{prefix}database_agent_template_uuid"),
+            ),
+            (
+                "database_agent_template_revisions.version",
+                compact_str::format_compact!("{prefix}version"),
+            ),
+            (
+                "database_agent_template_revisions.changelog",
+                compact_str::format_compact!("{prefix}changelog"),
+            ),
+            (
+                "database_agent_template_revisions.spec",
+                compact_str::format_compact!("{prefix}spec"),
+            ),
+            (
+                "database_agent_template_revisions.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            database_agent_template_uuid: row.try_get(
+                compact_str::format_compact!("{prefix}database_agent_template_uuid").as_str(),
+            )?,
+            version: row.try_get(compact_str::format_compact!("{prefix}version").as_str())?,
+            changelog: row.try_get(compact_str::format_compact!("{prefix}changelog").as_str())?,
+            spec: serde_json::from_value(
+                row.try_get(compact_str::format_compact!("{prefix}spec").as_str())?,
+            )?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl DatabaseAgentTemplateRevision {
+    pub async fn by_database_agent_template_uuid_version(
+        database: &crate::database::Database,
+        database_agent_template_uuid: uuid::Uuid,
+        version: i32,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM database_agent_template_revisions
+            WHERE database_agent_template_revisions.database_agent_template_uuid = $1
+                AND database_agent_template_revisions.version = $2
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(database_agent_template_uuid)
+        .bind(version)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_database_agent_template_uuid_with_pagination(
+        database: &crate::database::Database,
+        database_agent_template_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM database_agent_template_revisions
+            WHERE database_agent_template_revisions.database_agent_template_uuid = $1
+                AND ($2 IS NULL OR database_agent_template_revisions.changelog ILIKE '%' || $2 || '%')
+            ORDER BY database_agent_template_revisions.version DESC
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(database_agent_template_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    /// Returns the template as it was at the given revision, or `None` if that revision is
+    /// unknown.
+    pub async fn resolve_template(
+        database: &crate::database::Database,
+        template: &DatabaseAgentTemplate,
+        version: i32,
+    ) -> Result<Option<DatabaseAgentTemplate>, crate::database::DatabaseError> {
+        if version == template.version {
+            return Ok(Some(template.clone()));
+        }
+
+        Ok(
+            Self::by_database_agent_template_uuid_version(database, template.uuid, version)
+                .await?
+                .map(|revision| revision.apply_to(template)),
+        )
+    }
+
+    pub fn apply_to(&self, template: &DatabaseAgentTemplate) -> DatabaseAgentTemplate {
+        let mut template = template.clone();
+
+        template.docker_images = self.spec.docker_images.clone();
+        template.env = self.spec.env.clone();
+        template.image_uid = self.spec.image_uid;
+        template.image_gid = self.spec.image_gid;
+        template.cmd = self.spec.cmd.clone();
+        template.volumes = self.spec.volumes.clone();
+        template.socket_path = self.spec.socket_path.clone();
+        template.memory = self.spec.memory;
+        template.swap = self.spec.swap;
+        template.disk = self.spec.disk;
+        template.io_weight = self.spec.io_weight;
+        template.cpu = self.spec.cpu;
+        template.version = self.version;
+
+        template
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoAdminApiObject for DatabaseAgentTemplateRevision {
+    type AdminApiObject = AdminApiDatabaseAgentTemplateRevision;
+    type ExtraArgs<'a> = ();
+
+    async fn into_admin_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::AdminApiObject, crate::database::DatabaseError> {
+        let api_object = AdminApiDatabaseAgentTemplateRevision::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            AdminApiDatabaseAgentTemplateRevision {
+                uuid: self.uuid,
+                version: self.version,
+                changelog: self.changelog,
+                spec: self.spec,
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(Validate)]
+pub struct CreateDatabaseAgentTemplateRevisionOptions<'a> {
+    #[garde(skip)]
+    pub database_agent_template: &'a DatabaseAgentTemplate,
+    #[garde(length(chars, min = 1, max = 4096))]
+    pub changelog: Option<compact_str::CompactString>,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for DatabaseAgentTemplateRevision {
+    type CreateOptions<'a> = CreateDatabaseAgentTemplateRevisionOptions<'a>;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<DatabaseAgentTemplateRevision>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("database_agent_template_revisions");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set(
+                "database_agent_template_uuid",
+                options.database_agent_template.uuid,
+            )
+            .set("version", options.database_agent_template.version)
+            .set("changelog", &options.changelog)
+            .set(
+                "spec",
+                OrderedJson(DatabaseAgentTemplateRevisionSpec::from(
+                    options.database_agent_template,
+                )),
+            );
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut revision = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut revision, &options, state, transaction).await?;
+
+        Ok(revision)
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(DatabaseAgentTemplateRevision, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminDatabaseAgentTemplateRevision")]
+pub struct AdminApiDatabaseAgentTemplateRevision {
+    pub uuid: uuid::Uuid,
+
+    pub version: i32,
+    pub changelog: Option<compact_str::CompactString>,
+    pub spec: DatabaseAgentTemplateRevisionSpec,
+
+    pub created: chrono::DateTime<chrono::Utc>,
+}
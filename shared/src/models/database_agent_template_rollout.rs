@@ -0,0 +1,746 @@
+use crate::{
+    models::{
+        InsertQueryBuilder, database_agent_template::DatabaseAgentTemplate,
+        database_agent_template_revision::DatabaseAgentTemplateRevision,
+        server_database_instance::ServerDatabaseInstance,
+    },
+    prelude::*,
+};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Debug, ToSchema, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabaseAgentTemplateRolloutStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct DatabaseAgentTemplateRolloutFailure {
+    pub instance_uuid: uuid::Uuid,
+    pub name: compact_str::CompactString,
+    pub error: compact_str::CompactString,
+    pub rolled_back: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DatabaseAgentTemplateRollout {
+    pub uuid: uuid::Uuid,
+    pub database_agent_template_uuid: uuid::Uuid,
+
+    pub target_version: i32,
+    pub batch_size: i32,
+    pub halt_on_failure: bool,
+
+    pub updated: i32,
+    pub failures: Vec<DatabaseAgentTemplateRolloutFailure>,
+    pub error: Option<compact_str::CompactString>,
+
+    pub started: Option<chrono::NaiveDateTime>,
+    pub heartbeat: Option<chrono::NaiveDateTime>,
+    pub completed: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for DatabaseAgentTemplateRollout {
+    const NAME: &'static str = "database_agent_template_rollout";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "database_agent_template_rollouts.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "database_agent_template_rollouts.database_agent_template_uuid",
+                compact_str::format_compact!("{prefix}database_agent_template_uuid"),
+            ),
+            (
+                "database_agent_template_rollouts.target_version",
+                compact_str::format_compact!("{prefix}target_version"),
+            ),
+            (
+                "database_agent_template_rollouts.batch_size",
+                compact_str::format_compact!("{prefix}batch_size"),
+            ),
+            (
+                "database_agent_template_rollouts.halt_on_failure",
+                compact_str::format_compact!("{prefix}halt_on_failure"),
+            ),
+            (
+                "database_agent_template_rollouts.updated",
+                compact_str::format_compact!("{prefix}updated"),
+            ),
+            (
+                "database_agent_template_rollouts.failures",
+                compact_str::format_compact!("{prefix}failures"),
+            ),
+            (
+                "database_agent_template_rollouts.error",
+                compact_str::format_compact!("{prefix}error"),
+            ),
+            (
+                "database_agent_template_rollouts.started",
+                compact_str::format_compact!("{prefix}started"),
+            ),
+            (
+                "database_agent_template_rollouts.heartbeat",
+                compact_str::format_compact!("{prefix}heartbeat"),
+            ),
+            (
+                "database_agent_template_rollouts.completed",
+                compact_str::format_compact!("{prefix}completed"),
+            ),
+            (
+                "database_agent_template_rollouts.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            database_agent_template_uuid: row.try_get(
+                compact_str::format_compact!("{prefix}database_agent_template_uuid").as_str(),
+            )?,
+            target_version: row
+                .try_get(compact_str::format_compact!("{prefix}target_version").as_str())?,
+            batch_size: row.try_get(compact_str::format_compact!("{prefix}batch_size").as_str())?,
+            halt_on_failure: row
+                .try_get(compact_str::format_compact!("{prefix}halt_on_failure").as_str())?,
+            updated: row.try_get(compact_str::format_compact!("{prefix}updated").as_str())?,
+            failures: serde_json::from_value(row.try_get::<serde_json::Value, _>(
+                compact_str::format_compact!("{prefix}failures").as_str(),
+            )?)?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            started: row.try_get(compact_str::format_compact!("{prefix}started").as_str())?,
+            heartbeat: row.try_get(compact_str::format_compact!("{prefix}heartbeat").as_str())?,
+            completed: row.try_get(compact_str::format_compact!("{prefix}completed").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl DatabaseAgentTemplateRollout {
+    /// Running rollouts without a heartbeat for this long are considered interrupted. A batch
+    /// records its progress once all of its health checks are done.
+    const HEARTBEAT_TIMEOUT_SECONDS: i32 = 600;
+    /// How long an upgraded instance has to report itself as running.
+    const HEALTH_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+    const HEALTH_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+    /// Consecutive running checks after which an upgraded instance is considered healthy.
+    const HEALTH_CHECK_THRESHOLD: usize = 3;
+
+    pub async fn by_database_agent_template_uuid_uuid(
+        database: &crate::database::Database,
+        database_agent_template_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM database_agent_template_rollouts
+            WHERE database_agent_template_rollouts.database_agent_template_uuid = $1
+                AND database_agent_template_rollouts.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(database_agent_template_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_database_agent_template_uuid_with_pagination(
+        database: &crate::database::Database,
+        database_agent_template_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM database_agent_template_rollouts
+            WHERE database_agent_template_rollouts.database_agent_template_uuid = $1
+            ORDER BY database_agent_template_rollouts.created DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(database_agent_template_uuid)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn count_active_by_database_agent_template_uuid(
+        database: &crate::database::Database,
+        database_agent_template_uuid: uuid::Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM database_agent_template_rollouts
+            WHERE database_agent_template_rollouts.database_agent_template_uuid = $1
+                AND database_agent_template_rollouts.completed IS NULL
+                AND database_agent_template_rollouts.error IS NULL
+            "#,
+        )
+        .bind(database_agent_template_uuid)
+        .fetch_one(database.read())
+        .await
+    }
+
+    /// Fails running rollouts whose process stopped sending heartbeats. Instances that were
+    /// already upgraded keep their new revision.
+    pub async fn fail_interrupted(
+        database: &crate::database::Database,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE database_agent_template_rollouts
+            SET error = 'rollout was interrupted'
+            WHERE database_agent_template_rollouts.started IS NOT NULL
+                AND database_agent_template_rollouts.completed IS NULL
+                AND database_agent_template_rollouts.error IS NULL
+                AND database_agent_template_rollouts.heartbeat < NOW() - make_interval(secs => $1)
+            "#,
+        )
+        .bind(Self::HEARTBEAT_TIMEOUT_SECONDS)
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Starts pending rollouts, oldest first. Only one rollout per template is ever started.
+    pub async fn claim_pending(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            UPDATE database_agent_template_rollouts
+            SET started = NOW(), heartbeat = NOW()
+            WHERE database_agent_template_rollouts.uuid IN (
+                SELECT DISTINCT ON (database_agent_template_rollouts.database_agent_template_uuid)
+                    database_agent_template_rollouts.uuid
+                FROM database_agent_template_rollouts
+                WHERE database_agent_template_rollouts.started IS NULL
+                    AND database_agent_template_rollouts.error IS NULL
+                    AND NOT EXISTS (
+                        SELECT 1 FROM database_agent_template_rollouts running
+                        WHERE running.database_agent_template_uuid = database_agent_template_rollouts.database_agent_template_uuid
+                            AND running.started IS NOT NULL
+                            AND running.completed IS NULL
+                            AND running.error IS NULL
+                    )
+                ORDER BY database_agent_template_rollouts.database_agent_template_uuid, database_agent_template_rollouts.created
+            )
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        )))
+        .fetch_all(database.write())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Stops the rollout after its current batch, returning `false` if it already finished.
+    pub async fn cancel(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<bool, sqlx::Error> {
+        let cancelled = sqlx::query(
+            r#"
+            UPDATE database_agent_template_rollouts
+            SET error = 'rollout was cancelled'
+            WHERE database_agent_template_rollouts.uuid = $1
+                AND database_agent_template_rollouts.completed IS NULL
+                AND database_agent_template_rollouts.error IS NULL
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?
+        .rows_affected()
+            > 0;
+
+        if cancelled {
+            self.error = Some("rollout was cancelled".into());
+        }
+
+        Ok(cancelled)
+    }
+
+    #[inline]
+    pub fn status(&self) -> DatabaseAgentTemplateRolloutStatus {
+        if self.completed.is_some() {
+            DatabaseAgentTemplateRolloutStatus::Completed
+        } else if self.error.is_some() {
+            DatabaseAgentTemplateRolloutStatus::Failed
+        } else if self.started.is_some() {
+            DatabaseAgentTemplateRolloutStatus::Running
+        } else {
+            DatabaseAgentTemplateRolloutStatus::Pending
+        }
+    }
+
+    async fn is_cancelled(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT database_agent_template_rollouts.error IS NOT NULL
+            FROM database_agent_template_rollouts
+            WHERE database_agent_template_rollouts.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .fetch_one(database.read())
+        .await
+    }
+
+    async fn record_progress(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            UPDATE database_agent_template_rollouts
+            SET updated = $1, failures = $2, heartbeat = NOW()
+            WHERE database_agent_template_rollouts.uuid = $3
+            "#,
+        )
+        .bind(self.updated)
+        .bind(serde_json::to_value(&self.failures)?)
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Waits for an upgraded instance to report itself as running for a few consecutive
+    /// checks and to be reachable by its agent.
+    async fn health_check(
+        state: &crate::State,
+        instance: &ServerDatabaseInstance,
+    ) -> Result<(), anyhow::Error> {
+        let client = instance
+            .database_agent_host
+            .api_client(&state.database)
+            .await?;
+
+        let mut interval = tokio::time::interval(Self::HEALTH_CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        let deadline = tokio::time::Instant::now() + Self::HEALTH_CHECK_TIMEOUT;
+        let mut running_checks = 0;
+
+        while running_checks < Self::HEALTH_CHECK_THRESHOLD {
+            if tokio::time::Instant::now() >= deadline {
+                return Err(anyhow::anyhow!(
+                    "instance did not become healthy within {} seconds",
+                    Self::HEALTH_CHECK_TIMEOUT.as_secs()
+                ));
+            }
+
+            interval.tick().await;
+
+            match client
+                .get_instances_instance_utilization(instance.uuid)
+                .await
+            {
+                Ok(response)
+                    if matches!(
+                        response.utilization.state,
+                        db_agent_api::ContainerState::Running
+                    ) =>
+                {
+                    running_checks += 1
+                }
+                Ok(_) => running_checks = 0,
+                Err(err) => {
+                    tracing::debug!(
+                        instance = %instance.uuid,
+                        "failed to check database instance utilization: {:?}",
+                        err
+                    );
+                    running_checks = 0
+                }
+            }
+        }
+
+        if let Some(backend_auth_error) = client
+            .get_instances_instance(instance.uuid)
+            .await?
+            .instance
+            .backend_auth_error
+        {
+            return Err(anyhow::anyhow!(
+                "agent cannot authenticate with instance: {backend_auth_error}"
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Upgrades a single instance, restoring its previous container configuration if it does
+    /// not come up healthy. Returns the error and whether the rollback succeeded.
+    async fn upgrade_instance(
+        state: &crate::State,
+        template: &DatabaseAgentTemplate,
+        instance: &mut ServerDatabaseInstance,
+    ) -> Result<(), (anyhow::Error, bool)> {
+        if instance.r#type.as_str() != template.r#type.as_str() {
+            return Err((
+                anyhow::anyhow!("database agent template type does not match instance type"),
+                false,
+            ));
+        }
+
+        let client = match instance
+            .database_agent_host
+            .api_client(&state.database)
+            .await
+        {
+            Ok(client) => client,
+            Err(err) => return Err((err, false)),
+        };
+        let previous = match client.get_instances_instance(instance.uuid).await {
+            Ok(response) => response.instance,
+            Err(err) => return Err((err.into(), false)),
+        };
+        let previous_version = instance.template_version;
+
+        instance
+            .apply_template(state, template)
+            .await
+            .map_err(|err| (err, false))?;
+
+        let Err(err) = Self::health_check(state, instance).await else {
+            return Ok(());
+        };
+
+        let rollback = async {
+            client
+                .patch_instances_instance(
+                    instance.uuid,
+                    &db_agent_api::instances_instance::patch::RequestBody {
+                        suspended: None,
+                        memory: Some(previous.memory),
+                        swap: Some(previous.swap),
+                        disk: Some(previous.disk),
+                        io_weight: previous.io_weight,
+                        cpu: Some(previous.cpu),
+                        image: Some(previous.image),
+                        image_uid: Some(previous.image_uid),
+                        image_gid: Some(previous.image_gid),
+                        volumes: Some(previous.volumes),
+                        socket_path: Some(previous.socket_path),
+                        timezone: previous.timezone,
+                        env: Some(previous.env),
+                        cmd: previous.cmd,
+                    },
+                )
+                .await?;
+
+            instance
+                .set_template_version(&state.database, previous_version)
+                .await?;
+
+            Ok::<_, anyhow::Error>(())
+        };
+
+        match rollback.await {
+            Ok(()) => Err((err, true)),
+            Err(rollback_err) => {
+                tracing::error!(
+                    instance = %instance.uuid,
+                    "failed to roll back database instance after failed upgrade: {:?}",
+                    rollback_err
+                );
+
+                Err((err, false))
+            }
+        }
+    }
+
+    async fn roll_out(&mut self, state: &crate::State) -> Result<(), anyhow::Error> {
+        let template = DatabaseAgentTemplate::by_uuid_optional(
+            &state.database,
+            self.database_agent_template_uuid,
+        )
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("database agent template no longer exists"))?;
+        let template = DatabaseAgentTemplateRevision::resolve_template(
+            &state.database,
+            &template,
+            self.target_version,
+        )
+        .await?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "database agent template revision {} no longer exists",
+                self.target_version
+            )
+        })?;
+
+        loop {
+            if self.is_cancelled(&state.database).await? {
+                return Ok(());
+            }
+
+            let exclude = self
+                .failures
+                .iter()
+                .map(|failure| failure.instance_uuid)
+                .collect::<Vec<_>>();
+            let instances =
+                ServerDatabaseInstance::all_rollout_pending_by_database_agent_template_uuid(
+                    &state.database,
+                    template.uuid,
+                    template.version,
+                    &exclude,
+                    self.batch_size as i64,
+                )
+                .await?;
+            if instances.is_empty() {
+                return Ok(());
+            }
+
+            let results =
+                futures_util::future::join_all(instances.into_iter().map(async |mut instance| {
+                    let result = Self::upgrade_instance(state, &template, &mut instance).await;
+
+                    (instance, result)
+                }))
+                .await;
+
+            for (instance, result) in results {
+                match result {
+                    Ok(()) => self.updated += 1,
+                    Err((err, rolled_back)) => {
+                        tracing::warn!(
+                            rollout = %self.uuid,
+                            instance = %instance.uuid,
+                            rolled_back,
+                            "failed to upgrade database instance: {:?}",
+                            err
+                        );
+
+                        self.failures.push(DatabaseAgentTemplateRolloutFailure {
+                            instance_uuid: instance.uuid,
+                            name: instance.name,
+                            error: err.to_string().into(),
+                            rolled_back,
+                        });
+                    }
+                }
+            }
+
+            self.record_progress(&state.database).await?;
+
+            if self.halt_on_failure && !self.failures.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "rollout halted after {} instances failed to upgrade",
+                    self.failures.len()
+                ));
+            }
+        }
+    }
+
+    /// Upgrades the instances of the template in batches until none are left on another
+    /// revision, recording the outcome once it is done. A cancelled rollout keeps its
+    /// cancellation as its error.
+    pub async fn run(&mut self, state: &crate::State) -> Result<(), anyhow::Error> {
+        let rolled_out = self.roll_out(state).await;
+
+        let (error, completed): (Option<compact_str::CompactString>, Option<chrono::NaiveDateTime>) =
+            sqlx::query_as(
+                r#"
+                UPDATE database_agent_template_rollouts
+                SET updated = $1, failures = $2, heartbeat = NOW(),
+                    error = COALESCE(database_agent_template_rollouts.error, $3),
+                    completed = CASE WHEN database_agent_template_rollouts.error IS NULL AND $3 IS NULL THEN NOW() END
+                WHERE database_agent_template_rollouts.uuid = $4
+                RETURNING database_agent_template_rollouts.error, database_agent_template_rollouts.completed
+                "#,
+            )
+            .bind(self.updated)
+            .bind(serde_json::to_value(&self.failures)?)
+            .bind(rolled_out.as_ref().err().map(|err| err.to_string()))
+            .bind(self.uuid)
+            .fetch_one(state.database.write())
+            .await?;
+
+        self.error = error;
+        self.completed = completed;
+
+        rolled_out
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoAdminApiObject for DatabaseAgentTemplateRollout {
+    type AdminApiObject = AdminApiDatabaseAgentTemplateRollout;
+    type ExtraArgs<'a> = ();
+
+    async fn into_admin_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::AdminApiObject, crate::database::DatabaseError> {
+        let api_object = AdminApiDatabaseAgentTemplateRollout::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            AdminApiDatabaseAgentTemplateRollout {
+                uuid: self.uuid,
+                target_version: self.target_version,
+                batch_size: self.batch_size,
+                halt_on_failure: self.halt_on_failure,
+                status: self.status(),
+                updated: self.updated,
+                failures: self.failures,
+                error: self.error,
+                started: self.started.map(|started| started.and_utc()),
+                completed: self.completed.map(|completed| completed.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateDatabaseAgentTemplateRolloutOptions {
+    #[garde(skip)]
+    pub database_agent_template_uuid: uuid::Uuid,
+
+    #[garde(range(min = 1))]
+    pub target_version: i32,
+    #[garde(range(min = 1, max = 50))]
+    pub batch_size: i32,
+    #[garde(skip)]
+    pub halt_on_failure: bool,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for DatabaseAgentTemplateRollout {
+    type CreateOptions<'a> = CreateDatabaseAgentTemplateRolloutOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<DatabaseAgentTemplateRollout>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("database_agent_template_rollouts");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set(
+                "database_agent_template_uuid",
+                options.database_agent_template_uuid,
+            )
+            .set("target_version", options.target_version)
+            .set("batch_size", options.batch_size)
+            .set("halt_on_failure", options.halt_on_failure);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut rollout = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut rollout, &options, state, transaction).await?;
+
+        Ok(rollout)
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(DatabaseAgentTemplateRollout, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminDatabaseAgentTemplateRollout")]
+pub struct AdminApiDatabaseAgentTemplateRollout {
+    pub uuid: uuid::Uuid,
+
+    pub target_version: i32,
+    pub batch_size: i32,
+    pub halt_on_failure: bool,
+
+    pub status: DatabaseAgentTemplateRolloutStatus,
+    pub updated: i32,
+    pub failures: Vec<DatabaseAgentTemplateRolloutFailure>,
+    pub error: Option<compact_str::CompactString>,
+
+    pub started: Option<chrono::DateTime<chrono::Utc>>,
+    pub completed: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
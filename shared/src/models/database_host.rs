@@ -86,6 +86,14 @@ impl DatabaseType {
             DatabaseType::Mongodb => 27017,
         }
     }
+
+    pub const fn dump_extension(self) -> &'static str {
+        match self {
+            DatabaseType::Mysql => "sql",
+            DatabaseType::Postgres => "sql",
+            DatabaseType::Mongodb => "jsonl",
+        }
+    }
 }
 
 fn validate_connection_string(connection_string: &str, _context: &()) -> Result<(), garde::Error> {
@@ -140,6 +148,21 @@ pub enum DatabaseCredentials {
     },
 }
 
+/// The upper bound for a single health check, including establishing the connection.
+const HEALTH_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct DatabaseHostHealth {
+    pub healthy: bool,
+    pub latency_ms: Option<u64>,
+    pub version: Option<compact_str::CompactString>,
+    /// The combined size of all databases on the host in bytes.
+    pub disk_usage: Option<i64>,
+    pub error: Option<String>,
+
+    pub checked: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(ToSchema, Serialize)]
 pub struct ParsedConnectionDetails {
     pub host: compact_str::CompactString,
@@ -249,6 +272,10 @@ pub struct DatabaseHost {
 
     pub credentials: DatabaseCredentials,
 
+    pub max_databases: Option<i32>,
+    pub max_size: Option<i64>,
+    pub health: Option<DatabaseHostHealth>,
+
     pub created: chrono::NaiveDateTime,
 
     extension_data: super::ModelExtensionData,
@@ -305,6 +332,18 @@ impl BaseModel for DatabaseHost {
                 "database_hosts.credentials",
                 compact_str::format_compact!("{prefix}credentials"),
             ),
+            (
+                "database_hosts.max_databases",
+                compact_str::format_compact!("{prefix}max_databases"),
+            ),
+            (
+                "database_hosts.max_size",
+                compact_str::format_compact!("{prefix}max_size"),
+            ),
+            (
+                "database_hosts.health",
+                compact_str::format_compact!("{prefix}health"),
+            ),
             (
                 "database_hosts.created",
                 compact_str::format_compact!("{prefix}created"),
@@ -331,6 +370,15 @@ impl BaseModel for DatabaseHost {
             credentials: serde_json::from_value(
                 row.try_get(compact_str::format_compact!("{prefix}credentials").as_str())?,
             )?,
+            max_databases: row
+                .try_get(compact_str::format_compact!("{prefix}max_databases").as_str())?,
+            max_size: row.try_get(compact_str::format_compact!("{prefix}max_size").as_str())?,
+            health: row
+                .try_get::<Option<serde_json::Value>, _>(
+                    compact_str::format_compact!("{prefix}health").as_str(),
+                )?
+                .map(serde_json::from_value)
+                .transpose()?,
             created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
             extension_data: Self::map_extensions(prefix, row)?,
         })
@@ -378,28 +426,7 @@ impl DatabaseHost {
                 DatabasePool::Mysql(pool)
             }
             DatabaseType::Postgres => {
-                let options = match &self.credentials {
-                    DatabaseCredentials::ConnectionString { connection_string } => {
-                        sqlx::postgres::PgConnectOptions::from_str(connection_string).map_err(
-                            |err| {
-                                anyhow::anyhow!("failed to parse Postgres connection string: {err}")
-                            },
-                        )?
-                    }
-                    DatabaseCredentials::Details {
-                        host,
-                        port,
-                        username,
-                        password,
-                    } => sqlx::postgres::PgConnectOptions::new()
-                        .host(host)
-                        .port(*port)
-                        .username(username)
-                        .password(password)
-                        .database("postgres"),
-                };
-
-                let pool = sqlx::Pool::connect_with(options).await?;
+                let pool = sqlx::Pool::connect_with(self.postgres_connect_options()?).await?;
                 DatabasePool::Postgres(pool)
             }
             DatabaseType::Mongodb => {
@@ -444,6 +471,220 @@ impl DatabaseHost {
         Ok(pool)
     }
 
+    fn postgres_connect_options(
+        &self,
+    ) -> Result<sqlx::postgres::PgConnectOptions, crate::database::DatabaseError> {
+        Ok(match &self.credentials {
+            DatabaseCredentials::ConnectionString { connection_string } => {
+                sqlx::postgres::PgConnectOptions::from_str(connection_string).map_err(|err| {
+                    anyhow::anyhow!("failed to parse Postgres connection string: {err}")
+                })?
+            }
+            DatabaseCredentials::Details {
+                host,
+                port,
+                username,
+                password,
+            } => sqlx::postgres::PgConnectOptions::new()
+                .host(host)
+                .port(*port)
+                .username(username)
+                .password(password)
+                .database("postgres"),
+        })
+    }
+
+    /// Opens a dedicated admin connection to a specific database on a
+    /// Postgres host, pooled connections are always bound to `postgres`.
+    pub async fn connect_postgres_database(
+        &mut self,
+        database: &crate::database::Database,
+        name: &str,
+    ) -> Result<sqlx::PgConnection, crate::database::DatabaseError> {
+        if self.r#type != DatabaseType::Postgres {
+            return Err(anyhow::anyhow!("database host is not a Postgres host").into());
+        }
+
+        self.credentials.decrypt(database).await?;
+
+        let options = self.postgres_connect_options()?.database(name);
+
+        Ok(sqlx::ConnectOptions::connect(&options).await?)
+    }
+
+    /// Hosts that have never been checked are assumed to be healthy.
+    #[inline]
+    pub fn is_healthy(&self) -> bool {
+        self.health.as_ref().is_none_or(|health| health.healthy)
+    }
+
+    pub async fn has_capacity(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<bool, crate::database::DatabaseError> {
+        if let Some(max_size) = self.max_size
+            && self
+                .health
+                .as_ref()
+                .and_then(|health| health.disk_usage)
+                .is_some_and(|disk_usage| disk_usage >= max_size)
+        {
+            return Ok(false);
+        }
+
+        if let Some(max_databases) = self.max_databases {
+            let databases = super::server_database::ServerDatabase::count_by_database_host_uuid(
+                database, self.uuid,
+            )
+            .await?;
+
+            return Ok(databases < max_databases as i64);
+        }
+
+        Ok(true)
+    }
+
+    async fn probe(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<(std::time::Duration, compact_str::CompactString, i64), anyhow::Error> {
+        match self.get_connection(database).await? {
+            DatabasePool::Mysql(pool) => {
+                let start = std::time::Instant::now();
+                let version: String = sqlx::query_scalar("SELECT VERSION()")
+                    .fetch_one(&pool)
+                    .await?;
+                let latency = start.elapsed();
+
+                let disk_usage: Option<i64> = sqlx::query_scalar(
+                    "SELECT CAST(SUM(data_length + index_length) AS SIGNED) FROM information_schema.tables",
+                )
+                .fetch_one(&pool)
+                .await?;
+
+                Ok((latency, version.into(), disk_usage.unwrap_or(0)))
+            }
+            DatabasePool::Postgres(pool) => {
+                let start = std::time::Instant::now();
+                let version: String = sqlx::query_scalar("SHOW server_version")
+                    .fetch_one(&pool)
+                    .await?;
+                let latency = start.elapsed();
+
+                let disk_usage: Option<i64> = sqlx::query_scalar(
+                    "SELECT CAST(SUM(pg_database_size(datname)) AS BIGINT) FROM pg_database WHERE datallowconn",
+                )
+                .fetch_one(&pool)
+                .await?;
+
+                Ok((latency, version.into(), disk_usage.unwrap_or(0)))
+            }
+            DatabasePool::Mongodb(client) => {
+                let admin = client.database("admin");
+
+                let start = std::time::Instant::now();
+                let build_info = admin
+                    .run_command(mongodb::bson::doc! { "buildInfo": 1 })
+                    .await?;
+                let latency = start.elapsed();
+
+                let databases = admin
+                    .run_command(mongodb::bson::doc! { "listDatabases": 1, "nameOnly": false })
+                    .await?;
+                let disk_usage = match databases.get("totalSize") {
+                    Some(mongodb::bson::Bson::Int32(i)) => *i as i64,
+                    Some(mongodb::bson::Bson::Int64(i)) => *i,
+                    Some(mongodb::bson::Bson::Double(f)) => *f as i64,
+                    _ => 0,
+                };
+
+                Ok((
+                    latency,
+                    build_info.get_str("version").unwrap_or("unknown").into(),
+                    disk_usage,
+                ))
+            }
+        }
+    }
+
+    pub async fn check_health(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> DatabaseHostHealth {
+        let result = tokio::time::timeout(HEALTH_CHECK_TIMEOUT, self.probe(database)).await;
+
+        match result {
+            Ok(Ok((latency, version, disk_usage))) => DatabaseHostHealth {
+                healthy: true,
+                latency_ms: Some(latency.as_millis() as u64),
+                version: Some(version),
+                disk_usage: Some(disk_usage),
+                error: None,
+                checked: chrono::Utc::now(),
+            },
+            Ok(Err(err)) => DatabaseHostHealth {
+                healthy: false,
+                latency_ms: None,
+                version: None,
+                disk_usage: None,
+                error: Some(err.to_string()),
+                checked: chrono::Utc::now(),
+            },
+            Err(_) => DatabaseHostHealth {
+                healthy: false,
+                latency_ms: None,
+                version: None,
+                disk_usage: None,
+                error: Some(format!(
+                    "health check timed out after {}s",
+                    HEALTH_CHECK_TIMEOUT.as_secs()
+                )),
+                checked: chrono::Utc::now(),
+            },
+        }
+    }
+
+    pub async fn record_health(
+        &mut self,
+        database: &crate::database::Database,
+        health: DatabaseHostHealth,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            UPDATE database_hosts
+            SET health = $1
+            WHERE database_hosts.uuid = $2
+            "#,
+        )
+        .bind(serde_json::to_value(&health)?)
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?;
+
+        self.health = Some(health);
+
+        Ok(())
+    }
+
+    pub async fn all(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM database_hosts
+            ORDER BY database_hosts.created
+            "#,
+            Self::columns_sql(None)
+        )))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
     pub async fn all_with_pagination(
         database: &crate::database::Database,
         page: i64,
@@ -522,6 +763,18 @@ impl DatabaseHost {
             SELECT {}
             FROM database_hosts
             WHERE database_hosts.deployment_enabled
+            AND (database_hosts.health IS NULL OR (database_hosts.health->>'healthy')::boolean)
+            AND (
+                database_hosts.max_databases IS NULL
+                OR (
+                    SELECT COUNT(*) FROM server_databases
+                    WHERE server_databases.database_host_uuid = database_hosts.uuid
+                ) < database_hosts.max_databases
+            )
+            AND (
+                database_hosts.max_size IS NULL
+                OR COALESCE((database_hosts.health->>'disk_usage')::bigint, 0) < database_hosts.max_size
+            )
             AND (
                 EXISTS (
                     SELECT 1 FROM node_database_hosts
@@ -570,6 +823,9 @@ impl IntoAdminApiObject for DatabaseHost {
                 public_host: self.public_host,
                 public_port: self.public_port,
                 credentials: self.credentials,
+                max_databases: self.max_databases,
+                max_size: self.max_size,
+                health: self.health,
                 created: self.created.and_utc(),
             },
             api_object,
@@ -676,6 +932,13 @@ pub struct CreateDatabaseHostOptions {
 
     #[garde(dive)]
     pub credentials: DatabaseCredentials,
+
+    #[garde(range(min = 1))]
+    #[schema(minimum = 1)]
+    pub max_databases: Option<i32>,
+    #[garde(range(min = 1))]
+    #[schema(minimum = 1)]
+    pub max_size: Option<i64>,
 }
 
 #[async_trait::async_trait]
@@ -710,7 +973,9 @@ impl CreatableModel for DatabaseHost {
             .set("maintenance_enabled", options.maintenance_enabled)
             .set("public_host", &options.public_host)
             .set("public_port", options.public_port.map(|p| p as i32))
-            .set("credentials", serde_json::to_value(&options.credentials)?);
+            .set("credentials", serde_json::to_value(&options.credentials)?)
+            .set("max_databases", options.max_databases)
+            .set("max_size", options.max_size);
 
         let row = query_builder
             .returning(&Self::columns_sql(None))
@@ -754,6 +1019,23 @@ pub struct UpdateDatabaseHostOptions {
 
     #[garde(dive)]
     pub credentials: Option<DatabaseCredentials>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    #[garde(range(min = 1))]
+    #[schema(minimum = 1)]
+    pub max_databases: Option<Option<i32>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    #[garde(range(min = 1))]
+    #[schema(minimum = 1)]
+    pub max_size: Option<Option<i64>>,
 }
 
 #[async_trait::async_trait]
@@ -804,6 +1086,8 @@ impl UpdatableModel for DatabaseHost {
                     .map(serde_json::to_value)
                     .transpose()?,
             )
+            .set("max_databases", options.max_databases)
+            .set("max_size", options.max_size)
             .where_eq("uuid", self.uuid);
 
         query_builder.execute(&mut **transaction).await?;
@@ -826,6 +1110,12 @@ impl UpdatableModel for DatabaseHost {
         if let Some(credentials) = options.credentials {
             self.credentials = credentials;
         }
+        if let Some(max_databases) = options.max_databases {
+            self.max_databases = max_databases;
+        }
+        if let Some(max_size) = options.max_size {
+            self.max_size = max_size;
+        }
 
         self.run_after_update_handlers(state, transaction).await?;
 
@@ -888,6 +1178,10 @@ pub struct AdminApiDatabaseHost {
 
     pub credentials: DatabaseCredentials,
 
+    pub max_databases: Option<i32>,
+    pub max_size: Option<i64>,
+    pub health: Option<DatabaseHostHealth>,
+
     pub created: chrono::DateTime<chrono::Utc>,
 }
 
@@ -14,7 +14,7 @@ use std::{
 };
 use utoipa::ToSchema;
 
-fn validate_git_repository(
+pub(crate) fn validate_git_repository(
     git_repository: &compact_str::CompactString,
     _context: &(),
 ) -> Result<(), garde::Error> {
@@ -68,7 +68,10 @@ pub enum EggRepositoryCredentials {
 }
 
 impl EggRepositoryCredentials {
-    fn validate_scheme_for_url(&self, git_repository: &str) -> Result<(), anyhow::Error> {
+    pub(crate) fn validate_scheme_for_url(
+        &self,
+        git_repository: &str,
+    ) -> Result<(), anyhow::Error> {
         let scheme = gix::url::parse(git_repository)
             .map(|url| url.scheme)
             .unwrap_or(gix::url::Scheme::Https);
@@ -96,7 +99,7 @@ impl EggRepositoryCredentials {
         Ok(())
     }
 
-    fn validate_key_material(&self) -> Result<(), anyhow::Error> {
+    pub(crate) fn validate_key_material(&self) -> Result<(), anyhow::Error> {
         if let EggRepositoryCredentials::PrivateKey {
             private_key,
             passphrase,
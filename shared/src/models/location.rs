@@ -4,13 +4,23 @@ use crate::{
 };
 use garde::Validate;
 use serde::{Deserialize, Serialize};
-use sqlx::{Row, postgres::PgRow};
+use sqlx::{Row, Type, postgres::PgRow};
 use std::{
     collections::BTreeMap,
     sync::{Arc, LazyLock},
 };
 use utoipa::ToSchema;
 
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "file_scan_policy", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FileScanPolicy {
+    #[default]
+    Off,
+    Warn,
+    Block,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Location {
     pub uuid: uuid::Uuid,
@@ -20,6 +30,7 @@ pub struct Location {
     pub description: Option<compact_str::CompactString>,
 
     pub flag: Option<compact_str::CompactString>,
+    pub file_scan_policy: FileScanPolicy,
 
     pub created: chrono::NaiveDateTime,
 
@@ -65,6 +76,10 @@ impl BaseModel for Location {
                 "locations.flag",
                 compact_str::format_compact!("{prefix}flag"),
             ),
+            (
+                "locations.file_scan_policy",
+                compact_str::format_compact!("{prefix}file_scan_policy"),
+            ),
             (
                 "locations.created",
                 compact_str::format_compact!("{prefix}created"),
@@ -87,6 +102,8 @@ impl BaseModel for Location {
             description: row
                 .try_get(compact_str::format_compact!("{prefix}description").as_str())?,
             flag: row.try_get(compact_str::format_compact!("{prefix}flag").as_str())?,
+            file_scan_policy: row
+                .try_get(compact_str::format_compact!("{prefix}file_scan_policy").as_str())?,
             created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
             extension_data: Self::map_extensions(prefix, row)?,
         })
@@ -204,6 +221,7 @@ impl IntoAdminApiObject for Location {
                 name: self.name,
                 description: self.description,
                 flag: self.flag,
+                file_scan_policy: self.file_scan_policy,
                 created: self.created.and_utc(),
             },
             api_object,
@@ -268,6 +286,9 @@ pub struct CreateLocationOptions {
     #[garde(length(chars, min = 2, max = 2))]
     #[schema(min_length = 2, max_length = 2)]
     pub flag: Option<compact_str::CompactString>,
+    #[garde(skip)]
+    #[serde(default)]
+    pub file_scan_policy: FileScanPolicy,
 }
 
 #[async_trait::async_trait]
@@ -311,7 +332,8 @@ impl CreatableModel for Location {
             )
             .set("name", &options.name)
             .set("description", &options.description)
-            .set("flag", &options.flag);
+            .set("flag", &options.flag)
+            .set("file_scan_policy", options.file_scan_policy);
 
         let row = query_builder
             .returning(&Self::columns_sql(None))
@@ -353,6 +375,8 @@ pub struct UpdateLocationOptions {
         with = "::serde_with::rust::double_option"
     )]
     pub flag: Option<Option<compact_str::CompactString>>,
+    #[garde(skip)]
+    pub file_scan_policy: Option<FileScanPolicy>,
 }
 
 #[async_trait::async_trait]
@@ -410,6 +434,7 @@ impl UpdatableModel for Location {
             .set("name", options.name.as_ref())
             .set("description", options.description.as_ref())
             .set("flag", options.flag.as_ref())
+            .set("file_scan_policy", options.file_scan_policy)
             .where_eq("uuid", self.uuid);
 
         query_builder.execute(&mut **transaction).await?;
@@ -426,6 +451,9 @@ impl UpdatableModel for Location {
         if let Some(flag) = options.flag {
             self.flag = flag;
         }
+        if let Some(file_scan_policy) = options.file_scan_policy {
+            self.file_scan_policy = file_scan_policy;
+        }
 
         self.run_after_update_handlers(state, transaction).await?;
 
@@ -507,7 +535,8 @@ impl DuplicableModel for Location {
             )
             .set("name", &options.name)
             .set("description", &self.description)
-            .set("flag", &self.flag);
+            .set("flag", &self.flag)
+            .set("file_scan_policy", self.file_scan_policy);
 
         let row = query_builder
             .returning(&Self::columns_sql(None))
@@ -557,6 +586,7 @@ pub struct AdminApiLocation {
     pub description: Option<compact_str::CompactString>,
 
     pub flag: Option<compact_str::CompactString>,
+    pub file_scan_policy: FileScanPolicy,
 
     pub created: chrono::DateTime<chrono::Utc>,
 }
@@ -23,6 +23,8 @@ pub mod announcement;
 pub mod backup_configuration;
 pub mod database_agent_host;
 pub mod database_agent_template;
+pub mod database_agent_template_revision;
+pub mod database_agent_template_rollout;
 pub mod database_host;
 pub mod egg_configuration;
 pub mod egg_repository;
@@ -47,14 +49,32 @@ pub mod server;
 pub mod server_activity;
 pub mod server_allocation;
 pub mod server_backup;
+pub mod server_backup_database;
 pub mod server_backup_group;
+pub mod server_backup_replica;
+pub mod server_console_log_archive;
+pub mod server_console_log_chunk;
 pub mod server_database;
+pub mod server_database_access_token;
 pub mod server_database_instance;
+pub mod server_database_instance_database_quota;
+pub mod server_database_instance_snapshot;
+pub mod server_database_instance_snapshot_policy;
+pub mod server_database_migration;
+pub mod server_database_query_history;
+pub mod server_database_saved_query;
+pub mod server_deployment;
+pub mod server_deployment_source;
+pub mod server_file_event;
+pub mod server_file_share;
 pub mod server_mount;
+pub mod server_quarantined_file;
 pub mod server_schedule;
 pub mod server_schedule_step;
 pub mod server_subuser;
+pub mod server_trashed_file;
 pub mod server_variable;
+pub mod ssh_certificate_authority;
 pub mod system_backup_policy;
 pub mod system_backup_policy_location;
 pub mod system_backup_policy_node;
@@ -69,6 +89,7 @@ pub mod user_recovery_code;
 pub mod user_security_key;
 pub mod user_server_group;
 pub mod user_session;
+pub mod user_sftp_password;
 pub mod user_ssh_key;
 
 #[derive(ToSchema, Validate, Deserialize, Serialize)]
@@ -73,6 +73,7 @@ pub struct Node {
 
     pub deployment_enabled: bool,
     pub maintenance_enabled: bool,
+    pub file_scan_policy: Option<super::location::FileScanPolicy>,
 
     pub public_url: Option<reqwest::Url>,
     pub url: reqwest::Url,
@@ -127,6 +128,10 @@ impl BaseModel for Node {
                 "nodes.maintenance_enabled",
                 compact_str::format_compact!("{prefix}maintenance_enabled"),
             ),
+            (
+                "nodes.file_scan_policy",
+                compact_str::format_compact!("{prefix}node_file_scan_policy"),
+            ),
             (
                 "nodes.public_url",
                 compact_str::format_compact!("{prefix}public_url"),
@@ -180,6 +185,8 @@ impl BaseModel for Node {
                 .try_get(compact_str::format_compact!("{prefix}deployment_enabled").as_str())?,
             maintenance_enabled: row
                 .try_get(compact_str::format_compact!("{prefix}maintenance_enabled").as_str())?,
+            file_scan_policy: row
+                .try_get(compact_str::format_compact!("{prefix}node_file_scan_policy").as_str())?,
             public_url: row
                 .try_get::<Option<String>, _>(
                     compact_str::format_compact!("{prefix}public_url").as_str(),
@@ -684,6 +691,13 @@ impl Node {
         self.uuid == Self::AIO_NODE_UUID
     }
 
+    /// The node overrides the policy of its location when set.
+    #[inline]
+    pub fn effective_file_scan_policy(&self) -> super::location::FileScanPolicy {
+        self.file_scan_policy
+            .unwrap_or(self.location.file_scan_policy)
+    }
+
     #[inline]
     pub fn url(&self, path: &str) -> reqwest::Url {
         let mut url = self.url.clone();
@@ -808,6 +822,94 @@ impl Node {
     ) -> Result<String, anyhow::Error> {
         Ok(jwt.create_custom(database.blocking_decrypt(&self.token)?.as_bytes(), payload)?)
     }
+
+    /// Creates a url downloading `files` inside `root` of a server straight from wings. A single
+    /// file is served as-is, directories and multiple files are archived in `archive_format`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn file_download_url<F: AsRef<str> + Serialize>(
+        &self,
+        state: &crate::State,
+        server_uuid: uuid::Uuid,
+        user_uuid: uuid::Uuid,
+        root: &str,
+        files: &[F],
+        directory: bool,
+        archive_format: wings_api::StreamableArchiveFormat,
+    ) -> Result<reqwest::Url, anyhow::Error> {
+        #[derive(Serialize)]
+        struct FileDownloadJwt {
+            #[serde(flatten)]
+            base: crate::jwt::BasePayload,
+
+            file_path: std::path::PathBuf,
+            server_uuid: uuid::Uuid,
+            unique_id: uuid::Uuid,
+        }
+
+        #[derive(Serialize)]
+        struct FilesDownloadJwt<'a, F> {
+            #[serde(flatten)]
+            base: crate::jwt::BasePayload,
+
+            file_path: &'a str,
+            file_paths: &'a [F],
+            server_uuid: uuid::Uuid,
+            unique_id: uuid::Uuid,
+        }
+
+        let base = crate::jwt::BasePayload {
+            scope: "file-download".into(),
+            issuer: "panel".into(),
+            subject: None,
+            audience: Vec::new(),
+            expiration_time: Some(chrono::Utc::now().timestamp() + 900),
+            not_before: None,
+            issued_at: Some(chrono::Utc::now().timestamp()),
+            jwt_id: user_uuid.to_compact_string(),
+        };
+
+        let (token, path) = match files {
+            [file] => (
+                self.create_jwt(
+                    &state.database,
+                    &state.jwt,
+                    &FileDownloadJwt {
+                        base,
+                        file_path: std::path::Path::new(root).join(file.as_ref()),
+                        server_uuid,
+                        unique_id: uuid::Uuid::new_v4(),
+                    },
+                )?,
+                if directory {
+                    "/download/directory"
+                } else {
+                    "/download/file"
+                },
+            ),
+            files => (
+                self.create_jwt(
+                    &state.database,
+                    &state.jwt,
+                    &FilesDownloadJwt {
+                        base,
+                        file_path: root,
+                        file_paths: files,
+                        server_uuid,
+                        unique_id: uuid::Uuid::new_v4(),
+                    },
+                )?,
+                "/download/files",
+            ),
+        };
+
+        let mut url = self.public_url(state, path).await?;
+        url.set_query(Some(&format!(
+            "token={}&archive_format={archive_format}",
+            urlencoding::encode(&token)
+        )));
+
+        Ok(url)
+    }
 }
 
 #[async_trait::async_trait]
@@ -855,6 +957,7 @@ impl IntoAdminApiObject for Node {
                 description: self.description,
                 deployment_enabled: self.deployment_enabled,
                 maintenance_enabled: self.maintenance_enabled,
+                file_scan_policy: self.file_scan_policy,
                 public_url,
                 url: self.url.to_string(),
                 sftp_host: self.sftp_host,
@@ -932,6 +1035,9 @@ pub struct CreateNodeOptions {
     pub deployment_enabled: bool,
     #[garde(skip)]
     pub maintenance_enabled: bool,
+    #[garde(skip)]
+    #[serde(default)]
+    pub file_scan_policy: Option<super::location::FileScanPolicy>,
     #[garde(length(chars, min = 3, max = 255), url)]
     #[schema(min_length = 3, max_length = 255, format = "uri")]
     pub public_url: Option<compact_str::CompactString>,
@@ -998,6 +1104,7 @@ impl CreatableModel for Node {
             .set("description", &options.description)
             .set("deployment_enabled", options.deployment_enabled)
             .set("maintenance_enabled", options.maintenance_enabled)
+            .set("file_scan_policy", options.file_scan_policy)
             .set("public_url", &options.public_url)
             .set("url", &options.url)
             .set("sftp_host", &options.sftp_host)
@@ -1047,6 +1154,13 @@ pub struct UpdateNodeOptions {
     pub deployment_enabled: Option<bool>,
     #[garde(skip)]
     pub maintenance_enabled: Option<bool>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    #[garde(skip)]
+    pub file_scan_policy: Option<Option<super::location::FileScanPolicy>>,
     #[garde(length(chars, min = 3, max = 255), url)]
     #[schema(min_length = 3, max_length = 255, format = "uri")]
     #[serde(
@@ -1150,6 +1264,7 @@ impl UpdatableModel for Node {
             )
             .set("deployment_enabled", options.deployment_enabled)
             .set("maintenance_enabled", options.maintenance_enabled)
+            .set("file_scan_policy", options.file_scan_policy)
             .set(
                 "public_url",
                 options.public_url.as_ref().map(|u| u.as_ref()),
@@ -1181,6 +1296,9 @@ impl UpdatableModel for Node {
         if let Some(maintenance_enabled) = options.maintenance_enabled {
             self.maintenance_enabled = maintenance_enabled;
         }
+        if let Some(file_scan_policy) = options.file_scan_policy {
+            self.file_scan_policy = file_scan_policy;
+        }
         if let Some(public_url) = options.public_url {
             self.public_url = public_url
                 .try_map(|url| url.parse())
@@ -1291,6 +1409,7 @@ impl DuplicableModel for Node {
             .set("description", &self.description)
             .set("deployment_enabled", self.deployment_enabled)
             .set("maintenance_enabled", self.maintenance_enabled)
+            .set("file_scan_policy", self.file_scan_policy)
             .set("public_url", self.public_url.as_ref().map(|u| u.as_str()))
             .set("url", self.url.as_str())
             .set("sftp_host", &self.sftp_host)
@@ -1363,6 +1482,7 @@ pub struct AdminApiNode {
 
     pub deployment_enabled: bool,
     pub maintenance_enabled: bool,
+    pub file_scan_policy: Option<super::location::FileScanPolicy>,
 
     #[schema(format = "uri")]
     pub public_url: Option<String>,
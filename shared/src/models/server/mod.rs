@@ -159,6 +159,8 @@ pub struct Server {
     pub allocation_limit: i32,
     pub database_limit: i32,
     pub backup_limit: i32,
+    pub backup_storage_limit: Option<i64>,
+    pub database_size_limit: Option<i64>,
     pub schedule_limit: i32,
 
     pub subuser_permissions: Option<Arc<Vec<compact_str::CompactString>>>,
@@ -287,6 +289,14 @@ impl BaseModel for Server {
                 "servers.backup_limit",
                 compact_str::format_compact!("{prefix}backup_limit"),
             ),
+            (
+                "servers.backup_storage_limit",
+                compact_str::format_compact!("{prefix}backup_storage_limit"),
+            ),
+            (
+                "servers.database_size_limit",
+                compact_str::format_compact!("{prefix}database_size_limit"),
+            ),
             (
                 "servers.schedule_limit",
                 compact_str::format_compact!("{prefix}schedule_limit"),
@@ -383,6 +393,10 @@ impl BaseModel for Server {
                 .try_get(compact_str::format_compact!("{prefix}database_limit").as_str())?,
             backup_limit: row
                 .try_get(compact_str::format_compact!("{prefix}backup_limit").as_str())?,
+            backup_storage_limit: row
+                .try_get(compact_str::format_compact!("{prefix}backup_storage_limit").as_str())?,
+            database_size_limit: row
+                .try_get(compact_str::format_compact!("{prefix}database_size_limit").as_str())?,
             schedule_limit: row
                 .try_get(compact_str::format_compact!("{prefix}schedule_limit").as_str())?,
             subuser_permissions: row
@@ -536,6 +550,43 @@ impl Server {
             .await
     }
 
+    /// Get all servers out of `uuids` the user has access to, in the given order.
+    pub async fn by_user_uuids(
+        database: &crate::database::Database,
+        user: &super::user::User,
+        uuids: &[uuid::Uuid],
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, server_subusers.permissions, server_subusers.ignored_files
+            FROM servers
+            LEFT JOIN server_allocations ON server_allocations.uuid = servers.allocation_uuid
+            LEFT JOIN node_allocations ON node_allocations.uuid = server_allocations.allocation_uuid
+            JOIN users ON users.uuid = servers.owner_uuid
+            LEFT JOIN roles ON roles.uuid = users.role_uuid
+            JOIN nest_eggs ON nest_eggs.uuid = servers.egg_uuid
+            LEFT JOIN server_subusers ON server_subusers.server_uuid = servers.uuid AND server_subusers.user_uuid = $1
+            JOIN nests ON nests.uuid = nest_eggs.nest_uuid
+            WHERE servers.uuid = ANY($2) AND (servers.owner_uuid = $1 OR server_subusers.user_uuid = $1 OR $3)
+            ORDER BY array_position($2, servers.uuid)
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(user.uuid)
+        .bind(uuids)
+        .bind(
+            user.role.as_ref().map_or(user.admin, |r| {
+                r.admin_permissions.iter().any(|p| p == "servers.read")
+            }),
+        )
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
     pub async fn by_owner_uuid_with_pagination(
         database: &crate::database::Database,
         owner_uuid: uuid::Uuid,
@@ -1519,6 +1570,8 @@ impl Server {
                 allocations: self.allocation_limit,
                 databases: self.database_limit,
                 backups: self.backup_limit,
+                backup_storage: Some(self.backup_storage_limit),
+                database_size: Some(self.database_size_limit),
                 schedules: self.schedule_limit,
             },
             feature_limits,
@@ -1555,6 +1608,27 @@ impl Server {
         None
     }
 
+    /// Returns why the server cannot be acted on by users right now, if it cannot. Admins can
+    /// still act on suspended servers.
+    pub fn unavailable_reason(&self, admin: bool) -> Option<&'static str> {
+        if self.suspended {
+            if !admin {
+                return Some("server is suspended");
+            }
+        } else if self.destination_node.is_some() {
+            return Some("server is being transferred");
+        } else if let Some(status) = self.status {
+            return Some(match status {
+                ServerStatus::Installing => "server is currently installing",
+                ServerStatus::InstallFailed => "your server has failed its installation process",
+                ServerStatus::RestoringBackup => "server is restoring from a backup",
+                ServerStatus::BackupRestoreFailed => "your server has failed to restore a backup",
+            });
+        }
+
+        None
+    }
+
     pub fn is_ignored(&mut self, path: impl AsRef<std::path::Path>, is_dir: bool) -> bool {
         if let Some(ignored_files) = &self.subuser_ignored_files {
             if path.as_ref() == std::path::Path::new("/")
@@ -1800,6 +1874,8 @@ impl super::IntoAdminApiObject for Server {
                 allocations: self.allocation_limit,
                 databases: self.database_limit,
                 backups: self.backup_limit,
+                backup_storage: Some(self.backup_storage_limit),
+                database_size: Some(self.database_size_limit),
                 schedules: self.schedule_limit,
             },
             feature_limits,
@@ -1907,6 +1983,8 @@ impl super::IntoApiObject for Server {
                 allocations: self.allocation_limit,
                 databases: self.database_limit,
                 backups: self.backup_limit,
+                backup_storage: Some(self.backup_storage_limit),
+                database_size: Some(self.database_size_limit),
                 schedules: self.schedule_limit,
             },
             feature_limits,
@@ -2178,6 +2256,14 @@ impl CreatableModel for Server {
                 .set("allocation_limit", options.feature_limits.allocations)
                 .set("database_limit", options.feature_limits.databases)
                 .set("backup_limit", options.feature_limits.backups)
+                .set(
+                    "backup_storage_limit",
+                    options.feature_limits.backup_storage.flatten(),
+                )
+                .set(
+                    "database_size_limit",
+                    options.feature_limits.database_size.flatten(),
+                )
                 .set("schedule_limit", options.feature_limits.schedules);
 
             match query_builder
@@ -2499,6 +2585,8 @@ impl UpdatableModel for Server {
                 .set("allocation_limit", Some(feature_limits.allocations))
                 .set("database_limit", Some(feature_limits.databases))
                 .set("backup_limit", Some(feature_limits.backups))
+                .set("backup_storage_limit", feature_limits.backup_storage)
+                .set("database_size_limit", feature_limits.database_size)
                 .set("schedule_limit", Some(feature_limits.schedules));
         }
 
@@ -2563,6 +2651,12 @@ impl UpdatableModel for Server {
             self.allocation_limit = feature_limits.allocations;
             self.database_limit = feature_limits.databases;
             self.backup_limit = feature_limits.backups;
+            if let Some(backup_storage_limit) = feature_limits.backup_storage {
+                self.backup_storage_limit = backup_storage_limit;
+            }
+            if let Some(database_size_limit) = feature_limits.database_size {
+                self.database_size_limit = database_size_limit;
+            }
             self.schedule_limit = feature_limits.schedules;
         }
 
@@ -2738,6 +2832,26 @@ pub struct ApiServerFeatureLimits {
     #[garde(range(min = 0))]
     #[schema(minimum = 0)]
     pub backups: i32,
+    /// Maximum combined size of the server's backups in bytes, unlimited when null. Left as
+    /// it is on updates when omitted.
+    #[garde(inner(inner(range(min = 0))))]
+    #[schema(value_type = Option<i64>, minimum = 0)]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    pub backup_storage: Option<Option<i64>>,
+    /// Maximum size of each of the server's databases in bytes, unlimited when null. Left as
+    /// it is on updates when omitted.
+    #[garde(inner(inner(range(min = 0))))]
+    #[schema(value_type = Option<i64>, minimum = 0)]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    pub database_size: Option<Option<i64>>,
     #[garde(range(min = 0))]
     #[schema(minimum = 0)]
     pub schedules: i32,
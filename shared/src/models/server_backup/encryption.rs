@@ -0,0 +1,518 @@
+//! Client-side encryption for S3 backup archives.
+//!
+//! An encrypted archive is a sequence of segments. Every segment starts with a header made of
+//! the 8 byte magic `CLGPBAE2`, a random 7 byte nonce prefix, the segment index (u32, big
+//! endian), the last segment flag (u8) and the plaintext length of the segment (u64, big
+//! endian, `u64::MAX` for a segment that runs until the end of the archive). The segment
+//! itself is split into 64 KiB chunks, each sealed with AES-256-GCM under the nonce
+//! `prefix || chunk index (u32, big endian) || last chunk flag (u8)` and the segment header
+//! as associated data, so dropped, reordered or truncated chunks and segments fail to
+//! decrypt, and an archive only decrypts completely once its last segment was seen.
+//!
+//! Archives the panel streams itself are a single open ended segment, archives uploaded by
+//! wings get one segment per S3 part, encrypted by the panel before the part is forwarded to
+//! the bucket. The part shorter than the part size closes the archive, an archive ending on a
+//! part boundary gets an empty last segment appended when the upload is completed.
+//! Every server has its own data key, stored wrapped with the panel encryption key and
+//! exportable by the server owner.
+
+use crate::jwt::BasePayload;
+use base64::Engine;
+use futures_util::{Stream, StreamExt};
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, Nonce, UnboundKey};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+
+pub const MAGIC: &[u8; 8] = b"CLGPBAE2";
+pub const CHUNK_SIZE: usize = 64 * 1024;
+pub const KEY_LENGTH: usize = 32;
+
+const NONCE_PREFIX_LENGTH: usize = 7;
+const HEADER_LENGTH: usize = MAGIC.len() + NONCE_PREFIX_LENGTH + 4 + 1 + 8;
+const TAG_LENGTH: usize = 16;
+const RECORD_SIZE: usize = CHUNK_SIZE + TAG_LENGTH;
+const OPEN_ENDED: u64 = u64::MAX;
+
+/// Grants streaming the decrypted archive of a backup through the panel.
+#[derive(Serialize, Deserialize)]
+pub struct BackupDecryptJwt {
+    #[serde(flatten)]
+    pub base: BasePayload,
+
+    pub backup_uuid: uuid::Uuid,
+}
+
+/// Grants wings uploading a single part of an encrypted backup through the panel.
+#[derive(Serialize, Deserialize)]
+pub struct BackupUploadPartJwt {
+    #[serde(flatten)]
+    pub base: BasePayload,
+
+    pub backup_uuid: uuid::Uuid,
+    pub upload_id: compact_str::CompactString,
+    pub part_number: u16,
+}
+
+pub struct BackupEncryptionKey([u8; KEY_LENGTH]);
+
+impl BackupEncryptionKey {
+    #[inline]
+    pub fn generate() -> Self {
+        Self(rand::random())
+    }
+
+    pub fn from_slice(key: &[u8]) -> Result<Self, anyhow::Error> {
+        Ok(Self(key.try_into().map_err(|_| {
+            anyhow::anyhow!("backup encryption key must be {KEY_LENGTH} bytes long")
+        })?))
+    }
+
+    pub fn from_base64(key: &str) -> Result<Self, anyhow::Error> {
+        Self::from_slice(&base64::engine::general_purpose::STANDARD.decode(key.trim())?)
+    }
+
+    #[inline]
+    pub fn to_base64(&self) -> compact_str::CompactString {
+        base64::engine::general_purpose::STANDARD
+            .encode(self.0)
+            .into()
+    }
+
+    /// Loads the data key of a server, generating and storing it the first time it is needed.
+    pub async fn for_server(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+    ) -> Result<Self, anyhow::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT servers.backup_encryption_key
+            FROM servers
+            WHERE servers.uuid = $1
+            "#,
+        )
+        .bind(server_uuid)
+        .fetch_one(database.read())
+        .await?;
+
+        let wrapped_key: compact_str::CompactString =
+            match row.try_get::<Option<compact_str::CompactString>, _>("backup_encryption_key")? {
+                Some(wrapped_key) => wrapped_key,
+                None => {
+                    let wrapped_key = database.encrypt_base64(Self::generate().0).await?;
+
+                    // another request may have raced us here, whoever wrote first wins
+                    let row = sqlx::query(
+                        r#"
+                        UPDATE servers
+                        SET backup_encryption_key = COALESCE(servers.backup_encryption_key, $2)
+                        WHERE servers.uuid = $1
+                        RETURNING servers.backup_encryption_key
+                        "#,
+                    )
+                    .bind(server_uuid)
+                    .bind(&wrapped_key)
+                    .fetch_one(database.write())
+                    .await?;
+
+                    row.try_get("backup_encryption_key")?
+                }
+            };
+
+        Self::from_slice(&database.decrypt_base64_raw(&wrapped_key).await?)
+    }
+
+    #[inline]
+    fn cipher(&self) -> LessSafeKey {
+        LessSafeKey::new(
+            UnboundKey::new(&AES_256_GCM, &self.0).expect("key has the AES-256 key length"),
+        )
+    }
+}
+
+#[inline]
+fn chunk_nonce(prefix: &[u8; NONCE_PREFIX_LENGTH], index: u32, last: bool) -> Nonce {
+    let mut nonce = [0; 12];
+    nonce[..NONCE_PREFIX_LENGTH].copy_from_slice(prefix);
+    nonce[NONCE_PREFIX_LENGTH..11].copy_from_slice(&index.to_be_bytes());
+    nonce[11] = last as u8;
+
+    Nonce::assume_unique_for_key(nonce)
+}
+
+struct SegmentHeader {
+    bytes: [u8; HEADER_LENGTH],
+}
+
+impl SegmentHeader {
+    fn new(index: u32, last: bool, length: u64) -> Self {
+        let nonce_prefix: [u8; NONCE_PREFIX_LENGTH] = rand::random();
+
+        let mut bytes = [0; HEADER_LENGTH];
+        bytes[..MAGIC.len()].copy_from_slice(MAGIC);
+        bytes[MAGIC.len()..MAGIC.len() + NONCE_PREFIX_LENGTH].copy_from_slice(&nonce_prefix);
+        bytes[MAGIC.len() + NONCE_PREFIX_LENGTH..HEADER_LENGTH - 9]
+            .copy_from_slice(&index.to_be_bytes());
+        bytes[HEADER_LENGTH - 9] = last as u8;
+        bytes[HEADER_LENGTH - 8..].copy_from_slice(&length.to_be_bytes());
+
+        Self { bytes }
+    }
+
+    fn parse(data: &[u8]) -> Result<Self, anyhow::Error> {
+        if &data[..MAGIC.len()] != MAGIC {
+            return Err(anyhow::anyhow!("file is not an encrypted backup archive"));
+        }
+
+        Ok(Self {
+            bytes: data[..HEADER_LENGTH].try_into()?,
+        })
+    }
+
+    #[inline]
+    fn nonce_prefix(&self) -> &[u8; NONCE_PREFIX_LENGTH] {
+        self.bytes[MAGIC.len()..MAGIC.len() + NONCE_PREFIX_LENGTH]
+            .try_into()
+            .expect("header has the nonce prefix length")
+    }
+
+    #[inline]
+    fn index(&self) -> u32 {
+        u32::from_be_bytes(
+            self.bytes[MAGIC.len() + NONCE_PREFIX_LENGTH..HEADER_LENGTH - 9]
+                .try_into()
+                .expect("header has the index length"),
+        )
+    }
+
+    #[inline]
+    fn last(&self) -> bool {
+        self.bytes[HEADER_LENGTH - 9] != 0
+    }
+
+    #[inline]
+    fn length(&self) -> u64 {
+        u64::from_be_bytes(
+            self.bytes[HEADER_LENGTH - 8..]
+                .try_into()
+                .expect("header has the length length"),
+        )
+    }
+}
+
+struct SegmentSealer {
+    cipher: LessSafeKey,
+    header: SegmentHeader,
+    index: u32,
+}
+
+impl SegmentSealer {
+    fn seal(
+        &mut self,
+        chunk: &[u8],
+        last: bool,
+        output: &mut Vec<u8>,
+    ) -> Result<(), anyhow::Error> {
+        let mut record = Vec::with_capacity(chunk.len() + TAG_LENGTH);
+        record.extend_from_slice(chunk);
+
+        self.cipher
+            .seal_in_place_append_tag(
+                chunk_nonce(self.header.nonce_prefix(), self.index, last),
+                Aad::from(&self.header.bytes),
+                &mut record,
+            )
+            .map_err(|_| anyhow::anyhow!("failed to encrypt backup chunk"))?;
+        self.index = self
+            .index
+            .checked_add(1)
+            .ok_or_else(|| anyhow::anyhow!("backup archive is too large to encrypt"))?;
+
+        output.extend_from_slice(&record);
+
+        Ok(())
+    }
+}
+
+/// Encrypts one complete S3 part of an archive uploaded by wings as its own segment.
+/// `part_number` is the 1-based S3 part number, `last` marks the part closing the archive.
+pub fn encrypt_part(
+    key: &BackupEncryptionKey,
+    part_number: u16,
+    last: bool,
+    data: &[u8],
+) -> Result<Vec<u8>, anyhow::Error> {
+    let mut sealer = SegmentSealer {
+        cipher: key.cipher(),
+        header: SegmentHeader::new(
+            part_number.saturating_sub(1) as u32,
+            last,
+            data.len() as u64,
+        ),
+        index: 0,
+    };
+
+    let mut output = Vec::with_capacity(
+        HEADER_LENGTH + data.len() + data.len().div_ceil(CHUNK_SIZE).max(1) * TAG_LENGTH,
+    );
+    output.extend_from_slice(&sealer.header.bytes);
+
+    let mut chunks = data.chunks(CHUNK_SIZE).peekable();
+    if chunks.peek().is_none() {
+        sealer.seal(&[], true, &mut output)?;
+    }
+    while let Some(chunk) = chunks.next() {
+        sealer.seal(chunk, chunks.peek().is_none(), &mut output)?;
+    }
+
+    Ok(output)
+}
+
+/// Size of a part of `length` plaintext bytes once [`encrypt_part`] encrypted it.
+#[inline]
+pub fn encrypted_part_size(length: u64) -> u64 {
+    HEADER_LENGTH as u64 + length + length.div_ceil(CHUNK_SIZE as u64).max(1) * TAG_LENGTH as u64
+}
+
+/// An incremental archive transform, fed with arbitrarily sized pieces of input.
+pub trait ArchiveCipher: Send {
+    /// Consumes more input, returning whatever output is ready.
+    fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, anyhow::Error>;
+
+    /// Flushes the remaining input once the input has ended.
+    fn finish(&mut self) -> Result<Vec<u8>, anyhow::Error>;
+}
+
+/// Encrypts an archive of unknown length as a single open ended segment.
+pub struct BackupEncryptor {
+    sealer: SegmentSealer,
+    buffer: Vec<u8>,
+    header_written: bool,
+}
+
+impl BackupEncryptor {
+    pub fn new(key: &BackupEncryptionKey) -> Self {
+        Self {
+            sealer: SegmentSealer {
+                cipher: key.cipher(),
+                header: SegmentHeader::new(0, true, OPEN_ENDED),
+                index: 0,
+            },
+            buffer: Vec::with_capacity(CHUNK_SIZE * 2),
+            header_written: false,
+        }
+    }
+
+    fn write_header(&mut self, output: &mut Vec<u8>) {
+        if !self.header_written {
+            output.extend_from_slice(&self.sealer.header.bytes);
+            self.header_written = true;
+        }
+    }
+}
+
+impl ArchiveCipher for BackupEncryptor {
+    fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        self.buffer.extend_from_slice(data);
+
+        let mut output = Vec::new();
+        self.write_header(&mut output);
+
+        // a full chunk is only sealed once more data follows, the final chunk is marked as such
+        while self.buffer.len() > CHUNK_SIZE {
+            let chunk: Vec<u8> = self.buffer.drain(..CHUNK_SIZE).collect();
+            self.sealer.seal(&chunk, false, &mut output)?;
+        }
+
+        Ok(output)
+    }
+
+    fn finish(&mut self) -> Result<Vec<u8>, anyhow::Error> {
+        let mut output = Vec::new();
+        self.write_header(&mut output);
+
+        let chunk = std::mem::take(&mut self.buffer);
+        self.sealer.seal(&chunk, true, &mut output)?;
+
+        Ok(output)
+    }
+}
+
+struct OpenSegment {
+    header: SegmentHeader,
+    index: u32,
+    /// Plaintext bytes still expected, `None` for an open ended segment.
+    remaining: Option<u64>,
+}
+
+pub struct BackupDecryptor {
+    cipher: LessSafeKey,
+    segment: Option<OpenSegment>,
+    next_segment: u32,
+    /// Whether the segment flagged as the last one of the archive was decrypted.
+    finished: bool,
+    buffer: Vec<u8>,
+}
+
+impl BackupDecryptor {
+    pub fn new(key: &BackupEncryptionKey) -> Self {
+        Self {
+            cipher: key.cipher(),
+            segment: None,
+            next_segment: 0,
+            finished: false,
+            buffer: Vec::with_capacity(RECORD_SIZE * 2),
+        }
+    }
+
+    fn open(
+        &mut self,
+        length: usize,
+        last: bool,
+        output: &mut Vec<u8>,
+    ) -> Result<(), anyhow::Error> {
+        let Some(segment) = &mut self.segment else {
+            return Err(anyhow::anyhow!("backup archive header is missing"));
+        };
+
+        let mut record: Vec<u8> = self.buffer.drain(..length).collect();
+        let plaintext_length = self
+            .cipher
+            .open_in_place(
+                chunk_nonce(segment.header.nonce_prefix(), segment.index, last),
+                Aad::from(&segment.header.bytes),
+                &mut record,
+            )
+            .map_err(|_| {
+                anyhow::anyhow!("failed to decrypt backup chunk, wrong key or corrupted archive")
+            })?
+            .len();
+        segment.index = segment.index.wrapping_add(1);
+
+        output.extend_from_slice(&record[..plaintext_length]);
+
+        if last {
+            self.finished = segment.header.last();
+            self.segment = None;
+            self.next_segment = self.next_segment.wrapping_add(1);
+        }
+
+        Ok(())
+    }
+}
+
+impl ArchiveCipher for BackupDecryptor {
+    fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        self.buffer.extend_from_slice(data);
+
+        let mut output = Vec::new();
+        loop {
+            match &mut self.segment {
+                None => {
+                    if self.buffer.len() < HEADER_LENGTH {
+                        break;
+                    }
+
+                    if self.finished {
+                        return Err(anyhow::anyhow!(
+                            "encrypted backup archive continues after its last segment"
+                        ));
+                    }
+
+                    let header = SegmentHeader::parse(&self.buffer)?;
+                    if header.index() != self.next_segment {
+                        return Err(anyhow::anyhow!(
+                            "backup archive segments are missing or out of order"
+                        ));
+                    }
+
+                    self.buffer.drain(..HEADER_LENGTH);
+                    self.segment = Some(OpenSegment {
+                        remaining: (header.length() != OPEN_ENDED).then(|| header.length()),
+                        header,
+                        index: 0,
+                    });
+                }
+                Some(OpenSegment {
+                    remaining: Some(remaining),
+                    ..
+                }) => {
+                    let chunk_length = (*remaining).min(CHUNK_SIZE as u64);
+                    if self.buffer.len() < chunk_length as usize + TAG_LENGTH {
+                        break;
+                    }
+
+                    *remaining -= chunk_length;
+                    let last = *remaining == 0;
+                    self.open(chunk_length as usize + TAG_LENGTH, last, &mut output)?;
+                }
+                Some(OpenSegment {
+                    remaining: None, ..
+                }) => {
+                    // the final chunk of an open ended segment is only known once the input ends
+                    while self.buffer.len() > RECORD_SIZE {
+                        self.open(RECORD_SIZE, false, &mut output)?;
+                    }
+
+                    break;
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn finish(&mut self) -> Result<Vec<u8>, anyhow::Error> {
+        let mut output = Vec::new();
+
+        if let Some(OpenSegment {
+            remaining: None, ..
+        }) = &self.segment
+            && self.buffer.len() >= TAG_LENGTH
+        {
+            self.open(self.buffer.len(), true, &mut output)?;
+        }
+
+        // archives cut off at a segment boundary only lack the authenticated last segment
+        if !self.finished || self.segment.is_some() || !self.buffer.is_empty() {
+            return Err(anyhow::anyhow!("encrypted backup archive is truncated"));
+        }
+
+        Ok(output)
+    }
+}
+
+/// Runs a byte stream through an [`ArchiveCipher`].
+pub fn transform_stream<S, B, E>(
+    stream: S,
+    cipher: impl ArchiveCipher + 'static,
+) -> impl Stream<Item = Result<Vec<u8>, anyhow::Error>> + Send + 'static
+where
+    S: Stream<Item = Result<B, E>> + Send + 'static,
+    B: AsRef<[u8]> + Send + 'static,
+    E: Into<anyhow::Error> + Send + 'static,
+{
+    futures_util::stream::try_unfold(
+        (Box::pin(stream), Some(cipher)),
+        |(mut stream, mut cipher)| async move {
+            let Some(active_cipher) = &mut cipher else {
+                return Ok(None);
+            };
+
+            loop {
+                match stream.next().await {
+                    Some(chunk) => {
+                        let output = active_cipher
+                            .update(chunk.map_err(Into::<anyhow::Error>::into)?.as_ref())?;
+                        if !output.is_empty() {
+                            return Ok(Some((output, (stream, cipher))));
+                        }
+                    }
+                    None => {
+                        let output = active_cipher.finish()?;
+                        return Ok(Some((output, (stream, None))));
+                    }
+                }
+            }
+        },
+    )
+}
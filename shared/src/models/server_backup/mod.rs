@@ -1,4 +1,6 @@
+pub mod encryption;
 mod events;
+pub mod multipart;
 pub use events::ServerBackupEvent;
 
 use crate::{
@@ -65,6 +67,37 @@ impl BackupDisk {
 pub struct ServerBackupRestoreOptions {
     pub truncate_directory: bool,
     pub restore_startup: bool,
+    /// Database dumps of the backup to restore alongside the files.
+    pub databases: Vec<uuid::Uuid>,
+}
+
+pub struct ServerBackupPathRestoreOptions {
+    pub paths: Vec<compact_str::CompactString>,
+    pub target_directory: Option<compact_str::CompactString>,
+}
+
+#[derive(Debug, ToSchema, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupStorageGrouping {
+    #[default]
+    Server,
+    User,
+    BackupConfiguration,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct BackupStorageConsumer {
+    pub uuid: uuid::Uuid,
+    pub name: compact_str::CompactString,
+    pub backups: i64,
+    pub bytes: i64,
+    /// Only set when grouping by server and the server has a storage quota.
+    pub limit: Option<i64>,
+}
+
+enum BackupSource {
+    Primary(Box<super::backup_configuration::BackupConfiguration>),
+    Replica(super::server_backup_replica::ServerBackupReplica),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -120,6 +153,7 @@ pub struct ServerBackup {
     pub streaming: bool,
     pub locked: bool,
     pub shared: bool,
+    pub encrypted: bool,
 
     pub ignored_files: Vec<compact_str::CompactString>,
     pub checksum: Option<compact_str::CompactString>,
@@ -214,6 +248,10 @@ impl BaseModel for ServerBackup {
                 "server_backups.shared",
                 compact_str::format_compact!("{prefix}shared"),
             ),
+            (
+                "server_backups.encrypted",
+                compact_str::format_compact!("{prefix}encrypted"),
+            ),
             (
                 "server_backups.ignored_files",
                 compact_str::format_compact!("{prefix}ignored_files"),
@@ -298,6 +336,7 @@ impl BaseModel for ServerBackup {
             streaming: row.try_get(compact_str::format_compact!("{prefix}streaming").as_str())?,
             locked: row.try_get(compact_str::format_compact!("{prefix}locked").as_str())?,
             shared: row.try_get(compact_str::format_compact!("{prefix}shared").as_str())?,
+            encrypted: row.try_get(compact_str::format_compact!("{prefix}encrypted").as_str())?,
             ignored_files: row
                 .try_get(compact_str::format_compact!("{prefix}ignored_files").as_str())?,
             checksum: row.try_get(compact_str::format_compact!("{prefix}checksum").as_str())?,
@@ -320,6 +359,58 @@ impl BaseModel for ServerBackup {
 }
 
 impl ServerBackup {
+    /// Checks the backup limit, the backup storage quota and the creation ratelimit of
+    /// `server` before a user creates a backup for it. Callers should hold the
+    /// `servers::{uuid}::backups` lock so concurrent requests cannot exceed the limits.
+    pub async fn check_create_limits(
+        state: &crate::State,
+        server: &super::server::Server,
+    ) -> Result<(), anyhow::Error> {
+        let backups = Self::count_by_server_uuid(&state.database, server.uuid).await?;
+        if backups >= server.backup_limit as i64 {
+            return Err(
+                crate::response::DisplayError::new("maximum number of backups reached")
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .into(),
+            );
+        }
+
+        if let Some(backup_storage_limit) = server.backup_storage_limit
+            && Self::storage_usage_by_server_uuid(&state.database, server.uuid).await?
+                >= backup_storage_limit
+        {
+            return Err(
+                crate::response::DisplayError::new("backup storage quota reached")
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .into(),
+            );
+        }
+
+        let ratelimit = state
+            .settings
+            .get_as(|s| s.ratelimits.client_servers_backups_create)
+            .await?;
+        if state
+            .cache
+            .ratelimit(
+                "client/servers/backups/create",
+                ratelimit.hits,
+                ratelimit.window_seconds,
+                server.uuid.to_string(),
+            )
+            .await
+            .is_err()
+        {
+            return Err(
+                crate::response::DisplayError::new("too many backups created recently")
+                    .with_status(StatusCode::TOO_MANY_REQUESTS)
+                    .into(),
+            );
+        }
+
+        Ok(())
+    }
+
     pub async fn create_raw(
         state: &crate::State,
         mut options: CreateServerBackupOptions<'_>,
@@ -346,6 +437,14 @@ impl ServerBackup {
             .into());
         }
 
+        if options.include_databases && backup_configuration.backup_configs.s3.is_none() {
+            return Err(crate::response::DisplayError::new(
+                "backup configuration does not have an S3 configuration to store database dumps in",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .into());
+        }
+
         let mut transaction = state.database.write().begin().await?;
 
         let mut query_builder = InsertQueryBuilder::new("server_backups");
@@ -379,9 +478,27 @@ impl ServerBackup {
 
         transaction.commit().await?;
 
+        if options.include_databases {
+            backup.dump_databases_in_background(state);
+        }
+
         Ok(backup)
     }
 
+    /// Dumps the databases of the server next to the archive, wings never sees the dumps.
+    fn dump_databases_in_background(&self, state: &crate::State) {
+        let state = state.clone();
+        let backup = self.clone();
+
+        tokio::spawn(async move {
+            if let Err(err) =
+                super::server_backup_database::ServerBackupDatabase::dump_all(&state, &backup).await
+            {
+                tracing::error!(backup = %backup.uuid, "failed to dump server databases: {:?}", err);
+            }
+        });
+    }
+
     pub async fn by_server_uuid_uuid(
         database: &crate::database::Database,
         server_uuid: uuid::Uuid,
@@ -1007,6 +1124,124 @@ impl ServerBackup {
         .await
     }
 
+    /// Every byte stored for the backups matching `condition`: the archive itself, its database
+    /// dumps and its replicas, one row per stored object.
+    fn backup_storage_cte(condition: &str) -> String {
+        format!(
+            r#"
+            backup_storage AS (
+                SELECT
+                    server_backups.uuid AS backup_uuid,
+                    server_backups.server_uuid,
+                    server_backups.backup_configuration_uuid,
+                    server_backups.bytes
+                FROM server_backups
+                WHERE {condition}
+                UNION ALL
+                SELECT
+                    server_backups.uuid AS backup_uuid,
+                    server_backups.server_uuid,
+                    server_backups.backup_configuration_uuid,
+                    server_backup_databases.bytes
+                FROM server_backup_databases
+                JOIN server_backups ON server_backups.uuid = server_backup_databases.backup_uuid
+                WHERE {condition}
+                UNION ALL
+                SELECT
+                    server_backups.uuid AS backup_uuid,
+                    server_backups.server_uuid,
+                    server_backup_replicas.backup_configuration_uuid,
+                    server_backup_replicas.bytes
+                FROM server_backup_replicas
+                JOIN server_backups ON server_backups.uuid = server_backup_replicas.backup_uuid
+                WHERE {condition}
+            )
+            "#
+        )
+    }
+
+    /// Bytes counted against the server's `backup_storage_limit`. Mirrors
+    /// [`Self::count_by_server_uuid`], but also skips backups that are already being deleted
+    /// so eviction makes progress.
+    pub async fn storage_usage_by_server_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(sqlx::AssertSqlSafe(format!(
+            r#"
+            WITH {}
+            SELECT COALESCE(SUM(backup_storage.bytes), 0)::int8
+            FROM backup_storage
+            "#,
+            Self::backup_storage_cte(
+                "server_backups.server_uuid = $1
+                    AND server_backups.system_backup_policy_uuid IS NULL
+                    AND server_backups.deleted IS NULL
+                    AND server_backups.deleting IS NULL"
+            )
+        )))
+        .bind(server_uuid)
+        .fetch_one(database.read())
+        .await
+    }
+
+    pub async fn largest_storage_consumers(
+        database: &crate::database::Database,
+        grouping: BackupStorageGrouping,
+        limit: i64,
+    ) -> Result<Vec<BackupStorageConsumer>, sqlx::Error> {
+        let (key, columns, joins) = match grouping {
+            BackupStorageGrouping::Server => (
+                "servers.uuid",
+                "servers.uuid, servers.name, servers.backup_storage_limit",
+                "JOIN servers ON servers.uuid = backup_storage.server_uuid",
+            ),
+            BackupStorageGrouping::User => (
+                "users.uuid",
+                "users.uuid, users.username, NULL::int8",
+                "JOIN servers ON servers.uuid = backup_storage.server_uuid
+                JOIN users ON users.uuid = servers.owner_uuid",
+            ),
+            BackupStorageGrouping::BackupConfiguration => (
+                "backup_configurations.uuid",
+                "backup_configurations.uuid, backup_configurations.name, NULL::int8",
+                "JOIN backup_configurations
+                    ON backup_configurations.uuid = backup_storage.backup_configuration_uuid",
+            ),
+        };
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            WITH {}
+            SELECT
+                {columns},
+                COUNT(DISTINCT backup_storage.backup_uuid) AS backups,
+                COALESCE(SUM(backup_storage.bytes), 0)::int8 AS bytes
+            FROM backup_storage
+            {joins}
+            GROUP BY {key}
+            ORDER BY bytes DESC
+            LIMIT $1
+            "#,
+            Self::backup_storage_cte("server_backups.deleted IS NULL")
+        )))
+        .bind(limit)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(BackupStorageConsumer {
+                    uuid: row.try_get(0)?,
+                    name: row.try_get(1)?,
+                    limit: row.try_get(2)?,
+                    backups: row.try_get("backups")?,
+                    bytes: row.try_get("bytes")?,
+                })
+            })
+            .collect()
+    }
+
     /// In-flight system backups for a single policy on a node, ignoring rows older than a day
     /// (those are only failed-out when the wings node resets and must not starve the scheduler
     /// forever).
@@ -1056,6 +1291,48 @@ impl ServerBackup {
         }))
     }
 
+    /// Resolves where a backup can be read from. Falls back to a completed replica when the
+    /// primary backup configuration is gone or in maintenance mode.
+    async fn resolve_source(
+        &self,
+        state: &crate::State,
+        action: &str,
+    ) -> Result<BackupSource, anyhow::Error> {
+        let backup_configuration = match &self.backup_configuration {
+            Some(backup_configuration) => {
+                Some(backup_configuration.fetch_cached(&state.database).await?)
+            }
+            None => None,
+        };
+
+        match backup_configuration {
+            Some(backup_configuration) if !backup_configuration.maintenance_enabled => {
+                Ok(BackupSource::Primary(Box::new(backup_configuration)))
+            }
+            backup_configuration => {
+                if let Some(replica) =
+                    super::server_backup_replica::ServerBackupReplica::restorable_by_backup_uuid(
+                        state, self.uuid,
+                    )
+                    .await?
+                {
+                    return Ok(BackupSource::Replica(replica));
+                }
+
+                Err(crate::response::DisplayError::new(match backup_configuration {
+                    Some(_) => format!(
+                        "cannot {action} backup while backup configuration is in maintenance mode"
+                    ),
+                    None => {
+                        format!("no backup configuration available, unable to {action} backup")
+                    }
+                })
+                .with_status(StatusCode::EXPECTATION_FAILED)
+                .into())
+            }
+        }
+    }
+
     pub async fn download_url(
         &self,
         state: &crate::State,
@@ -1063,26 +1340,48 @@ impl ServerBackup {
         node: &super::node::Node,
         archive_format: wings_api::StreamableArchiveFormat,
     ) -> Result<String, anyhow::Error> {
-        let backup_configuration = self
-            .backup_configuration
-            .as_ref()
-            .ok_or_else(|| {
-                crate::response::DisplayError::new(
-                    "no backup configuration available, unable to restore backup",
-                )
-                .with_status(StatusCode::EXPECTATION_FAILED)
-            })?
-            .fetch_cached(&state.database)
-            .await?;
+        let source = self.resolve_source(state, "download").await?;
+
+        if self.encrypted {
+            // encrypted archives are decrypted as-is, so they can only be served in the format
+            // they were uploaded in
+            let stored_format = self
+                .upload_path
+                .as_deref()
+                .and_then(Self::s3_archive_format);
+            match stored_format {
+                Some(stored_format) if stored_format.to_string() == archive_format.to_string() => {}
+                Some(stored_format) => {
+                    return Err(crate::response::DisplayError::new(format!(
+                        "encrypted backups can only be downloaded as {stored_format}"
+                    ))
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .into());
+                }
+                None => {
+                    return Err(crate::response::DisplayError::new(
+                        "backup does not have an upload path",
+                    )
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .into());
+                }
+            }
 
-        if backup_configuration.maintenance_enabled {
-            return Err(crate::response::DisplayError::new(
-                "cannot restore backup while backup configuration is in maintenance mode",
-            )
-            .with_status(StatusCode::EXPECTATION_FAILED)
-            .into());
+            return self
+                .decrypted_download_url(state, std::time::Duration::from_mins(15))
+                .await;
         }
 
+        let backup_configuration = match source {
+            BackupSource::Primary(backup_configuration) => *backup_configuration,
+            BackupSource::Replica(replica) => {
+                return Ok(replica
+                    .presigned_url(state, std::time::Duration::from_mins(15))
+                    .await?
+                    .into());
+            }
+        };
+
         if matches!(self.disk, BackupDisk::S3)
             && let Some(mut s3_configuration) = backup_configuration.backup_configs.s3
         {
@@ -1114,6 +1413,20 @@ impl ServerBackup {
             return Ok(presigned.uri().to_string());
         }
 
+        self.node_download_url(state, node, user.uuid, true, archive_format)
+            .await
+    }
+
+    /// Creates a download url for the backup served by wings itself. `public` picks between the
+    /// public node url (handed to users) and the internal one (used by the panel).
+    pub async fn node_download_url(
+        &self,
+        state: &crate::State,
+        node: &super::node::Node,
+        jwt_id: uuid::Uuid,
+        public: bool,
+        archive_format: wings_api::StreamableArchiveFormat,
+    ) -> Result<String, anyhow::Error> {
         #[derive(Serialize)]
         struct BackupDownloadJwt {
             #[serde(flatten)]
@@ -1135,14 +1448,18 @@ impl ServerBackup {
                     expiration_time: Some(chrono::Utc::now().timestamp() + 900),
                     not_before: None,
                     issued_at: Some(chrono::Utc::now().timestamp()),
-                    jwt_id: user.uuid.to_compact_string(),
+                    jwt_id: jwt_id.to_compact_string(),
                 },
                 backup_uuid: self.uuid,
                 unique_id: uuid::Uuid::new_v4(),
             },
         )?;
 
-        let mut url = node.public_url(state, "/download/backup").await?;
+        let mut url = if public {
+            node.public_url(state, "/download/backup").await?
+        } else {
+            node.url("/download/backup")
+        };
         url.set_query(Some(&format!(
             "token={}&archive_format={}",
             urlencoding::encode(&token),
@@ -1152,6 +1469,37 @@ impl ServerBackup {
         Ok(url.to_string())
     }
 
+    /// Looks up the given database dumps of this backup, failing unless all of them exist and
+    /// have been completed.
+    pub async fn restorable_databases(
+        &self,
+        database: &crate::database::Database,
+        uuids: &[uuid::Uuid],
+    ) -> Result<Vec<super::server_backup_database::ServerBackupDatabase>, anyhow::Error> {
+        if uuids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let databases: Vec<_> =
+            super::server_backup_database::ServerBackupDatabase::all_by_backup_uuid(
+                database, self.uuid,
+            )
+            .await?
+            .into_iter()
+            .filter(|dump| uuids.contains(&dump.uuid))
+            .collect();
+
+        if databases.len() != uuids.len() || databases.iter().any(|dump| dump.completed.is_none()) {
+            return Err(crate::response::DisplayError::new(
+                "database dump not found or not completed",
+            )
+            .with_status(StatusCode::NOT_FOUND)
+            .into());
+        }
+
+        Ok(databases)
+    }
+
     pub async fn restore(
         self,
         state: &crate::State,
@@ -1159,48 +1507,118 @@ impl ServerBackup {
         mut server: super::server::Server,
         options: ServerBackupRestoreOptions,
     ) -> Result<(), anyhow::Error> {
-        let backup_configuration = self
-            .backup_configuration
-            .as_ref()
-            .ok_or_else(|| {
-                crate::response::DisplayError::new(
-                    "no backup configuration available, unable to restore backup",
-                )
-                .with_status(StatusCode::EXPECTATION_FAILED)
-            })?
-            .fetch_cached(&state.database)
-            .await?;
+        let source = self.resolve_source(state, "restore").await?;
 
-        if backup_configuration.maintenance_enabled {
-            return Err(crate::response::DisplayError::new(
-                "cannot restore backup while backup configuration is in maintenance mode",
-            )
-            .with_status(StatusCode::EXPECTATION_FAILED)
-            .into());
-        }
+        let databases = self
+            .restorable_databases(&state.database, &options.databases)
+            .await?;
 
         if options.restore_startup {
             self.restore_startup(state, transaction, &mut server)
                 .await?;
         }
 
-        server
+        let client = server
             .node
             .fetch_cached(&state.database)
             .await?
             .api_client(&state.database)
-            .await?
-            .post_servers_server_backup_backup_restore(
-                server.uuid,
-                self.uuid,
-                &wings_api::servers_server_backup_backup_restore::post::RequestBody {
-                    adapter: self.disk.to_wings_adapter(),
-                    download_url: self.wings_restore_download_url(state, server.uuid).await?,
-                    truncate_directory: options.truncate_directory,
-                },
-            )
             .await?;
 
+        let replica_request =
+            async |replica: &super::server_backup_replica::ServerBackupReplica| {
+                Ok::<_, anyhow::Error>(
+                    wings_api::servers_server_backup_backup_restore::post::RequestBody {
+                        adapter: wings_api::BackupAdapter::S3,
+                        download_url: Some(if self.encrypted {
+                            self.decrypted_download_url(state, std::time::Duration::from_mins(60))
+                                .await?
+                                .into()
+                        } else {
+                            replica
+                                .presigned_url(state, std::time::Duration::from_mins(60))
+                                .await?
+                        }),
+                        truncate_directory: options.truncate_directory,
+                    },
+                )
+            };
+
+        match source {
+            BackupSource::Primary(_) => {
+                match client
+                    .post_servers_server_backup_backup_restore(
+                        server.uuid,
+                        self.uuid,
+                        &wings_api::servers_server_backup_backup_restore::post::RequestBody {
+                            adapter: self.disk.to_wings_adapter(),
+                            download_url: self
+                                .wings_restore_download_url(state, server.uuid)
+                                .await?,
+                            truncate_directory: options.truncate_directory,
+                        },
+                    )
+                    .await
+                {
+                    Ok(_) => {}
+                    Err(wings_api::client::ApiHttpError::Http(StatusCode::NOT_FOUND, err)) => {
+                        let Some(replica) =
+                            super::server_backup_replica::ServerBackupReplica::restorable_by_backup_uuid(
+                                state, self.uuid,
+                            )
+                            .await?
+                        else {
+                            return Err(crate::response::DisplayError::new(
+                                crate::ApiError::new_wings_value(err).to_string(),
+                            )
+                            .with_status(StatusCode::NOT_FOUND)
+                            .into());
+                        };
+
+                        tracing::warn!(
+                            backup = %self.uuid,
+                            replica = %replica.uuid,
+                            "primary backup copy is missing, restoring from replica"
+                        );
+
+                        client
+                            .post_servers_server_backup_backup_restore(
+                                server.uuid,
+                                self.uuid,
+                                &replica_request(&replica).await?,
+                            )
+                            .await?;
+                    }
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            BackupSource::Replica(replica) => {
+                client
+                    .post_servers_server_backup_backup_restore(
+                        server.uuid,
+                        self.uuid,
+                        &replica_request(&replica).await?,
+                    )
+                    .await?;
+            }
+        }
+
+        if !databases.is_empty() {
+            let state = state.clone();
+            let backup = self.clone();
+            let server_uuid = server.uuid;
+
+            tokio::spawn(async move {
+                super::server_backup_database::ServerBackupDatabase::restore_all(
+                    &state,
+                    &backup,
+                    server_uuid,
+                    databases,
+                )
+                .await;
+            });
+        }
+
         Self::get_event_emitter().emit(
             state.clone(),
             ServerBackupEvent::RestoreStarted {
@@ -1370,6 +1788,30 @@ impl ServerBackup {
             return Ok(None);
         }
 
+        if self.encrypted {
+            // wings has no access to the data key, so it restores through the panel instead
+            return Ok(Some(
+                self.decrypted_download_url(state, std::time::Duration::from_mins(60))
+                    .await?
+                    .into(),
+            ));
+        }
+
+        self.s3_object_url(state, server_uuid, std::time::Duration::from_mins(60))
+            .await
+    }
+
+    /// Presigns the stored S3 object of the backup as-is, without decrypting it.
+    pub async fn s3_object_url(
+        &self,
+        state: &crate::State,
+        server_uuid: uuid::Uuid,
+        expires_in: std::time::Duration,
+    ) -> Result<Option<compact_str::CompactString>, anyhow::Error> {
+        if !matches!(self.disk, BackupDisk::S3) {
+            return Ok(None);
+        }
+
         let backup_configuration = self
             .backup_configuration
             .as_ref()
@@ -1396,9 +1838,7 @@ impl ServerBackup {
             None => &Self::s3_path(server_uuid, self.uuid, compression_type),
         };
 
-        let presigning_config = aws_sdk_s3::presigning::PresigningConfig::expires_in(
-            std::time::Duration::from_mins(60),
-        )?;
+        let presigning_config = aws_sdk_s3::presigning::PresigningConfig::expires_in(expires_in)?;
         let presigned = client
             .get_object()
             .bucket(bucket)
@@ -1409,6 +1849,80 @@ impl ServerBackup {
         Ok(Some(presigned.uri().to_compact_string()))
     }
 
+    /// Creates a panel url that streams the decrypted archive of an encrypted backup.
+    pub async fn decrypted_download_url(
+        &self,
+        state: &crate::State,
+        expires_in: std::time::Duration,
+    ) -> Result<String, anyhow::Error> {
+        let now = chrono::Utc::now().timestamp();
+
+        let token = state.jwt.create(&encryption::BackupDecryptJwt {
+            base: BasePayload {
+                scope: "backup-decrypt".into(),
+                issuer: "panel".into(),
+                subject: None,
+                audience: Vec::new(),
+                expiration_time: Some(now + expires_in.as_secs() as i64),
+                not_before: None,
+                issued_at: Some(now),
+                jwt_id: uuid::Uuid::new_v4().to_compact_string(),
+            },
+            backup_uuid: self.uuid,
+        })?;
+
+        let mut url = state
+            .settings
+            .get_as(|s| reqwest::Url::parse(&s.app.url))
+            .await??;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("app url cannot be used as a base url"))?
+            .pop_if_empty()
+            .extend(&["api", "backups", "download"]);
+        url.set_query(Some(&format!("token={}", urlencoding::encode(&token))));
+
+        Ok(url.to_string())
+    }
+
+    /// Streams the decrypted archive of an encrypted backup from wherever it is available.
+    pub async fn decrypted_stream(
+        &self,
+        state: &crate::State,
+    ) -> Result<
+        impl futures_util::Stream<Item = Result<Vec<u8>, anyhow::Error>> + Send + 'static,
+        anyhow::Error,
+    > {
+        let Some(server) = &self.server else {
+            return Err(anyhow::anyhow!("backup is no longer attached to a server"));
+        };
+
+        let source_url = match self.resolve_source(state, "download").await? {
+            BackupSource::Primary(_) => self
+                .s3_object_url(state, server.uuid, std::time::Duration::from_mins(15))
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("encrypted backup is not stored on S3"))?,
+            BackupSource::Replica(replica) => {
+                replica
+                    .presigned_url(state, std::time::Duration::from_mins(15))
+                    .await?
+            }
+        };
+
+        let key = encryption::BackupEncryptionKey::for_server(&state.database, server.uuid).await?;
+
+        let response = state
+            .client
+            .get(source_url.as_str())
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(encryption::transform_stream(
+            response.bytes_stream(),
+            encryption::BackupDecryptor::new(&key),
+        ))
+    }
+
     pub async fn export(
         &self,
         state: &crate::State,
@@ -1499,6 +2013,151 @@ impl ServerBackup {
         }
     }
 
+    /// Normalizes a path inside the backup to one relative to the backup root. Paths that
+    /// would leave the backup are rejected.
+    pub fn normalize_path(path: &str) -> Result<compact_str::CompactString, anyhow::Error> {
+        let mut normalized = compact_str::CompactString::default();
+
+        for component in std::path::Path::new(path).components() {
+            match component {
+                std::path::Component::Normal(component) => {
+                    if !normalized.is_empty() {
+                        normalized.push('/');
+                    }
+                    normalized.push_str(&component.to_string_lossy());
+                }
+                std::path::Component::RootDir | std::path::Component::CurDir => {}
+                _ => {
+                    return Err(crate::response::DisplayError::new(format!(
+                        "path {path:?} leaves the backup"
+                    ))
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .into());
+                }
+            }
+        }
+
+        Ok(normalized)
+    }
+
+    /// The path wings exposes the contents of a browsable backup under, relative to the root
+    /// of the server filesystem.
+    pub fn browse_path(&self, path: &str) -> Result<compact_str::CompactString, anyhow::Error> {
+        let normalized = Self::normalize_path(path)?;
+
+        Ok(if normalized.is_empty() {
+            compact_str::format_compact!("/.backups/{}", self.uuid)
+        } else {
+            compact_str::format_compact!("/.backups/{}/{normalized}", self.uuid)
+        })
+    }
+
+    /// Checks that the contents of the backup can be browsed through the filesystem of the
+    /// given server.
+    pub fn ensure_browsable(&self, server: &super::server::Server) -> Result<(), anyhow::Error> {
+        if self.deleting.is_some() || self.completed.is_none() || !self.successful {
+            return Err(
+                crate::response::DisplayError::new("backup is not available for browsing")
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .into(),
+            );
+        }
+
+        if !self.browsable {
+            return Err(
+                crate::response::DisplayError::new("backup is not browsable")
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .into(),
+            );
+        }
+
+        if self.node.uuid != server.node.uuid {
+            return Err(crate::response::DisplayError::new(
+                "backup is stored on a different node than the server",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Copies individual files and directories out of a browsable backup instead of restoring
+    /// the whole archive. Every path keeps its location relative to the backup root, placed
+    /// below `target_directory` when one is given, and existing files are overwritten.
+    pub async fn restore_paths(
+        &self,
+        state: &crate::State,
+        server: &super::server::Server,
+        options: ServerBackupPathRestoreOptions,
+    ) -> Result<wings_api::servers_server_files_copy_many::post::Response, anyhow::Error> {
+        self.ensure_browsable(server)?;
+
+        let target_directory =
+            Self::normalize_path(options.target_directory.as_deref().unwrap_or_default())?;
+        if target_directory == ".backups" || target_directory.starts_with(".backups/") {
+            return Err(crate::response::DisplayError::new(
+                "cannot restore into the backups directory",
+            )
+            .with_status(StatusCode::BAD_REQUEST)
+            .into());
+        }
+
+        let mut files = Vec::with_capacity(options.paths.len());
+        for path in &options.paths {
+            let path = Self::normalize_path(path)?;
+            if path.is_empty() {
+                return Err(crate::response::DisplayError::new(
+                    "paths must point to a file or directory inside the backup",
+                )
+                .with_status(StatusCode::BAD_REQUEST)
+                .into());
+            }
+
+            files.push(
+                wings_api::servers_server_files_copy_many::post::RequestBodyFiles {
+                    from: compact_str::format_compact!(".backups/{}/{path}", self.uuid),
+                    to: if target_directory.is_empty() {
+                        path
+                    } else {
+                        compact_str::format_compact!("{target_directory}/{path}")
+                    },
+                },
+            );
+        }
+
+        let client = server
+            .node
+            .fetch_cached(&state.database)
+            .await?
+            .api_client(&state.database)
+            .await?;
+
+        match client
+            .post_servers_server_files_copy_many(
+                server.uuid,
+                &wings_api::servers_server_files_copy_many::post::RequestBody {
+                    root: "/".into(),
+                    files,
+                    overwrite: true,
+                    foreground: true,
+                },
+            )
+            .await
+        {
+            Ok(response) => Ok(response),
+            Err(wings_api::client::ApiHttpError::Http(
+                status @ (StatusCode::NOT_FOUND | StatusCode::EXPECTATION_FAILED),
+                err,
+            )) => Err(crate::response::DisplayError::new(
+                crate::ApiError::new_wings_value(err).to_string(),
+            )
+            .with_status(status)
+            .into()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
     pub async fn evict_one_by_server_uuid(
         state: &crate::State,
         server: &super::server::Server,
@@ -1963,6 +2622,35 @@ impl ServerBackup {
         }
     }
 
+    /// Queues a replica of a completed backup if its backup configuration replicates to a
+    /// secondary one. The copy itself is done by the `replicate_backups` background task.
+    pub async fn queue_replication(
+        &self,
+        state: &crate::State,
+    ) -> Result<Option<super::server_backup_replica::ServerBackupReplica>, anyhow::Error> {
+        let Some(backup_configuration) = &self.backup_configuration else {
+            return Ok(None);
+        };
+        let Some(replication_backup_configuration) = backup_configuration
+            .fetch_cached(&state.database)
+            .await?
+            .replication_backup_configuration
+        else {
+            return Ok(None);
+        };
+
+        let replica = super::server_backup_replica::ServerBackupReplica::create(
+            state,
+            super::server_backup_replica::CreateServerBackupReplicaOptions {
+                backup_uuid: self.uuid,
+                backup_configuration_uuid: replication_backup_configuration.uuid,
+            },
+        )
+        .await?;
+
+        Ok(Some(replica))
+    }
+
     #[inline]
     pub fn default_name() -> compact_str::CompactString {
         let now = chrono::Local::now();
@@ -1990,6 +2678,30 @@ impl ServerBackup {
         )
     }
 
+    /// Derives the archive format of an object created by [`Self::s3_path`], ignoring the
+    /// `.enc` suffix of encrypted uploads.
+    pub fn s3_archive_format(path: &str) -> Option<wings_api::StreamableArchiveFormat> {
+        let path = path.strip_suffix(".enc").unwrap_or(path);
+
+        Some(if path.ends_with(".tar") {
+            wings_api::StreamableArchiveFormat::Tar
+        } else if path.ends_with(".tar.gz") {
+            wings_api::StreamableArchiveFormat::TarGz
+        } else if path.ends_with(".tar.xz") {
+            wings_api::StreamableArchiveFormat::TarXz
+        } else if path.ends_with(".tar.lz") {
+            wings_api::StreamableArchiveFormat::TarLzip
+        } else if path.ends_with(".tar.bz2") {
+            wings_api::StreamableArchiveFormat::TarBz2
+        } else if path.ends_with(".tar.lz4") {
+            wings_api::StreamableArchiveFormat::TarLz4
+        } else if path.ends_with(".tar.zst") {
+            wings_api::StreamableArchiveFormat::TarZstd
+        } else {
+            return None;
+        })
+    }
+
     #[inline]
     pub fn s3_content_type(name: &str) -> &'static str {
         if name.ends_with("tar") {
@@ -2011,12 +2723,47 @@ impl ServerBackup {
         }
     }
 
+    async fn admin_api_replicas(
+        &self,
+        state: &crate::State,
+        relations: &ServerBackupRelations,
+    ) -> Result<
+        Vec<super::server_backup_replica::AdminApiServerBackupReplica>,
+        crate::database::DatabaseError,
+    > {
+        let mut replicas = Vec::new();
+        for replica in relations.replicas(self.uuid) {
+            replicas.push(replica.clone().into_admin_api_object(state, ()).await?);
+        }
+
+        Ok(replicas)
+    }
+
+    async fn admin_api_databases(
+        &self,
+        state: &crate::State,
+        relations: &ServerBackupRelations,
+    ) -> Result<
+        Vec<super::server_backup_database::AdminApiServerBackupDatabase>,
+        crate::database::DatabaseError,
+    > {
+        let mut databases = Vec::new();
+        for database in relations.databases(self.uuid) {
+            databases.push(database.clone().into_admin_api_object(state, ()).await?);
+        }
+
+        Ok(databases)
+    }
+
     pub async fn into_admin_node_api_object(
         self,
         state: &crate::State,
         storage_url_retriever: &StorageUrlRetriever<'_>,
+        relations: &ServerBackupRelations,
     ) -> Result<AdminApiNodeServerBackup, crate::database::DatabaseError> {
         let deletion_status = self.deletion_status();
+        let replicas = self.admin_api_replicas(state, relations).await?;
+        let databases = self.admin_api_databases(state, relations).await?;
 
         Ok(AdminApiNodeServerBackup {
             uuid: self.uuid,
@@ -2045,10 +2792,13 @@ impl ServerBackup {
             is_browsable: self.browsable,
             is_streaming: self.streaming,
             is_shared: self.shared,
+            is_encrypted: self.encrypted,
             checksum: self.checksum,
             bytes: self.bytes,
             files: self.files,
             deletion_status,
+            replicas,
+            databases,
             metadata: self.metadata,
             completed: self.completed.map(|dt| dt.and_utc()),
             created: self.created.and_utc(),
@@ -2056,17 +2806,95 @@ impl ServerBackup {
     }
 }
 
+/// Replicas and database dumps of a page of backups, loaded in bulk instead of once per backup.
+#[derive(Default)]
+pub struct ServerBackupRelations {
+    replicas: HashMap<uuid::Uuid, Vec<super::server_backup_replica::ServerBackupReplica>>,
+    databases: HashMap<uuid::Uuid, Vec<super::server_backup_database::ServerBackupDatabase>>,
+}
+
+impl ServerBackupRelations {
+    pub async fn load(
+        database: &crate::database::Database,
+        backup_uuids: &[uuid::Uuid],
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let mut relations = Self::default();
+
+        for replica in super::server_backup_replica::ServerBackupReplica::all_by_backup_uuids(
+            database,
+            backup_uuids,
+        )
+        .await?
+        {
+            relations
+                .replicas
+                .entry(replica.backup_uuid)
+                .or_default()
+                .push(replica);
+        }
+
+        for dump in super::server_backup_database::ServerBackupDatabase::all_by_backup_uuids(
+            database,
+            backup_uuids,
+        )
+        .await?
+        {
+            relations
+                .databases
+                .entry(dump.backup_uuid)
+                .or_default()
+                .push(dump);
+        }
+
+        Ok(relations)
+    }
+
+    pub async fn load_for(
+        database: &crate::database::Database,
+        backups: &[ServerBackup],
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let backup_uuids: Vec<_> = backups.iter().map(|backup| backup.uuid).collect();
+
+        Self::load(database, &backup_uuids).await
+    }
+
+    #[inline]
+    fn replicas(
+        &self,
+        backup_uuid: uuid::Uuid,
+    ) -> &[super::server_backup_replica::ServerBackupReplica] {
+        self.replicas
+            .get(&backup_uuid)
+            .map_or(&[], |replicas| replicas.as_slice())
+    }
+
+    #[inline]
+    fn databases(
+        &self,
+        backup_uuid: uuid::Uuid,
+    ) -> &[super::server_backup_database::ServerBackupDatabase] {
+        self.databases
+            .get(&backup_uuid)
+            .map_or(&[], |databases| databases.as_slice())
+    }
+}
+
 #[async_trait::async_trait]
 impl IntoAdminApiObject for ServerBackup {
     type AdminApiObject = AdminApiServerBackup;
-    type ExtraArgs<'a> = &'a crate::storage::StorageUrlRetriever<'a>;
+    type ExtraArgs<'a> = (
+        &'a crate::storage::StorageUrlRetriever<'a>,
+        &'a ServerBackupRelations,
+    );
 
     async fn into_admin_api_object<'a>(
         self,
         state: &crate::State,
-        storage_url_retriever: Self::ExtraArgs<'a>,
+        (storage_url_retriever, relations): Self::ExtraArgs<'a>,
     ) -> Result<Self::AdminApiObject, crate::database::DatabaseError> {
         let deletion_status = self.deletion_status();
+        let replicas = self.admin_api_replicas(state, relations).await?;
+        let databases = self.admin_api_databases(state, relations).await?;
         let api_object = AdminApiServerBackup::init_hooks(&self, state).await?;
 
         let api_object = finish_extendible!(
@@ -2091,10 +2919,13 @@ impl IntoAdminApiObject for ServerBackup {
                 is_browsable: self.browsable,
                 is_streaming: self.streaming,
                 is_shared: self.shared,
+                is_encrypted: self.encrypted,
                 checksum: self.checksum,
                 bytes: self.bytes,
                 files: self.files,
                 deletion_status,
+                replicas,
+                databases,
                 metadata: self.metadata,
                 completed: self.completed.map(|dt| dt.and_utc()),
                 created: self.created.and_utc(),
@@ -2110,14 +2941,25 @@ impl IntoAdminApiObject for ServerBackup {
 #[async_trait::async_trait]
 impl IntoApiObject for ServerBackup {
     type ApiObject = ApiServerBackup;
-    type ExtraArgs<'a> = ();
+    type ExtraArgs<'a> = &'a ServerBackupRelations;
 
     async fn into_api_object<'a>(
         self,
         state: &crate::State,
-        _args: Self::ExtraArgs<'a>,
+        relations: Self::ExtraArgs<'a>,
     ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
         let deletion_status = self.deletion_status();
+
+        let mut replicas = Vec::new();
+        for replica in relations.replicas(self.uuid) {
+            replicas.push(replica.clone().into_api_object(state, ()).await?);
+        }
+
+        let mut databases = Vec::new();
+        for database in relations.databases(self.uuid) {
+            databases.push(database.clone().into_api_object(state, ()).await?);
+        }
+
         let api_object = ApiServerBackup::init_hooks(&self, state).await?;
 
         let api_object = finish_extendible!(
@@ -2130,10 +2972,13 @@ impl IntoApiObject for ServerBackup {
                 is_locked: self.locked,
                 is_browsable: self.browsable,
                 is_streaming: self.streaming,
+                is_encrypted: self.encrypted,
                 checksum: self.checksum,
                 bytes: self.bytes,
                 files: self.files,
                 deletion_status,
+                replicas,
+                databases,
                 metadata: self.metadata,
                 completed: self.completed.map(|dt| dt.and_utc()),
                 created: self.created.and_utc(),
@@ -2162,6 +3007,8 @@ pub struct CreateServerBackupOptions<'a> {
     pub ignored_files: Vec<compact_str::CompactString>,
     #[garde(skip)]
     pub metadata: serde_json::Value,
+    #[garde(skip)]
+    pub include_databases: bool,
 }
 
 #[async_trait::async_trait]
@@ -2216,6 +3063,16 @@ impl CreatableModel for ServerBackup {
             .into());
         }
 
+        if options.include_databases && backup_configuration.backup_configs.s3.is_none() {
+            return Err(anyhow::Error::new(
+                crate::response::DisplayError::new(
+                    "backup configuration does not have an S3 configuration to store database dumps in",
+                )
+                .with_status(StatusCode::EXPECTATION_FAILED),
+            )
+            .into());
+        }
+
         let mut transaction = state.database.write().begin().await?;
 
         let mut query_builder = InsertQueryBuilder::new("server_backups");
@@ -2249,6 +3106,10 @@ impl CreatableModel for ServerBackup {
 
         transaction.commit().await?;
 
+        if options.include_databases {
+            backup.dump_databases_in_background(state);
+        }
+
         let server = options.server.clone();
         let database = Arc::clone(&state.database);
         let backup_uuid = backup.uuid;
@@ -2589,6 +3450,28 @@ impl ServerBackup {
 
         transaction.commit().await?;
 
+        if let Err(err) = super::server_backup_replica::ServerBackupReplica::purge_by_backup_uuid(
+            state, self.uuid,
+        )
+        .await
+        {
+            tracing::warn!(
+                backup = %self.uuid,
+                "failed to purge backup replicas: {:#?}",
+                err
+            );
+        }
+
+        if let Err(err) =
+            super::server_backup_database::ServerBackupDatabase::purge_by_backup(state, self).await
+        {
+            tracing::warn!(
+                backup = %self.uuid,
+                "failed to purge backup database dumps: {:#?}",
+                err
+            );
+        }
+
         Self::get_event_emitter().emit(
             state.clone(),
             ServerBackupEvent::DeletionCompleted {
@@ -2860,6 +3743,7 @@ pub struct AdminApiNodeServerBackup {
     pub is_browsable: bool,
     pub is_streaming: bool,
     pub is_shared: bool,
+    pub is_encrypted: bool,
 
     pub checksum: Option<compact_str::CompactString>,
     pub bytes: i64,
@@ -2867,6 +3751,8 @@ pub struct AdminApiNodeServerBackup {
 
     pub metadata: serde_json::Value,
     pub deletion_status: Option<ServerBackupDeletionStatus>,
+    pub replicas: Vec<super::server_backup_replica::AdminApiServerBackupReplica>,
+    pub databases: Vec<super::server_backup_database::AdminApiServerBackupDatabase>,
 
     pub completed: Option<chrono::DateTime<chrono::Utc>>,
     pub created: chrono::DateTime<chrono::Utc>,
@@ -2891,6 +3777,7 @@ pub struct AdminApiServerBackup {
     pub is_browsable: bool,
     pub is_streaming: bool,
     pub is_shared: bool,
+    pub is_encrypted: bool,
 
     pub checksum: Option<compact_str::CompactString>,
     pub bytes: i64,
@@ -2898,6 +3785,8 @@ pub struct AdminApiServerBackup {
 
     pub metadata: serde_json::Value,
     pub deletion_status: Option<ServerBackupDeletionStatus>,
+    pub replicas: Vec<super::server_backup_replica::AdminApiServerBackupReplica>,
+    pub databases: Vec<super::server_backup_database::AdminApiServerBackupDatabase>,
 
     pub completed: Option<chrono::DateTime<chrono::Utc>>,
     pub created: chrono::DateTime<chrono::Utc>,
@@ -2919,6 +3808,7 @@ pub struct ApiServerBackup {
     pub is_locked: bool,
     pub is_browsable: bool,
     pub is_streaming: bool,
+    pub is_encrypted: bool,
 
     pub checksum: Option<compact_str::CompactString>,
     pub bytes: i64,
@@ -2926,6 +3816,8 @@ pub struct ApiServerBackup {
 
     pub metadata: serde_json::Value,
     pub deletion_status: Option<ServerBackupDeletionStatus>,
+    pub replicas: Vec<super::server_backup_replica::ApiServerBackupReplica>,
+    pub databases: Vec<super::server_backup_database::ApiServerBackupDatabase>,
 
     pub completed: Option<chrono::DateTime<chrono::Utc>>,
     pub created: chrono::DateTime<chrono::Utc>,
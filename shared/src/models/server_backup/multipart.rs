@@ -0,0 +1,140 @@
+use aws_sdk_s3::{
+    Client as S3Client,
+    primitives::ByteStream,
+    types::{CompletedMultipartUpload, CompletedPart},
+};
+
+/// S3 rejects non-final parts smaller than this.
+const MIN_PART_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Buffers a byte stream produced by the panel into the parts of an S3 multipart upload.
+pub struct MultipartUpload {
+    client: S3Client,
+    bucket: compact_str::CompactString,
+    file_path: compact_str::CompactString,
+    upload_id: String,
+    part_size: usize,
+    buffer: Vec<u8>,
+    parts: Vec<CompletedPart>,
+    bytes: i64,
+}
+
+impl MultipartUpload {
+    pub async fn create(
+        client: S3Client,
+        bucket: compact_str::CompactString,
+        file_path: compact_str::CompactString,
+        part_size: u64,
+    ) -> Result<Self, anyhow::Error> {
+        let multipart = client
+            .create_multipart_upload()
+            .bucket(&*bucket)
+            .key(&*file_path)
+            .content_type(super::ServerBackup::s3_content_type(&file_path))
+            .send()
+            .await?;
+        let upload_id = multipart
+            .upload_id()
+            .ok_or_else(|| anyhow::anyhow!("S3 did not return an upload_id"))?
+            .to_string();
+
+        let part_size = part_size.max(MIN_PART_SIZE) as usize;
+
+        Ok(Self {
+            client,
+            bucket,
+            file_path,
+            upload_id,
+            part_size,
+            buffer: Vec::with_capacity(part_size),
+            parts: Vec::new(),
+            bytes: 0,
+        })
+    }
+
+    #[inline]
+    pub fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    /// Buffers more data, uploading a part once a full one is buffered.
+    /// Returns whether a part was uploaded.
+    pub async fn write(&mut self, data: &[u8]) -> Result<bool, anyhow::Error> {
+        self.buffer.extend_from_slice(data);
+        self.bytes += data.len() as i64;
+
+        if self.buffer.len() < self.part_size {
+            return Ok(false);
+        }
+
+        self.upload_part().await?;
+
+        Ok(true)
+    }
+
+    async fn upload_part(&mut self) -> Result<(), anyhow::Error> {
+        let part_number = self.parts.len() as i32 + 1;
+        let part = self
+            .client
+            .upload_part()
+            .bucket(&*self.bucket)
+            .key(&*self.file_path)
+            .upload_id(&self.upload_id)
+            .part_number(part_number)
+            .body(ByteStream::from(std::mem::replace(
+                &mut self.buffer,
+                Vec::with_capacity(self.part_size),
+            )))
+            .send()
+            .await?;
+
+        self.parts.push(
+            CompletedPart::builder()
+                .part_number(part_number)
+                .set_e_tag(part.e_tag().map(String::from))
+                .build(),
+        );
+
+        Ok(())
+    }
+
+    /// Uploads whatever is still buffered and completes the upload, returning the total size.
+    pub async fn complete(&mut self) -> Result<i64, anyhow::Error> {
+        if !self.buffer.is_empty() || self.parts.is_empty() {
+            self.upload_part().await?;
+        }
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&*self.bucket)
+            .key(&*self.file_path)
+            .upload_id(&self.upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(std::mem::take(&mut self.parts)))
+                    .build(),
+            )
+            .send()
+            .await?;
+
+        Ok(self.bytes)
+    }
+
+    pub async fn abort(&self) {
+        if let Err(err) = self
+            .client
+            .abort_multipart_upload()
+            .bucket(&*self.bucket)
+            .key(&*self.file_path)
+            .upload_id(&self.upload_id)
+            .send()
+            .await
+        {
+            tracing::warn!(
+                file_path = %self.file_path,
+                "failed to abort multipart upload: {:#?}",
+                err
+            );
+        }
+    }
+}
@@ -0,0 +1,903 @@
+use crate::{
+    models::{
+        InsertQueryBuilder,
+        server_backup::{
+            ServerBackup,
+            encryption::{self, BackupEncryptionKey},
+            multipart::MultipartUpload,
+        },
+        server_database::ServerDatabase,
+        server_database_instance::ServerDatabaseInstance,
+    },
+    prelude::*,
+};
+use futures_util::{StreamExt, TryStreamExt, stream::BoxStream};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+pub(crate) type DumpStream = BoxStream<'static, Result<Vec<u8>, anyhow::Error>>;
+
+#[derive(Debug, ToSchema, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerBackupDatabaseStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerBackupDatabase {
+    pub uuid: uuid::Uuid,
+    pub backup_uuid: uuid::Uuid,
+    pub server_database_uuid: Option<uuid::Uuid>,
+    pub server_database_instance_uuid: Option<uuid::Uuid>,
+    pub instance_database_uuid: Option<uuid::Uuid>,
+
+    pub name: compact_str::CompactString,
+    pub encrypted: bool,
+
+    pub bytes: i64,
+    pub upload_path: Option<compact_str::CompactString>,
+    pub error: Option<compact_str::CompactString>,
+
+    pub completed: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerBackupDatabase {
+    const NAME: &'static str = "server_backup_database";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_backup_databases.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_backup_databases.backup_uuid",
+                compact_str::format_compact!("{prefix}backup_uuid"),
+            ),
+            (
+                "server_backup_databases.server_database_uuid",
+                compact_str::format_compact!("{prefix}server_database_uuid"),
+            ),
+            (
+                "server_backup_databases.server_database_instance_uuid",
+                compact_str::format_compact!("{prefix}server_database_instance_uuid"),
+            ),
+            (
+                "server_backup_databases.instance_database_uuid",
+                compact_str::format_compact!("{prefix}instance_database_uuid"),
+            ),
+            (
+                "server_backup_databases.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "server_backup_databases.encrypted",
+                compact_str::format_compact!("{prefix}encrypted"),
+            ),
+            (
+                "server_backup_databases.bytes",
+                compact_str::format_compact!("{prefix}bytes"),
+            ),
+            (
+                "server_backup_databases.upload_path",
+                compact_str::format_compact!("{prefix}upload_path"),
+            ),
+            (
+                "server_backup_databases.error",
+                compact_str::format_compact!("{prefix}error"),
+            ),
+            (
+                "server_backup_databases.completed",
+                compact_str::format_compact!("{prefix}completed"),
+            ),
+            (
+                "server_backup_databases.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            backup_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}backup_uuid").as_str())?,
+            server_database_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}server_database_uuid").as_str())?,
+            server_database_instance_uuid: row.try_get(
+                compact_str::format_compact!("{prefix}server_database_instance_uuid").as_str(),
+            )?,
+            instance_database_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}instance_database_uuid").as_str())?,
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            encrypted: row.try_get(compact_str::format_compact!("{prefix}encrypted").as_str())?,
+            bytes: row.try_get(compact_str::format_compact!("{prefix}bytes").as_str())?,
+            upload_path: row
+                .try_get(compact_str::format_compact!("{prefix}upload_path").as_str())?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            completed: row.try_get(compact_str::format_compact!("{prefix}completed").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerBackupDatabase {
+    pub async fn all_by_backup_uuid(
+        database: &crate::database::Database,
+        backup_uuid: uuid::Uuid,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_backup_databases
+            WHERE server_backup_databases.backup_uuid = $1
+            ORDER BY server_backup_databases.created, server_backup_databases.name
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(backup_uuid)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn all_by_backup_uuids(
+        database: &crate::database::Database,
+        backup_uuids: &[uuid::Uuid],
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_backup_databases
+            WHERE server_backup_databases.backup_uuid = ANY($1)
+            ORDER BY server_backup_databases.created, server_backup_databases.name
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(backup_uuids)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    #[inline]
+    pub fn status(&self) -> ServerBackupDatabaseStatus {
+        if self.completed.is_some() {
+            ServerBackupDatabaseStatus::Completed
+        } else if self.error.is_some() {
+            ServerBackupDatabaseStatus::Failed
+        } else {
+            ServerBackupDatabaseStatus::Pending
+        }
+    }
+
+    async fn s3_configuration(
+        state: &crate::State,
+        backup: &ServerBackup,
+    ) -> Result<super::backup_configuration::BackupConfigsS3, anyhow::Error> {
+        let Some(mut s3_configuration) = backup
+            .backup_configuration
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("backup does not have a backup configuration"))?
+            .fetch_cached(&state.database)
+            .await?
+            .backup_configs
+            .s3
+        else {
+            return Err(crate::response::DisplayError::new(
+                "backup configuration does not have an S3 configuration to store database dumps in",
+            )
+            .with_status(reqwest::StatusCode::EXPECTATION_FAILED)
+            .into());
+        };
+        s3_configuration.decrypt(&state.database).await?;
+
+        Ok(s3_configuration)
+    }
+
+    /// Dumps every database and database instance of the backed up server into the S3 bucket
+    /// of the backup configuration. Each dump is tracked separately, a failing one does not
+    /// stop the others or fail the backup itself.
+    pub async fn dump_all(
+        state: &crate::State,
+        backup: &ServerBackup,
+    ) -> Result<(), anyhow::Error> {
+        let Some(server) = &backup.server else {
+            return Err(anyhow::anyhow!("backup is no longer attached to a server"));
+        };
+
+        let s3_configuration = Self::s3_configuration(state, backup).await?;
+        let encryption_key = if s3_configuration.encryption_enabled {
+            Some(BackupEncryptionKey::for_server(&state.database, server.uuid).await?)
+        } else {
+            None
+        };
+
+        for mut server_database in
+            ServerDatabase::all_by_server_uuid(&state.database, server.uuid).await?
+        {
+            let dump = Self::create(
+                state,
+                CreateServerBackupDatabaseOptions {
+                    backup_uuid: backup.uuid,
+                    server_database_uuid: Some(server_database.uuid),
+                    server_database_instance_uuid: None,
+                    instance_database_uuid: None,
+                    name: server_database.name.clone(),
+                    encrypted: encryption_key.is_some(),
+                },
+            )
+            .await?;
+
+            let file_path = dump.s3_path(
+                server.uuid,
+                server_database.database_host.r#type.dump_extension(),
+            );
+            let (sender, receiver) = tokio::sync::mpsc::channel(4);
+
+            let ((), uploaded) = tokio::join!(
+                server_database.dump(&state.database, sender),
+                Self::upload(
+                    s3_configuration.clone(),
+                    file_path,
+                    encryption_key.as_ref(),
+                    futures_util::stream::unfold(receiver, |mut receiver| async move {
+                        receiver.recv().await.map(|chunk| (chunk, receiver))
+                    })
+                    .boxed(),
+                )
+            );
+
+            dump.finish(state, uploaded).await?;
+        }
+
+        for instance in
+            ServerDatabaseInstance::all_by_server_uuid(&state.database, server.uuid).await?
+        {
+            let databases = match Self::instance_databases(state, &instance).await {
+                Ok(databases) => databases,
+                Err(err) => {
+                    let dump = Self::create(
+                        state,
+                        CreateServerBackupDatabaseOptions {
+                            backup_uuid: backup.uuid,
+                            server_database_uuid: None,
+                            server_database_instance_uuid: Some(instance.uuid),
+                            instance_database_uuid: None,
+                            name: instance.name.clone(),
+                            encrypted: encryption_key.is_some(),
+                        },
+                    )
+                    .await?;
+
+                    dump.finish(state, Err(err)).await?;
+                    continue;
+                }
+            };
+
+            for (instance_database_uuid, name) in databases {
+                let dump = Self::create(
+                    state,
+                    CreateServerBackupDatabaseOptions {
+                        backup_uuid: backup.uuid,
+                        server_database_uuid: None,
+                        server_database_instance_uuid: Some(instance.uuid),
+                        instance_database_uuid,
+                        name,
+                        encrypted: encryption_key.is_some(),
+                    },
+                )
+                .await?;
+
+                let uploaded = async {
+                    let export = instance
+                        .database_agent_host
+                        .api_client(&state.database)
+                        .await?
+                        .get_instances_instance_export(
+                            instance.uuid,
+                            &db_agent_api::instances_instance_export::get::Query {
+                                db: dump.instance_database_uuid.map(|_| dump.name.clone()),
+                                ..Default::default()
+                            },
+                        )
+                        .await?;
+
+                    Self::upload(
+                        s3_configuration.clone(),
+                        dump.s3_path(server.uuid, instance.r#type.dump_extension()),
+                        encryption_key.as_ref(),
+                        tokio_util::io::ReaderStream::new(export)
+                            .map(|chunk| {
+                                chunk
+                                    .map(|chunk| chunk.to_vec())
+                                    .map_err(Into::<anyhow::Error>::into)
+                            })
+                            .boxed(),
+                    )
+                    .await
+                }
+                .await;
+
+                dump.finish(state, uploaded).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists the databases of an instance as `(uuid, name)` pairs. Redis instances are dumped
+    /// as a whole, so they yield a single entry without a database uuid.
+    pub(crate) async fn instance_databases(
+        state: &crate::State,
+        instance: &ServerDatabaseInstance,
+    ) -> Result<Vec<(Option<uuid::Uuid>, compact_str::CompactString)>, anyhow::Error> {
+        if matches!(instance.r#type, db_agent_api::DatabaseAgentType::Redis) {
+            return Ok(vec![(None, instance.name.clone())]);
+        }
+
+        let databases = instance
+            .database_agent_host
+            .api_client(&state.database)
+            .await?
+            .get_instances_instance_databases(instance.uuid)
+            .await?
+            .databases;
+
+        Ok(databases
+            .into_iter()
+            .map(|database| (Some(database.uuid), database.name))
+            .collect())
+    }
+
+    fn s3_path(&self, server_uuid: uuid::Uuid, extension: &str) -> compact_str::CompactString {
+        let mut path = compact_str::format_compact!(
+            "{server_uuid}/{}/databases/{}.{extension}",
+            self.backup_uuid,
+            self.uuid
+        );
+        if self.encrypted {
+            path.push_str(".enc");
+        }
+
+        path
+    }
+
+    pub(crate) async fn upload(
+        s3_configuration: super::backup_configuration::BackupConfigsS3,
+        file_path: compact_str::CompactString,
+        encryption_key: Option<&BackupEncryptionKey>,
+        stream: DumpStream,
+    ) -> Result<(compact_str::CompactString, i64), anyhow::Error> {
+        let mut stream = match encryption_key {
+            Some(key) => {
+                encryption::transform_stream(stream, encryption::BackupEncryptor::new(key)).boxed()
+            }
+            None => stream,
+        };
+
+        let part_size = s3_configuration.part_size;
+        let (client, bucket) = s3_configuration.into_client();
+        let mut upload = MultipartUpload::create(client, bucket, file_path, part_size).await?;
+
+        let uploaded = async {
+            while let Some(chunk) = stream.next().await {
+                upload.write(&chunk?).await?;
+            }
+
+            upload.complete().await
+        }
+        .await;
+
+        match uploaded {
+            Ok(bytes) => Ok((upload.file_path().into(), bytes)),
+            Err(err) => {
+                upload.abort().await;
+                Err(err)
+            }
+        }
+    }
+
+    async fn finish(
+        &self,
+        state: &crate::State,
+        uploaded: Result<(compact_str::CompactString, i64), anyhow::Error>,
+    ) -> Result<(), anyhow::Error> {
+        match uploaded {
+            Ok((upload_path, bytes)) => {
+                sqlx::query(
+                    r#"
+                    UPDATE server_backup_databases
+                    SET bytes = $2, upload_path = $3, error = NULL, completed = NOW()
+                    WHERE server_backup_databases.uuid = $1
+                    "#,
+                )
+                .bind(self.uuid)
+                .bind(bytes)
+                .bind(upload_path)
+                .execute(state.database.write())
+                .await?;
+            }
+            Err(err) => {
+                tracing::warn!(
+                    backup = %self.backup_uuid,
+                    database = %self.name,
+                    "failed to dump database for backup: {:#?}",
+                    err
+                );
+
+                sqlx::query(
+                    r#"
+                    UPDATE server_backup_databases
+                    SET error = $2
+                    WHERE server_backup_databases.uuid = $1
+                    "#,
+                )
+                .bind(self.uuid)
+                .bind(err.to_string())
+                .execute(state.database.write())
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn download(
+        &self,
+        state: &crate::State,
+        backup: &ServerBackup,
+    ) -> Result<DumpStream, anyhow::Error> {
+        let Some(upload_path) = &self.upload_path else {
+            return Err(anyhow::anyhow!(
+                "database dump does not have an upload path"
+            ));
+        };
+        let Some(server) = &backup.server else {
+            return Err(anyhow::anyhow!("backup is no longer attached to a server"));
+        };
+
+        let encryption_key = if self.encrypted {
+            Some(BackupEncryptionKey::for_server(&state.database, server.uuid).await?)
+        } else {
+            None
+        };
+
+        Self::download_path(
+            state,
+            Self::s3_configuration(state, backup).await?,
+            upload_path,
+            encryption_key.as_ref(),
+        )
+        .await
+    }
+
+    /// Streams a stored dump back from S3, decrypting it when a key is given.
+    pub(crate) async fn download_path(
+        state: &crate::State,
+        s3_configuration: super::backup_configuration::BackupConfigsS3,
+        upload_path: &str,
+        encryption_key: Option<&BackupEncryptionKey>,
+    ) -> Result<DumpStream, anyhow::Error> {
+        let (client, bucket) = s3_configuration.into_client();
+        let presigned = client
+            .get_object()
+            .bucket(&*bucket)
+            .key(upload_path)
+            .presigned(aws_sdk_s3::presigning::PresigningConfig::expires_in(
+                std::time::Duration::from_mins(60),
+            )?)
+            .await?;
+
+        let response = state
+            .client
+            .get(presigned.uri())
+            .send()
+            .await?
+            .error_for_status()?;
+
+        match encryption_key {
+            Some(key) => Ok(encryption::transform_stream(
+                response.bytes_stream(),
+                encryption::BackupDecryptor::new(key),
+            )
+            .boxed()),
+            None => Ok(response
+                .bytes_stream()
+                .map(|chunk| {
+                    chunk
+                        .map(|chunk| chunk.to_vec())
+                        .map_err(Into::<anyhow::Error>::into)
+                })
+                .boxed()),
+        }
+    }
+
+    /// Restores the dump into the database it was taken from, replacing its contents. The
+    /// database has to be attached to the server the backup is restored to.
+    pub async fn restore(
+        &self,
+        state: &crate::State,
+        backup: &ServerBackup,
+        server_uuid: uuid::Uuid,
+    ) -> Result<(), anyhow::Error> {
+        if let Some(server_database_uuid) = self.server_database_uuid {
+            let mut server_database = ServerDatabase::by_server_uuid_uuid(
+                &state.database,
+                server_uuid,
+                server_database_uuid,
+            )
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("database no longer exists"))?;
+
+            let dump = self.download(state, backup).await?;
+            return server_database.restore_dump(&state.database, dump).await;
+        }
+
+        let Some(instance_uuid) = self.server_database_instance_uuid else {
+            return Err(anyhow::anyhow!("database no longer exists"));
+        };
+        let instance = ServerDatabaseInstance::by_server_uuid_uuid(
+            &state.database,
+            server_uuid,
+            instance_uuid,
+        )
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("database instance no longer exists"))?;
+
+        let client = instance
+            .database_agent_host
+            .api_client(&state.database)
+            .await?;
+
+        // databases may have been renamed since, so the current name is looked up
+        let db = match self.instance_database_uuid {
+            Some(instance_database_uuid) => Some(
+                client
+                    .get_instances_instance_databases_database(
+                        instance.uuid,
+                        instance_database_uuid,
+                    )
+                    .await?
+                    .database
+                    .name,
+            ),
+            None => None,
+        };
+
+        let dump = self.download(state, backup).await?;
+        client
+            .post_instances_instance_import(
+                instance.uuid,
+                db_agent_api::client::AsyncRequestReader::new(tokio_util::io::StreamReader::new(
+                    dump.map_ok(tokio_util::bytes::Bytes::from)
+                        .map_err(std::io::Error::other),
+                )),
+                &db_agent_api::instances_instance_import::post::Query {
+                    source_db: self.instance_database_uuid.map(|_| self.name.clone()),
+                    db,
+                    wipe: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Restores the given dumps one after another, logging the outcome of each one as server
+    /// activity.
+    pub async fn restore_all(
+        state: &crate::State,
+        backup: &ServerBackup,
+        server_uuid: uuid::Uuid,
+        dumps: Vec<Self>,
+    ) {
+        for dump in dumps {
+            let (event, error) = match dump.restore(state, backup, server_uuid).await {
+                Ok(()) => ("server:backup.database-restored", None),
+                Err(err) => {
+                    tracing::warn!(
+                        backup = %backup.uuid,
+                        database = %dump.name,
+                        "failed to restore database dump: {:#?}",
+                        err
+                    );
+
+                    (
+                        "server:backup.database-restore-failed",
+                        Some(err.to_string()),
+                    )
+                }
+            };
+
+            if let Err(err) = super::server_activity::ServerActivity::create(
+                state,
+                super::server_activity::CreateServerActivityOptions {
+                    server_uuid,
+                    user_uuid: None,
+                    impersonator_uuid: None,
+                    api_key_uuid: None,
+                    schedule_uuid: None,
+                    event: event.into(),
+                    ip: None,
+                    data: serde_json::json!({
+                        "uuid": backup.uuid,
+                        "name": backup.name,
+                        "database_uuid": dump.uuid,
+                        "database_name": dump.name,
+                        "error": error,
+                    }),
+                    created: None,
+                },
+            )
+            .await
+            {
+                tracing::warn!(
+                    backup = %backup.uuid,
+                    "failed to log database restore activity: {:#?}",
+                    err
+                );
+            }
+        }
+    }
+
+    /// Removes every database dump of a backup, including the stored files. Failing to delete
+    /// a file is logged and does not keep the record around.
+    pub async fn purge_by_backup(
+        state: &crate::State,
+        backup: &ServerBackup,
+    ) -> Result<u64, anyhow::Error> {
+        let dumps = Self::all_by_backup_uuid(&state.database, backup.uuid).await?;
+
+        if dumps.iter().any(|dump| dump.upload_path.is_some()) {
+            match Self::s3_configuration(state, backup).await {
+                Ok(s3_configuration) => {
+                    let (client, bucket) = s3_configuration.into_client();
+
+                    for dump in &dumps {
+                        let Some(upload_path) = &dump.upload_path else {
+                            continue;
+                        };
+
+                        if let Err(err) = client
+                            .delete_object()
+                            .bucket(&*bucket)
+                            .key(&**upload_path)
+                            .send()
+                            .await
+                        {
+                            tracing::warn!(
+                                backup = %backup.uuid,
+                                database = %dump.name,
+                                "failed to delete database dump from S3: {:?}",
+                                err
+                            );
+                        }
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        backup = %backup.uuid,
+                        "unable to load S3 configuration to delete database dumps: {:#?}",
+                        err
+                    );
+                }
+            }
+        }
+
+        let deleted = sqlx::query(
+            r#"
+            DELETE FROM server_backup_databases
+            WHERE server_backup_databases.backup_uuid = $1
+            "#,
+        )
+        .bind(backup.uuid)
+        .execute(state.database.write())
+        .await?
+        .rows_affected();
+
+        Ok(deleted)
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoAdminApiObject for ServerBackupDatabase {
+    type AdminApiObject = AdminApiServerBackupDatabase;
+    type ExtraArgs<'a> = ();
+
+    async fn into_admin_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::AdminApiObject, crate::database::DatabaseError> {
+        let status = self.status();
+        let api_object = AdminApiServerBackupDatabase::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            AdminApiServerBackupDatabase {
+                uuid: self.uuid,
+                server_database_uuid: self.server_database_uuid,
+                server_database_instance_uuid: self.server_database_instance_uuid,
+                name: self.name,
+                status,
+                encrypted: self.encrypted,
+                bytes: self.bytes,
+                error: self.error,
+                completed: self.completed.map(|dt| dt.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerBackupDatabase {
+    type ApiObject = ApiServerBackupDatabase;
+    type ExtraArgs<'a> = ();
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let status = self.status();
+        let api_object = ApiServerBackupDatabase::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            ApiServerBackupDatabase {
+                uuid: self.uuid,
+                server_database_uuid: self.server_database_uuid,
+                server_database_instance_uuid: self.server_database_instance_uuid,
+                name: self.name,
+                status,
+                bytes: self.bytes,
+                completed: self.completed.map(|dt| dt.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(Validate)]
+pub struct CreateServerBackupDatabaseOptions {
+    #[garde(skip)]
+    pub backup_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub server_database_uuid: Option<uuid::Uuid>,
+    #[garde(skip)]
+    pub server_database_instance_uuid: Option<uuid::Uuid>,
+    #[garde(skip)]
+    pub instance_database_uuid: Option<uuid::Uuid>,
+    #[garde(length(chars, min = 1, max = 255))]
+    pub name: compact_str::CompactString,
+    #[garde(skip)]
+    pub encrypted: bool,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerBackupDatabase {
+    type CreateOptions<'a> = CreateServerBackupDatabaseOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerBackupDatabase>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_backup_databases");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set("backup_uuid", options.backup_uuid)
+            .set("server_database_uuid", options.server_database_uuid)
+            .set(
+                "server_database_instance_uuid",
+                options.server_database_instance_uuid,
+            )
+            .set("instance_database_uuid", options.instance_database_uuid)
+            .set("name", &options.name)
+            .set("encrypted", options.encrypted);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut dump = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut dump, &options, state, transaction).await?;
+
+        Ok(dump)
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerBackupDatabase, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerBackupDatabase")]
+pub struct ApiServerBackupDatabase {
+    pub uuid: uuid::Uuid,
+    pub server_database_uuid: Option<uuid::Uuid>,
+    pub server_database_instance_uuid: Option<uuid::Uuid>,
+    pub name: compact_str::CompactString,
+
+    pub status: ServerBackupDatabaseStatus,
+    pub bytes: i64,
+
+    pub completed: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerBackupDatabase, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminServerBackupDatabase")]
+pub struct AdminApiServerBackupDatabase {
+    pub uuid: uuid::Uuid,
+    pub server_database_uuid: Option<uuid::Uuid>,
+    pub server_database_instance_uuid: Option<uuid::Uuid>,
+    pub name: compact_str::CompactString,
+
+    pub status: ServerBackupDatabaseStatus,
+    pub encrypted: bool,
+    pub bytes: i64,
+    pub error: Option<compact_str::CompactString>,
+
+    pub completed: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
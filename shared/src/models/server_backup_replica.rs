@@ -0,0 +1,685 @@
+use crate::{
+    models::{
+        InsertQueryBuilder,
+        server_backup::{BackupDisk, multipart::MultipartUpload},
+    },
+    prelude::*,
+};
+use compact_str::ToCompactString;
+use futures_util::StreamExt;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Debug, ToSchema, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerBackupReplicaStatus {
+    Pending,
+    Replicated,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerBackupReplica {
+    pub uuid: uuid::Uuid,
+    pub backup_uuid: uuid::Uuid,
+    pub backup_configuration: Option<Fetchable<super::backup_configuration::BackupConfiguration>>,
+
+    pub bytes: i64,
+    pub upload_path: Option<compact_str::CompactString>,
+
+    pub attempts: i32,
+    pub error: Option<compact_str::CompactString>,
+
+    pub last_attempt: Option<chrono::NaiveDateTime>,
+    pub completed: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerBackupReplica {
+    const NAME: &'static str = "server_backup_replica";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_backup_replicas.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_backup_replicas.backup_uuid",
+                compact_str::format_compact!("{prefix}backup_uuid"),
+            ),
+            (
+                "server_backup_replicas.backup_configuration_uuid",
+                compact_str::format_compact!("{prefix}backup_configuration_uuid"),
+            ),
+            (
+                "server_backup_replicas.bytes",
+                compact_str::format_compact!("{prefix}bytes"),
+            ),
+            (
+                "server_backup_replicas.upload_path",
+                compact_str::format_compact!("{prefix}upload_path"),
+            ),
+            (
+                "server_backup_replicas.attempts",
+                compact_str::format_compact!("{prefix}attempts"),
+            ),
+            (
+                "server_backup_replicas.error",
+                compact_str::format_compact!("{prefix}error"),
+            ),
+            (
+                "server_backup_replicas.last_attempt",
+                compact_str::format_compact!("{prefix}last_attempt"),
+            ),
+            (
+                "server_backup_replicas.completed",
+                compact_str::format_compact!("{prefix}completed"),
+            ),
+            (
+                "server_backup_replicas.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            backup_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}backup_uuid").as_str())?,
+            backup_configuration:
+                super::backup_configuration::BackupConfiguration::get_fetchable_from_row(
+                    row,
+                    compact_str::format_compact!("{prefix}backup_configuration_uuid"),
+                ),
+            bytes: row.try_get(compact_str::format_compact!("{prefix}bytes").as_str())?,
+            upload_path: row
+                .try_get(compact_str::format_compact!("{prefix}upload_path").as_str())?,
+            attempts: row.try_get(compact_str::format_compact!("{prefix}attempts").as_str())?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            last_attempt: row
+                .try_get(compact_str::format_compact!("{prefix}last_attempt").as_str())?,
+            completed: row.try_get(compact_str::format_compact!("{prefix}completed").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerBackupReplica {
+    pub const MAX_REPLICATION_ATTEMPTS: i32 = 8;
+
+    pub async fn all_by_backup_uuid(
+        database: &crate::database::Database,
+        backup_uuid: uuid::Uuid,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_backup_replicas
+            WHERE server_backup_replicas.backup_uuid = $1
+            ORDER BY server_backup_replicas.created
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(backup_uuid)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn all_by_backup_uuids(
+        database: &crate::database::Database,
+        backup_uuids: &[uuid::Uuid],
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_backup_replicas
+            WHERE server_backup_replicas.backup_uuid = ANY($1)
+            ORDER BY server_backup_replicas.created
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(backup_uuids)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Returns the oldest completed replica of a backup whose backup configuration is
+    /// still around and not in maintenance mode.
+    pub async fn restorable_by_backup_uuid(
+        state: &crate::State,
+        backup_uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, anyhow::Error> {
+        for replica in Self::all_by_backup_uuid(&state.database, backup_uuid).await? {
+            if replica.completed.is_none() {
+                continue;
+            }
+
+            let Some(backup_configuration) = &replica.backup_configuration else {
+                continue;
+            };
+            if backup_configuration
+                .fetch_cached(&state.database)
+                .await?
+                .maintenance_enabled
+            {
+                continue;
+            }
+
+            return Ok(Some(replica));
+        }
+
+        Ok(None)
+    }
+
+    /// Claims replicas that are due for a (re)try, backing off exponentially between attempts.
+    /// Running replications bump `last_attempt` after every uploaded part, so they are never
+    /// claimed twice.
+    pub async fn claim_due(
+        database: &crate::database::Database,
+        limit: i64,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            UPDATE server_backup_replicas
+            SET last_attempt = NOW(), attempts = server_backup_replicas.attempts + 1
+            WHERE server_backup_replicas.uuid IN (
+                SELECT server_backup_replicas.uuid
+                FROM server_backup_replicas
+                WHERE
+                    server_backup_replicas.completed IS NULL
+                    AND server_backup_replicas.backup_configuration_uuid IS NOT NULL
+                    AND server_backup_replicas.attempts < $1
+                    AND (
+                        server_backup_replicas.last_attempt IS NULL
+                        OR server_backup_replicas.last_attempt < NOW() - make_interval(mins => LEAST(60.0, 5.0 * POWER(2.0, server_backup_replicas.attempts))::int)
+                    )
+                ORDER BY server_backup_replicas.created
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(Self::MAX_REPLICATION_ATTEMPTS)
+        .bind(limit)
+        .fetch_all(database.write())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    #[inline]
+    pub fn status(&self) -> ServerBackupReplicaStatus {
+        if self.completed.is_some() {
+            ServerBackupReplicaStatus::Replicated
+        } else if self.attempts >= Self::MAX_REPLICATION_ATTEMPTS
+            || self.backup_configuration.is_none()
+        {
+            ServerBackupReplicaStatus::Failed
+        } else {
+            ServerBackupReplicaStatus::Pending
+        }
+    }
+
+    async fn s3_configuration(
+        &self,
+        state: &crate::State,
+    ) -> Result<super::backup_configuration::BackupConfigsS3, anyhow::Error> {
+        let backup_configuration = self
+            .backup_configuration
+            .as_ref()
+            .ok_or_else(|| {
+                crate::response::DisplayError::new(
+                    "replica does not have a backup configuration assigned",
+                )
+                .with_status(reqwest::StatusCode::EXPECTATION_FAILED)
+            })?
+            .fetch_cached(&state.database)
+            .await?;
+
+        let Some(mut s3_configuration) = backup_configuration.backup_configs.s3 else {
+            return Err(crate::response::DisplayError::new(
+                "replica backup configuration does not have an S3 configuration",
+            )
+            .with_status(reqwest::StatusCode::EXPECTATION_FAILED)
+            .into());
+        };
+        s3_configuration.decrypt(&state.database).await?;
+
+        Ok(s3_configuration)
+    }
+
+    pub async fn presigned_url(
+        &self,
+        state: &crate::State,
+        expires_in: std::time::Duration,
+    ) -> Result<compact_str::CompactString, anyhow::Error> {
+        let Some(upload_path) = &self.upload_path else {
+            return Err(
+                crate::response::DisplayError::new("replica does not have an upload path")
+                    .with_status(reqwest::StatusCode::EXPECTATION_FAILED)
+                    .into(),
+            );
+        };
+
+        let (client, bucket) = self.s3_configuration(state).await?.into_client();
+
+        let presigning_config = aws_sdk_s3::presigning::PresigningConfig::expires_in(expires_in)?;
+        let presigned = client
+            .get_object()
+            .bucket(bucket)
+            .key(&**upload_path)
+            .presigned(presigning_config)
+            .await?;
+
+        Ok(presigned.uri().to_compact_string())
+    }
+
+    /// Copies the primary backup archive into the replica bucket. The archive is streamed
+    /// straight from the primary location (S3 or the node) into a multipart upload.
+    pub async fn replicate(&self, state: &crate::State) -> Result<(), anyhow::Error> {
+        let backup =
+            super::server_backup::ServerBackup::by_uuid(&state.database, self.backup_uuid).await?;
+        let Some(server) = &backup.server else {
+            return Err(anyhow::anyhow!("backup is no longer attached to a server"));
+        };
+
+        let s3_configuration = self.s3_configuration(state).await?;
+        let part_size = s3_configuration.part_size;
+
+        let (source_url, compression_type) = if backup.disk == BackupDisk::S3 {
+            let compression_type = match &backup.backup_configuration {
+                Some(backup_configuration) => backup_configuration
+                    .fetch_cached(&state.database)
+                    .await?
+                    .backup_configs
+                    .s3
+                    .map_or(s3_configuration.compression_type, |s3| s3.compression_type),
+                None => s3_configuration.compression_type,
+            };
+
+            let source_url = backup
+                .s3_object_url(state, server.uuid, std::time::Duration::from_mins(60))
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("primary backup does not have an S3 location"))?;
+
+            (source_url.to_string(), compression_type)
+        } else {
+            let node = backup.node.fetch_cached(&state.database).await?;
+
+            let source_url = backup
+                .node_download_url(
+                    state,
+                    &node,
+                    self.uuid,
+                    false,
+                    match s3_configuration.compression_type {
+                        wings_api::CompressionType::None => wings_api::StreamableArchiveFormat::Tar,
+                        wings_api::CompressionType::Gz => wings_api::StreamableArchiveFormat::TarGz,
+                        wings_api::CompressionType::Xz => wings_api::StreamableArchiveFormat::TarXz,
+                        wings_api::CompressionType::Lzip => {
+                            wings_api::StreamableArchiveFormat::TarLzip
+                        }
+                        wings_api::CompressionType::Bz2 => {
+                            wings_api::StreamableArchiveFormat::TarBz2
+                        }
+                        wings_api::CompressionType::Lz4 => {
+                            wings_api::StreamableArchiveFormat::TarLz4
+                        }
+                        wings_api::CompressionType::Zstd => {
+                            wings_api::StreamableArchiveFormat::TarZstd
+                        }
+                    },
+                )
+                .await?;
+
+            (source_url, s3_configuration.compression_type)
+        };
+
+        let response = state
+            .client
+            .get(source_url)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let mut file_path =
+            super::server_backup::ServerBackup::s3_path(server.uuid, backup.uuid, compression_type);
+        if backup.encrypted {
+            file_path.push_str(".enc");
+        }
+
+        let (client, bucket) = s3_configuration.into_client();
+        let mut upload = MultipartUpload::create(client, bucket, file_path, part_size).await?;
+
+        let uploaded = async {
+            let mut stream = std::pin::pin!(response.bytes_stream());
+            while let Some(chunk) = stream.next().await {
+                if upload.write(&chunk?).await? {
+                    sqlx::query(
+                        "UPDATE server_backup_replicas
+                        SET last_attempt = NOW()
+                        WHERE server_backup_replicas.uuid = $1",
+                    )
+                    .bind(self.uuid)
+                    .execute(state.database.write())
+                    .await?;
+                }
+            }
+
+            upload.complete().await
+        }
+        .await;
+
+        let bytes = match uploaded {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                upload.abort().await;
+                return Err(err);
+            }
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE server_backup_replicas
+            SET bytes = $2, upload_path = $3, error = NULL, completed = NOW()
+            WHERE server_backup_replicas.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(bytes)
+        .bind(upload.file_path())
+        .execute(state.database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn fail_attempt(
+        &self,
+        state: &crate::State,
+        error: &anyhow::Error,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query(
+            r#"
+            UPDATE server_backup_replicas
+            SET error = $2
+            WHERE server_backup_replicas.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(error.to_string())
+        .execute(state.database.write())
+        .await?;
+
+        if self.attempts >= Self::MAX_REPLICATION_ATTEMPTS
+            && let Ok(backup) =
+                super::server_backup::ServerBackup::by_uuid(&state.database, self.backup_uuid).await
+            && let Some(server) = &backup.server
+            && let Err(err) = super::server_activity::ServerActivity::create(
+                state,
+                super::server_activity::CreateServerActivityOptions {
+                    server_uuid: server.uuid,
+                    user_uuid: None,
+                    impersonator_uuid: None,
+                    api_key_uuid: None,
+                    schedule_uuid: None,
+                    event: "server:backup.replication-failed".into(),
+                    ip: None,
+                    data: serde_json::json!({
+                        "uuid": backup.uuid,
+                        "name": backup.name,
+                    }),
+                    created: None,
+                },
+            )
+            .await
+        {
+            tracing::warn!(
+                replica = %self.uuid,
+                "failed to log backup replication failure activity: {:#?}",
+                err
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Removes every replica of a backup, including the stored archives. Failing to delete an
+    /// archive is logged and does not keep the replica record around.
+    pub async fn purge_by_backup_uuid(
+        state: &crate::State,
+        backup_uuid: uuid::Uuid,
+    ) -> Result<u64, anyhow::Error> {
+        let replicas = Self::all_by_backup_uuid(&state.database, backup_uuid).await?;
+
+        for replica in &replicas {
+            let Some(upload_path) = &replica.upload_path else {
+                continue;
+            };
+
+            let s3_configuration = match replica.s3_configuration(state).await {
+                Ok(s3_configuration) => s3_configuration,
+                Err(err) => {
+                    tracing::warn!(
+                        replica = %replica.uuid,
+                        "unable to load S3 configuration to delete backup replica: {:#?}",
+                        err
+                    );
+                    continue;
+                }
+            };
+            let (client, bucket) = s3_configuration.into_client();
+
+            if let Err(err) = client
+                .delete_object()
+                .bucket(bucket)
+                .key(&**upload_path)
+                .send()
+                .await
+            {
+                tracing::warn!(
+                    replica = %replica.uuid,
+                    "failed to delete backup replica from S3: {:?}",
+                    err
+                );
+            }
+        }
+
+        let deleted = sqlx::query(
+            r#"
+            DELETE FROM server_backup_replicas
+            WHERE server_backup_replicas.backup_uuid = $1
+            "#,
+        )
+        .bind(backup_uuid)
+        .execute(state.database.write())
+        .await?
+        .rows_affected();
+
+        Ok(deleted)
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoAdminApiObject for ServerBackupReplica {
+    type AdminApiObject = AdminApiServerBackupReplica;
+    type ExtraArgs<'a> = ();
+
+    async fn into_admin_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::AdminApiObject, crate::database::DatabaseError> {
+        let status = self.status();
+        let api_object = AdminApiServerBackupReplica::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            AdminApiServerBackupReplica {
+                uuid: self.uuid,
+                backup_configuration_uuid: self.backup_configuration.map(|c| c.uuid),
+                status,
+                bytes: self.bytes,
+                attempts: self.attempts,
+                error: self.error,
+                last_attempt: self.last_attempt.map(|dt| dt.and_utc()),
+                completed: self.completed.map(|dt| dt.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerBackupReplica {
+    type ApiObject = ApiServerBackupReplica;
+    type ExtraArgs<'a> = ();
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let status = self.status();
+        let api_object = ApiServerBackupReplica::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            ApiServerBackupReplica {
+                uuid: self.uuid,
+                status,
+                bytes: self.bytes,
+                completed: self.completed.map(|dt| dt.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(Validate)]
+pub struct CreateServerBackupReplicaOptions {
+    #[garde(skip)]
+    pub backup_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub backup_configuration_uuid: uuid::Uuid,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerBackupReplica {
+    type CreateOptions<'a> = CreateServerBackupReplicaOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerBackupReplica>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_backup_replicas");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder.set("backup_uuid", options.backup_uuid).set(
+            "backup_configuration_uuid",
+            options.backup_configuration_uuid,
+        );
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut replica = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut replica, &options, state, transaction).await?;
+
+        Ok(replica)
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerBackupReplica, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerBackupReplica")]
+pub struct ApiServerBackupReplica {
+    pub uuid: uuid::Uuid,
+
+    pub status: ServerBackupReplicaStatus,
+    pub bytes: i64,
+
+    pub completed: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerBackupReplica, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminServerBackupReplica")]
+pub struct AdminApiServerBackupReplica {
+    pub uuid: uuid::Uuid,
+    pub backup_configuration_uuid: Option<uuid::Uuid>,
+
+    pub status: ServerBackupReplicaStatus,
+    pub bytes: i64,
+    pub attempts: i32,
+    pub error: Option<compact_str::CompactString>,
+
+    pub last_attempt: Option<chrono::DateTime<chrono::Utc>>,
+    pub completed: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
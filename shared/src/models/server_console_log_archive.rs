@@ -0,0 +1,428 @@
+use crate::{models::InsertQueryBuilder, prelude::*};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use tokio::io::AsyncReadExt;
+use utoipa::ToSchema;
+
+pub const COLLECT_LINES: u64 = 1000;
+/// Upper bound of console lines pulled from wings in a single collection.
+const MAX_COLLECT_WINDOW: u64 = 200_000;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerConsoleLogArchive {
+    pub uuid: uuid::Uuid,
+    pub server_uuid: uuid::Uuid,
+    pub user: Option<Fetchable<super::user::User>>,
+
+    pub collected_lines: i64,
+    pub last_line_hash: Option<compact_str::CompactString>,
+    pub stalled: bool,
+
+    pub last_collected: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerConsoleLogArchive {
+    const NAME: &'static str = "server_console_log_archive";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_console_log_archives.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_console_log_archives.server_uuid",
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            (
+                "server_console_log_archives.user_uuid",
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            (
+                "server_console_log_archives.collected_lines",
+                compact_str::format_compact!("{prefix}collected_lines"),
+            ),
+            (
+                "server_console_log_archives.last_line_hash",
+                compact_str::format_compact!("{prefix}last_line_hash"),
+            ),
+            (
+                "server_console_log_archives.stalled",
+                compact_str::format_compact!("{prefix}stalled"),
+            ),
+            (
+                "server_console_log_archives.last_collected",
+                compact_str::format_compact!("{prefix}last_collected"),
+            ),
+            (
+                "server_console_log_archives.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}server_uuid").as_str())?,
+            user: super::user::User::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            collected_lines: row
+                .try_get(compact_str::format_compact!("{prefix}collected_lines").as_str())?,
+            last_line_hash: row
+                .try_get(compact_str::format_compact!("{prefix}last_line_hash").as_str())?,
+            stalled: row.try_get(compact_str::format_compact!("{prefix}stalled").as_str())?,
+            last_collected: row
+                .try_get(compact_str::format_compact!("{prefix}last_collected").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerConsoleLogArchive {
+    pub async fn by_server_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_console_log_archives
+            WHERE server_console_log_archives.server_uuid = $1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn all_collectable(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_console_log_archives
+            JOIN servers ON servers.uuid = server_console_log_archives.server_uuid
+            WHERE NOT servers.suspended AND servers.status IS NULL
+            "#,
+            Self::columns_sql(None)
+        )))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Pulls the latest console output of the server from wings and archives every line
+    /// that was not seen in the previous collection. Returns the amount of archived lines.
+    ///
+    /// Wings console lines carry no timestamps, so the archive counts how many lines of the
+    /// current log it has collected and remembers a hash of the last one. Everything after
+    /// that count is new, unless the line at the count no longer matches the hash, in which
+    /// case the log was reset by a restart and is treated as new entirely. Logs longer than
+    /// [`MAX_COLLECT_WINDOW`] lines cannot be lined up anymore, the archive is marked as
+    /// stalled until the log is reset.
+    pub async fn collect(&mut self, state: &crate::State) -> Result<usize, anyhow::Error> {
+        let server = super::server::Server::by_uuid(&state.database, self.server_uuid).await?;
+        let client = server
+            .node
+            .fetch_cached(&state.database)
+            .await?
+            .api_client(&state.database)
+            .await?;
+
+        let mut window =
+            (self.collected_lines.max(0) as u64 + COLLECT_LINES).min(MAX_COLLECT_WINDOW);
+        let logs = loop {
+            let mut reader = client
+                .get_servers_server_logs(
+                    server.uuid,
+                    &wings_api::servers_server_logs::get::Query {
+                        lines: Some(window),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+
+            let mut logs = String::new();
+            reader.read_to_string(&mut logs).await?;
+
+            // a full window may hide older lines, widen it until the whole log fits
+            if (logs.lines().count() as u64) < window || window >= MAX_COLLECT_WINDOW {
+                break logs;
+            }
+
+            window = (window * 2).min(MAX_COLLECT_WINDOW);
+        };
+
+        let lines = logs.lines().collect::<Vec<_>>();
+
+        if lines.len() as u64 >= MAX_COLLECT_WINDOW {
+            if !self.stalled {
+                tracing::warn!(
+                    server = %self.server_uuid,
+                    "console log outgrew the collection window, archive is stalled until the log is reset"
+                );
+
+                sqlx::query(
+                    r#"
+                    UPDATE server_console_log_archives
+                    SET stalled = true
+                    WHERE server_console_log_archives.uuid = $1
+                    "#,
+                )
+                .bind(self.uuid)
+                .execute(state.database.write())
+                .await?;
+
+                self.stalled = true;
+            }
+
+            return Ok(0);
+        }
+
+        let collected_lines = self.collected_lines.max(0) as usize;
+        // archives collected before line hashes were stored only have the count to go by
+        let is_continuation = collected_lines == 0
+            || lines.get(collected_lines - 1).is_some_and(|line| {
+                self.last_line_hash.as_deref().is_none_or(|last_line_hash| {
+                    last_line_hash == hex::encode(sha2::Sha256::digest(line))
+                })
+            });
+        let new_lines = if is_continuation {
+            &lines[collected_lines.min(lines.len())..]
+        } else {
+            &lines[..]
+        };
+
+        let now = chrono::Utc::now().naive_utc();
+        let started = self.last_collected.unwrap_or(now);
+
+        if !new_lines.is_empty() {
+            super::server_console_log_chunk::ServerConsoleLogChunk::create(
+                state,
+                super::server_console_log_chunk::CreateServerConsoleLogChunkOptions {
+                    server_uuid: self.server_uuid,
+                    lines: new_lines.len() as i32,
+                    content: new_lines.join("\n"),
+                    started,
+                },
+            )
+            .await?;
+        }
+
+        let last_line_hash = lines
+            .last()
+            .map(|line| compact_str::CompactString::from(hex::encode(sha2::Sha256::digest(line))));
+
+        sqlx::query(
+            r#"
+            UPDATE server_console_log_archives
+            SET collected_lines = $2, last_line_hash = $3, stalled = false, last_collected = $4
+            WHERE server_console_log_archives.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(lines.len() as i64)
+        .bind(last_line_hash.as_deref())
+        .bind(now)
+        .execute(state.database.write())
+        .await?;
+
+        self.collected_lines = lines.len() as i64;
+        self.last_line_hash = last_line_hash;
+        self.stalled = false;
+        self.last_collected = Some(now);
+
+        Ok(new_lines.len())
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerConsoleLogArchive {
+    type ApiObject = ApiServerConsoleLogArchive;
+    type ExtraArgs<'a> = &'a crate::storage::StorageUrlRetriever<'a>;
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        storage_url_retriever: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiServerConsoleLogArchive::init_hooks(&self, state).await?;
+
+        let user = if let Some(user) = self.user {
+            Some(
+                user.fetch_cached(&state.database)
+                    .await?
+                    .into_api_object(state, storage_url_retriever)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        let stats = super::server_console_log_chunk::ServerConsoleLogChunk::stats_by_server_uuid(
+            &state.database,
+            self.server_uuid,
+        )
+        .await?;
+
+        let api_object = finish_extendible!(
+            ApiServerConsoleLogArchive {
+                uuid: self.uuid,
+                user,
+                stats,
+                stalled: self.stalled,
+                last_collected: self.last_collected.map(|dt| dt.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerConsoleLogArchiveOptions {
+    #[garde(skip)]
+    pub server_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub user_uuid: Option<uuid::Uuid>,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerConsoleLogArchive {
+    type CreateOptions<'a> = CreateServerConsoleLogArchiveOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerConsoleLogArchive>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_console_log_archives");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set("server_uuid", options.server_uuid)
+            .set("user_uuid", options.user_uuid);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut archive = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut archive, &options, state, transaction).await?;
+
+        Ok(archive)
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for ServerConsoleLogArchive {
+    type DeleteOptions = ();
+
+    fn get_delete_handlers() -> &'static LazyLock<DeleteHandlerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteHandlerList<ServerConsoleLogArchive>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete_with_transaction(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), anyhow::Error> {
+        self.run_delete_handlers(&options, state, transaction)
+            .await?;
+
+        super::server_console_log_chunk::ServerConsoleLogChunk::delete_by_server_uuid(
+            transaction,
+            self.server_uuid,
+        )
+        .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM server_console_log_archives
+            WHERE server_console_log_archives.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut **transaction)
+        .await?;
+
+        self.run_after_delete_handlers(&options, state, transaction)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerConsoleLogArchive, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerConsoleLogArchive")]
+pub struct ApiServerConsoleLogArchive {
+    pub uuid: uuid::Uuid,
+    pub user: Option<super::user::ApiUser>,
+
+    #[schema(inline)]
+    pub stats: super::server_console_log_chunk::ServerConsoleLogChunkStats,
+    pub stalled: bool,
+
+    pub last_collected: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,359 @@
+use crate::{models::InsertQueryBuilder, prelude::*};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Serialize)]
+pub struct ServerConsoleLogChunkStats {
+    pub chunks: i64,
+    pub lines: i64,
+    pub size: i64,
+    pub compressed_size: i64,
+
+    pub oldest: Option<chrono::DateTime<chrono::Utc>>,
+    pub newest: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerConsoleLogChunk {
+    pub uuid: uuid::Uuid,
+    pub server_uuid: uuid::Uuid,
+
+    pub lines: i32,
+    pub size: i64,
+    pub compressed_size: i64,
+
+    pub started: chrono::NaiveDateTime,
+    pub ended: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerConsoleLogChunk {
+    const NAME: &'static str = "server_console_log_chunk";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_console_log_chunks.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_console_log_chunks.server_uuid",
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            (
+                "server_console_log_chunks.lines",
+                compact_str::format_compact!("{prefix}lines"),
+            ),
+            (
+                "server_console_log_chunks.size",
+                compact_str::format_compact!("{prefix}size"),
+            ),
+            (
+                "server_console_log_chunks.compressed_size",
+                compact_str::format_compact!("{prefix}compressed_size"),
+            ),
+            (
+                "server_console_log_chunks.started",
+                compact_str::format_compact!("{prefix}started"),
+            ),
+            (
+                "server_console_log_chunks.ended",
+                compact_str::format_compact!("{prefix}ended"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}server_uuid").as_str())?,
+            lines: row.try_get(compact_str::format_compact!("{prefix}lines").as_str())?,
+            size: row.try_get(compact_str::format_compact!("{prefix}size").as_str())?,
+            compressed_size: row
+                .try_get(compact_str::format_compact!("{prefix}compressed_size").as_str())?,
+            started: row.try_get(compact_str::format_compact!("{prefix}started").as_str())?,
+            ended: row.try_get(compact_str::format_compact!("{prefix}ended").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerConsoleLogChunk {
+    /// Chunks in the given time range, oldest first.
+    pub async fn by_server_uuid_range(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        start: Option<chrono::DateTime<chrono::Utc>>,
+        end: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_console_log_chunks
+            WHERE server_console_log_chunks.server_uuid = $1
+                AND ($2 IS NULL OR server_console_log_chunks.ended >= $2)
+                AND ($3 IS NULL OR server_console_log_chunks.started <= $3)
+            ORDER BY server_console_log_chunks.started
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(start.map(|dt| dt.naive_utc()))
+        .bind(end.map(|dt| dt.naive_utc()))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Chunks containing all words of `query` in the given time range, newest first.
+    /// This only narrows down the chunks, the matching lines still have to be picked
+    /// out of the decompressed content.
+    pub async fn search_by_server_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        query: &str,
+        start: Option<chrono::DateTime<chrono::Utc>>,
+        end: Option<chrono::DateTime<chrono::Utc>>,
+        limit: i64,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_console_log_chunks
+            WHERE server_console_log_chunks.server_uuid = $1
+                AND server_console_log_chunks.search_vector @@ plainto_tsquery('simple', $2)
+                AND ($3 IS NULL OR server_console_log_chunks.ended >= $3)
+                AND ($4 IS NULL OR server_console_log_chunks.started <= $4)
+            ORDER BY server_console_log_chunks.started DESC
+            LIMIT $5
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(query)
+        .bind(start.map(|dt| dt.naive_utc()))
+        .bind(end.map(|dt| dt.naive_utc()))
+        .bind(limit)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn stats_by_server_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+    ) -> Result<ServerConsoleLogChunkStats, crate::database::DatabaseError> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                COUNT(*) AS chunks,
+                COALESCE(SUM(server_console_log_chunks.lines), 0)::int8 AS lines,
+                COALESCE(SUM(server_console_log_chunks.size), 0)::int8 AS size,
+                COALESCE(SUM(server_console_log_chunks.compressed_size), 0)::int8 AS compressed_size,
+                MIN(server_console_log_chunks.started) AS oldest,
+                MAX(server_console_log_chunks.ended) AS newest
+            FROM server_console_log_chunks
+            WHERE server_console_log_chunks.server_uuid = $1
+            "#,
+        )
+        .bind(server_uuid)
+        .fetch_one(database.read())
+        .await?;
+
+        Ok(ServerConsoleLogChunkStats {
+            chunks: row.try_get("chunks")?,
+            lines: row.try_get("lines")?,
+            size: row.try_get("size")?,
+            compressed_size: row.try_get("compressed_size")?,
+            oldest: row
+                .try_get::<Option<chrono::NaiveDateTime>, _>("oldest")?
+                .map(|dt| dt.and_utc()),
+            newest: row
+                .try_get::<Option<chrono::NaiveDateTime>, _>("newest")?
+                .map(|dt| dt.and_utc()),
+        })
+    }
+
+    pub async fn content(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<String, crate::database::DatabaseError> {
+        let content: Vec<u8> = sqlx::query_scalar(
+            r#"
+            SELECT server_console_log_chunks.content
+            FROM server_console_log_chunks
+            WHERE server_console_log_chunks.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .fetch_one(database.read())
+        .await?;
+
+        let content = tokio::task::spawn_blocking(move || zstd::decode_all(content.as_slice()))
+            .await
+            .map_err(anyhow::Error::from)?
+            .map_err(anyhow::Error::from)?;
+
+        Ok(String::from_utf8_lossy(&content).into_owned())
+    }
+
+    pub async fn delete_by_server_uuid(
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        server_uuid: uuid::Uuid,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_console_log_chunks
+            WHERE server_console_log_chunks.server_uuid = $1
+            "#,
+        )
+        .bind(server_uuid)
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_older_than(
+        database: &crate::database::Database,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_console_log_chunks
+            WHERE server_console_log_chunks.ended < $1
+            "#,
+        )
+        .bind(cutoff.naive_utc())
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Drops the oldest chunks of every server whose archive takes up more than
+    /// `max_size` compressed bytes.
+    pub async fn delete_exceeding_size(
+        database: &crate::database::Database,
+        max_size: i64,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_console_log_chunks
+            WHERE server_console_log_chunks.uuid IN (
+                SELECT uuid
+                FROM (
+                    SELECT
+                        uuid,
+                        SUM(compressed_size) OVER (
+                            PARTITION BY server_console_log_chunks.server_uuid
+                            ORDER BY server_console_log_chunks.ended DESC
+                        ) total_size
+                    FROM server_console_log_chunks
+                ) sub
+                WHERE sub.total_size > $1
+            )
+            "#,
+        )
+        .bind(max_size)
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerConsoleLogChunkOptions {
+    #[garde(skip)]
+    pub server_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub lines: i32,
+    #[garde(length(min = 1))]
+    #[schema(min_length = 1)]
+    pub content: String,
+    #[garde(skip)]
+    pub started: chrono::NaiveDateTime,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerConsoleLogChunk {
+    type CreateOptions<'a> = CreateServerConsoleLogChunkOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerConsoleLogChunk>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_console_log_chunks");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        let compressed =
+            zstd::encode_all(options.content.as_bytes(), 3).map_err(anyhow::Error::from)?;
+
+        query_builder
+            .set("server_uuid", options.server_uuid)
+            .set("lines", options.lines)
+            .set("size", options.content.len() as i64)
+            .set("compressed_size", compressed.len() as i64)
+            .set("content", compressed)
+            .set("started", options.started)
+            .set_expr(
+                "search_vector",
+                "to_tsvector('simple', $1)",
+                vec![options.content.as_str()],
+            );
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut chunk = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut chunk, &options, state, transaction).await?;
+
+        Ok(chunk)
+    }
+}
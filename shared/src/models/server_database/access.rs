@@ -0,0 +1,166 @@
+use super::ServerDatabase;
+use crate::models::database_host::DatabasePool;
+
+impl ServerDatabase {
+    /// Creates a user on the database host that can only read this database.
+    pub(crate) async fn create_read_only_user(
+        &mut self,
+        database: &crate::database::Database,
+        username: &str,
+        password: &str,
+    ) -> Result<(), anyhow::Error> {
+        match self.database_host.get_connection(database).await? {
+            DatabasePool::Mysql(pool) => {
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "CREATE USER '{username}'@'%' IDENTIFIED BY '{password}'"
+                )))
+                .execute(&pool)
+                .await?;
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "GRANT SELECT, SHOW VIEW ON `{}`.* TO '{username}'@'%'",
+                    self.name
+                )))
+                .execute(&pool)
+                .await?;
+            }
+            DatabasePool::Postgres(pool) => {
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "CREATE USER \"{username}\" WITH PASSWORD '{password}'"
+                )))
+                .execute(&pool)
+                .await?;
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "GRANT CONNECT ON DATABASE \"{}\" TO \"{username}\"",
+                    self.name
+                )))
+                .execute(&pool)
+                .await?;
+
+                // the user may only read, but PUBLIC can create temporary tables and, before
+                // postgres 15, objects in the public schema, so both are left to the owner
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "REVOKE TEMPORARY ON DATABASE \"{}\" FROM PUBLIC",
+                    self.name
+                )))
+                .execute(&pool)
+                .await?;
+
+                let mut connection = self
+                    .database_host
+                    .connect_postgres_database(database, &self.name)
+                    .await?;
+                sqlx::raw_sql(sqlx::AssertSqlSafe(format!(
+                    r#"
+                    DO $$
+                    BEGIN
+                        IF EXISTS (
+                            SELECT FROM pg_namespace,
+                                aclexplode(coalesce(nspacl, acldefault('n', nspowner))) AS acl
+                            WHERE nspname = 'public'
+                                AND acl.grantee = 0
+                                AND acl.privilege_type = 'CREATE'
+                        ) THEN
+                            REVOKE CREATE ON SCHEMA public FROM PUBLIC;
+                            GRANT CREATE ON SCHEMA public TO "{}";
+                        END IF;
+                    END $$;
+                    "#,
+                    self.username
+                )))
+                .execute(&mut connection)
+                .await?;
+
+                // table privileges can only be granted from inside the database, by its owner
+                self.run_query(
+                    database,
+                    &format!(
+                        "GRANT USAGE ON SCHEMA public TO \"{username}\";
+                        GRANT SELECT ON ALL TABLES IN SCHEMA public TO \"{username}\";
+                        ALTER DEFAULT PRIVILEGES IN SCHEMA public GRANT SELECT ON TABLES TO \"{username}\";"
+                    ),
+                    1,
+                    false,
+                )
+                .await?;
+            }
+            DatabasePool::Mongodb(client) => {
+                let cmd = mongodb::bson::doc! {
+                    "createUser": username,
+                    "pwd": password,
+                    "roles": [
+                        { "role": "read", "db": self.name.to_string() }
+                    ]
+                };
+                client.database(&self.name).run_command(cmd).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drops a user created by [`Self::create_read_only_user`], closing its open sessions.
+    pub(crate) async fn drop_read_only_user(
+        &mut self,
+        database: &crate::database::Database,
+        username: &str,
+    ) -> Result<(), anyhow::Error> {
+        match self.database_host.get_connection(database).await? {
+            DatabasePool::Mysql(pool) => {
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "DROP USER IF EXISTS '{username}'@'%'"
+                )))
+                .execute(&pool)
+                .await?;
+
+                let sessions: Vec<u64> = sqlx::query_scalar(
+                    "SELECT ID FROM information_schema.PROCESSLIST WHERE USER = ?",
+                )
+                .bind(username)
+                .fetch_all(&pool)
+                .await?;
+                for session in sessions {
+                    sqlx::query(sqlx::AssertSqlSafe(format!("KILL {session}")))
+                        .execute(&pool)
+                        .await
+                        .ok();
+                }
+            }
+            DatabasePool::Postgres(pool) => {
+                self.run_query(
+                    database,
+                    &format!(
+                        "ALTER DEFAULT PRIVILEGES IN SCHEMA public REVOKE SELECT ON TABLES FROM \"{username}\";
+                        REVOKE SELECT ON ALL TABLES IN SCHEMA public FROM \"{username}\";
+                        REVOKE USAGE ON SCHEMA public FROM \"{username}\";"
+                    ),
+                    1,
+                    false,
+                )
+                .await?;
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "REVOKE CONNECT ON DATABASE \"{}\" FROM \"{username}\"",
+                    self.name
+                )))
+                .execute(&pool)
+                .await?;
+                sqlx::query(
+                    "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE usename = $1",
+                )
+                .bind(username)
+                .execute(&pool)
+                .await?;
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "DROP USER IF EXISTS \"{username}\""
+                )))
+                .execute(&pool)
+                .await?;
+            }
+            DatabasePool::Mongodb(client) => {
+                let cmd = mongodb::bson::doc! { "dropUser": username };
+                client.database(&self.name).run_command(cmd).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
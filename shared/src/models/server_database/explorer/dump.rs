@@ -0,0 +1,766 @@
+//! Logical dumps of server databases.
+//!
+//! SQL databases are dumped to plain SQL scripts, read inside a single consistent snapshot:
+//! every table is dropped, recreated from its definition and refilled through multi-row
+//! inserts whose literals are rendered by the database itself. MongoDB databases are dumped to
+//! JSON lines, each holding a collection name and either a document or an index in canonical
+//! extended JSON.
+
+use super::{ExplorerConnection, SchemaColumn, ServerDatabase, display};
+use crate::models::database_host::{DatabasePool, DatabaseType};
+use futures_util::{Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+
+const DUMP_CHUNK_SIZE: usize = 1024 * 1024;
+const MONGODB_INSERT_BATCH: usize = 1000;
+
+/// Receives the chunks of a dump. A failing dump sends its error as the last item, so the
+/// receiving end can tell a finished dump from a truncated one.
+pub type DumpSender = tokio::sync::mpsc::Sender<Result<Vec<u8>, anyhow::Error>>;
+
+pub(super) async fn send(output: &DumpSender, data: Vec<u8>) -> Result<(), anyhow::Error> {
+    output
+        .send(Ok(data))
+        .await
+        .map_err(|_| anyhow::anyhow!("database dump consumer has gone away"))
+}
+
+async fn send_statement(output: &DumpSender, statement: &str) -> Result<(), anyhow::Error> {
+    send(output, format!("{statement};\n").into_bytes()).await
+}
+
+/// Collects the rendered rows of a table into multi-row inserts.
+pub struct DumpRows<'a> {
+    output: &'a DumpSender,
+    prefix: String,
+    buffer: Vec<u8>,
+}
+
+impl<'a> DumpRows<'a> {
+    fn new(output: &'a DumpSender, prefix: String) -> Self {
+        Self {
+            output,
+            prefix,
+            buffer: Vec::new(),
+        }
+    }
+
+    pub async fn push(&mut self, row: &[u8]) -> Result<(), anyhow::Error> {
+        if self.buffer.is_empty() {
+            self.buffer.extend_from_slice(self.prefix.as_bytes());
+        } else {
+            self.buffer.extend_from_slice(b",\n");
+        }
+        self.buffer.extend_from_slice(row);
+
+        if self.buffer.len() >= DUMP_CHUNK_SIZE {
+            self.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<(), anyhow::Error> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        self.buffer.extend_from_slice(b";\n");
+        send(self.output, std::mem::take(&mut self.buffer)).await
+    }
+}
+
+/// The statements recreating a table. `finish` runs once the data of every table is loaded,
+/// so foreign keys and indexes do not slow down or break the inserts.
+pub struct TableDefinition {
+    pub create: Vec<String>,
+    pub finish: Vec<String>,
+}
+
+enum SplitState {
+    Code,
+    Quoted {
+        quote: u8,
+        backslash_escapes: bool,
+        escape_next: bool,
+    },
+    LineComment,
+    /// MySQL runs the contents of `/*! */` comments, so those are kept in the statement.
+    BlockComment {
+        depth: usize,
+        executable: bool,
+    },
+}
+
+/// Splits a SQL script into statements on `;` outside of quoted strings, identifiers and
+/// comments. Comments are left out of the statements, apart from the executable ones of MySQL.
+struct StatementSplitter {
+    mysql: bool,
+    pending: Vec<u8>,
+    statement: Vec<u8>,
+    state: SplitState,
+}
+
+impl StatementSplitter {
+    fn new(r#type: DatabaseType) -> Self {
+        Self {
+            mysql: matches!(r#type, DatabaseType::Mysql),
+            pending: Vec::new(),
+            statement: Vec::new(),
+            state: SplitState::Code,
+        }
+    }
+
+    fn statement(bytes: &[u8]) -> Result<Option<String>, anyhow::Error> {
+        let statement = std::str::from_utf8(bytes)
+            .map_err(|_| anyhow::anyhow!("database dump is not valid UTF-8"))?
+            .trim();
+
+        Ok((!statement.is_empty()).then(|| statement.to_string()))
+    }
+
+    fn push(&mut self, data: &[u8]) -> Result<Vec<String>, anyhow::Error> {
+        self.pending.extend_from_slice(data);
+
+        self.scan(false)
+    }
+
+    /// Scans the pending bytes, stopping early when a byte cannot be told apart without the
+    /// bytes after it, unless the script has `ended`.
+    fn scan(&mut self, ended: bool) -> Result<Vec<String>, anyhow::Error> {
+        let mut statements = Vec::new();
+        let mut index = 0;
+
+        while index < self.pending.len() {
+            let byte = self.pending[index];
+            let next = self.pending.get(index + 1).copied();
+            let after_next = self.pending.get(index + 2).copied();
+            let mut consumed = 1;
+
+            match &mut self.state {
+                SplitState::Code => match byte {
+                    b'\'' | b'"' | b'`' => {
+                        // postgres only honours backslashes inside E'' strings
+                        let backslash_escapes = byte != b'`'
+                            && (self.mysql
+                                || (byte == b'\''
+                                    && matches!(self.statement.last(), Some(b'E' | b'e'))));
+
+                        self.statement.push(byte);
+                        self.state = SplitState::Quoted {
+                            quote: byte,
+                            backslash_escapes,
+                            escape_next: false,
+                        };
+                    }
+                    b';' => {
+                        statements.extend(Self::statement(&self.statement)?);
+                        self.statement.clear();
+                    }
+                    b'#' if self.mysql => self.state = SplitState::LineComment,
+                    b'-' | b'/' => {
+                        let needs_after_next = self.mysql
+                            && matches!((byte, next), (b'-', Some(b'-')) | (b'/', Some(b'*')));
+                        if !ended && (next.is_none() || (needs_after_next && after_next.is_none()))
+                        {
+                            break;
+                        }
+
+                        match (byte, next) {
+                            // mysql wants whitespace after the dashes, `1--1` is an expression
+                            (b'-', Some(b'-'))
+                                if !self.mysql
+                                    || after_next.is_none_or(|byte| {
+                                        byte.is_ascii_whitespace() || byte.is_ascii_control()
+                                    }) =>
+                            {
+                                self.state = SplitState::LineComment;
+                                consumed = 2;
+                            }
+                            (b'/', Some(b'*')) => {
+                                let executable = self.mysql && after_next == Some(b'!');
+                                if executable {
+                                    self.statement.extend_from_slice(b"/*");
+                                }
+
+                                self.state = SplitState::BlockComment {
+                                    depth: 1,
+                                    executable,
+                                };
+                                consumed = 2;
+                            }
+                            _ => self.statement.push(byte),
+                        }
+                    }
+                    _ => self.statement.push(byte),
+                },
+                SplitState::Quoted {
+                    quote,
+                    backslash_escapes,
+                    escape_next,
+                } => {
+                    if *escape_next {
+                        *escape_next = false;
+                    } else if byte == b'\\' && *backslash_escapes {
+                        *escape_next = true;
+                    } else if byte == *quote {
+                        self.state = SplitState::Code;
+                    }
+
+                    self.statement.push(byte);
+                }
+                SplitState::LineComment => {
+                    if byte == b'\n' {
+                        self.statement.push(b'\n');
+                        self.state = SplitState::Code;
+                    }
+                }
+                SplitState::BlockComment { depth, executable } => {
+                    if matches!(byte, b'*' | b'/') && next.is_none() && !ended {
+                        break;
+                    }
+
+                    if byte == b'*' && next == Some(b'/') {
+                        *depth -= 1;
+                        consumed = 2;
+
+                        if *executable {
+                            self.statement.extend_from_slice(b"*/");
+                        }
+                        if *depth == 0 {
+                            if !*executable {
+                                self.statement.push(b' ');
+                            }
+                            self.state = SplitState::Code;
+                        }
+                    } else if byte == b'/' && next == Some(b'*') && !self.mysql {
+                        // postgres block comments nest
+                        *depth += 1;
+                        consumed = 2;
+                    } else if *executable {
+                        self.statement.push(byte);
+                    }
+                }
+            }
+
+            index += consumed;
+        }
+
+        self.pending.drain(..index);
+
+        Ok(statements)
+    }
+
+    fn finish(mut self) -> Result<Vec<String>, anyhow::Error> {
+        let mut statements = self.scan(true)?;
+
+        match self.state {
+            SplitState::Quoted { .. } => {
+                return Err(anyhow::anyhow!("database dump ends inside a quoted value"));
+            }
+            SplitState::BlockComment { .. } => {
+                return Err(anyhow::anyhow!("database dump ends inside a comment"));
+            }
+            SplitState::Code | SplitState::LineComment => {}
+        }
+
+        statements.extend(Self::statement(&self.statement)?);
+
+        Ok(statements)
+    }
+}
+
+/// Runs every statement of a SQL dump on `connection`.
+async fn apply_dump<S, B, E>(
+    connection: &mut dyn ExplorerConnection,
+    r#type: DatabaseType,
+    dump: S,
+) -> Result<(), anyhow::Error>
+where
+    S: Stream<Item = Result<B, E>> + Send,
+    B: AsRef<[u8]> + Send,
+    E: Into<anyhow::Error>,
+{
+    let mut splitter = StatementSplitter::new(r#type);
+    let mut dump = std::pin::pin!(dump);
+    while let Some(chunk) = dump.next().await {
+        for statement in splitter.push(chunk.map_err(Into::<anyhow::Error>::into)?.as_ref())? {
+            connection.execute_ddl(statement).await?;
+        }
+    }
+
+    for statement in splitter.finish()? {
+        connection.execute_ddl(statement).await?;
+    }
+
+    Ok(())
+}
+
+async fn insert_documents(
+    collection: Option<&mongodb::Collection<mongodb::bson::Document>>,
+    documents: &mut Vec<mongodb::bson::Document>,
+) -> Result<(), anyhow::Error> {
+    if let Some(collection) = collection
+        && !documents.is_empty()
+    {
+        collection.insert_many(std::mem::take(documents)).await?;
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+struct MongodbDumpLine {
+    collection: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    document: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    index: Option<serde_json::Value>,
+}
+
+impl ServerDatabase {
+    /// Writes a logical dump of this database into `output`, in chunks of about 1 MiB.
+    pub async fn dump(&mut self, database: &crate::database::Database, output: DumpSender) {
+        let dumped = if matches!(self.database_host.r#type, DatabaseType::Mongodb) {
+            self.dump_mongodb(database, &output).await
+        } else {
+            self.dump_sql(database, &output).await
+        };
+
+        if let Err(err) = dumped {
+            output.send(Err(err)).await.ok();
+        }
+    }
+
+    async fn dump_sql(
+        &mut self,
+        database: &crate::database::Database,
+        output: &DumpSender,
+    ) -> Result<(), anyhow::Error> {
+        let mut connection = self.connect_as_tenant(database, true).await?;
+        connection.inner.close_on_drop();
+        let connection = &mut *connection.inner;
+
+        connection.begin_snapshot().await?;
+
+        let tables: Vec<_> = connection
+            .schema_tables()
+            .await?
+            .into_iter()
+            .filter(|table| !table.view)
+            .collect();
+
+        for statement in connection.dump_header() {
+            send_statement(output, statement).await?;
+        }
+        for table in tables.iter().rev() {
+            let qualified = connection.qualified_table(table.schema.as_deref(), &table.name);
+            send_statement(output, &connection.drop_table_sql(&qualified)).await?;
+        }
+
+        let mut schemas: Vec<&str> = tables
+            .iter()
+            .filter_map(|table| table.schema.as_deref())
+            .filter(|schema| *schema != "public")
+            .collect();
+        schemas.sort_unstable();
+        schemas.dedup();
+        for schema in schemas {
+            send_statement(
+                output,
+                &format!(
+                    "CREATE SCHEMA IF NOT EXISTS {}",
+                    connection.quote_ident(schema)
+                ),
+            )
+            .await?;
+        }
+        for statement in connection.dump_types().await? {
+            send_statement(output, &statement).await?;
+        }
+
+        let mut finish = Vec::new();
+        for table in &tables {
+            let qualified = connection.qualified_table(table.schema.as_deref(), &table.name);
+
+            let definition = connection.table_definition(&qualified, table).await?;
+            for statement in &definition.create {
+                send_statement(output, statement).await?;
+            }
+            finish.extend(definition.finish);
+
+            let columns: Vec<&SchemaColumn> = table
+                .columns
+                .iter()
+                .filter(|column| connection.dumps_column(column))
+                .collect();
+            if columns.is_empty() {
+                continue;
+            }
+
+            let mut rows = DumpRows::new(
+                output,
+                format!(
+                    "INSERT INTO {qualified} ({}) VALUES\n",
+                    columns
+                        .iter()
+                        .map(|column| connection.quote_ident(&column.name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+            connection
+                .dump_rows(
+                    format!(
+                        "SELECT {} FROM {qualified}",
+                        connection.row_literal(&columns)
+                    ),
+                    &mut rows,
+                )
+                .await?;
+            rows.flush().await?;
+        }
+
+        for statement in finish {
+            send_statement(output, &statement).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn dump_mongodb(
+        &mut self,
+        database: &crate::database::Database,
+        output: &DumpSender,
+    ) -> Result<(), anyhow::Error> {
+        let DatabasePool::Mongodb(client) = self.database_host.get_connection(database).await?
+        else {
+            return Err(anyhow::anyhow!("database host is not a MongoDB host"));
+        };
+        let db = client.database(&self.name);
+
+        let mut buffer = Vec::new();
+        for collection_name in db.list_collection_names().await? {
+            if collection_name.starts_with("system.") {
+                continue;
+            }
+
+            let collection = db.collection::<mongodb::bson::Document>(&collection_name);
+
+            let mut indexes = collection.list_indexes().await?;
+            while let Some(index) = indexes.try_next().await? {
+                if index
+                    .options
+                    .as_ref()
+                    .and_then(|options| options.name.as_deref())
+                    == Some("_id_")
+                {
+                    continue;
+                }
+
+                serde_json::to_writer(
+                    &mut buffer,
+                    &MongodbDumpLine {
+                        collection: collection_name.clone(),
+                        document: None,
+                        index: Some(mongodb::bson::to_bson(&index)?.into_canonical_extjson()),
+                    },
+                )?;
+                buffer.push(b'\n');
+            }
+
+            let mut documents = collection.find(mongodb::bson::doc! {}).await?;
+            while let Some(document) = documents.try_next().await? {
+                serde_json::to_writer(
+                    &mut buffer,
+                    &MongodbDumpLine {
+                        collection: collection_name.clone(),
+                        document: Some(
+                            mongodb::bson::Bson::Document(document).into_canonical_extjson(),
+                        ),
+                        index: None,
+                    },
+                )?;
+                buffer.push(b'\n');
+
+                if buffer.len() >= DUMP_CHUNK_SIZE {
+                    send(output, std::mem::take(&mut buffer)).await?;
+                }
+            }
+        }
+
+        if !buffer.is_empty() {
+            send(output, buffer).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Restores a dump written by [`ServerDatabase::dump`], replacing every table or collection
+    /// contained in it. Anything not contained in the dump is left alone.
+    ///
+    /// A failing restore leaves SQL databases untouched: postgres runs the whole dump in a
+    /// single transaction, MySQL cannot roll back DDL and restores into a staging database whose
+    /// tables are swapped in once the dump has been applied completely.
+    pub async fn restore_dump<S, B, E>(
+        &mut self,
+        database: &crate::database::Database,
+        dump: S,
+    ) -> Result<(), anyhow::Error>
+    where
+        S: Stream<Item = Result<B, E>> + Send,
+        B: AsRef<[u8]> + Send,
+        E: Into<anyhow::Error>,
+    {
+        match self.database_host.r#type {
+            DatabaseType::Mongodb => self.restore_mongodb_dump(database, dump).await,
+            DatabaseType::Mysql => self.restore_mysql_dump(database, dump).await,
+            DatabaseType::Postgres => {
+                let mut connection = self.connect_as_tenant(database, false).await?;
+                // dropping the connection halfway rolls the transaction back
+                connection.inner.close_on_drop();
+                let connection = &mut *connection.inner;
+
+                connection.disable_statement_timeout().await?;
+                connection.execute_ddl("BEGIN".to_string()).await?;
+                apply_dump(connection, DatabaseType::Postgres, dump).await?;
+                connection.execute_ddl("COMMIT".to_string()).await?;
+
+                Ok(())
+            }
+        }
+    }
+
+    async fn restore_mysql_dump<S, B, E>(
+        &mut self,
+        database: &crate::database::Database,
+        dump: S,
+    ) -> Result<(), anyhow::Error>
+    where
+        S: Stream<Item = Result<B, E>> + Send,
+        B: AsRef<[u8]> + Send,
+        E: Into<anyhow::Error>,
+    {
+        let DatabasePool::Mysql(pool) = self.database_host.get_connection(database).await? else {
+            return Err(anyhow::anyhow!("database host is not a MySQL host"));
+        };
+
+        let staging = format!("restore_{}", self.uuid.simple());
+        let replaced = format!("replaced_{}", self.uuid.simple());
+
+        for statement in [
+            format!("DROP DATABASE IF EXISTS `{staging}`"),
+            format!("DROP DATABASE IF EXISTS `{replaced}`"),
+            format!(
+                "CREATE DATABASE `{staging}` DEFAULT CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci"
+            ),
+            format!(
+                "GRANT ALL PRIVILEGES ON `{staging}`.* TO '{}'@'%'",
+                self.username
+            ),
+        ] {
+            sqlx::raw_sql(sqlx::AssertSqlSafe(statement))
+                .execute(&pool)
+                .await?;
+        }
+
+        let restored = async {
+            // the dump is restored as the database user, it is not trusted with the host
+            let permit = super::TENANT_CONNECTIONS.clone().acquire_owned().await?;
+            let details = self
+                .database_host
+                .credentials
+                .parse_connection_details(database)
+                .await?;
+            let password = database.decrypt(self.password.clone()).await?;
+
+            let staging_pool = super::mysql::MysqlExplorer::create_pool(
+                &details.host,
+                details.port,
+                &self.username,
+                &password,
+                &staging,
+            );
+            let applied = async {
+                let mut connection = super::mysql::MysqlExplorer {
+                    connection: staging_pool.acquire().await?,
+                    database: staging.clone(),
+                };
+                connection.close_on_drop();
+
+                connection.disable_statement_timeout().await?;
+                apply_dump(&mut connection, DatabaseType::Mysql, dump).await
+            }
+            .await;
+            staging_pool.close().await;
+            drop(permit);
+            applied?;
+
+            let tables: Vec<(String,)> = sqlx::query_as(
+                "SELECT TABLE_NAME FROM information_schema.TABLES WHERE TABLE_SCHEMA = ? AND TABLE_TYPE = 'BASE TABLE'",
+            )
+            .bind(&staging)
+            .fetch_all(&pool)
+            .await?;
+            if tables.is_empty() {
+                return Ok(());
+            }
+
+            let existing: Vec<(String,)> = sqlx::query_as(
+                "SELECT TABLE_NAME FROM information_schema.TABLES WHERE TABLE_SCHEMA = ?",
+            )
+            .bind(self.name.as_str())
+            .fetch_all(&pool)
+            .await?;
+
+            // a single RENAME TABLE is atomic, the old tables move out as the new ones move in
+            let mut renames = Vec::new();
+            for (table,) in &tables {
+                let replaces = existing.iter().any(|(existing,)| existing == table);
+                let table = table.replace('`', "``");
+
+                if replaces {
+                    renames.push(format!(
+                        "`{}`.`{table}` TO `{replaced}`.`{table}`",
+                        self.name
+                    ));
+                }
+                renames.push(format!(
+                    "`{staging}`.`{table}` TO `{}`.`{table}`",
+                    self.name
+                ));
+            }
+
+            let mut connection = pool.acquire().await?;
+            connection.close_on_drop();
+
+            for statement in [
+                "SET SESSION FOREIGN_KEY_CHECKS = 0".to_string(),
+                format!(
+                    "CREATE DATABASE `{replaced}` DEFAULT CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci"
+                ),
+                format!("RENAME TABLE {}", renames.join(", ")),
+                format!("DROP DATABASE `{replaced}`"),
+            ] {
+                sqlx::raw_sql(sqlx::AssertSqlSafe(statement))
+                    .execute(&mut *connection)
+                    .await?;
+            }
+
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+
+        for statement in [
+            format!(
+                "REVOKE ALL PRIVILEGES ON `{staging}`.* FROM '{}'@'%'",
+                self.username
+            ),
+            format!("DROP DATABASE IF EXISTS `{staging}`"),
+            format!("DROP DATABASE IF EXISTS `{replaced}`"),
+        ] {
+            if let Err(err) = sqlx::raw_sql(sqlx::AssertSqlSafe(statement))
+                .execute(&pool)
+                .await
+            {
+                tracing::warn!(
+                    database = %self.uuid,
+                    "failed to clean up after restoring database dump: {:?}",
+                    err
+                );
+            }
+        }
+
+        restored
+    }
+
+    async fn restore_mongodb_dump<S, B, E>(
+        &mut self,
+        database: &crate::database::Database,
+        dump: S,
+    ) -> Result<(), anyhow::Error>
+    where
+        S: Stream<Item = Result<B, E>> + Send,
+        B: AsRef<[u8]> + Send,
+        E: Into<anyhow::Error>,
+    {
+        let DatabasePool::Mongodb(client) = self.database_host.get_connection(database).await?
+        else {
+            return Err(anyhow::anyhow!("database host is not a MongoDB host"));
+        };
+        let db = client.database(&self.name);
+
+        let mut collection: Option<mongodb::Collection<mongodb::bson::Document>> = None;
+        let mut documents = Vec::new();
+
+        let mut buffer = Vec::new();
+        let mut dump = std::pin::pin!(dump);
+        loop {
+            let chunk = dump
+                .next()
+                .await
+                .transpose()
+                .map_err(Into::<anyhow::Error>::into)?;
+            let ended = chunk.is_none();
+            if let Some(chunk) = chunk {
+                buffer.extend_from_slice(chunk.as_ref());
+            } else if !buffer.is_empty() {
+                buffer.push(b'\n');
+            }
+
+            let mut start = 0;
+            while let Some(position) = buffer[start..].iter().position(|byte| *byte == b'\n') {
+                let line = &buffer[start..start + position];
+                start += position + 1;
+
+                if line.iter().all(u8::is_ascii_whitespace) {
+                    continue;
+                }
+
+                let line: MongodbDumpLine = serde_json::from_slice(line)
+                    .map_err(|err| anyhow::anyhow!("invalid MongoDB dump line: {err}"))?;
+
+                if collection
+                    .as_ref()
+                    .is_none_or(|collection| collection.name() != line.collection)
+                {
+                    insert_documents(collection.as_ref(), &mut documents).await?;
+
+                    let next = db.collection(&line.collection);
+                    next.drop().await?;
+                    collection = Some(next);
+                }
+
+                if let Some(index) = line.index {
+                    let index: mongodb::IndexModel =
+                        mongodb::bson::from_bson(mongodb::bson::Bson::try_from(index)?)?;
+                    if let Some(collection) = &collection {
+                        collection.create_index(index).await?;
+                    }
+                }
+
+                if let Some(document) = line.document {
+                    let mongodb::bson::Bson::Document(document) =
+                        mongodb::bson::Bson::try_from(document)?
+                    else {
+                        return Err(display("MongoDB dump contains a non-document value"));
+                    };
+
+                    documents.push(document);
+                    if documents.len() >= MONGODB_INSERT_BATCH {
+                        insert_documents(collection.as_ref(), &mut documents).await?;
+                    }
+                }
+            }
+            buffer.drain(..start);
+
+            if ended {
+                break;
+            }
+        }
+
+        insert_documents(collection.as_ref(), &mut documents).await?;
+
+        Ok(())
+    }
+}
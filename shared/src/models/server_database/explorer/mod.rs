@@ -10,8 +10,19 @@ use std::{
 };
 use utoipa::ToSchema;
 
+mod dump;
+mod mongo;
 mod mysql;
 mod postgres;
+mod transfer;
+
+pub use dump::DumpSender;
+use dump::{DumpRows, TableDefinition};
+use transfer::ExportRows;
+pub use transfer::{
+    ExportEncoder, ExportFormat, ExportOptions, ExportSource, IMPORT_MAX_ERRORS, ImportFormat,
+    ImportOptions, ImportRowError, ImportSummary, ImportTarget, SqlDialect, ValueKind, import_rows,
+};
 
 pub const QUERY_MAX_LENGTH: usize = 65535;
 pub const QUERY_DEFAULT_ROWS: u32 = 100;
@@ -40,6 +51,7 @@ const TENANT_POOL_ACQUIRE_TIMEOUT: std::time::Duration = std::time::Duration::fr
 enum TenantPool {
     Mysql(sqlx::Pool<sqlx::MySql>),
     Postgres(sqlx::Pool<sqlx::Postgres>),
+    Mongodb(mongodb::Client),
 }
 
 impl TenantPool {
@@ -47,6 +59,7 @@ impl TenantPool {
         match self {
             Self::Mysql(pool) => pool.close().await,
             Self::Postgres(pool) => pool.close().await,
+            Self::Mongodb(client) => client.shutdown().await,
         }
     }
 }
@@ -112,6 +125,14 @@ pub trait ExplorerConnection: Send {
         schema: Option<&str>,
         table: &str,
     ) -> Result<Vec<SchemaColumn>, anyhow::Error>;
+    /// The indexes, constraints and foreign keys of one table, or of every table when none is
+    /// given.
+    async fn table_keys(
+        &mut self,
+        table: Option<(Option<&str>, &str)>,
+    ) -> Result<Vec<TableKeyEntry>, anyhow::Error>;
+    fn drop_index_sql(&self, qualified: &str, schema: Option<&str>, index: &str) -> String;
+    fn drop_constraint_sql(&self, qualified: &str, key: &TableKey) -> String;
     async fn column_types(&mut self) -> Result<Vec<String>, anyhow::Error>;
     async fn resolve_type(&mut self, input: &str) -> Result<String, anyhow::Error>;
 
@@ -128,6 +149,34 @@ pub trait ExplorerConnection: Send {
         expects_single_row: bool,
     ) -> Result<u64, anyhow::Error>;
     async fn execute_ddl(&mut self, sql: String) -> Result<(), anyhow::Error>;
+
+    /// Lifts the session statement timeout, dumps and restores take as long as they take.
+    async fn disable_statement_timeout(&mut self) -> Result<(), anyhow::Error>;
+    /// Starts the read only transaction every read of a dump happens in.
+    async fn begin_snapshot(&mut self) -> Result<(), anyhow::Error>;
+    fn dump_header(&self) -> &'static [&'static str];
+    fn drop_table_sql(&self, qualified: &str) -> String;
+    async fn dump_types(&mut self) -> Result<Vec<String>, anyhow::Error>;
+    async fn table_definition(
+        &mut self,
+        qualified: &str,
+        table: &SchemaTable,
+    ) -> Result<TableDefinition, anyhow::Error>;
+    /// Whether the values of a column are part of a dump, generated ones are left out.
+    fn dumps_column(&self, column: &SchemaColumn) -> bool;
+    /// An expression rendering a row as a parenthesized list of SQL literals.
+    fn row_literal(&self, columns: &[&SchemaColumn]) -> String;
+    async fn dump_rows(
+        &mut self,
+        sql: String,
+        rows: &mut DumpRows<'_>,
+    ) -> Result<(), anyhow::Error>;
+    /// Feeds the rows of the first result set of `sql` into an export.
+    async fn export_rows(
+        &mut self,
+        sql: String,
+        rows: &mut ExportRows<'_>,
+    ) -> Result<(), anyhow::Error>;
 }
 
 fn display(message: impl Into<Cow<'static, str>>) -> anyhow::Error {
@@ -147,7 +196,7 @@ fn unknown_column(column: &str) -> anyhow::Error {
 }
 
 fn unsupported_engine() -> anyhow::Error {
-    crate::response::DisplayError::new("querying MongoDB databases is not supported")
+    crate::response::DisplayError::new("this operation is not supported on MongoDB databases")
         .with_status(axum::http::StatusCode::EXPECTATION_FAILED)
         .into()
 }
@@ -171,6 +220,48 @@ impl ServerDatabase {
     ) -> Result<TenantConnection, anyhow::Error> {
         let permit = TENANT_CONNECTIONS.clone().acquire_owned().await?;
 
+        let mut inner: Box<dyn ExplorerConnection> = match self.tenant_pool(database).await? {
+            TenantPool::Mysql(pool) => Box::new(mysql::MysqlExplorer {
+                connection: pool.acquire().await?,
+                database: self.name.to_string(),
+            }),
+            TenantPool::Postgres(pool) => Box::new(postgres::PostgresExplorer {
+                connection: pool.acquire().await?,
+            }),
+            TenantPool::Mongodb(_) => return Err(unsupported_engine()),
+        };
+
+        inner.set_read_only(read_only).await?;
+
+        Ok(TenantConnection {
+            inner,
+            _permit: permit,
+        })
+    }
+
+    /// MongoDB has no SQL to share with the other engines, so it gets an explorer of its own.
+    /// It has no session level read only mode either, reads and writes are kept apart by the
+    /// operations it offers instead.
+    async fn connect_mongodb_as_tenant(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<mongo::MongodbExplorer, anyhow::Error> {
+        let permit = TENANT_CONNECTIONS.clone().acquire_owned().await?;
+
+        let TenantPool::Mongodb(client) = self.tenant_pool(database).await? else {
+            return Err(anyhow::anyhow!("database host is not a MongoDB host"));
+        };
+
+        Ok(mongo::MongodbExplorer {
+            database: client.database(&self.name),
+            _permit: permit,
+        })
+    }
+
+    async fn tenant_pool(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<TenantPool, anyhow::Error> {
         let pool = {
             let mut pools = TENANT_POOLS.lock().await;
             match pools.get_mut(&self.uuid) {
@@ -209,7 +300,15 @@ impl ServerDatabase {
                             &self.name,
                         ))
                     }
-                    DatabaseType::Mongodb => return Err(unsupported_engine()),
+                    DatabaseType::Mongodb => {
+                        TenantPool::Mongodb(mongo::MongodbExplorer::create_client(
+                            &details.host,
+                            details.port,
+                            &self.username,
+                            &password,
+                            &self.name,
+                        )?)
+                    }
                 };
 
                 TENANT_POOLS.lock().await.insert(
@@ -225,22 +324,7 @@ impl ServerDatabase {
             }
         };
 
-        let mut inner: Box<dyn ExplorerConnection> = match pool {
-            TenantPool::Mysql(pool) => Box::new(mysql::MysqlExplorer {
-                connection: pool.acquire().await?,
-                database: self.name.to_string(),
-            }),
-            TenantPool::Postgres(pool) => Box::new(postgres::PostgresExplorer {
-                connection: pool.acquire().await?,
-            }),
-        };
-
-        inner.set_read_only(read_only).await?;
-
-        Ok(TenantConnection {
-            inner,
-            _permit: permit,
-        })
+        Ok(pool)
     }
 
     pub async fn run_query(
@@ -250,16 +334,28 @@ impl ServerDatabase {
         max_rows: u32,
         read_only: bool,
     ) -> Result<Vec<QueryResultSet>, anyhow::Error> {
-        let mut connection = self.connect_as_tenant(database, read_only).await?;
-        connection.inner.close_on_drop();
+        let max_rows = max_rows.min(QUERY_MAX_ROWS) as usize;
 
-        let results = tokio::time::timeout(QUERY_CONNECTION_TIMEOUT, async move {
-            connection
-                .inner
-                .run_query(sql, max_rows.min(QUERY_MAX_ROWS) as usize)
-                .await
-        })
-        .await
+        let results = if matches!(self.database_host.r#type, DatabaseType::Mongodb) {
+            if !read_only {
+                return Err(display("MongoDB queries can only be run read only"));
+            }
+
+            let connection = self.connect_mongodb_as_tenant(database).await?;
+
+            tokio::time::timeout(QUERY_CONNECTION_TIMEOUT, async move {
+                connection.run_query(sql, max_rows).await
+            })
+            .await
+        } else {
+            let mut connection = self.connect_as_tenant(database, read_only).await?;
+            connection.inner.close_on_drop();
+
+            tokio::time::timeout(QUERY_CONNECTION_TIMEOUT, async move {
+                connection.inner.run_query(sql, max_rows).await
+            })
+            .await
+        }
         .map_err(|_| {
             crate::response::DisplayError::new("query timed out")
                 .with_status(axum::http::StatusCode::REQUEST_TIMEOUT)
@@ -384,6 +480,94 @@ pub struct SchemaColumn {
     pub binary: bool,
 }
 
+#[derive(ToSchema, Serialize, Clone)]
+pub struct SchemaIndex {
+    pub name: CompactString,
+    pub columns: Vec<CompactString>,
+    pub unique: bool,
+    /// The index as the engine describes it, e.g. the key document of a MongoDB index.
+    pub definition: String,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConstraintKind {
+    Unique,
+    Check,
+    Exclusion,
+}
+
+#[derive(ToSchema, Serialize, Clone)]
+pub struct SchemaConstraint {
+    pub name: CompactString,
+    pub kind: ConstraintKind,
+    pub columns: Vec<CompactString>,
+    pub definition: String,
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferentialAction {
+    #[default]
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+impl ReferentialAction {
+    pub const fn sql(self) -> &'static str {
+        match self {
+            Self::NoAction => "NO ACTION",
+            Self::Restrict => "RESTRICT",
+            Self::Cascade => "CASCADE",
+            Self::SetNull => "SET NULL",
+            Self::SetDefault => "SET DEFAULT",
+        }
+    }
+
+    fn parse(rule: &str) -> Self {
+        match rule {
+            "RESTRICT" | "r" => Self::Restrict,
+            "CASCADE" | "c" => Self::Cascade,
+            "SET NULL" | "n" => Self::SetNull,
+            "SET DEFAULT" | "d" => Self::SetDefault,
+            _ => Self::NoAction,
+        }
+    }
+}
+
+#[derive(ToSchema, Serialize, Clone)]
+pub struct SchemaForeignKey {
+    pub name: CompactString,
+    pub columns: Vec<CompactString>,
+    pub referenced_schema: Option<CompactString>,
+    pub referenced_table: CompactString,
+    pub referenced_columns: Vec<CompactString>,
+    pub on_update: ReferentialAction,
+    pub on_delete: ReferentialAction,
+}
+
+/// An index, constraint or foreign key of a table, as reported by [`ExplorerConnection::table_keys`].
+pub enum TableKey {
+    Index(SchemaIndex),
+    Constraint(SchemaConstraint),
+    ForeignKey(SchemaForeignKey),
+}
+
+impl TableKey {
+    fn name(&self) -> &str {
+        match self {
+            Self::Index(index) => &index.name,
+            Self::Constraint(constraint) => &constraint.name,
+            Self::ForeignKey(foreign_key) => &foreign_key.name,
+        }
+    }
+}
+
+type TableKeyEntry = (Option<CompactString>, CompactString, TableKey);
+
 #[derive(ToSchema, Serialize, Clone)]
 pub struct SchemaTable {
     pub schema: Option<CompactString>,
@@ -391,6 +575,29 @@ pub struct SchemaTable {
     pub view: bool,
     pub row_estimate: Option<i64>,
     pub columns: Vec<SchemaColumn>,
+    /// Indexes that do not back the primary key or a constraint.
+    pub indexes: Vec<SchemaIndex>,
+    pub constraints: Vec<SchemaConstraint>,
+    pub foreign_keys: Vec<SchemaForeignKey>,
+}
+
+impl SchemaTable {
+    fn attach_keys(tables: &mut [Self], keys: Vec<TableKeyEntry>) {
+        for (schema, table_name, key) in keys {
+            let Some(table) = tables
+                .iter_mut()
+                .find(|table| table.name == table_name && table.schema == schema)
+            else {
+                continue;
+            };
+
+            match key {
+                TableKey::Index(index) => table.indexes.push(index),
+                TableKey::Constraint(constraint) => table.constraints.push(constraint),
+                TableKey::ForeignKey(foreign_key) => table.foreign_keys.push(foreign_key),
+            }
+        }
+    }
 }
 
 impl ServerDatabase {
@@ -398,6 +605,14 @@ impl ServerDatabase {
         &mut self,
         database: &crate::database::Database,
     ) -> Result<Vec<SchemaTable>, anyhow::Error> {
+        if matches!(self.database_host.r#type, DatabaseType::Mongodb) {
+            return self
+                .connect_mongodb_as_tenant(database)
+                .await?
+                .schema_tables()
+                .await;
+        }
+
         let mut connection = self.connect_as_tenant(database, true).await?;
 
         connection.inner.schema_tables().await
@@ -623,6 +838,14 @@ impl ServerDatabase {
         database: &crate::database::Database,
         options: &BrowseOptions,
     ) -> Result<QueryResultSet, anyhow::Error> {
+        if matches!(self.database_host.r#type, DatabaseType::Mongodb) {
+            return self
+                .connect_mongodb_as_tenant(database)
+                .await?
+                .browse(options)
+                .await;
+        }
+
         let direction = if options.descending { "DESC" } else { "ASC" };
 
         let mut connection = self.connect_as_tenant(database, true).await?;
@@ -695,7 +918,7 @@ pub struct RowUpdate {
     pub values: Vec<RowValue>,
 }
 
-#[derive(ToSchema, Validate, Deserialize)]
+#[derive(ToSchema, Validate, Deserialize, Clone)]
 pub struct RowInsert {
     #[garde(dive)]
     pub values: Vec<RowValue>,
@@ -787,6 +1010,14 @@ impl ServerDatabase {
     ) -> Result<u64, anyhow::Error> {
         check_batch(operation.len())?;
 
+        if matches!(self.database_host.r#type, DatabaseType::Mongodb) {
+            return self
+                .connect_mongodb_as_tenant(database)
+                .await?
+                .mutate(table, operation)
+                .await;
+        }
+
         let mut connection = self.connect_as_tenant(database, false).await?;
         let columns = connection.inner.table_columns(schema, table).await?;
         if columns.is_empty() {
@@ -1254,6 +1485,272 @@ impl ServerDatabase {
     }
 }
 
+pub const KEY_MAX_COLUMNS: usize = 32;
+
+#[derive(ToSchema, Validate, Deserialize, Clone)]
+pub struct IndexDefinition {
+    #[garde(length(chars, min = 1, max = 255))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub name: CompactString,
+
+    #[garde(length(min = 1, max = KEY_MAX_COLUMNS), inner(length(chars, min = 1, max = 255)))]
+    #[schema(min_items = 1, max_items = 32)]
+    pub columns: Vec<CompactString>,
+
+    #[garde(skip)]
+    #[serde(default)]
+    pub unique: bool,
+}
+
+#[derive(ToSchema, Validate, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConstraintDefinition {
+    Unique {
+        #[garde(length(chars, min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        name: CompactString,
+
+        #[garde(length(min = 1, max = KEY_MAX_COLUMNS), inner(length(chars, min = 1, max = 255)))]
+        #[schema(min_items = 1, max_items = 32)]
+        columns: Vec<CompactString>,
+    },
+    /// The expression is raw SQL, creating one takes the same trust as running a query.
+    Check {
+        #[garde(length(chars, min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        name: CompactString,
+
+        #[garde(length(chars, min = 1, max = 4096))]
+        #[schema(min_length = 1, max_length = 4096)]
+        expression: String,
+    },
+    ForeignKey {
+        #[garde(length(chars, min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        name: CompactString,
+
+        #[garde(length(min = 1, max = KEY_MAX_COLUMNS), inner(length(chars, min = 1, max = 255)))]
+        #[schema(min_items = 1, max_items = 32)]
+        columns: Vec<CompactString>,
+
+        #[garde(inner(length(chars, min = 1, max = 255)))]
+        #[schema(min_length = 1, max_length = 255)]
+        referenced_schema: Option<CompactString>,
+
+        #[garde(length(chars, min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        referenced_table: CompactString,
+
+        #[garde(length(min = 1, max = KEY_MAX_COLUMNS), inner(length(chars, min = 1, max = 255)))]
+        #[schema(min_items = 1, max_items = 32)]
+        referenced_columns: Vec<CompactString>,
+
+        #[garde(skip)]
+        #[serde(default)]
+        on_update: ReferentialAction,
+
+        #[garde(skip)]
+        #[serde(default)]
+        on_delete: ReferentialAction,
+    },
+}
+
+impl ConstraintDefinition {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Unique { name, .. }
+            | Self::Check { name, .. }
+            | Self::ForeignKey { name, .. } => name,
+        }
+    }
+}
+
+fn quoted_key_columns(
+    connection: &dyn ExplorerConnection,
+    columns: &[SchemaColumn],
+    key_columns: &[CompactString],
+) -> Result<String, anyhow::Error> {
+    let mut quoted = Vec::with_capacity(key_columns.len());
+    for (index, column) in key_columns.iter().enumerate() {
+        if !columns.iter().any(|entry| entry.name == *column) {
+            return Err(unknown_column(column));
+        }
+        if key_columns[..index].contains(column) {
+            return Err(display(format!("column {column} is listed twice")));
+        }
+
+        quoted.push(connection.quote_ident(column));
+    }
+
+    Ok(quoted.join(", "))
+}
+
+impl ServerDatabase {
+    pub async fn create_index(
+        &mut self,
+        database: &crate::database::Database,
+        schema: Option<&str>,
+        table: &str,
+        index: &IndexDefinition,
+    ) -> Result<(), anyhow::Error> {
+        self.run_table_ddl(database, schema, table, |connection, qualified, columns| {
+            Ok(format!(
+                "CREATE {}INDEX {} ON {qualified} ({})",
+                if index.unique { "UNIQUE " } else { "" },
+                connection.quote_ident(&index.name),
+                quoted_key_columns(connection, columns, &index.columns)?
+            ))
+        })
+        .await
+    }
+
+    pub async fn add_constraint(
+        &mut self,
+        database: &crate::database::Database,
+        schema: Option<&str>,
+        table: &str,
+        constraint: &ConstraintDefinition,
+    ) -> Result<(), anyhow::Error> {
+        self.run_table_ddl(database, schema, table, |connection, qualified, columns| {
+            let definition = match constraint {
+                ConstraintDefinition::Unique { columns: keys, .. } => {
+                    format!(
+                        "UNIQUE ({})",
+                        quoted_key_columns(connection, columns, keys)?
+                    )
+                }
+                ConstraintDefinition::Check { expression, .. } => format!("CHECK ({expression})"),
+                ConstraintDefinition::ForeignKey {
+                    columns: keys,
+                    referenced_schema,
+                    referenced_table,
+                    referenced_columns,
+                    on_update,
+                    on_delete,
+                    ..
+                } => {
+                    if keys.len() != referenced_columns.len() {
+                        return Err(display(
+                            "a foreign key needs as many referenced columns as columns",
+                        ));
+                    }
+
+                    let referenced: Vec<String> = referenced_columns
+                        .iter()
+                        .map(|column| connection.quote_ident(column))
+                        .collect();
+
+                    format!(
+                        "FOREIGN KEY ({}) REFERENCES {} ({}) ON UPDATE {} ON DELETE {}",
+                        quoted_key_columns(connection, columns, keys)?,
+                        connection.qualified_table(
+                            referenced_schema.as_deref().or(schema),
+                            referenced_table
+                        ),
+                        referenced.join(", "),
+                        on_update.sql(),
+                        on_delete.sql()
+                    )
+                }
+            };
+
+            Ok(format!(
+                "ALTER TABLE {qualified} ADD CONSTRAINT {} {definition}",
+                connection.quote_ident(constraint.name())
+            ))
+        })
+        .await
+    }
+
+    async fn run_key_ddl(
+        &mut self,
+        database: &crate::database::Database,
+        schema: Option<&str>,
+        table: &str,
+        name: &str,
+        build: impl FnOnce(&dyn ExplorerConnection, &str, &TableKey) -> Result<String, anyhow::Error>,
+    ) -> Result<(), anyhow::Error> {
+        let mut connection = self.connect_as_tenant(database, false).await?;
+        connection.inner.close_on_drop();
+
+        run_ddl(async move {
+            if connection
+                .inner
+                .table_columns(schema, table)
+                .await?
+                .is_empty()
+            {
+                return Err(unknown_table(table));
+            }
+
+            let keys = connection.inner.table_keys(Some((schema, table))).await?;
+            let Some((_, _, key)) = keys.iter().find(|(_, _, key)| key.name() == name) else {
+                return Err(crate::response::DisplayError::new(format!(
+                    "index or constraint {name} does not exist"
+                ))
+                .with_status(axum::http::StatusCode::NOT_FOUND)
+                .into());
+            };
+
+            let qualified = connection.inner.qualified_table(schema, table);
+            let sql = build(&*connection.inner, &qualified, key)?;
+
+            connection.inner.execute_ddl(sql).await
+        })
+        .await
+    }
+
+    pub async fn drop_index(
+        &mut self,
+        database: &crate::database::Database,
+        schema: Option<&str>,
+        table: &str,
+        index: &str,
+    ) -> Result<(), anyhow::Error> {
+        self.run_key_ddl(
+            database,
+            schema,
+            table,
+            index,
+            |connection, qualified, key| {
+                if !matches!(key, TableKey::Index(_)) {
+                    return Err(display(format!(
+                        "{index} is a constraint, drop it as a constraint instead"
+                    )));
+                }
+
+                Ok(connection.drop_index_sql(qualified, schema, index))
+            },
+        )
+        .await
+    }
+
+    pub async fn drop_constraint(
+        &mut self,
+        database: &crate::database::Database,
+        schema: Option<&str>,
+        table: &str,
+        constraint: &str,
+    ) -> Result<(), anyhow::Error> {
+        self.run_key_ddl(
+            database,
+            schema,
+            table,
+            constraint,
+            |connection, qualified, key| {
+                if matches!(key, TableKey::Index(_)) {
+                    return Err(display(format!(
+                        "{constraint} is an index, drop it as an index instead"
+                    )));
+                }
+
+                Ok(connection.drop_constraint_sql(qualified, key))
+            },
+        )
+        .await
+    }
+}
+
 /// DDL is not bounded by the session statement timeout on every engine (mysql's
 /// `max_execution_time` only applies to reads), so the outer timeout is what guarantees the
 /// tenant-connection permit is released
@@ -1364,6 +1861,9 @@ impl From<db_agent_api::SchemaTable> for SchemaTable {
             view: table.view,
             row_estimate: table.row_estimate,
             columns: table.columns.into_iter().map(Into::into).collect(),
+            indexes: Vec::new(),
+            constraints: Vec::new(),
+            foreign_keys: Vec::new(),
         }
     }
 }
@@ -0,0 +1,622 @@
+use super::{
+    BrowseFilter, BrowseOptions, FilterOperator, QUERY_MAX_BYTES, QUERY_STATEMENT_TIMEOUT_MS,
+    QueryColumn, QueryResultSet, QueryValue, RowOperation, RowValue, SchemaColumn, SchemaIndex,
+    SchemaTable, TENANT_POOL_ACQUIRE_TIMEOUT, TENANT_POOL_IDLE_TIMEOUT,
+    TENANT_POOL_MAX_CONNECTIONS, display, unknown_table,
+};
+use futures_util::TryStreamExt;
+use mongodb::{
+    bson::{Bson, Document, doc, oid::ObjectId},
+    error::{ErrorKind, WriteFailure},
+    options::{ClientOptions, Credential, ServerAddress},
+    results::CollectionType,
+};
+
+const SCHEMA_SAMPLE_DOCUMENTS: i64 = 100;
+
+pub(super) struct MongodbExplorer {
+    pub(super) database: mongodb::Database,
+    pub(super) _permit: tokio::sync::OwnedSemaphorePermit,
+}
+
+impl MongodbExplorer {
+    pub(super) fn create_client(
+        host: &str,
+        port: u16,
+        username: &str,
+        password: &str,
+        database: &str,
+    ) -> Result<mongodb::Client, anyhow::Error> {
+        let mut options = ClientOptions::default();
+        options.hosts.push(ServerAddress::Tcp {
+            host: host.to_string(),
+            port: Some(port),
+        });
+        options.credential = Some(
+            Credential::builder()
+                .username(username.to_string())
+                .password(password.to_string())
+                .source(database.to_string())
+                .build(),
+        );
+        options.min_pool_size = Some(0);
+        options.max_pool_size = Some(TENANT_POOL_MAX_CONNECTIONS);
+        options.max_idle_time = Some(TENANT_POOL_IDLE_TIMEOUT);
+        options.connect_timeout = Some(TENANT_POOL_ACQUIRE_TIMEOUT);
+        options.server_selection_timeout = Some(TENANT_POOL_ACQUIRE_TIMEOUT);
+
+        Ok(mongodb::Client::with_options(options)?)
+    }
+}
+
+fn max_time() -> std::time::Duration {
+    std::time::Duration::from_millis(QUERY_STATEMENT_TIMEOUT_MS)
+}
+
+fn mongodb_error(err: mongodb::error::Error) -> anyhow::Error {
+    match &*err.kind {
+        ErrorKind::Command(command_error) => display(command_error.message.clone()),
+        ErrorKind::Write(WriteFailure::WriteError(write_error)) => {
+            display(write_error.message.clone())
+        }
+        ErrorKind::InsertMany(insert_error) => match insert_error
+            .write_errors
+            .as_ref()
+            .and_then(|errors| errors.first())
+        {
+            Some(write_error) => display(write_error.message.clone()),
+            None => err.into(),
+        },
+        _ => err.into(),
+    }
+}
+
+fn type_name(value: &Bson) -> &'static str {
+    match value {
+        Bson::Double(_) => "double",
+        Bson::String(_) => "string",
+        Bson::Array(_) => "array",
+        Bson::Document(_) => "object",
+        Bson::Boolean(_) => "bool",
+        Bson::Null | Bson::Undefined => "null",
+        Bson::RegularExpression(_) => "regex",
+        Bson::JavaScriptCode(_) | Bson::JavaScriptCodeWithScope(_) => "javascript",
+        Bson::Int32(_) => "int",
+        Bson::Int64(_) => "long",
+        Bson::Timestamp(_) => "timestamp",
+        Bson::Binary(_) => "binData",
+        Bson::ObjectId(_) => "objectId",
+        Bson::DateTime(_) => "date",
+        Bson::Symbol(_) => "symbol",
+        Bson::Decimal128(_) => "decimal",
+        Bson::MaxKey => "maxKey",
+        Bson::MinKey => "minKey",
+        Bson::DbPointer(_) => "dbPointer",
+    }
+}
+
+fn render_value(value: &Bson) -> QueryValue {
+    match value {
+        Bson::Null | Bson::Undefined => QueryValue::Null,
+        Bson::String(value) => QueryValue::Text {
+            value: value.clone(),
+        },
+        Bson::ObjectId(id) => QueryValue::Text { value: id.to_hex() },
+        Bson::Binary(binary) => QueryValue::Binary {
+            value: hex::encode(&binary.bytes),
+        },
+        value => QueryValue::Text {
+            value: value.clone().into_relaxed_extjson().to_string(),
+        },
+    }
+}
+
+/// Values arrive as text, the same as for SQL columns. Anything that parses as extended JSON
+/// is taken as such (so `42`, `true` and `{"$date": ...}` keep their types), a plain object id
+/// is taken as one and everything else is a string.
+fn parse_value(value: Option<&str>) -> Bson {
+    let Some(value) = value else {
+        return Bson::Null;
+    };
+
+    if let Ok(id) = ObjectId::parse_str(value) {
+        return Bson::ObjectId(id);
+    }
+
+    serde_json::from_str::<serde_json::Value>(value)
+        .ok()
+        .and_then(|value| Bson::try_from(value).ok())
+        .unwrap_or_else(|| Bson::String(value.to_string()))
+}
+
+fn field_name(name: &str) -> Result<&str, anyhow::Error> {
+    if name.starts_with('$') || name.contains('\0') {
+        return Err(display(format!("{name} is not a valid field name")));
+    }
+
+    Ok(name)
+}
+
+fn filter_condition(filter: &BrowseFilter) -> Result<Document, anyhow::Error> {
+    let field = field_name(&filter.column)?;
+
+    if matches!(
+        filter.operator,
+        FilterOperator::IsNull | FilterOperator::NotNull
+    ) {
+        if filter.value.is_some() {
+            return Err(display(format!(
+                "a null check on {field} does not take a value"
+            )));
+        }
+
+        return Ok(if matches!(filter.operator, FilterOperator::IsNull) {
+            doc! { field: Bson::Null }
+        } else {
+            doc! { field: { "$ne": Bson::Null } }
+        });
+    }
+
+    let value = filter
+        .value
+        .as_deref()
+        .ok_or_else(|| display(format!("a value is required to filter on {field}")))?;
+
+    let operator = match filter.operator {
+        FilterOperator::Eq => "$eq",
+        FilterOperator::Ne => "$ne",
+        FilterOperator::Lt => "$lt",
+        FilterOperator::Lte => "$lte",
+        FilterOperator::Gt => "$gt",
+        FilterOperator::Gte => "$gte",
+        operator => {
+            let escaped = regex::escape(value);
+            let pattern = match operator {
+                FilterOperator::Contains => escaped,
+                FilterOperator::StartsWith => format!("^{escaped}"),
+                _ => format!("{escaped}$"),
+            };
+
+            return Ok(doc! { field: { "$regex": pattern, "$options": "i" } });
+        }
+    };
+
+    Ok(doc! { field: { operator: parse_value(Some(value)) } })
+}
+
+fn filter_document(filters: &[BrowseFilter]) -> Result<Document, anyhow::Error> {
+    let conditions = filters
+        .iter()
+        .map(filter_condition)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if conditions.is_empty() {
+        return Ok(Document::new());
+    }
+
+    Ok(doc! { "$and": conditions })
+}
+
+/// Documents do not share a shape, every top level field seen becomes a column.
+fn documents_result(documents: &[Document], truncated: bool) -> QueryResultSet {
+    let mut columns: Vec<QueryColumn> = Vec::new();
+
+    for document in documents {
+        for (key, value) in document {
+            match columns
+                .iter_mut()
+                .find(|column| column.name == key.as_str())
+            {
+                Some(column) => {
+                    if column.type_name == "null" {
+                        column.type_name = type_name(value).into();
+                        column.binary = matches!(value, Bson::Binary(_));
+                    }
+                }
+                None => columns.push(QueryColumn {
+                    name: key.as_str().into(),
+                    type_name: type_name(value).into(),
+                    type_oid: None,
+                    binary: matches!(value, Bson::Binary(_)),
+                }),
+            }
+        }
+    }
+
+    let rows = documents
+        .iter()
+        .map(|document| {
+            columns
+                .iter()
+                .map(|column| {
+                    document
+                        .get(column.name.as_str())
+                        .map(render_value)
+                        .unwrap_or(QueryValue::Null)
+                })
+                .collect()
+        })
+        .collect();
+
+    QueryResultSet {
+        columns,
+        rows,
+        rows_affected: 0,
+        truncated,
+    }
+}
+
+async fn collect_documents(
+    mut cursor: mongodb::Cursor<Document>,
+    max_rows: usize,
+) -> Result<QueryResultSet, anyhow::Error> {
+    let mut documents = Vec::new();
+    let mut bytes = 0;
+    let mut truncated = false;
+
+    while let Some(document) = cursor.try_next().await.map_err(mongodb_error)? {
+        if documents.len() >= max_rows || bytes >= QUERY_MAX_BYTES {
+            truncated = true;
+            break;
+        }
+
+        bytes += mongodb::bson::to_vec(&document).map_or(0, |encoded| encoded.len());
+        documents.push(document);
+    }
+
+    Ok(documents_result(&documents, truncated))
+}
+
+fn integer(command: &Document, key: &str) -> Result<Option<i64>, anyhow::Error> {
+    match command.get(key) {
+        None => Ok(None),
+        Some(Bson::Int32(value)) => Ok(Some(*value as i64)),
+        Some(Bson::Int64(value)) => Ok(Some(*value)),
+        Some(Bson::Double(value)) if value.fract() == 0.0 => Ok(Some(*value as i64)),
+        Some(_) => Err(display(format!("{key} must be an integer"))),
+    }
+}
+
+fn optional_document(command: &Document, key: &str) -> Result<Option<Document>, anyhow::Error> {
+    match command.get(key) {
+        None => Ok(None),
+        Some(Bson::Document(document)) => Ok(Some(document.clone())),
+        Some(_) => Err(display(format!("{key} must be an object"))),
+    }
+}
+
+fn document_ids(keys: &[RowValue]) -> Result<Bson, anyhow::Error> {
+    match keys {
+        [key] if key.column == "_id" => Ok(parse_value(key.value.as_deref())),
+        _ => Err(display("documents are addressed by their _id alone")),
+    }
+}
+
+impl MongodbExplorer {
+    async fn check_collection(&self, collection: &str) -> Result<(), anyhow::Error> {
+        let names = self
+            .database
+            .list_collection_names()
+            .filter(doc! { "name": collection })
+            .await
+            .map_err(mongodb_error)?;
+
+        if names.is_empty() {
+            return Err(unknown_table(collection));
+        }
+
+        Ok(())
+    }
+
+    pub(super) async fn schema_tables(&self) -> Result<Vec<SchemaTable>, anyhow::Error> {
+        let specifications: Vec<_> = self
+            .database
+            .list_collections()
+            .await
+            .map_err(mongodb_error)?
+            .try_collect()
+            .await
+            .map_err(mongodb_error)?;
+
+        let mut tables = Vec::with_capacity(specifications.len());
+        for specification in specifications {
+            if specification.name.starts_with("system.") {
+                continue;
+            }
+
+            let view = matches!(specification.collection_type, CollectionType::View);
+            let collection = self.database.collection::<Document>(&specification.name);
+
+            let sample = collection
+                .find(doc! {})
+                .limit(SCHEMA_SAMPLE_DOCUMENTS)
+                .max_time(max_time())
+                .await
+                .map_err(mongodb_error)?;
+            let sample = collect_documents(sample, SCHEMA_SAMPLE_DOCUMENTS as usize).await?;
+
+            let (row_estimate, indexes) = if view {
+                (None, Vec::new())
+            } else {
+                let row_estimate = collection
+                    .estimated_document_count()
+                    .await
+                    .map_err(mongodb_error)?;
+                let indexes: Vec<_> = collection
+                    .list_indexes()
+                    .await
+                    .map_err(mongodb_error)?
+                    .try_collect()
+                    .await
+                    .map_err(mongodb_error)?;
+
+                (
+                    Some(row_estimate as i64),
+                    indexes
+                        .into_iter()
+                        .map(|index| {
+                            let options = index.options.unwrap_or_default();
+
+                            SchemaIndex {
+                                name: options.name.unwrap_or_default().into(),
+                                columns: index.keys.keys().map(|key| key.as_str().into()).collect(),
+                                unique: options.unique.unwrap_or(false),
+                                definition: Bson::Document(index.keys)
+                                    .into_relaxed_extjson()
+                                    .to_string(),
+                            }
+                        })
+                        .collect(),
+                )
+            };
+
+            let mut columns: Vec<SchemaColumn> = sample
+                .columns
+                .into_iter()
+                .map(|column| SchemaColumn {
+                    primary_key: column.name == "_id",
+                    nullable: column.name != "_id",
+                    name: column.name,
+                    type_name: column.type_name,
+                    cast_type: None,
+                    default: None,
+                    auto_increment: false,
+                    generated: false,
+                    binary: column.binary,
+                })
+                .collect();
+            if !view && !columns.iter().any(|column| column.primary_key) {
+                columns.insert(
+                    0,
+                    SchemaColumn {
+                        name: "_id".into(),
+                        type_name: "objectId".into(),
+                        cast_type: None,
+                        nullable: false,
+                        default: None,
+                        primary_key: true,
+                        auto_increment: false,
+                        generated: false,
+                        binary: false,
+                    },
+                );
+            }
+
+            tables.push(SchemaTable {
+                schema: None,
+                name: specification.name.into(),
+                view,
+                row_estimate,
+                columns,
+                indexes,
+                constraints: Vec::new(),
+                foreign_keys: Vec::new(),
+            });
+        }
+
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(tables)
+    }
+
+    pub(super) async fn browse(
+        &self,
+        options: &BrowseOptions,
+    ) -> Result<QueryResultSet, anyhow::Error> {
+        self.check_collection(&options.table).await?;
+
+        let filter = filter_document(&options.filters)?;
+        let collection = self.database.collection::<Document>(&options.table);
+        let mut find = collection
+            .find(filter)
+            .skip(options.offset)
+            .limit(options.limit as i64)
+            .max_time(max_time());
+        if let Some(order_by) = &options.order_by {
+            let field = field_name(order_by)?;
+            let direction = if options.descending { -1 } else { 1 };
+
+            find = find.sort(doc! { field: direction });
+        }
+
+        collect_documents(find.await.map_err(mongodb_error)?, options.limit as usize).await
+    }
+
+    pub(super) async fn mutate(
+        &self,
+        collection: &str,
+        operation: RowOperation<'_>,
+    ) -> Result<u64, anyhow::Error> {
+        self.check_collection(collection).await?;
+        let collection = self.database.collection::<Document>(collection);
+
+        match operation {
+            RowOperation::Insert(rows) => {
+                let mut documents = Vec::with_capacity(rows.len());
+                for row in rows {
+                    let mut document = Document::new();
+                    for entry in &row.values {
+                        let field = field_name(&entry.column)?;
+                        if field.contains('.') {
+                            return Err(display(format!(
+                                "field {field} cannot contain a dot when inserting"
+                            )));
+                        }
+
+                        document.insert(field, parse_value(entry.value.as_deref()));
+                    }
+
+                    documents.push(document);
+                }
+
+                let result = collection
+                    .insert_many(documents)
+                    .await
+                    .map_err(mongodb_error)?;
+
+                Ok(result.inserted_ids.len() as u64)
+            }
+            RowOperation::Update(rows) => {
+                let mut updates = Vec::with_capacity(rows.len());
+                for row in rows {
+                    let id = document_ids(&row.keys)?;
+
+                    let mut set = Document::new();
+                    for entry in &row.values {
+                        let field = field_name(&entry.column)?;
+                        if field == "_id" {
+                            return Err(display("the _id of a document cannot be changed"));
+                        }
+
+                        set.insert(field, parse_value(entry.value.as_deref()));
+                    }
+                    if set.is_empty() {
+                        return Err(display("no fields were given to update"));
+                    }
+
+                    updates.push((id, set));
+                }
+
+                // every document is updated on its own, so all of them go through one transaction
+                let mut session = self
+                    .database
+                    .client()
+                    .start_session()
+                    .await
+                    .map_err(mongodb_error)?;
+                session.start_transaction().await.map_err(mongodb_error)?;
+
+                let mut affected = 0;
+                for (id, set) in updates {
+                    let matched = match collection
+                        .update_one(doc! { "_id": id }, doc! { "$set": set })
+                        .session(&mut session)
+                        .await
+                    {
+                        Ok(result) => result.matched_count,
+                        Err(err) => {
+                            session.abort_transaction().await.ok();
+                            return Err(mongodb_error(err));
+                        }
+                    };
+
+                    if matched != 1 {
+                        session.abort_transaction().await.map_err(mongodb_error)?;
+
+                        return Err(display(
+                            "a document did not match exactly once, nothing was changed",
+                        ));
+                    }
+
+                    affected += matched;
+                }
+
+                session.commit_transaction().await.map_err(mongodb_error)?;
+
+                Ok(affected)
+            }
+            RowOperation::Delete(rows) => {
+                let ids = rows
+                    .iter()
+                    .map(|row| document_ids(&row.keys))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let result = collection
+                    .delete_many(doc! { "_id": { "$in": ids } })
+                    .await
+                    .map_err(mongodb_error)?;
+
+                Ok(result.deleted_count)
+            }
+        }
+    }
+
+    /// Runs a `find` or `aggregate` command given as extended JSON, e.g.
+    /// `{"find": "players", "filter": {"level": {"$gt": 10}}, "sort": {"level": -1}}` or
+    /// `{"aggregate": "players", "pipeline": [{"$group": {"_id": "$team"}}]}`. Nothing that
+    /// writes is accepted.
+    pub(super) async fn run_query(
+        &self,
+        query: &str,
+        max_rows: usize,
+    ) -> Result<Vec<QueryResultSet>, anyhow::Error> {
+        let invalid =
+            || display("a MongoDB query must be a find or aggregate command written as JSON");
+
+        let command = serde_json::from_str::<serde_json::Value>(query)
+            .ok()
+            .and_then(|value| Bson::try_from(value).ok())
+            .ok_or_else(invalid)?;
+        let Bson::Document(command) = command else {
+            return Err(invalid());
+        };
+
+        let cursor = if let Ok(collection) = command.get_str("find") {
+            let collection = self.database.collection::<Document>(collection);
+            let mut find = collection
+                .find(optional_document(&command, "filter")?.unwrap_or_default())
+                .limit(integer(&command, "limit")?.unwrap_or(max_rows as i64 + 1))
+                .max_time(max_time());
+            if let Some(sort) = optional_document(&command, "sort")? {
+                find = find.sort(sort);
+            }
+            if let Some(projection) = optional_document(&command, "projection")? {
+                find = find.projection(projection);
+            }
+            if let Some(skip) = integer(&command, "skip")? {
+                find = find.skip(skip.max(0) as u64);
+            }
+
+            find.await.map_err(mongodb_error)?
+        } else if let Ok(collection) = command.get_str("aggregate") {
+            let pipeline = command
+                .get_array("pipeline")
+                .map_err(|_| display("an aggregate command needs a pipeline array"))?;
+
+            let mut stages = Vec::with_capacity(pipeline.len());
+            for stage in pipeline {
+                let Bson::Document(stage) = stage else {
+                    return Err(display("every pipeline stage must be an object"));
+                };
+                if stage.contains_key("$out") || stage.contains_key("$merge") {
+                    return Err(display(
+                        "pipelines writing with $out or $merge are not allowed",
+                    ));
+                }
+
+                stages.push(stage.clone());
+            }
+
+            self.database
+                .collection::<Document>(collection)
+                .aggregate(stages)
+                .max_time(max_time())
+                .await
+                .map_err(mongodb_error)?
+        } else {
+            return Err(invalid());
+        };
+
+        Ok(vec![collect_documents(cursor, max_rows).await?])
+    }
+}
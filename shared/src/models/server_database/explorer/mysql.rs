@@ -1,7 +1,9 @@
 use super::{
-    ExplorerConnection, QUERY_STATEMENT_TIMEOUT_MS, QueryColumn, QueryResultSet, QueryValue,
-    ResultCollector, SchemaColumn, SchemaTable, Statement, TENANT_POOL_ACQUIRE_TIMEOUT,
-    TENANT_POOL_IDLE_TIMEOUT, TENANT_POOL_MAX_CONNECTIONS, display, query_error, render_type,
+    ConstraintKind, DumpRows, ExplorerConnection, ExportRows, QUERY_STATEMENT_TIMEOUT_MS,
+    QueryColumn, QueryResultSet, QueryValue, ReferentialAction, ResultCollector, SchemaColumn,
+    SchemaConstraint, SchemaForeignKey, SchemaIndex, SchemaTable, Statement,
+    TENANT_POOL_ACQUIRE_TIMEOUT, TENANT_POOL_IDLE_TIMEOUT, TENANT_POOL_MAX_CONNECTIONS,
+    TableDefinition, TableKey, TableKeyEntry, display, query_error, render_type,
 };
 use compact_str::CompactString;
 use futures_util::StreamExt;
@@ -12,6 +14,24 @@ pub(super) struct MysqlExplorer {
     pub(super) database: String,
 }
 
+impl MysqlExplorer {
+    async fn is_mariadb(&mut self) -> Result<bool, anyhow::Error> {
+        let version: (String,) = sqlx::query_as("SELECT VERSION()")
+            .fetch_one(&mut *self.connection)
+            .await?;
+
+        Ok(version.0.contains("MariaDB"))
+    }
+
+    fn quoted_columns(&self, columns: &[CompactString]) -> String {
+        columns
+            .iter()
+            .map(|column| self.quote_ident(column))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 impl MysqlExplorer {
     pub(super) fn create_pool(
         host: &str,
@@ -156,6 +176,55 @@ fn mysql_column(row: &MySqlRow) -> Result<SchemaColumn, sqlx::Error> {
     })
 }
 
+const MYSQL_INDEXES: &str = "SELECT s.TABLE_NAME, s.INDEX_NAME, s.INDEX_TYPE, s.COLUMN_NAME,
+    CAST(s.NON_UNIQUE = 0 AS SIGNED) AS is_unique
+    FROM information_schema.STATISTICS s
+    WHERE s.TABLE_SCHEMA = ? AND (? IS NULL OR s.TABLE_NAME = ?) AND s.INDEX_NAME <> 'PRIMARY'
+      AND NOT EXISTS (
+        SELECT 1 FROM information_schema.TABLE_CONSTRAINTS tc
+        WHERE tc.TABLE_SCHEMA = s.TABLE_SCHEMA AND tc.TABLE_NAME = s.TABLE_NAME
+          AND tc.CONSTRAINT_NAME = s.INDEX_NAME AND tc.CONSTRAINT_TYPE = 'UNIQUE'
+      )
+    ORDER BY s.TABLE_NAME, s.INDEX_NAME, s.SEQ_IN_INDEX";
+
+const MYSQL_UNIQUE_CONSTRAINTS: &str = "SELECT tc.TABLE_NAME, tc.CONSTRAINT_NAME, kcu.COLUMN_NAME
+    FROM information_schema.TABLE_CONSTRAINTS tc
+    JOIN information_schema.KEY_COLUMN_USAGE kcu ON kcu.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA
+        AND kcu.TABLE_NAME = tc.TABLE_NAME AND kcu.CONSTRAINT_NAME = tc.CONSTRAINT_NAME
+    WHERE tc.TABLE_SCHEMA = ? AND (? IS NULL OR tc.TABLE_NAME = ?) AND tc.CONSTRAINT_TYPE = 'UNIQUE'
+    ORDER BY tc.TABLE_NAME, tc.CONSTRAINT_NAME, kcu.ORDINAL_POSITION";
+
+// mariadb names column level checks after their column, so its check names are only unique
+// per table and have to be matched on the table as well
+const MARIADB_CHECK_CONSTRAINTS: &str = "SELECT TABLE_NAME, CONSTRAINT_NAME, CHECK_CLAUSE
+    FROM information_schema.CHECK_CONSTRAINTS
+    WHERE CONSTRAINT_SCHEMA = ? AND (? IS NULL OR TABLE_NAME = ?)
+    ORDER BY TABLE_NAME, CONSTRAINT_NAME";
+
+const MYSQL_CHECK_CONSTRAINTS: &str = "SELECT tc.TABLE_NAME, tc.CONSTRAINT_NAME, cc.CHECK_CLAUSE
+    FROM information_schema.TABLE_CONSTRAINTS tc
+    JOIN information_schema.CHECK_CONSTRAINTS cc ON cc.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA
+        AND cc.CONSTRAINT_NAME = tc.CONSTRAINT_NAME
+    WHERE tc.TABLE_SCHEMA = ? AND (? IS NULL OR tc.TABLE_NAME = ?) AND tc.CONSTRAINT_TYPE = 'CHECK'
+    ORDER BY tc.TABLE_NAME, tc.CONSTRAINT_NAME";
+
+const MYSQL_FOREIGN_KEYS: &str = "SELECT kcu.TABLE_NAME, kcu.CONSTRAINT_NAME, kcu.COLUMN_NAME,
+    kcu.REFERENCED_TABLE_SCHEMA, kcu.REFERENCED_TABLE_NAME, kcu.REFERENCED_COLUMN_NAME,
+    rc.UPDATE_RULE, rc.DELETE_RULE
+    FROM information_schema.KEY_COLUMN_USAGE kcu
+    JOIN information_schema.REFERENTIAL_CONSTRAINTS rc ON rc.CONSTRAINT_SCHEMA = kcu.CONSTRAINT_SCHEMA
+        AND rc.TABLE_NAME = kcu.TABLE_NAME AND rc.CONSTRAINT_NAME = kcu.CONSTRAINT_NAME
+    WHERE kcu.TABLE_SCHEMA = ? AND (? IS NULL OR kcu.TABLE_NAME = ?)
+      AND kcu.REFERENCED_TABLE_NAME IS NOT NULL
+    ORDER BY kcu.TABLE_NAME, kcu.CONSTRAINT_NAME, kcu.ORDINAL_POSITION";
+
+/// Whether `key` continues the key of the last entry, keys span one row per column.
+fn same_key(entries: &[TableKeyEntry], table: &str, key: &str) -> bool {
+    entries
+        .last()
+        .is_some_and(|(_, last_table, last_key)| last_table == table && last_key.name() == key)
+}
+
 pub(super) const MYSQL_TYPES: &[&str] = &[
     "tinyint",
     "smallint",
@@ -272,6 +341,9 @@ impl ExplorerConnection for MysqlExplorer {
                     view: row.try_get::<String, _>("TABLE_TYPE")? == "VIEW",
                     row_estimate: row.try_get("row_estimate")?,
                     columns: Vec::new(),
+                    indexes: Vec::new(),
+                    constraints: Vec::new(),
+                    foreign_keys: Vec::new(),
                 })
             })
             .collect::<Result<_, sqlx::Error>>()?;
@@ -285,6 +357,9 @@ impl ExplorerConnection for MysqlExplorer {
             table.columns.push(mysql_column(&row)?);
         }
 
+        let keys = self.table_keys(None).await?;
+        SchemaTable::attach_keys(&mut tables, keys);
+
         Ok(tables)
     }
 
@@ -306,6 +381,180 @@ impl ExplorerConnection for MysqlExplorer {
         Ok(columns)
     }
 
+    async fn table_keys(
+        &mut self,
+        table: Option<(Option<&str>, &str)>,
+    ) -> Result<Vec<TableKeyEntry>, anyhow::Error> {
+        let table = table.map(|(_, table)| table);
+        let mut keys: Vec<TableKeyEntry> = Vec::new();
+
+        let indexes = sqlx::query(MYSQL_INDEXES)
+            .bind(&self.database)
+            .bind(table)
+            .bind(table)
+            .fetch_all(&mut *self.connection)
+            .await?;
+        for row in indexes {
+            let table_name: CompactString = row.try_get("TABLE_NAME")?;
+            let name: CompactString = row.try_get("INDEX_NAME")?;
+            // functional indexes have an expression instead of a column name
+            let column: Option<CompactString> = row.try_get("COLUMN_NAME")?;
+
+            if !same_key(&keys, &table_name, &name) {
+                let index_type: String = row.try_get("INDEX_TYPE")?;
+
+                keys.push((
+                    None,
+                    table_name,
+                    TableKey::Index(SchemaIndex {
+                        name,
+                        columns: Vec::new(),
+                        unique: row.try_get::<i64, _>("is_unique")? != 0,
+                        definition: index_type,
+                    }),
+                ));
+            }
+
+            if let Some((_, _, TableKey::Index(index))) = keys.last_mut()
+                && let Some(column) = column
+            {
+                index.columns.push(column);
+            }
+        }
+
+        let uniques = sqlx::query(MYSQL_UNIQUE_CONSTRAINTS)
+            .bind(&self.database)
+            .bind(table)
+            .bind(table)
+            .fetch_all(&mut *self.connection)
+            .await?;
+        for row in uniques {
+            let table_name: CompactString = row.try_get("TABLE_NAME")?;
+            let name: CompactString = row.try_get("CONSTRAINT_NAME")?;
+
+            if !same_key(&keys, &table_name, &name) {
+                keys.push((
+                    None,
+                    table_name,
+                    TableKey::Constraint(SchemaConstraint {
+                        name,
+                        kind: ConstraintKind::Unique,
+                        columns: Vec::new(),
+                        definition: String::new(),
+                    }),
+                ));
+            }
+
+            if let Some((_, _, TableKey::Constraint(constraint))) = keys.last_mut() {
+                constraint.columns.push(row.try_get("COLUMN_NAME")?);
+            }
+        }
+
+        let checks = if self.is_mariadb().await? {
+            MARIADB_CHECK_CONSTRAINTS
+        } else {
+            MYSQL_CHECK_CONSTRAINTS
+        };
+        // servers before mysql 8.0.16 have no check constraints to report
+        let checks = sqlx::query(checks)
+            .bind(&self.database)
+            .bind(table)
+            .bind(table)
+            .fetch_all(&mut *self.connection)
+            .await
+            .unwrap_or_default();
+        for row in checks {
+            let clause: String = row.try_get("CHECK_CLAUSE")?;
+
+            keys.push((
+                None,
+                row.try_get("TABLE_NAME")?,
+                TableKey::Constraint(SchemaConstraint {
+                    name: row.try_get("CONSTRAINT_NAME")?,
+                    kind: ConstraintKind::Check,
+                    columns: Vec::new(),
+                    definition: format!("CHECK ({clause})"),
+                }),
+            ));
+        }
+
+        let foreign_keys = sqlx::query(MYSQL_FOREIGN_KEYS)
+            .bind(&self.database)
+            .bind(table)
+            .bind(table)
+            .fetch_all(&mut *self.connection)
+            .await?;
+        for row in foreign_keys {
+            let table_name: CompactString = row.try_get("TABLE_NAME")?;
+            let name: CompactString = row.try_get("CONSTRAINT_NAME")?;
+
+            if !same_key(&keys, &table_name, &name) {
+                let referenced_schema: CompactString = row.try_get("REFERENCED_TABLE_SCHEMA")?;
+
+                keys.push((
+                    None,
+                    table_name,
+                    TableKey::ForeignKey(SchemaForeignKey {
+                        name,
+                        columns: Vec::new(),
+                        referenced_schema: (referenced_schema != self.database)
+                            .then_some(referenced_schema),
+                        referenced_table: row.try_get("REFERENCED_TABLE_NAME")?,
+                        referenced_columns: Vec::new(),
+                        on_update: ReferentialAction::parse(row.try_get::<&str, _>("UPDATE_RULE")?),
+                        on_delete: ReferentialAction::parse(row.try_get::<&str, _>("DELETE_RULE")?),
+                    }),
+                ));
+            }
+
+            if let Some((_, _, TableKey::ForeignKey(foreign_key))) = keys.last_mut() {
+                foreign_key.columns.push(row.try_get("COLUMN_NAME")?);
+                foreign_key
+                    .referenced_columns
+                    .push(row.try_get("REFERENCED_COLUMN_NAME")?);
+            }
+        }
+
+        for (_, _, key) in &mut keys {
+            match key {
+                TableKey::Index(index) => {
+                    index.definition = format!(
+                        "USING {} ({})",
+                        index.definition,
+                        self.quoted_columns(&index.columns)
+                    );
+                }
+                TableKey::Constraint(constraint) if constraint.kind == ConstraintKind::Unique => {
+                    constraint.definition =
+                        format!("UNIQUE ({})", self.quoted_columns(&constraint.columns));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(keys)
+    }
+
+    fn drop_index_sql(&self, qualified: &str, _schema: Option<&str>, index: &str) -> String {
+        format!("DROP INDEX {} ON {qualified}", self.quote_ident(index))
+    }
+
+    fn drop_constraint_sql(&self, qualified: &str, key: &TableKey) -> String {
+        let (kind, name) = match key {
+            TableKey::ForeignKey(foreign_key) => ("FOREIGN KEY", &foreign_key.name),
+            TableKey::Index(index) => ("INDEX", &index.name),
+            TableKey::Constraint(constraint) => match constraint.kind {
+                ConstraintKind::Unique => ("INDEX", &constraint.name),
+                _ => ("CONSTRAINT", &constraint.name),
+            },
+        };
+
+        format!(
+            "ALTER TABLE {qualified} DROP {kind} {}",
+            self.quote_ident(name)
+        )
+    }
+
     async fn column_types(&mut self) -> Result<Vec<String>, anyhow::Error> {
         Ok(MYSQL_TYPES.iter().map(ToString::to_string).collect())
     }
@@ -405,4 +654,118 @@ impl ExplorerConnection for MysqlExplorer {
 
         Ok(())
     }
+
+    async fn disable_statement_timeout(&mut self) -> Result<(), anyhow::Error> {
+        let statement = if self.is_mariadb().await? {
+            "SET SESSION max_statement_time = 0"
+        } else {
+            "SET SESSION max_execution_time = 0"
+        };
+
+        sqlx::raw_sql(statement)
+            .execute(&mut *self.connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn begin_snapshot(&mut self) -> Result<(), anyhow::Error> {
+        self.disable_statement_timeout().await?;
+
+        sqlx::raw_sql("SET SESSION TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .execute(&mut *self.connection)
+            .await?;
+        sqlx::raw_sql("START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY")
+            .execute(&mut *self.connection)
+            .await?;
+
+        Ok(())
+    }
+
+    fn dump_header(&self) -> &'static [&'static str] {
+        &["SET NAMES utf8mb4", "SET FOREIGN_KEY_CHECKS = 0"]
+    }
+
+    fn drop_table_sql(&self, qualified: &str) -> String {
+        format!("DROP TABLE IF EXISTS {qualified}")
+    }
+
+    async fn dump_types(&mut self) -> Result<Vec<String>, anyhow::Error> {
+        Ok(Vec::new())
+    }
+
+    async fn table_definition(
+        &mut self,
+        qualified: &str,
+        _table: &SchemaTable,
+    ) -> Result<TableDefinition, anyhow::Error> {
+        let row = sqlx::raw_sql(sqlx::AssertSqlSafe(format!(
+            "SHOW CREATE TABLE {qualified}"
+        )))
+        .fetch_one(&mut *self.connection)
+        .await
+        .map_err(query_error)?;
+
+        // foreign key checks are off for the whole dump, so the tables can be created in any order
+        Ok(TableDefinition {
+            create: vec![row.try_get(1)?],
+            finish: Vec::new(),
+        })
+    }
+
+    fn dumps_column(&self, column: &SchemaColumn) -> bool {
+        !column.generated
+    }
+
+    fn row_literal(&self, columns: &[&SchemaColumn]) -> String {
+        let literals: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                let quoted = self.quote_ident(&column.name);
+
+                if column.binary {
+                    format!(
+                        "IF({quoted} IS NULL, 'NULL', IF(LENGTH({quoted}) = 0, '''''', CONCAT('0x', HEX({quoted}))))"
+                    )
+                } else {
+                    format!("QUOTE({quoted})")
+                }
+            })
+            .collect();
+
+        format!("CONCAT('(', {}, ')')", literals.join(", ', ', "))
+    }
+
+    async fn dump_rows(
+        &mut self,
+        sql: String,
+        rows: &mut DumpRows<'_>,
+    ) -> Result<(), anyhow::Error> {
+        let mut stream = sqlx::raw_sql(sqlx::AssertSqlSafe(sql)).fetch(&mut *self.connection);
+
+        while let Some(row) = stream.next().await {
+            let row: Vec<u8> = row.map_err(query_error)?.try_get_unchecked(0)?;
+            rows.push(&row).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn export_rows(
+        &mut self,
+        sql: String,
+        rows: &mut ExportRows<'_>,
+    ) -> Result<(), anyhow::Error> {
+        let mut stream = sqlx::raw_sql(sqlx::AssertSqlSafe(sql)).fetch_many(&mut *self.connection);
+
+        while let Some(item) = stream.next().await {
+            match item.map_err(query_error)? {
+                Either::Left(_) if rows.started() => break,
+                Either::Left(_) => {}
+                Either::Right(row) => rows.push(|| mysql_columns(&row), mysql_row(&row)).await?,
+            }
+        }
+
+        Ok(())
+    }
 }
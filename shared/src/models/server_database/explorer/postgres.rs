@@ -1,7 +1,9 @@
 use super::{
-    ExplorerConnection, QUERY_STATEMENT_TIMEOUT_MS, QueryColumn, QueryResultSet, QueryValue,
-    ResultCollector, SchemaColumn, SchemaTable, Statement, TENANT_POOL_ACQUIRE_TIMEOUT,
-    TENANT_POOL_IDLE_TIMEOUT, TENANT_POOL_MAX_CONNECTIONS, display, query_error, render_type,
+    ConstraintKind, DumpRows, ExplorerConnection, ExportRows, QUERY_STATEMENT_TIMEOUT_MS,
+    QueryColumn, QueryResultSet, QueryValue, ReferentialAction, ResultCollector, SchemaColumn,
+    SchemaConstraint, SchemaForeignKey, SchemaIndex, SchemaTable, Statement,
+    TENANT_POOL_ACQUIRE_TIMEOUT, TENANT_POOL_IDLE_TIMEOUT, TENANT_POOL_MAX_CONNECTIONS,
+    TableDefinition, TableKey, TableKeyEntry, display, query_error, render_type,
 };
 use compact_str::CompactString;
 use futures_util::StreamExt;
@@ -279,6 +281,82 @@ const POSTGRES_USER_TYPES: &str = "SELECT t.oid::regtype::text FROM pg_catalog.p
       AND n.nspname <> 'information_schema' AND n.nspname !~ '^pg_'
     ORDER BY 1";
 
+const POSTGRES_ENUM_TYPES: &str = "SELECT t.oid::regtype::text AS name,
+    format('CREATE TYPE %s AS ENUM (%s)', t.oid::regtype,
+        string_agg(quote_literal(e.enumlabel), ', ' ORDER BY e.enumsortorder)) AS definition
+    FROM pg_catalog.pg_type t
+    JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+    JOIN pg_catalog.pg_enum e ON e.enumtypid = t.oid
+    WHERE n.nspname <> 'information_schema' AND n.nspname !~ '^pg_'
+    GROUP BY t.oid
+    ORDER BY 1";
+
+const POSTGRES_TABLE_CONSTRAINTS: &str = "SELECT conname AS name, contype::text AS contype,
+    pg_catalog.pg_get_constraintdef(oid) AS definition
+    FROM pg_catalog.pg_constraint
+    WHERE conrelid = $1::regclass AND contype IN ('u', 'c', 'x', 'f')
+    ORDER BY conname";
+
+const POSTGRES_TABLE_INDEXES: &str = "SELECT pg_catalog.pg_get_indexdef(i.indexrelid)
+    FROM pg_catalog.pg_index i
+    WHERE i.indrelid = $1::regclass
+      AND NOT EXISTS (
+        SELECT 1 FROM pg_catalog.pg_constraint c
+        WHERE c.conindid = i.indexrelid AND c.contype IN ('p', 'u', 'x')
+      )
+    ORDER BY i.indexrelid";
+
+const POSTGRES_INDEXES: &str = "SELECT n.nspname AS schema, t.relname AS table_name,
+    ic.relname AS name, i.indisunique AS is_unique,
+    pg_catalog.pg_get_indexdef(i.indexrelid) AS definition,
+    ARRAY(
+        SELECT a.attname::text FROM unnest(i.indkey::int2[]) WITH ORDINALITY k(attnum, ord)
+        JOIN pg_catalog.pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = k.attnum
+        ORDER BY k.ord
+    ) AS columns
+    FROM pg_catalog.pg_index i
+    JOIN pg_catalog.pg_class ic ON ic.oid = i.indexrelid
+    JOIN pg_catalog.pg_class t ON t.oid = i.indrelid
+    JOIN pg_catalog.pg_namespace n ON n.oid = t.relnamespace
+    WHERE ($2::text IS NULL OR (n.nspname = $1 AND t.relname = $2))
+      AND n.nspname <> 'information_schema' AND n.nspname !~ '^pg_'
+      AND pg_catalog.has_table_privilege(t.oid, 'SELECT')
+      AND NOT EXISTS (
+        SELECT 1 FROM pg_catalog.pg_constraint c
+        WHERE c.conindid = i.indexrelid AND c.contype IN ('p', 'u', 'x')
+      )
+    ORDER BY n.nspname, t.relname, ic.relname";
+
+const POSTGRES_CONSTRAINTS: &str = "SELECT n.nspname AS schema, t.relname AS table_name,
+    c.conname AS name, c.contype::text AS contype,
+    pg_catalog.pg_get_constraintdef(c.oid) AS definition,
+    ARRAY(
+        SELECT a.attname::text FROM unnest(c.conkey) WITH ORDINALITY k(attnum, ord)
+        JOIN pg_catalog.pg_attribute a ON a.attrelid = c.conrelid AND a.attnum = k.attnum
+        ORDER BY k.ord
+    ) AS columns,
+    rn.nspname AS referenced_schema, rt.relname AS referenced_table,
+    ARRAY(
+        SELECT a.attname::text FROM unnest(c.confkey) WITH ORDINALITY k(attnum, ord)
+        JOIN pg_catalog.pg_attribute a ON a.attrelid = c.confrelid AND a.attnum = k.attnum
+        ORDER BY k.ord
+    ) AS referenced_columns,
+    c.confupdtype::text AS on_update, c.confdeltype::text AS on_delete
+    FROM pg_catalog.pg_constraint c
+    JOIN pg_catalog.pg_class t ON t.oid = c.conrelid
+    JOIN pg_catalog.pg_namespace n ON n.oid = t.relnamespace
+    LEFT JOIN pg_catalog.pg_class rt ON rt.oid = c.confrelid
+    LEFT JOIN pg_catalog.pg_namespace rn ON rn.oid = rt.relnamespace
+    WHERE c.contype IN ('u', 'c', 'x', 'f')
+      AND ($2::text IS NULL OR (n.nspname = $1 AND t.relname = $2))
+      AND n.nspname <> 'information_schema' AND n.nspname !~ '^pg_'
+      AND pg_catalog.has_table_privilege(t.oid, 'SELECT')
+    ORDER BY n.nspname, t.relname, c.conname";
+
+fn compact_columns(columns: Vec<String>) -> Vec<CompactString> {
+    columns.into_iter().map(Into::into).collect()
+}
+
 #[async_trait::async_trait]
 impl ExplorerConnection for PostgresExplorer {
     fn close_on_drop(&mut self) {
@@ -358,6 +436,9 @@ impl ExplorerConnection for PostgresExplorer {
                     view: matches!(row.try_get::<String, _>("relkind")?.as_str(), "v" | "m"),
                     row_estimate: row.try_get("row_estimate")?,
                     columns: Vec::new(),
+                    indexes: Vec::new(),
+                    constraints: Vec::new(),
+                    foreign_keys: Vec::new(),
                 })
             })
             .collect::<Result<_, sqlx::Error>>()?;
@@ -374,6 +455,9 @@ impl ExplorerConnection for PostgresExplorer {
             table.columns.push(postgres_column(&row)?);
         }
 
+        let keys = self.table_keys(None).await?;
+        SchemaTable::attach_keys(&mut tables, keys);
+
         Ok(tables)
     }
 
@@ -395,6 +479,98 @@ impl ExplorerConnection for PostgresExplorer {
         Ok(columns)
     }
 
+    async fn table_keys(
+        &mut self,
+        table: Option<(Option<&str>, &str)>,
+    ) -> Result<Vec<TableKeyEntry>, anyhow::Error> {
+        let (schema, table) = match table {
+            Some((schema, table)) => (schema.unwrap_or("public"), Some(table)),
+            None => ("public", None),
+        };
+        let mut keys: Vec<TableKeyEntry> = Vec::new();
+
+        let indexes = sqlx::query(POSTGRES_INDEXES)
+            .bind(schema)
+            .bind(table)
+            .fetch_all(&mut *self.connection)
+            .await?;
+        for row in indexes {
+            keys.push((
+                Some(row.try_get("schema")?),
+                row.try_get("table_name")?,
+                TableKey::Index(SchemaIndex {
+                    name: row.try_get("name")?,
+                    columns: compact_columns(row.try_get("columns")?),
+                    unique: row.try_get("is_unique")?,
+                    definition: row.try_get("definition")?,
+                }),
+            ));
+        }
+
+        let constraints = sqlx::query(POSTGRES_CONSTRAINTS)
+            .bind(schema)
+            .bind(table)
+            .fetch_all(&mut *self.connection)
+            .await?;
+        for row in constraints {
+            let name: CompactString = row.try_get("name")?;
+            let columns = compact_columns(row.try_get("columns")?);
+
+            let kind = match row.try_get::<&str, _>("contype")? {
+                "u" => ConstraintKind::Unique,
+                "c" => ConstraintKind::Check,
+                "x" => ConstraintKind::Exclusion,
+                _ => {
+                    keys.push((
+                        Some(row.try_get("schema")?),
+                        row.try_get("table_name")?,
+                        TableKey::ForeignKey(SchemaForeignKey {
+                            name,
+                            columns,
+                            referenced_schema: row.try_get("referenced_schema")?,
+                            referenced_table: row
+                                .try_get::<Option<CompactString>, _>("referenced_table")?
+                                .unwrap_or_default(),
+                            referenced_columns: compact_columns(row.try_get("referenced_columns")?),
+                            on_update: ReferentialAction::parse(row.try_get("on_update")?),
+                            on_delete: ReferentialAction::parse(row.try_get("on_delete")?),
+                        }),
+                    ));
+
+                    continue;
+                }
+            };
+
+            keys.push((
+                Some(row.try_get("schema")?),
+                row.try_get("table_name")?,
+                TableKey::Constraint(SchemaConstraint {
+                    name,
+                    kind,
+                    columns,
+                    definition: row.try_get("definition")?,
+                }),
+            ));
+        }
+
+        Ok(keys)
+    }
+
+    fn drop_index_sql(&self, _qualified: &str, schema: Option<&str>, index: &str) -> String {
+        format!(
+            "DROP INDEX {}.{}",
+            self.quote_ident(schema.unwrap_or("public")),
+            self.quote_ident(index)
+        )
+    }
+
+    fn drop_constraint_sql(&self, qualified: &str, key: &TableKey) -> String {
+        format!(
+            "ALTER TABLE {qualified} DROP CONSTRAINT {}",
+            self.quote_ident(key.name())
+        )
+    }
+
     async fn column_types(&mut self) -> Result<Vec<String>, anyhow::Error> {
         let user_types: Vec<String> = sqlx::query_scalar(POSTGRES_USER_TYPES)
             .fetch_all(&mut *self.connection)
@@ -522,4 +698,186 @@ impl ExplorerConnection for PostgresExplorer {
 
         Ok(())
     }
+
+    async fn disable_statement_timeout(&mut self) -> Result<(), anyhow::Error> {
+        sqlx::raw_sql("SET statement_timeout = 0")
+            .execute(&mut *self.connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn begin_snapshot(&mut self) -> Result<(), anyhow::Error> {
+        self.disable_statement_timeout().await?;
+
+        sqlx::raw_sql("BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *self.connection)
+            .await?;
+
+        Ok(())
+    }
+
+    fn dump_header(&self) -> &'static [&'static str] {
+        &[
+            "SET client_encoding = 'UTF8'",
+            "SET standard_conforming_strings = on",
+        ]
+    }
+
+    fn drop_table_sql(&self, qualified: &str) -> String {
+        format!("DROP TABLE IF EXISTS {qualified} CASCADE")
+    }
+
+    async fn dump_types(&mut self) -> Result<Vec<String>, anyhow::Error> {
+        let rows = sqlx::query(POSTGRES_ENUM_TYPES)
+            .fetch_all(&mut *self.connection)
+            .await?;
+
+        let mut statements = Vec::with_capacity(rows.len() * 2);
+        for row in rows {
+            let name: String = row.try_get("name")?;
+
+            statements.push(format!("DROP TYPE IF EXISTS {name} CASCADE"));
+            statements.push(row.try_get("definition")?);
+        }
+
+        Ok(statements)
+    }
+
+    async fn table_definition(
+        &mut self,
+        qualified: &str,
+        table: &SchemaTable,
+    ) -> Result<TableDefinition, anyhow::Error> {
+        let mut definitions = Vec::with_capacity(table.columns.len() + 1);
+        let mut finish = Vec::new();
+
+        for column in &table.columns {
+            let name = self.quote_ident(&column.name);
+            let mut definition = format!("{name} {}", column.type_name);
+
+            if column.auto_increment {
+                // serial columns come back as identities, their sequences are not part of the dump
+                definition.push_str(" GENERATED BY DEFAULT AS IDENTITY");
+                finish.push(format!(
+                    "SELECT pg_catalog.setval(pg_catalog.pg_get_serial_sequence('{}', '{}'), COALESCE(MAX({name}), 0) + 1, false) FROM {qualified}",
+                    qualified.replace('\'', "''"),
+                    column.name.replace('\'', "''"),
+                ));
+            } else if column.generated {
+                if let Some(expression) = &column.default {
+                    definition.push_str(&format!(" GENERATED ALWAYS AS ({expression}) STORED"));
+                }
+            } else if let Some(default) = &column.default {
+                definition.push_str(&format!(" DEFAULT {default}"));
+            }
+
+            if !column.nullable {
+                definition.push_str(" NOT NULL");
+            }
+
+            definitions.push(definition);
+        }
+
+        let primary: Vec<String> = table
+            .columns
+            .iter()
+            .filter(|column| column.primary_key)
+            .map(|column| self.quote_ident(&column.name))
+            .collect();
+        if !primary.is_empty() {
+            definitions.push(format!("PRIMARY KEY ({})", primary.join(", ")));
+        }
+
+        let constraints = sqlx::query(POSTGRES_TABLE_CONSTRAINTS)
+            .bind(qualified)
+            .fetch_all(&mut *self.connection)
+            .await?;
+        for row in constraints {
+            let name = self.quote_ident(row.try_get("name")?);
+            let definition: String = row.try_get("definition")?;
+
+            if row.try_get::<String, _>("contype")? == "f" {
+                finish.push(format!(
+                    "ALTER TABLE {qualified} ADD CONSTRAINT {name} {definition}"
+                ));
+            } else {
+                definitions.push(format!("CONSTRAINT {name} {definition}"));
+            }
+        }
+
+        let indexes: Vec<String> = sqlx::query_scalar(POSTGRES_TABLE_INDEXES)
+            .bind(qualified)
+            .fetch_all(&mut *self.connection)
+            .await?;
+        finish.extend(indexes);
+
+        Ok(TableDefinition {
+            create: vec![format!(
+                "CREATE TABLE {qualified} ({})",
+                definitions.join(", ")
+            )],
+            finish,
+        })
+    }
+
+    fn dumps_column(&self, column: &SchemaColumn) -> bool {
+        // identities are reported as generated, but their values have to survive the dump
+        !column.generated || column.auto_increment
+    }
+
+    fn row_literal(&self, columns: &[&SchemaColumn]) -> String {
+        let literals: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                let quoted = self.quote_ident(&column.name);
+
+                if column.binary {
+                    format!(
+                        "CASE WHEN {quoted} IS NULL THEN 'NULL' ELSE 'decode(''' || encode({quoted}, 'hex') || ''', ''hex'')' END"
+                    )
+                } else {
+                    format!("quote_nullable({quoted})")
+                }
+            })
+            .collect();
+
+        format!("'(' || {} || ')'", literals.join(" || ', ' || "))
+    }
+
+    async fn dump_rows(
+        &mut self,
+        sql: String,
+        rows: &mut DumpRows<'_>,
+    ) -> Result<(), anyhow::Error> {
+        let mut stream = sqlx::raw_sql(sqlx::AssertSqlSafe(sql)).fetch(&mut *self.connection);
+
+        while let Some(row) = stream.next().await {
+            let row: String = row.map_err(query_error)?.try_get(0)?;
+            rows.push(row.as_bytes()).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn export_rows(
+        &mut self,
+        sql: String,
+        rows: &mut ExportRows<'_>,
+    ) -> Result<(), anyhow::Error> {
+        let mut stream = sqlx::raw_sql(sqlx::AssertSqlSafe(sql)).fetch_many(&mut *self.connection);
+
+        while let Some(item) = stream.next().await {
+            match item.map_err(query_error)? {
+                Either::Left(_) if rows.started() => break,
+                Either::Left(_) => {}
+                Either::Right(row) => {
+                    rows.push(|| postgres_columns(&row), postgres_row(&row))
+                        .await?
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,904 @@
+//! Exports of tables and query results, and bulk imports into existing tables.
+//!
+//! Exports are streamed through a [`DumpSender`] as CSV, JSON lines or `INSERT` statements.
+//! Imports read CSV or JSON lines, map their fields onto the columns of the table, coerce the
+//! values to the column types and insert them in batches. A batch that fails is retried row by
+//! row, so every rejected row can be reported with its own error.
+
+use super::{
+    DumpSender, MUTATE_MAX_ROWS, QUERY_MAX_LENGTH, QueryColumn, QueryValue, RowInsert,
+    RowOperation, RowValue, SchemaColumn, ServerDatabase, display, dump::send, unknown_column,
+    unknown_table,
+};
+use crate::models::database_host::DatabaseType;
+use compact_str::CompactString;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+use utoipa::ToSchema;
+
+const EXPORT_CHUNK_SIZE: usize = 256 * 1024;
+const IMPORT_MAX_RECORD_BYTES: usize = 1024 * 1024;
+pub const IMPORT_MAX_ERRORS: usize = 100;
+
+#[derive(ToSchema, Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+    Sql,
+}
+
+impl ExportFormat {
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+            Self::Sql => "sql",
+        }
+    }
+
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Jsonl => "application/jsonl",
+            Self::Sql => "application/sql",
+        }
+    }
+}
+
+#[derive(ToSchema, Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    Csv,
+    Jsonl,
+}
+
+/// The SQL flavour `INSERT` statements of an export are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    Mysql,
+    Postgres,
+}
+
+impl SqlDialect {
+    pub const fn from_database_type(r#type: DatabaseType) -> Option<Self> {
+        match r#type {
+            DatabaseType::Mysql => Some(Self::Mysql),
+            DatabaseType::Postgres => Some(Self::Postgres),
+            DatabaseType::Mongodb => None,
+        }
+    }
+
+    pub const fn from_agent_type(r#type: db_agent_api::DatabaseAgentType) -> Option<Self> {
+        match r#type {
+            db_agent_api::DatabaseAgentType::Mariadb => Some(Self::Mysql),
+            db_agent_api::DatabaseAgentType::Postgres => Some(Self::Postgres),
+            _ => None,
+        }
+    }
+
+    fn quote_ident(self, identifier: &str) -> String {
+        match self {
+            Self::Mysql => format!("`{}`", identifier.replace('`', "``")),
+            Self::Postgres => format!("\"{}\"", identifier.replace('"', "\"\"")),
+        }
+    }
+
+    fn string_literal(self, value: &str) -> String {
+        match self {
+            Self::Mysql => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''")),
+            Self::Postgres => format!("'{}'", value.replace('\'', "''")),
+        }
+    }
+
+    fn binary_literal(self, hex: &str) -> String {
+        match self {
+            Self::Mysql => format!("X'{hex}'"),
+            Self::Postgres => format!("decode('{hex}', 'hex')"),
+        }
+    }
+}
+
+/// What an export reads from.
+pub enum ExportSource {
+    Table {
+        schema: Option<CompactString>,
+        table: CompactString,
+    },
+    /// The first result set of a query. `table` names the target of SQL exports.
+    Query { sql: String, table: CompactString },
+}
+
+#[derive(ToSchema, Validate, Deserialize)]
+pub struct ExportOptions {
+    #[garde(skip)]
+    pub format: ExportFormat,
+
+    #[garde(inner(length(chars, min = 1, max = 255)))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub schema: Option<CompactString>,
+
+    /// The table to export, or the table `INSERT` statements of a query export are aimed at.
+    #[garde(inner(length(chars, min = 1, max = 255)))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub table: Option<CompactString>,
+
+    /// Exports the first result set of this query instead of a whole table.
+    #[garde(inner(length(chars, min = 1, max = QUERY_MAX_LENGTH)))]
+    #[schema(min_length = 1, max_length = 65535)]
+    pub query: Option<String>,
+}
+
+impl ExportOptions {
+    pub fn source(&self) -> Result<ExportSource, anyhow::Error> {
+        match (&self.query, &self.table) {
+            (Some(sql), table) => Ok(ExportSource::Query {
+                sql: sql.clone(),
+                table: table.clone().unwrap_or_else(|| "query".into()),
+            }),
+            (None, Some(table)) => Ok(ExportSource::Table {
+                schema: self.schema.clone(),
+                table: table.clone(),
+            }),
+            (None, None) => Err(display("either a table or a query has to be given")),
+        }
+    }
+
+    pub fn file_name(&self) -> String {
+        let name = self
+            .table
+            .as_deref()
+            .unwrap_or("query")
+            .replace(|c: char| c == '"' || c == '\\' || c.is_control(), "_");
+
+        format!("{name}.{}", self.format.extension())
+    }
+}
+
+impl ExportSource {
+    fn target(&self) -> (Option<&str>, &str) {
+        match self {
+            Self::Table { schema, table } => (schema.as_deref(), table),
+            Self::Query { table, .. } => (None, table),
+        }
+    }
+}
+
+/// Renders rows in an export format, the column list has to be given before the first row.
+pub struct ExportEncoder {
+    format: ExportFormat,
+    dialect: Option<SqlDialect>,
+    target: String,
+    columns: Vec<QueryColumn>,
+}
+
+impl ExportEncoder {
+    pub fn new(
+        format: ExportFormat,
+        dialect: Option<SqlDialect>,
+        source: &ExportSource,
+    ) -> Result<Self, anyhow::Error> {
+        let (schema, table) = source.target();
+
+        let target = match (format, dialect) {
+            (ExportFormat::Sql, None) => {
+                return Err(display(
+                    "SQL exports are only available for MySQL and PostgreSQL databases",
+                ));
+            }
+            (ExportFormat::Sql, Some(dialect)) => match schema {
+                Some(schema) => format!(
+                    "{}.{}",
+                    dialect.quote_ident(schema),
+                    dialect.quote_ident(table)
+                ),
+                None => dialect.quote_ident(table),
+            },
+            _ => String::new(),
+        };
+
+        Ok(Self {
+            format,
+            dialect,
+            target,
+            columns: Vec::new(),
+        })
+    }
+
+    pub fn columns(&mut self, columns: Vec<QueryColumn>, buffer: &mut Vec<u8>) {
+        if self.format == ExportFormat::Csv {
+            let header: Vec<String> = columns
+                .iter()
+                .map(|column| csv_field(&column.name))
+                .collect();
+            buffer.extend_from_slice(header.join(",").as_bytes());
+            buffer.extend_from_slice(b"\r\n");
+        }
+
+        self.columns = columns;
+    }
+
+    pub fn row(&self, values: &[QueryValue], buffer: &mut Vec<u8>) -> Result<(), anyhow::Error> {
+        match self.format {
+            ExportFormat::Csv => {
+                let fields: Vec<String> = values
+                    .iter()
+                    .map(|value| match value {
+                        QueryValue::Null => String::new(),
+                        QueryValue::Text { value } | QueryValue::Binary { value } => {
+                            csv_field(value)
+                        }
+                    })
+                    .collect();
+                buffer.extend_from_slice(fields.join(",").as_bytes());
+                buffer.extend_from_slice(b"\r\n");
+            }
+            ExportFormat::Jsonl => {
+                let object: serde_json::Map<String, serde_json::Value> = self
+                    .columns
+                    .iter()
+                    .zip(values)
+                    .map(|(column, value)| {
+                        let value = match value {
+                            QueryValue::Null => serde_json::Value::Null,
+                            QueryValue::Text { value } | QueryValue::Binary { value } => {
+                                serde_json::Value::String(value.clone())
+                            }
+                        };
+
+                        (column.name.to_string(), value)
+                    })
+                    .collect();
+                serde_json::to_writer(&mut *buffer, &object)?;
+                buffer.push(b'\n');
+            }
+            ExportFormat::Sql => {
+                let Some(dialect) = self.dialect else {
+                    return Err(display("SQL exports need a SQL database"));
+                };
+
+                let names: Vec<String> = self
+                    .columns
+                    .iter()
+                    .map(|column| dialect.quote_ident(&column.name))
+                    .collect();
+                let literals: Vec<String> = values
+                    .iter()
+                    .map(|value| match value {
+                        QueryValue::Null => "NULL".to_string(),
+                        QueryValue::Text { value } => dialect.string_literal(value),
+                        QueryValue::Binary { value } => dialect.binary_literal(value),
+                    })
+                    .collect();
+
+                buffer.extend_from_slice(
+                    format!(
+                        "INSERT INTO {} ({}) VALUES ({});\n",
+                        self.target,
+                        names.join(", "),
+                        literals.join(", ")
+                    )
+                    .as_bytes(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Renders the rows of the first result set of an export and sends them on in chunks.
+pub struct ExportRows<'a> {
+    output: &'a DumpSender,
+    encoder: ExportEncoder,
+    buffer: Vec<u8>,
+    started: bool,
+}
+
+impl ExportRows<'_> {
+    /// Whether a row has been pushed yet, later result sets of a query are not exported.
+    pub fn started(&self) -> bool {
+        self.started
+    }
+
+    pub async fn push(
+        &mut self,
+        columns: impl FnOnce() -> Vec<QueryColumn>,
+        values: Vec<QueryValue>,
+    ) -> Result<(), anyhow::Error> {
+        if !self.started {
+            self.started = true;
+            self.encoder.columns(columns(), &mut self.buffer);
+        }
+        self.encoder.row(&values, &mut self.buffer)?;
+
+        if self.buffer.len() >= EXPORT_CHUNK_SIZE {
+            send(self.output, std::mem::take(&mut self.buffer)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<(), anyhow::Error> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        send(self.output, std::mem::take(&mut self.buffer)).await
+    }
+}
+
+impl ServerDatabase {
+    /// Streams a table or a query result into `output`. Like [`ServerDatabase::dump`], a failed
+    /// export sends its error as the last item.
+    pub async fn export(
+        &mut self,
+        database: &crate::database::Database,
+        source: &ExportSource,
+        format: ExportFormat,
+        output: DumpSender,
+    ) {
+        if let Err(err) = self.export_rows(database, source, format, &output).await {
+            output.send(Err(err)).await.ok();
+        }
+    }
+
+    async fn export_rows(
+        &mut self,
+        database: &crate::database::Database,
+        source: &ExportSource,
+        format: ExportFormat,
+        output: &DumpSender,
+    ) -> Result<(), anyhow::Error> {
+        let encoder = ExportEncoder::new(
+            format,
+            SqlDialect::from_database_type(self.database_host.r#type),
+            source,
+        )?;
+
+        let mut connection = self.connect_as_tenant(database, true).await?;
+        connection.inner.close_on_drop();
+
+        let sql = match source {
+            ExportSource::Table { schema, table } => {
+                if connection
+                    .inner
+                    .table_columns(schema.as_deref(), table)
+                    .await?
+                    .is_empty()
+                {
+                    return Err(unknown_table(table));
+                }
+
+                // a whole table takes as long as it takes, queries keep the statement timeout
+                connection.inner.disable_statement_timeout().await?;
+
+                format!(
+                    "SELECT * FROM {}",
+                    connection.inner.qualified_table(schema.as_deref(), table)
+                )
+            }
+            ExportSource::Query { sql, .. } => sql.clone(),
+        };
+
+        let mut rows = ExportRows {
+            output,
+            encoder,
+            buffer: Vec::new(),
+            started: false,
+        };
+        connection.inner.export_rows(sql, &mut rows).await?;
+
+        rows.flush().await
+    }
+}
+
+#[derive(ToSchema, Validate, Deserialize)]
+pub struct ImportOptions {
+    #[garde(inner(length(chars, min = 1, max = 255)))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub schema: Option<CompactString>,
+
+    #[garde(length(chars, min = 1, max = 255))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub table: CompactString,
+
+    #[garde(skip)]
+    pub format: ImportFormat,
+
+    /// Comma separated `field:column` pairs. Fields left out are skipped, without a mapping
+    /// every field goes into the column of the same name.
+    #[garde(inner(length(chars, max = 16384)))]
+    #[schema(max_length = 16384)]
+    pub mapping: Option<String>,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct ImportRowError {
+    /// The 1-based position of the record in the file, not counting a CSV header.
+    pub row: u64,
+    pub message: String,
+}
+
+#[derive(ToSchema, Serialize, Default)]
+pub struct ImportSummary {
+    pub imported: u64,
+    pub failed: u64,
+    /// Whether the import stopped early after too many rows failed.
+    pub aborted: bool,
+    pub errors: Vec<ImportRowError>,
+}
+
+/// Where imported rows end up, one batch of at most [`MUTATE_MAX_ROWS`] rows at a time.
+#[async_trait::async_trait]
+pub trait ImportTarget: Send {
+    async fn insert(&mut self, rows: &[RowInsert]) -> Result<u64, anyhow::Error>;
+}
+
+/// How values read from a file are brought into the shape of a column type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Integer,
+    Float,
+    Boolean,
+    Binary,
+    Text,
+}
+
+impl ValueKind {
+    pub fn classify(column: &SchemaColumn, rendered_type: &str) -> Self {
+        if column.binary {
+            return Self::Binary;
+        }
+
+        let base = rendered_type
+            .split('(')
+            .next()
+            .unwrap_or(rendered_type)
+            .trim()
+            .to_ascii_lowercase();
+
+        match base.as_str() {
+            "tinyint" | "smallint" | "mediumint" | "int" | "integer" | "bigint" | "int2"
+            | "int4" | "int8" | "serial" | "bigserial" | "smallserial" | "year" => Self::Integer,
+            "decimal" | "numeric" | "real" | "float" | "double" | "double precision" | "float4"
+            | "float8" => Self::Float,
+            "bool" | "boolean" => Self::Boolean,
+            _ => Self::Text,
+        }
+    }
+
+    fn coerce(self, value: String) -> Result<Option<String>, String> {
+        if self != Self::Text && value.trim().is_empty() {
+            return Ok(None);
+        }
+
+        match self {
+            Self::Integer => {
+                let trimmed = value.trim();
+                if trimmed.parse::<i128>().is_ok() {
+                    Ok(Some(trimmed.to_string()))
+                } else if trimmed.eq_ignore_ascii_case("true") {
+                    Ok(Some("1".to_string()))
+                } else if trimmed.eq_ignore_ascii_case("false") {
+                    Ok(Some("0".to_string()))
+                } else {
+                    Err(format!("{value} is not an integer"))
+                }
+            }
+            Self::Float => {
+                let trimmed = value.trim();
+                if trimmed.parse::<f64>().is_ok() {
+                    Ok(Some(trimmed.to_string()))
+                } else {
+                    Err(format!("{value} is not a number"))
+                }
+            }
+            Self::Boolean => match value.trim().to_ascii_lowercase().as_str() {
+                "true" | "t" | "yes" | "y" | "on" | "1" => Ok(Some("true".to_string())),
+                "false" | "f" | "no" | "n" | "off" | "0" => Ok(Some("false".to_string())),
+                _ => Err(format!("{value} is not a boolean")),
+            },
+            Self::Binary => {
+                let trimmed = value.trim();
+                let hex = trimmed
+                    .strip_prefix("0x")
+                    .or_else(|| trimmed.strip_prefix("\\x"))
+                    .unwrap_or(trimmed);
+                if hex.len().is_multiple_of(2) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    Ok(Some(hex.to_ascii_lowercase()))
+                } else {
+                    Err(format!("{value} is not valid hexadecimal"))
+                }
+            }
+            Self::Text => Ok(Some(value)),
+        }
+    }
+}
+
+fn parse_mapping(
+    mapping: Option<&str>,
+    columns: &[SchemaColumn],
+) -> Result<Option<Vec<(String, CompactString)>>, anyhow::Error> {
+    let Some(mapping) = mapping.filter(|mapping| !mapping.trim().is_empty()) else {
+        return Ok(None);
+    };
+
+    let mut pairs: Vec<(String, CompactString)> = Vec::new();
+    for pair in mapping.split(',') {
+        let Some((field, column)) = pair.split_once(':') else {
+            return Err(display(format!(
+                "mapping entry {pair} is not written as field:column"
+            )));
+        };
+        let (field, column) = (field.trim(), column.trim());
+
+        if !columns.iter().any(|entry| entry.name == column) {
+            return Err(unknown_column(column));
+        }
+        if pairs.iter().any(|(_, mapped)| mapped == column) {
+            return Err(display(format!("column {column} is mapped twice")));
+        }
+
+        pairs.push((field.to_string(), column.into()));
+    }
+
+    Ok(Some(pairs))
+}
+
+/// Reads the records of an import, one `(field, value)` list per row.
+struct RecordReader<R> {
+    reader: R,
+    format: ImportFormat,
+    header: Vec<String>,
+    buffer: Vec<u8>,
+}
+
+type Record = Vec<(String, Option<String>)>;
+
+impl<R: AsyncBufRead + Unpin + Send> RecordReader<R> {
+    async fn new(reader: R, format: ImportFormat) -> Result<Self, anyhow::Error> {
+        let mut reader = Self {
+            reader,
+            format,
+            header: Vec::new(),
+            buffer: Vec::new(),
+        };
+
+        if format == ImportFormat::Csv {
+            let Some(header) = reader.read_csv_record().await? else {
+                return Err(display("the file is empty"));
+            };
+            reader.header = header;
+        }
+
+        Ok(reader)
+    }
+
+    /// The next chunk of the file up to a newline that is not inside a quoted CSV field.
+    async fn read_line(&mut self) -> Result<bool, anyhow::Error> {
+        self.buffer.clear();
+
+        loop {
+            let read = (&mut self.reader)
+                .take((IMPORT_MAX_RECORD_BYTES + 1 - self.buffer.len()) as u64)
+                .read_until(b'\n', &mut self.buffer)
+                .await?;
+            if self.buffer.len() > IMPORT_MAX_RECORD_BYTES {
+                return Err(display(format!(
+                    "a record is larger than {IMPORT_MAX_RECORD_BYTES} bytes"
+                )));
+            }
+
+            let quotes = self.buffer.iter().filter(|byte| **byte == b'"').count();
+            if read == 0 || self.format != ImportFormat::Csv || quotes % 2 == 0 {
+                return Ok(!self.buffer.is_empty());
+            }
+        }
+    }
+
+    async fn read_csv_record(&mut self) -> Result<Option<Vec<String>>, anyhow::Error> {
+        loop {
+            if !self.read_line().await? {
+                return Ok(None);
+            }
+
+            let line = std::str::from_utf8(&self.buffer)
+                .map_err(|_| display("the file is not valid UTF-8"))?
+                .trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = Vec::new();
+            let mut field = String::new();
+            let mut quoted = false;
+            let mut chars = line.chars().peekable();
+            while let Some(c) = chars.next() {
+                match (c, quoted) {
+                    ('"', true) if chars.peek() == Some(&'"') => {
+                        field.push('"');
+                        chars.next();
+                    }
+                    ('"', _) => quoted = !quoted,
+                    (',', false) => fields.push(std::mem::take(&mut field)),
+                    (c, _) => field.push(c),
+                }
+            }
+            fields.push(field);
+
+            return Ok(Some(fields));
+        }
+    }
+
+    async fn next(&mut self) -> Result<Option<Result<Record, String>>, anyhow::Error> {
+        match self.format {
+            ImportFormat::Csv => {
+                let Some(fields) = self.read_csv_record().await? else {
+                    return Ok(None);
+                };
+
+                if fields.len() != self.header.len() {
+                    return Ok(Some(Err(format!(
+                        "expected {} fields, found {}",
+                        self.header.len(),
+                        fields.len()
+                    ))));
+                }
+
+                Ok(Some(Ok(self
+                    .header
+                    .iter()
+                    .cloned()
+                    .zip(fields.into_iter().map(Some))
+                    .collect())))
+            }
+            ImportFormat::Jsonl => loop {
+                if !self.read_line().await? {
+                    return Ok(None);
+                }
+                if self.buffer.trim_ascii().is_empty() {
+                    continue;
+                }
+
+                let object = match serde_json::from_slice::<
+                    serde_json::Map<String, serde_json::Value>,
+                >(&self.buffer)
+                {
+                    Ok(object) => object,
+                    Err(err) => return Ok(Some(Err(format!("not a JSON object: {err}")))),
+                };
+
+                return Ok(Some(Ok(object
+                    .into_iter()
+                    .map(|(field, value)| {
+                        let value = match value {
+                            serde_json::Value::Null => None,
+                            serde_json::Value::String(value) => Some(value),
+                            value => Some(value.to_string()),
+                        };
+
+                        (field, value)
+                    })
+                    .collect())));
+            },
+        }
+    }
+}
+
+struct Importer<'a> {
+    columns: &'a [SchemaColumn],
+    kinds: &'a [ValueKind],
+    mapping: Option<Vec<(String, CompactString)>>,
+    target: &'a mut dyn ImportTarget,
+    batch: Vec<(u64, RowInsert)>,
+    summary: ImportSummary,
+}
+
+impl Importer<'_> {
+    fn fail(&mut self, row: u64, message: String) {
+        self.summary.failed += 1;
+        if self.summary.errors.len() < IMPORT_MAX_ERRORS {
+            self.summary.errors.push(ImportRowError { row, message });
+        }
+        if self.summary.failed as usize > IMPORT_MAX_ERRORS {
+            self.summary.aborted = true;
+        }
+    }
+
+    fn row(&self, record: Record) -> Result<RowInsert, String> {
+        let mut values = Vec::with_capacity(record.len());
+
+        for (field, value) in record {
+            let column = match &self.mapping {
+                Some(mapping) => match mapping.iter().find(|(mapped, _)| *mapped == field) {
+                    Some((_, column)) => column.as_str(),
+                    None => continue,
+                },
+                None => field.as_str(),
+            };
+
+            let Some(index) = self.columns.iter().position(|entry| entry.name == column) else {
+                return Err(format!("field {field} does not match a column"));
+            };
+            let column = &self.columns[index];
+            if column.generated {
+                return Err(format!("column {} is generated", column.name));
+            }
+
+            let value = match value {
+                Some(value) => self.kinds[index]
+                    .coerce(value)
+                    .map_err(|err| format!("{}: {err}", column.name))?,
+                None => None,
+            };
+
+            values.push(RowValue {
+                column: column.name.clone(),
+                value,
+            });
+        }
+
+        Ok(RowInsert { values })
+    }
+
+    async fn flush(&mut self) {
+        if self.batch.is_empty() {
+            return;
+        }
+
+        let batch = std::mem::take(&mut self.batch);
+        let rows: Vec<RowInsert> = batch.iter().map(|(_, row)| row.clone()).collect();
+
+        match self.target.insert(&rows).await {
+            Ok(inserted) => self.summary.imported += inserted,
+            Err(_) => {
+                for (number, row) in batch {
+                    if self.summary.aborted {
+                        break;
+                    }
+
+                    match self.target.insert(std::slice::from_ref(&row)).await {
+                        Ok(inserted) => self.summary.imported += inserted,
+                        Err(err) => self.fail(number, err.to_string()),
+                    }
+                }
+            }
+        }
+    }
+
+    async fn run<R: AsyncBufRead + Unpin + Send>(
+        mut self,
+        reader: R,
+        format: ImportFormat,
+    ) -> Result<ImportSummary, anyhow::Error> {
+        let mut records = RecordReader::new(reader, format).await?;
+
+        if let (ImportFormat::Csv, None) = (format, &self.mapping)
+            && let Some(field) = records
+                .header
+                .iter()
+                .find(|field| !self.columns.iter().any(|column| column.name == **field))
+        {
+            return Err(display(format!(
+                "field {field} does not match a column, map it or leave it out"
+            )));
+        }
+
+        let mut number = 0;
+        while !self.summary.aborted
+            && let Some(record) = records.next().await?
+        {
+            number += 1;
+
+            match record.and_then(|record| self.row(record)) {
+                Ok(row) => self.batch.push((number, row)),
+                Err(message) => self.fail(number, message),
+            }
+
+            if self.batch.len() >= MUTATE_MAX_ROWS {
+                self.flush().await;
+            }
+        }
+
+        if !self.summary.aborted {
+            self.flush().await;
+        }
+
+        Ok(self.summary)
+    }
+}
+
+/// Imports `reader` into a table with the given columns. `kinds` holds the [`ValueKind`] of
+/// every column, in the same order.
+pub async fn import_rows<R: AsyncBufRead + Unpin + Send>(
+    reader: R,
+    options: &ImportOptions,
+    columns: &[SchemaColumn],
+    kinds: &[ValueKind],
+    target: &mut dyn ImportTarget,
+) -> Result<ImportSummary, anyhow::Error> {
+    let importer = Importer {
+        columns,
+        kinds,
+        mapping: parse_mapping(options.mapping.as_deref(), columns)?,
+        target,
+        batch: Vec::new(),
+        summary: ImportSummary::default(),
+    };
+
+    importer.run(reader, options.format).await
+}
+
+struct TableImportTarget<'a> {
+    server_database: &'a mut ServerDatabase,
+    database: &'a crate::database::Database,
+    schema: Option<&'a str>,
+    table: &'a str,
+}
+
+#[async_trait::async_trait]
+impl ImportTarget for TableImportTarget<'_> {
+    async fn insert(&mut self, rows: &[RowInsert]) -> Result<u64, anyhow::Error> {
+        self.server_database
+            .mutate_rows(
+                self.database,
+                self.schema,
+                self.table,
+                RowOperation::Insert(rows),
+            )
+            .await
+    }
+}
+
+impl ServerDatabase {
+    pub async fn import(
+        &mut self,
+        database: &crate::database::Database,
+        options: &ImportOptions,
+        reader: impl AsyncBufRead + Unpin + Send,
+    ) -> Result<ImportSummary, anyhow::Error> {
+        let (columns, kinds) = {
+            let mut connection = self.connect_as_tenant(database, true).await?;
+
+            let columns = connection
+                .inner
+                .table_columns(options.schema.as_deref(), &options.table)
+                .await?;
+            if columns.is_empty() {
+                return Err(unknown_table(&options.table));
+            }
+
+            let mut kinds = Vec::with_capacity(columns.len());
+            for column in &columns {
+                let rendered = connection
+                    .inner
+                    .resolve_type(&column.type_name)
+                    .await
+                    .unwrap_or_else(|_| column.type_name.to_string());
+
+                kinds.push(ValueKind::classify(column, &rendered));
+            }
+
+            (columns, kinds)
+        };
+
+        let mut target = TableImportTarget {
+            server_database: self,
+            database,
+            schema: options.schema.as_deref(),
+            table: &options.table,
+        };
+
+        import_rows(reader, options, &columns, &kinds, &mut target).await
+    }
+}
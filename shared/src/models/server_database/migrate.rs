@@ -0,0 +1,318 @@
+use super::ServerDatabase;
+use crate::models::{
+    database_host::{DatabaseHost, DatabasePool},
+    server_database_migration::ServerDatabaseMigration,
+};
+use futures_util::TryStreamExt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+impl ServerDatabase {
+    /// Whether a database or user with the name of this one exists on the current database host.
+    async fn exists_on_host(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<bool, anyhow::Error> {
+        match self.database_host.get_connection(database).await? {
+            DatabasePool::Mysql(pool) => {
+                let databases: i64 = sqlx::query_scalar(
+                    "SELECT COUNT(*) FROM information_schema.SCHEMATA WHERE SCHEMA_NAME = ?",
+                )
+                .bind(&self.name)
+                .fetch_one(&pool)
+                .await?;
+                let users: i64 =
+                    sqlx::query_scalar("SELECT COUNT(*) FROM mysql.user WHERE User = ?")
+                        .bind(&self.username)
+                        .fetch_one(&pool)
+                        .await?;
+
+                Ok(databases > 0 || users > 0)
+            }
+            DatabasePool::Postgres(pool) => Ok(sqlx::query_scalar(
+                r#"
+                SELECT EXISTS (SELECT 1 FROM pg_database WHERE datname = $1)
+                    OR EXISTS (SELECT 1 FROM pg_roles WHERE rolname = $2)
+                "#,
+            )
+            .bind(&self.name)
+            .bind(&self.username)
+            .fetch_one(&pool)
+            .await?),
+            DatabasePool::Mongodb(client) => {
+                if client
+                    .list_database_names()
+                    .await?
+                    .iter()
+                    .any(|name| name == self.name.as_str())
+                {
+                    return Ok(true);
+                }
+
+                let users = client
+                    .database(&self.name)
+                    .run_command(mongodb::bson::doc! { "usersInfo": self.username.as_str() })
+                    .await?;
+
+                Ok(users
+                    .get_array("users")
+                    .is_ok_and(|users| !users.is_empty()))
+            }
+        }
+    }
+
+    /// Creates this database and its user on the current database host, reusing the stored
+    /// credentials. Anything with the same name already on the host is only dropped when it is
+    /// the leftover of an earlier failed migration of this database.
+    async fn provision(
+        &mut self,
+        database: &crate::database::Database,
+        migration_uuid: uuid::Uuid,
+    ) -> Result<(), anyhow::Error> {
+        if self.exists_on_host(database).await? {
+            if !ServerDatabaseMigration::has_failed_provisioned(
+                database,
+                self.uuid,
+                self.database_host.uuid,
+            )
+            .await?
+            {
+                return Err(crate::response::DisplayError::new(format!(
+                    "a database or user named {} already exists on the target database host",
+                    self.name
+                ))
+                .with_status(reqwest::StatusCode::CONFLICT)
+                .into());
+            }
+
+            self.drop_on_host(database).await?;
+        }
+
+        ServerDatabaseMigration::mark_provisioned(database, migration_uuid).await?;
+
+        let password = database.decrypt(self.password.clone()).await?;
+
+        match self.database_host.get_connection(database).await? {
+            DatabasePool::Mysql(pool) => {
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "CREATE USER IF NOT EXISTS '{}'@'%' IDENTIFIED BY '{}'",
+                    self.username, password
+                )))
+                .execute(&pool)
+                .await?;
+                sqlx::query(sqlx::AssertSqlSafe(format!("CREATE DATABASE IF NOT EXISTS `{}` DEFAULT CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci;", self.name)))
+                    .execute(&pool)
+                    .await?;
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "GRANT ALL PRIVILEGES ON `{}`.* TO '{}'@'%' WITH GRANT OPTION",
+                    self.name, self.username
+                )))
+                .execute(&pool)
+                .await?;
+            }
+            DatabasePool::Postgres(pool) => {
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "CREATE USER \"{}\" WITH PASSWORD '{}'",
+                    self.username, password
+                )))
+                .execute(&pool)
+                .await?;
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "CREATE DATABASE \"{}\" WITH OWNER \"{}\" ENCODING 'UTF8'",
+                    self.name, self.username
+                )))
+                .execute(&pool)
+                .await?;
+            }
+            DatabasePool::Mongodb(client) => {
+                let cmd = mongodb::bson::doc! {
+                    "createUser": self.username.to_string(),
+                    "pwd": password.into_string(),
+                    "roles": [
+                        { "role": "readWrite", "db": self.name.to_string() }
+                    ]
+                };
+                client.database(&self.name).run_command(cmd).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drops this database and its user from the current database host, without touching the
+    /// database record.
+    async fn drop_on_host(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<(), anyhow::Error> {
+        if self.name.contains(|c| ['"', '\'', '`'].contains(&c))
+            || self.username.contains(|c| ['"', '\'', '`'].contains(&c))
+        {
+            return Err(anyhow::anyhow!(
+                "unable to drop database with escape characters"
+            ));
+        }
+
+        match self.database_host.get_connection(database).await? {
+            DatabasePool::Mysql(pool) => {
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "DROP DATABASE IF EXISTS `{}`",
+                    self.name
+                )))
+                .execute(&pool)
+                .await?;
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "DROP USER IF EXISTS '{}'@'%'",
+                    self.username
+                )))
+                .execute(&pool)
+                .await?;
+            }
+            DatabasePool::Postgres(pool) => {
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "DROP DATABASE IF EXISTS \"{}\"",
+                    self.name
+                )))
+                .execute(&pool)
+                .await?;
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "DROP USER IF EXISTS \"{}\"",
+                    self.username
+                )))
+                .execute(&pool)
+                .await?;
+            }
+            DatabasePool::Mongodb(client) => {
+                let db = client.database(&self.name);
+
+                let _ = db
+                    .run_command(mongodb::bson::doc! { "dropUser": self.username.as_str() })
+                    .await;
+                db.drop().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drops the copy of this database from `target` and gives the source its write access
+    /// back after a migration failed before the record was switched over.
+    pub(crate) async fn clean_up_failed_migration(
+        &mut self,
+        database: &crate::database::Database,
+        target: &DatabaseHost,
+    ) {
+        let mut target_database = self.clone();
+        target_database.database_host = target.clone();
+
+        if let Err(err) = target_database.drop_on_host(database).await {
+            tracing::warn!(
+                database = %self.uuid,
+                database_host = %target.uuid,
+                "failed to clean up database after failed migration: {:?}",
+                err
+            );
+        }
+        if !self.quota_exceeded
+            && let Err(err) = self.restore_write_access(database).await
+        {
+            tracing::warn!(
+                database = %self.uuid,
+                "failed to restore write access after failed migration: {:?}",
+                err
+            );
+        }
+    }
+
+    /// Moves this database to `target`, a host of the same type. The source is made read-only
+    /// while its dump is restored on the target, then the record is switched over and the source
+    /// database is dropped. Dumped bytes are added to `transferred` as they pass through.
+    pub async fn migrate_to(
+        &mut self,
+        database: &crate::database::Database,
+        target: &DatabaseHost,
+        migration_uuid: uuid::Uuid,
+        transferred: &AtomicU64,
+    ) -> Result<(), anyhow::Error> {
+        if target.r#type != self.database_host.r#type {
+            return Err(crate::response::DisplayError::new(
+                "target database host has a different database type",
+            )
+            .into());
+        }
+        if target.uuid == self.database_host.uuid {
+            return Err(crate::response::DisplayError::new(
+                "database is already on the target database host",
+            )
+            .into());
+        }
+
+        let mut target_database = self.clone();
+        target_database.database_host = target.clone();
+        target_database.provision(database, migration_uuid).await?;
+
+        if !self.quota_exceeded {
+            self.revoke_write_access(database).await?;
+        }
+
+        let (sender, receiver) = tokio::sync::mpsc::channel(4);
+        let dump = futures_util::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|chunk| (chunk, receiver))
+        })
+        .inspect_ok(|chunk: &Vec<u8>| {
+            transferred.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        });
+
+        let ((), restored) = tokio::join!(
+            self.dump(database, sender),
+            target_database.restore_dump(database, dump)
+        );
+
+        let switched = match restored {
+            Ok(()) if self.quota_exceeded => target_database.revoke_write_access(database).await,
+            restored => restored,
+        };
+        let switched = match switched {
+            Ok(()) => sqlx::query(
+                r#"
+                UPDATE server_databases
+                SET database_host_uuid = $1
+                WHERE server_databases.uuid = $2 AND server_databases.database_host_uuid = $3
+                "#,
+            )
+            .bind(target.uuid)
+            .bind(self.uuid)
+            .bind(self.database_host.uuid)
+            .execute(database.write())
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| {
+                if result.rows_affected() == 0 {
+                    Err(anyhow::anyhow!(
+                        "database was moved or deleted during the migration"
+                    ))
+                } else {
+                    Ok(())
+                }
+            }),
+            Err(err) => Err(err),
+        };
+
+        if let Err(err) = switched {
+            self.clean_up_failed_migration(database, target).await;
+
+            return Err(err);
+        }
+
+        let mut source_database = std::mem::replace(self, target_database);
+        if let Err(err) = source_database.drop_on_host(database).await {
+            tracing::warn!(
+                database = %self.uuid,
+                database_host = %source_database.database_host.uuid,
+                "failed to drop migrated database from its previous host: {:?}",
+                err
+            );
+        }
+
+        Ok(())
+    }
+}
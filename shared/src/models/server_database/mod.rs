@@ -15,14 +15,22 @@ use std::{
 };
 use utoipa::ToSchema;
 
+mod access;
 mod explorer;
+mod migrate;
+mod quota;
 pub use explorer::{
     BROWSE_DEFAULT_ROWS, BROWSE_MAX_FILTERS, BROWSE_MAX_ROWS, BrowseFilter, BrowseOptions,
-    CREATE_TABLE_MAX_COLUMNS, ColumnDefinition, FilterOperator, MUTATE_MAX_ROWS,
-    QUERY_ACTIVITY_LENGTH, QUERY_DEFAULT_ROWS, QUERY_MAX_LENGTH, QUERY_MAX_ROWS, QueryColumn,
-    QueryResultSet, QueryValue, RowDelete, RowInsert, RowOperation, RowUpdate, RowValue,
-    SchemaColumn, SchemaTable, TenantConnection,
+    CREATE_TABLE_MAX_COLUMNS, ColumnDefinition, ConstraintDefinition, ConstraintKind, DumpSender,
+    ExportEncoder, ExportFormat, ExportOptions, ExportSource, FilterOperator, IMPORT_MAX_ERRORS,
+    ImportFormat, ImportOptions, ImportRowError, ImportSummary, ImportTarget, IndexDefinition,
+    KEY_MAX_COLUMNS, MUTATE_MAX_ROWS, QUERY_ACTIVITY_LENGTH, QUERY_DEFAULT_ROWS, QUERY_MAX_LENGTH,
+    QUERY_MAX_ROWS, QueryColumn, QueryResultSet, QueryValue, ReferentialAction, RowDelete,
+    RowInsert, RowOperation, RowUpdate, RowValue, SchemaColumn, SchemaConstraint, SchemaForeignKey,
+    SchemaIndex, SchemaTable, SqlDialect, TenantConnection, ValueKind, import_rows,
 };
+pub(crate) use quota::quota_warning;
+pub use quota::{QUOTA_WARNING_THRESHOLDS, QuotaEnforcement};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ServerDatabase {
@@ -36,6 +44,12 @@ pub struct ServerDatabase {
     pub username: compact_str::CompactString,
     pub password: Vec<u8>,
 
+    pub size_limit: Option<i64>,
+    pub size: Option<i64>,
+    pub size_checked: Option<chrono::NaiveDateTime>,
+    pub quota_exceeded: bool,
+    pub quota_warning: Option<i16>,
+
     pub created: chrono::NaiveDateTime,
 
     extension_data: super::ModelExtensionData,
@@ -84,6 +98,26 @@ impl BaseModel for ServerDatabase {
                 "server_databases.password",
                 compact_str::format_compact!("{prefix}password"),
             ),
+            (
+                "server_databases.size_limit",
+                compact_str::format_compact!("{prefix}size_limit"),
+            ),
+            (
+                "server_databases.size",
+                compact_str::format_compact!("{prefix}size"),
+            ),
+            (
+                "server_databases.size_checked",
+                compact_str::format_compact!("{prefix}size_checked"),
+            ),
+            (
+                "server_databases.quota_exceeded",
+                compact_str::format_compact!("{prefix}quota_exceeded"),
+            ),
+            (
+                "server_databases.quota_warning",
+                compact_str::format_compact!("{prefix}quota_warning"),
+            ),
             (
                 "server_databases.created",
                 compact_str::format_compact!("{prefix}created"),
@@ -111,6 +145,14 @@ impl BaseModel for ServerDatabase {
             locked: row.try_get(compact_str::format_compact!("{prefix}locked").as_str())?,
             username: row.try_get(compact_str::format_compact!("{prefix}username").as_str())?,
             password: row.try_get(compact_str::format_compact!("{prefix}password").as_str())?,
+            size_limit: row.try_get(compact_str::format_compact!("{prefix}size_limit").as_str())?,
+            size: row.try_get(compact_str::format_compact!("{prefix}size").as_str())?,
+            size_checked: row
+                .try_get(compact_str::format_compact!("{prefix}size_checked").as_str())?,
+            quota_exceeded: row
+                .try_get(compact_str::format_compact!("{prefix}quota_exceeded").as_str())?,
+            quota_warning: row
+                .try_get(compact_str::format_compact!("{prefix}quota_warning").as_str())?,
             created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
             extension_data: Self::map_extensions(prefix, row)?,
         })
@@ -545,6 +587,10 @@ impl ServerDatabase {
                 is_locked: self.locked,
                 username: self.username,
                 password: state.database.decrypt(self.password).await?,
+                size_limit: self.size_limit,
+                size: self.size,
+                size_checked: self.size_checked.map(|size_checked| size_checked.and_utc()),
+                is_quota_exceeded: self.quota_exceeded,
                 created: self.created.and_utc(),
             },
             api_object,
@@ -592,6 +638,10 @@ impl IntoAdminApiObject for ServerDatabase {
                 is_locked: self.locked,
                 username: self.username,
                 password: state.database.decrypt(self.password).await?,
+                size_limit: self.size_limit,
+                size: self.size,
+                size_checked: self.size_checked.map(|size_checked| size_checked.and_utc()),
+                is_quota_exceeded: self.quota_exceeded,
                 created: self.created.and_utc(),
             },
             api_object,
@@ -637,6 +687,10 @@ impl IntoApiObject for ServerDatabase {
                 } else {
                     None
                 },
+                size_limit: self.size_limit,
+                size: self.size,
+                size_checked: self.size_checked.map(|size_checked| size_checked.and_utc()),
+                is_quota_exceeded: self.quota_exceeded,
                 created: self.created.and_utc(),
             },
             api_object,
@@ -956,6 +1010,12 @@ impl DeletableModel for ServerDatabase {
     ) -> Result<(), anyhow::Error> {
         let database_name = self.name.clone();
         let database_username = self.username.clone();
+        let access_token_usernames =
+            super::server_database_access_token::ServerDatabaseAccessToken::all_usernames_by_server_database_uuid(
+                &state.database,
+                self.uuid,
+            )
+            .await?;
         let mut database_host = self.database_host.clone();
         let self_clone = self.clone();
         let state_clone = state.clone();
@@ -984,6 +1044,15 @@ impl DeletableModel for ServerDatabase {
                         )))
                         .execute(&pool)
                         .await;
+                        for username in &access_token_usernames {
+                            sqlx::query(sqlx::AssertSqlSafe(format!(
+                                "DROP USER IF EXISTS '{}'@'%'",
+                                username
+                            )))
+                            .execute(&pool)
+                            .await
+                            .ok();
+                        }
 
                         database?;
                         user?;
@@ -1001,6 +1070,16 @@ impl DeletableModel for ServerDatabase {
                         )))
                         .execute(&pool)
                         .await;
+                        // the privileges of access token users went away with the database
+                        for username in &access_token_usernames {
+                            sqlx::query(sqlx::AssertSqlSafe(format!(
+                                "DROP USER IF EXISTS \"{}\"",
+                                username
+                            )))
+                            .execute(&pool)
+                            .await
+                            .ok();
+                        }
 
                         database?;
                         user?;
@@ -1013,6 +1092,11 @@ impl DeletableModel for ServerDatabase {
                                 mongodb::bson::doc! { "dropUser": database_username.as_str() },
                             )
                             .await;
+                        for username in &access_token_usernames {
+                            db.run_command(mongodb::bson::doc! { "dropUser": username.as_str() })
+                                .await
+                                .ok();
+                        }
                         let database = db.drop().await;
 
                         user?;
@@ -1071,6 +1155,11 @@ pub struct AdminApiServerServerDatabase {
     pub username: compact_str::CompactString,
     pub password: compact_str::CompactString,
 
+    pub size_limit: Option<i64>,
+    pub size: Option<i64>,
+    pub size_checked: Option<chrono::DateTime<chrono::Utc>>,
+    pub is_quota_exceeded: bool,
+
     pub created: chrono::DateTime<chrono::Utc>,
 }
 
@@ -1093,6 +1182,11 @@ pub struct AdminApiServerDatabase {
     pub username: compact_str::CompactString,
     pub password: compact_str::CompactString,
 
+    pub size_limit: Option<i64>,
+    pub size: Option<i64>,
+    pub size_checked: Option<chrono::DateTime<chrono::Utc>>,
+    pub is_quota_exceeded: bool,
+
     pub created: chrono::DateTime<chrono::Utc>,
 }
 
@@ -1114,5 +1208,10 @@ pub struct ApiServerDatabase {
     pub username: compact_str::CompactString,
     pub password: Option<compact_str::CompactString>,
 
+    pub size_limit: Option<i64>,
+    pub size: Option<i64>,
+    pub size_checked: Option<chrono::DateTime<chrono::Utc>>,
+    pub is_quota_exceeded: bool,
+
     pub created: chrono::DateTime<chrono::Utc>,
 }
@@ -0,0 +1,364 @@
+use super::ServerDatabase;
+use crate::{models::database_host::DatabasePool, prelude::*};
+use sqlx::Row;
+
+/// Usage percentages at which the owner of a server is warned before its
+/// database quota is enforced, in ascending order.
+pub const QUOTA_WARNING_THRESHOLDS: [i16; 2] = [80, 95];
+
+/// Returns the highest warning threshold reached by `size`, if any.
+pub(crate) fn quota_warning(size: i64, size_limit: Option<i64>) -> Option<i16> {
+    size_limit.and_then(|size_limit| {
+        let percent = size.saturating_mul(100) / size_limit.max(1);
+
+        QUOTA_WARNING_THRESHOLDS
+            .into_iter()
+            .rev()
+            .find(|threshold| percent >= *threshold as i64)
+    })
+}
+
+pub enum QuotaEnforcement {
+    Unchanged,
+    /// Usage crossed a warning threshold that was not notified yet.
+    Warning(i16),
+    /// Usage reached the limit, write access has been revoked.
+    Exceeded,
+    /// Usage dropped below the limit again, write access has been restored.
+    Restored,
+}
+
+/// Builds a block that transfers every object in the user schemas of the
+/// connected database from `source_role` to `target_role`, running
+/// `schema_statements` for every user schema afterwards.
+///
+/// `REASSIGN OWNED` is not used as it would also touch objects of the admin
+/// role outside of this database.
+fn postgres_reassign_objects(
+    source_role: &str,
+    target_role: &str,
+    schema_statements: &str,
+) -> String {
+    format!(
+        r#"
+        DO $reassign$
+        DECLARE
+            source_role text := '{source_role}';
+            target_role text := '{target_role}';
+            source_oid oid := (SELECT oid FROM pg_roles WHERE rolname = '{source_role}');
+            object record;
+        BEGIN
+            FOR object IN
+                SELECT nspname FROM pg_namespace
+                WHERE nspowner = source_oid AND nspname NOT LIKE 'pg\_%' AND nspname <> 'information_schema'
+            LOOP
+                EXECUTE format('ALTER SCHEMA %I OWNER TO %I', object.nspname, target_role);
+            END LOOP;
+
+            FOR object IN
+                SELECT pg_namespace.nspname, pg_class.relname, pg_class.relkind FROM pg_class
+                JOIN pg_namespace ON pg_namespace.oid = pg_class.relnamespace
+                WHERE pg_class.relowner = source_oid
+                    AND pg_class.relkind IN ('r', 'p', 'v', 'm', 'f', 'S')
+                    AND pg_namespace.nspname NOT LIKE 'pg\_%' AND pg_namespace.nspname <> 'information_schema'
+                    AND NOT EXISTS (
+                        SELECT 1 FROM pg_depend
+                        WHERE pg_depend.classid = 'pg_class'::regclass AND pg_depend.objid = pg_class.oid
+                            AND pg_depend.deptype IN ('a', 'i')
+                    )
+            LOOP
+                EXECUTE format(
+                    'ALTER %s %I.%I OWNER TO %I',
+                    CASE object.relkind
+                        WHEN 'v' THEN 'VIEW'
+                        WHEN 'm' THEN 'MATERIALIZED VIEW'
+                        WHEN 'f' THEN 'FOREIGN TABLE'
+                        WHEN 'S' THEN 'SEQUENCE'
+                        ELSE 'TABLE'
+                    END,
+                    object.nspname, object.relname, target_role
+                );
+            END LOOP;
+
+            FOR object IN
+                SELECT pg_proc.oid::regprocedure AS signature, pg_proc.prokind FROM pg_proc
+                JOIN pg_namespace ON pg_namespace.oid = pg_proc.pronamespace
+                WHERE pg_proc.proowner = source_oid
+                    AND pg_namespace.nspname NOT LIKE 'pg\_%' AND pg_namespace.nspname <> 'information_schema'
+            LOOP
+                EXECUTE format(
+                    'ALTER %s %s OWNER TO %I',
+                    CASE object.prokind WHEN 'p' THEN 'PROCEDURE' WHEN 'a' THEN 'AGGREGATE' ELSE 'FUNCTION' END,
+                    object.signature, target_role
+                );
+            END LOOP;
+
+            FOR object IN
+                SELECT pg_type.oid::regtype AS name FROM pg_type
+                JOIN pg_namespace ON pg_namespace.oid = pg_type.typnamespace
+                WHERE pg_type.typowner = source_oid
+                    AND pg_namespace.nspname NOT LIKE 'pg\_%' AND pg_namespace.nspname <> 'information_schema'
+                    AND NOT EXISTS (
+                        SELECT 1 FROM pg_depend
+                        WHERE pg_depend.classid = 'pg_type'::regclass AND pg_depend.objid = pg_type.oid
+                            AND pg_depend.deptype IN ('a', 'i')
+                    )
+            LOOP
+                EXECUTE format('ALTER TYPE %s OWNER TO %I', object.name, target_role);
+            END LOOP;
+
+            FOR object IN
+                SELECT nspname FROM pg_namespace
+                WHERE nspname NOT LIKE 'pg\_%' AND nspname <> 'information_schema'
+            LOOP
+                {schema_statements}
+            END LOOP;
+        END
+        $reassign$
+        "#,
+        source_role = source_role.replace('\'', "''"),
+        target_role = target_role.replace('\'', "''"),
+    )
+}
+
+impl ServerDatabase {
+    /// Returns all databases that have an effective size limit or are still
+    /// marked as over quota, paired with their effective limit in bytes.
+    pub async fn all_with_size_limit(
+        database: &crate::database::Database,
+    ) -> Result<Vec<(Self, Option<i64>)>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COALESCE(server_databases.size_limit, servers.database_size_limit) AS effective_size_limit
+            FROM server_databases
+            JOIN database_hosts ON database_hosts.uuid = server_databases.database_host_uuid
+            JOIN servers ON servers.uuid = server_databases.server_uuid
+            WHERE server_databases.size_limit IS NOT NULL
+                OR servers.database_size_limit IS NOT NULL
+                OR server_databases.quota_exceeded
+            "#,
+            Self::columns_sql(None)
+        )))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| -> Result<_, crate::database::DatabaseError> {
+                Ok((Self::map(None, &row)?, row.try_get("effective_size_limit")?))
+            })
+            .try_collect_vec()
+    }
+
+    pub async fn update_size_limit(
+        &mut self,
+        database: &crate::database::Database,
+        size_limit: Option<i64>,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            UPDATE server_databases
+            SET size_limit = $1
+            WHERE server_databases.uuid = $2
+            "#,
+        )
+        .bind(size_limit)
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?;
+
+        self.size_limit = size_limit;
+
+        Ok(())
+    }
+
+    pub(super) async fn revoke_write_access(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<(), anyhow::Error> {
+        match self.database_host.get_connection(database).await? {
+            DatabasePool::Mysql(pool) => {
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "REVOKE ALL PRIVILEGES, GRANT OPTION FROM '{}'@'%'",
+                    self.username
+                )))
+                .execute(&pool)
+                .await?;
+                // deleting and dropping stays possible so the owner can free up space
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "GRANT SELECT, DELETE, DROP, LOCK TABLES, SHOW VIEW ON `{}`.* TO '{}'@'%'",
+                    self.name, self.username
+                )))
+                .execute(&pool)
+                .await?;
+            }
+            DatabasePool::Postgres(pool) => {
+                // role settings can be changed by the tenant, so the database and everything in it
+                // is taken over instead, leaving only what is needed to free up space
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "ALTER DATABASE \"{}\" OWNER TO CURRENT_USER",
+                    self.name
+                )))
+                .execute(&pool)
+                .await?;
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "REVOKE CREATE, TEMPORARY ON DATABASE \"{}\" FROM PUBLIC",
+                    self.name
+                )))
+                .execute(&pool)
+                .await?;
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "GRANT CONNECT ON DATABASE \"{}\" TO \"{}\"",
+                    self.name, self.username
+                )))
+                .execute(&pool)
+                .await?;
+                sqlx::query(
+                    "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE usename = $1",
+                )
+                .bind(&self.username)
+                .execute(&pool)
+                .await?;
+
+                let mut connection = self
+                    .database_host
+                    .connect_postgres_database(database, &self.name)
+                    .await?;
+                let admin: String = sqlx::query_scalar("SELECT current_user::text")
+                    .fetch_one(&mut connection)
+                    .await?;
+
+                sqlx::raw_sql(sqlx::AssertSqlSafe(postgres_reassign_objects(
+                    &self.username,
+                    &admin,
+                    r#"
+                    EXECUTE format('REVOKE CREATE ON SCHEMA %I FROM PUBLIC', object.nspname);
+                    EXECUTE format('REVOKE ALL ON SCHEMA %I FROM %I', object.nspname, source_role);
+                    EXECUTE format('GRANT USAGE ON SCHEMA %I TO %I', object.nspname, source_role);
+                    EXECUTE format('GRANT SELECT, DELETE, TRUNCATE ON ALL TABLES IN SCHEMA %I TO %I', object.nspname, source_role);
+                    EXECUTE format('GRANT SELECT ON ALL SEQUENCES IN SCHEMA %I TO %I', object.nspname, source_role);
+                    "#,
+                )))
+                .execute(&mut connection)
+                .await?;
+            }
+            DatabasePool::Mongodb(client) => {
+                let cmd = mongodb::bson::doc! {
+                    "updateUser": self.username.to_string(),
+                    "roles": [
+                        { "role": "read", "db": self.name.to_string() }
+                    ]
+                };
+                client.database(&self.name).run_command(cmd).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(super) async fn restore_write_access(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<(), anyhow::Error> {
+        match self.database_host.get_connection(database).await? {
+            DatabasePool::Mysql(pool) => {
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "GRANT ALL PRIVILEGES ON `{}`.* TO '{}'@'%' WITH GRANT OPTION",
+                    self.name, self.username
+                )))
+                .execute(&pool)
+                .await?;
+            }
+            DatabasePool::Postgres(pool) => {
+                sqlx::query(sqlx::AssertSqlSafe(format!(
+                    "ALTER DATABASE \"{}\" OWNER TO \"{}\"",
+                    self.name, self.username
+                )))
+                .execute(&pool)
+                .await?;
+
+                let mut connection = self
+                    .database_host
+                    .connect_postgres_database(database, &self.name)
+                    .await?;
+                let admin: String = sqlx::query_scalar("SELECT current_user::text")
+                    .fetch_one(&mut connection)
+                    .await?;
+
+                sqlx::raw_sql(sqlx::AssertSqlSafe(postgres_reassign_objects(
+                    &admin,
+                    &self.username,
+                    r#"
+                    EXECUTE format('GRANT ALL ON SCHEMA %I TO %I', object.nspname, target_role);
+                    "#,
+                )))
+                .execute(&mut connection)
+                .await?;
+            }
+            DatabasePool::Mongodb(client) => {
+                let cmd = mongodb::bson::doc! {
+                    "updateUser": self.username.to_string(),
+                    "roles": [
+                        { "role": "readWrite", "db": self.name.to_string() }
+                    ]
+                };
+                client.database(&self.name).run_command(cmd).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Measures the size of the database and compares it to `size_limit`,
+    /// revoking or restoring write access when the quota state changes.
+    pub async fn enforce_quota(
+        &mut self,
+        database: &crate::database::Database,
+        size_limit: Option<i64>,
+    ) -> Result<QuotaEnforcement, anyhow::Error> {
+        let size = self.get_size(database).await?;
+
+        let quota_exceeded = size_limit.is_some_and(|size_limit| size >= size_limit);
+        let quota_warning = quota_warning(size, size_limit);
+
+        let enforcement = if quota_exceeded && !self.quota_exceeded {
+            self.revoke_write_access(database).await?;
+
+            QuotaEnforcement::Exceeded
+        } else if !quota_exceeded && self.quota_exceeded {
+            self.restore_write_access(database).await?;
+
+            QuotaEnforcement::Restored
+        } else if !quota_exceeded
+            && let Some(threshold) = quota_warning
+            && self.quota_warning.is_none_or(|warning| warning < threshold)
+        {
+            QuotaEnforcement::Warning(threshold)
+        } else {
+            QuotaEnforcement::Unchanged
+        };
+
+        let size_checked = chrono::Utc::now().naive_utc();
+
+        sqlx::query(
+            r#"
+            UPDATE server_databases
+            SET size = $1, size_checked = $2, quota_exceeded = $3, quota_warning = $4
+            WHERE server_databases.uuid = $5
+            "#,
+        )
+        .bind(size)
+        .bind(size_checked)
+        .bind(quota_exceeded)
+        .bind(quota_warning)
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?;
+
+        self.size = Some(size);
+        self.size_checked = Some(size_checked);
+        self.quota_exceeded = quota_exceeded;
+        self.quota_warning = quota_warning;
+
+        Ok(enforcement)
+    }
+}
@@ -0,0 +1,652 @@
+use crate::{
+    models::{
+        InsertQueryBuilder, server_database::ServerDatabase,
+        server_database_instance::ServerDatabaseInstance,
+    },
+    prelude::*,
+};
+use garde::Validate;
+use rand::distr::SampleString;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+
+/// The maximum number of access tokens a single database can have at once.
+pub const MAX_ACCESS_TOKENS_PER_DATABASE: i64 = 10;
+/// The longest an access token can be valid for.
+pub const MAX_ACCESS_TOKEN_LIFETIME: chrono::TimeDelta = chrono::TimeDelta::days(90);
+
+pub fn validate_expires(
+    expires: &chrono::DateTime<chrono::Utc>,
+    context: &(),
+) -> Result<(), garde::Error> {
+    crate::utils::validate_time_in_future(expires, context)?;
+
+    if *expires > chrono::Utc::now() + MAX_ACCESS_TOKEN_LIFETIME {
+        return Err(garde::Error::new(format!(
+            "time must be at most {} days in the future",
+            MAX_ACCESS_TOKEN_LIFETIME.num_days()
+        )));
+    }
+
+    Ok(())
+}
+
+/// A time limited, read only database user for external tools. The password is only
+/// returned when the token is created and never stored by the panel.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerDatabaseAccessToken {
+    pub uuid: uuid::Uuid,
+    pub server: Fetchable<super::server::Server>,
+
+    pub server_database_uuid: Option<uuid::Uuid>,
+    pub server_database_instance_uuid: Option<uuid::Uuid>,
+    pub instance_database_uuid: Option<uuid::Uuid>,
+    pub agent_user_uuid: Option<uuid::Uuid>,
+
+    pub name: compact_str::CompactString,
+    pub username: compact_str::CompactString,
+
+    pub expires: chrono::NaiveDateTime,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerDatabaseAccessToken {
+    const NAME: &'static str = "server_database_access_token";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_database_access_tokens.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_database_access_tokens.server_uuid",
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            (
+                "server_database_access_tokens.server_database_uuid",
+                compact_str::format_compact!("{prefix}server_database_uuid"),
+            ),
+            (
+                "server_database_access_tokens.server_database_instance_uuid",
+                compact_str::format_compact!("{prefix}server_database_instance_uuid"),
+            ),
+            (
+                "server_database_access_tokens.instance_database_uuid",
+                compact_str::format_compact!("{prefix}instance_database_uuid"),
+            ),
+            (
+                "server_database_access_tokens.agent_user_uuid",
+                compact_str::format_compact!("{prefix}agent_user_uuid"),
+            ),
+            (
+                "server_database_access_tokens.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "server_database_access_tokens.username",
+                compact_str::format_compact!("{prefix}username"),
+            ),
+            (
+                "server_database_access_tokens.expires",
+                compact_str::format_compact!("{prefix}expires"),
+            ),
+            (
+                "server_database_access_tokens.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server: super::server::Server::get_fetchable(
+                row.try_get(compact_str::format_compact!("{prefix}server_uuid").as_str())?,
+            ),
+            server_database_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}server_database_uuid").as_str())?,
+            server_database_instance_uuid: row.try_get(
+                compact_str::format_compact!("{prefix}server_database_instance_uuid").as_str(),
+            )?,
+            instance_database_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}instance_database_uuid").as_str())?,
+            agent_user_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}agent_user_uuid").as_str())?,
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            username: row.try_get(compact_str::format_compact!("{prefix}username").as_str())?,
+            expires: row.try_get(compact_str::format_compact!("{prefix}expires").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerDatabaseAccessToken {
+    /// Usernames are kept alphanumeric, database agents reject anything else.
+    #[inline]
+    pub fn generate_username(server_uuid: uuid::Uuid) -> String {
+        let server_id = format!("{:08x}", server_uuid.as_u128() >> 96);
+        format!(
+            "ro{}{}",
+            server_id,
+            rand::distr::Alphanumeric
+                .sample_string(&mut rand::rng(), 8)
+                .to_lowercase()
+        )
+    }
+
+    pub async fn by_server_database_uuid_uuid(
+        database: &crate::database::Database,
+        server_database_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_database_access_tokens
+            WHERE server_database_access_tokens.server_database_uuid = $1
+                AND server_database_access_tokens.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_server_database_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_database_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_database_access_tokens
+            WHERE server_database_access_tokens.server_database_uuid = $1
+                AND ($2 IS NULL OR server_database_access_tokens.name ILIKE '%' || $2 || '%')
+            ORDER BY server_database_access_tokens.created DESC
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn count_by_server_database_uuid(
+        database: &crate::database::Database,
+        server_database_uuid: uuid::Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM server_database_access_tokens
+            WHERE server_database_access_tokens.server_database_uuid = $1
+            "#,
+        )
+        .bind(server_database_uuid)
+        .fetch_one(database.read())
+        .await
+    }
+
+    pub async fn all_usernames_by_server_database_uuid(
+        database: &crate::database::Database,
+        server_database_uuid: uuid::Uuid,
+    ) -> Result<Vec<compact_str::CompactString>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT server_database_access_tokens.username
+            FROM server_database_access_tokens
+            WHERE server_database_access_tokens.server_database_uuid = $1
+            "#,
+        )
+        .bind(server_database_uuid)
+        .fetch_all(database.read())
+        .await
+    }
+
+    pub async fn by_server_database_instance_uuid_instance_database_uuid_uuid(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+        instance_database_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_database_access_tokens
+            WHERE server_database_access_tokens.server_database_instance_uuid = $1
+                AND server_database_access_tokens.instance_database_uuid = $2
+                AND server_database_access_tokens.uuid = $3
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_instance_uuid)
+        .bind(instance_database_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_server_database_instance_uuid_instance_database_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+        instance_database_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_database_access_tokens
+            WHERE server_database_access_tokens.server_database_instance_uuid = $1
+                AND server_database_access_tokens.instance_database_uuid = $2
+                AND ($3 IS NULL OR server_database_access_tokens.name ILIKE '%' || $3 || '%')
+            ORDER BY server_database_access_tokens.created DESC
+            LIMIT $4 OFFSET $5
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_instance_uuid)
+        .bind(instance_database_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn count_by_server_database_instance_uuid_instance_database_uuid(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+        instance_database_uuid: uuid::Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM server_database_access_tokens
+            WHERE server_database_access_tokens.server_database_instance_uuid = $1
+                AND server_database_access_tokens.instance_database_uuid = $2
+            "#,
+        )
+        .bind(server_database_instance_uuid)
+        .bind(instance_database_uuid)
+        .fetch_one(database.read())
+        .await
+    }
+
+    pub async fn all_expired(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_database_access_tokens
+            WHERE server_database_access_tokens.expires < NOW()
+            ORDER BY server_database_access_tokens.expires
+            "#,
+            Self::columns_sql(None)
+        )))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Drops the database user behind the token. Users that are already gone are ignored.
+    async fn revoke(&self, state: &crate::State) -> Result<(), anyhow::Error> {
+        if let Some(server_database_uuid) = self.server_database_uuid {
+            let mut server_database =
+                match ServerDatabase::by_uuid_optional(&state.database, server_database_uuid)
+                    .await?
+                {
+                    Some(server_database) => server_database,
+                    None => return Ok(()),
+                };
+
+            server_database
+                .drop_read_only_user(&state.database, &self.username)
+                .await?;
+        } else if let Some(server_database_instance_uuid) = self.server_database_instance_uuid
+            && let Some(agent_user_uuid) = self.agent_user_uuid
+        {
+            let instance = match ServerDatabaseInstance::by_uuid_optional(
+                &state.database,
+                server_database_instance_uuid,
+            )
+            .await?
+            {
+                Some(instance) => instance,
+                None => return Ok(()),
+            };
+
+            match instance
+                .database_agent_host
+                .api_client(&state.database)
+                .await?
+                .delete_instances_instance_users_user(instance.uuid, agent_user_uuid)
+                .await
+            {
+                Ok(_) => {}
+                Err(db_agent_api::client::ApiHttpError::Http(
+                    axum::http::StatusCode::NOT_FOUND,
+                    _,
+                )) => {}
+                Err(err) => {
+                    return Err(super::server_database_instance::database_agent_error(err));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerDatabaseAccessToken {
+    type ApiObject = ApiServerDatabaseAccessToken;
+    type ExtraArgs<'a> = ();
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiServerDatabaseAccessToken::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            ApiServerDatabaseAccessToken {
+                uuid: self.uuid,
+                name: self.name,
+                username: self.username,
+                expires: self.expires.and_utc(),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+pub enum ServerDatabaseAccessTokenTarget<'a> {
+    Database(&'a ServerDatabase),
+    InstanceDatabase {
+        instance: &'a ServerDatabaseInstance,
+        database_uuid: uuid::Uuid,
+    },
+}
+
+#[derive(Validate)]
+pub struct CreateServerDatabaseAccessTokenOptions<'a> {
+    #[garde(skip)]
+    pub target: ServerDatabaseAccessTokenTarget<'a>,
+
+    #[garde(length(chars, min = 1, max = 63))]
+    pub name: compact_str::CompactString,
+    #[garde(custom(validate_expires))]
+    pub expires: chrono::DateTime<chrono::Utc>,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerDatabaseAccessToken {
+    type CreateOptions<'a> = CreateServerDatabaseAccessTokenOptions<'a>;
+    type CreateResult = (String, Self);
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerDatabaseAccessToken>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self::CreateResult, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_database_access_tokens");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        let (username, password, agent_user_uuid) = match &options.target {
+            ServerDatabaseAccessTokenTarget::Database(server_database) => {
+                let username = Self::generate_username(server_database.server.uuid);
+                let password = ServerDatabase::generate_password();
+
+                ServerDatabase::clone(server_database)
+                    .create_read_only_user(&state.database, &username, &password)
+                    .await?;
+
+                query_builder
+                    .set("server_uuid", server_database.server.uuid)
+                    .set("server_database_uuid", server_database.uuid);
+
+                (username.into(), password, None)
+            }
+            ServerDatabaseAccessTokenTarget::InstanceDatabase {
+                instance,
+                database_uuid,
+            } => {
+                let response = instance
+                    .database_agent_host
+                    .api_client(&state.database)
+                    .await?
+                    .post_instances_instance_users(
+                        instance.uuid,
+                        &db_agent_api::instances_instance_users::post::RequestBody {
+                            username: Self::generate_username(instance.server.uuid).into(),
+                            databases: vec![db_agent_api::UserDatabase {
+                                database_uuid: *database_uuid,
+                                permission: db_agent_api::DatabasePermission::ReadOnly,
+                            }],
+                        },
+                    )
+                    .await
+                    .map_err(super::server_database_instance::database_agent_error)?;
+
+                query_builder
+                    .set("server_uuid", instance.server.uuid)
+                    .set("server_database_instance_uuid", instance.uuid)
+                    .set("instance_database_uuid", *database_uuid);
+
+                (
+                    response.username,
+                    response.user.password.into(),
+                    Some(response.user.uuid),
+                )
+            }
+        };
+
+        query_builder
+            .set("agent_user_uuid", agent_user_uuid)
+            .set("name", &options.name)
+            .set("username", &username)
+            .set("expires", options.expires.naive_utc());
+
+        let row = match query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await
+        {
+            Ok(row) => row,
+            Err(err) => {
+                let cleanup = match &options.target {
+                    ServerDatabaseAccessTokenTarget::Database(server_database) => {
+                        ServerDatabase::clone(server_database)
+                            .drop_read_only_user(&state.database, &username)
+                            .await
+                    }
+                    ServerDatabaseAccessTokenTarget::InstanceDatabase { instance, .. } => {
+                        async {
+                            instance
+                                .database_agent_host
+                                .api_client(&state.database)
+                                .await?
+                                .delete_instances_instance_users_user(
+                                    instance.uuid,
+                                    agent_user_uuid.unwrap_or_default(),
+                                )
+                                .await?;
+
+                            Ok::<_, anyhow::Error>(())
+                        }
+                        .await
+                    }
+                };
+
+                if let Err(err) = cleanup {
+                    tracing::warn!(
+                        username = %username,
+                        "failed to clean up database access token user: {:?}",
+                        err
+                    );
+                }
+
+                return Err(err.into());
+            }
+        };
+        let mut result = (password, Self::map(None, &row)?);
+
+        Self::run_after_create_handlers(&mut result, &options, state, transaction).await?;
+
+        Ok(result)
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for ServerDatabaseAccessToken {
+    type DeleteOptions = ();
+
+    fn get_delete_handlers() -> &'static LazyLock<DeleteHandlerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteHandlerList<ServerDatabaseAccessToken>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete_with_transaction(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), anyhow::Error> {
+        self.run_delete_handlers(&options, state, transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM server_database_access_tokens
+            WHERE server_database_access_tokens.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut **transaction)
+        .await?;
+
+        self.run_after_delete_handlers(&options, state, transaction)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+    ) -> Result<(), anyhow::Error> {
+        self.revoke(state).await?;
+
+        let mut transaction = state.database.write().begin().await?;
+        self.delete_with_transaction(state, options, &mut transaction)
+            .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerDatabaseAccessToken, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerDatabaseAccessToken")]
+pub struct ApiServerDatabaseAccessToken {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub username: compact_str::CompactString,
+
+    pub expires: chrono::DateTime<chrono::Utc>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -20,6 +20,7 @@ pub struct ServerDatabaseInstance {
     pub database_agent_template:
         Option<Fetchable<super::database_agent_template::DatabaseAgentTemplate>>,
     pub template_version: Option<i32>,
+    pub pinned_template_version: Option<i32>,
 
     pub r#type: db_agent_api::DatabaseAgentType,
 
@@ -57,6 +58,13 @@ pub struct ResolvedInstanceSpec {
     pub cpu: i32,
 }
 
+#[derive(ToSchema, Serialize)]
+pub struct TemplateVersionUsage {
+    pub template_version: Option<i32>,
+    pub pinned_template_version: Option<i32>,
+    pub instances: i64,
+}
+
 impl BaseModel for ServerDatabaseInstance {
     const NAME: &'static str = "server_database_agent";
 
@@ -92,6 +100,10 @@ impl BaseModel for ServerDatabaseInstance {
                 "server_database_instances.template_version",
                 compact_str::format_compact!("{prefix}template_version"),
             ),
+            (
+                "server_database_instances.pinned_template_version",
+                compact_str::format_compact!("{prefix}pinned_template_version"),
+            ),
             (
                 "server_database_instances.image",
                 compact_str::format_compact!("{prefix}image"),
@@ -165,6 +177,9 @@ impl BaseModel for ServerDatabaseInstance {
                 .map(super::database_agent_template::DatabaseAgentTemplate::get_fetchable),
             template_version: row
                 .try_get(compact_str::format_compact!("{prefix}template_version").as_str())?,
+            pinned_template_version: row.try_get(
+                compact_str::format_compact!("{prefix}pinned_template_version").as_str(),
+            )?,
             r#type: row.try_get(compact_str::format_compact!("{prefix}type").as_str())?,
             name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
             locked: row.try_get(compact_str::format_compact!("{prefix}locked").as_str())?,
@@ -217,10 +232,104 @@ impl ServerDatabaseInstance {
         })
     }
 
+    /// The template revision this instance should be running, which is its pinned revision
+    /// if it has one and the latest revision of its template otherwise.
+    #[inline]
+    pub fn target_template_version(
+        &self,
+        template: &super::database_agent_template::DatabaseAgentTemplate,
+    ) -> i32 {
+        self.pinned_template_version.unwrap_or(template.version)
+    }
+
+    pub fn update_available(
+        &self,
+        template: Option<&super::database_agent_template::DatabaseAgentTemplate>,
+    ) -> bool {
+        match (template, self.template_version) {
+            (Some(template), Some(template_version)) => match self.pinned_template_version {
+                Some(pinned_template_version) => pinned_template_version != template_version,
+                None => template.version > template_version,
+            },
+            _ => false,
+        }
+    }
+
+    /// Applies the given template, usually resolved at a specific revision, to the instance
+    /// on its agent and records the revision it is now running.
+    pub async fn apply_template(
+        &mut self,
+        state: &crate::State,
+        template: &super::database_agent_template::DatabaseAgentTemplate,
+    ) -> Result<(), anyhow::Error> {
+        let spec = self
+            .resolve_spec(Some(template))
+            .ok_or_else(|| anyhow::anyhow!("cannot resolve database instance configuration"))?;
+
+        let timezone = self
+            .server
+            .fetch_cached(&state.database)
+            .await?
+            .timezone
+            .clone();
+
+        self.database_agent_host
+            .api_client(&state.database)
+            .await?
+            .patch_instances_instance(
+                self.uuid,
+                &db_agent_api::instances_instance::patch::RequestBody {
+                    suspended: None,
+                    memory: Some(spec.memory),
+                    swap: Some(spec.swap),
+                    disk: Some(spec.disk),
+                    io_weight: spec.io_weight.map(i64::from),
+                    cpu: Some(spec.cpu as i64),
+                    image: Some(spec.image),
+                    image_uid: spec.image_uid.map(|uid| uid as u32),
+                    image_gid: spec.image_gid.map(|gid| gid as u32),
+                    volumes: spec.volumes,
+                    socket_path: spec.socket_path,
+                    timezone,
+                    env: Some(spec.env),
+                    cmd: spec.cmd,
+                },
+            )
+            .await
+            .map_err(database_agent_error)?;
+
+        self.set_template_version(&state.database, Some(template.version))
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_pinned_template_version(
+        &mut self,
+        database: &crate::database::Database,
+        version: Option<i32>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE server_database_instances
+            SET pinned_template_version = $2
+            WHERE server_database_instances.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(version)
+        .execute(database.write())
+        .await?;
+
+        self.pinned_template_version = version;
+
+        Ok(())
+    }
+
     pub async fn set_template_version(
         &mut self,
         database: &crate::database::Database,
-        version: i32,
+        version: Option<i32>,
     ) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
@@ -234,7 +343,7 @@ impl ServerDatabaseInstance {
         .execute(database.write())
         .await?;
 
-        self.template_version = Some(version);
+        self.template_version = version;
 
         Ok(())
     }
@@ -403,6 +512,75 @@ impl ServerDatabaseInstance {
         })
     }
 
+    /// Returns instances of a template that a rollout to `version` still has to update,
+    /// skipping pinned, locked and excluded instances and those on hosts in maintenance.
+    pub async fn all_rollout_pending_by_database_agent_template_uuid(
+        database: &crate::database::Database,
+        database_agent_template_uuid: uuid::Uuid,
+        version: i32,
+        exclude: &[uuid::Uuid],
+        limit: i64,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_database_instances
+            JOIN database_agent_hosts ON database_agent_hosts.uuid = server_database_instances.database_agent_host_uuid
+            WHERE server_database_instances.database_agent_template_uuid = $1
+                AND server_database_instances.template_version IS DISTINCT FROM $2
+                AND server_database_instances.pinned_template_version IS NULL
+                AND NOT server_database_instances.locked
+                AND NOT database_agent_hosts.maintenance_enabled
+                AND server_database_instances.uuid <> ALL($3)
+            ORDER BY server_database_instances.created
+            LIMIT $4
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(database_agent_template_uuid)
+        .bind(version)
+        .bind(exclude)
+        .bind(limit)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn template_version_usage_by_database_agent_template_uuid(
+        database: &crate::database::Database,
+        database_agent_template_uuid: uuid::Uuid,
+    ) -> Result<Vec<TemplateVersionUsage>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, (Option<i32>, Option<i32>, i64)>(
+            r#"
+            SELECT
+                server_database_instances.template_version,
+                server_database_instances.pinned_template_version,
+                COUNT(*)
+            FROM server_database_instances
+            WHERE server_database_instances.database_agent_template_uuid = $1
+            GROUP BY server_database_instances.template_version, server_database_instances.pinned_template_version
+            ORDER BY server_database_instances.template_version DESC NULLS LAST, server_database_instances.pinned_template_version DESC NULLS FIRST
+            "#,
+        )
+        .bind(database_agent_template_uuid)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(template_version, pinned_template_version, instances)| TemplateVersionUsage {
+                    template_version,
+                    pinned_template_version,
+                    instances,
+                },
+            )
+            .collect())
+    }
+
     pub async fn all_by_server_uuid(
         database: &crate::database::Database,
         server_uuid: uuid::Uuid,
@@ -519,10 +697,7 @@ impl ServerDatabaseInstance {
 
             env
         };
-        let update_available = match (&template, self.template_version) {
-            (Some(template), Some(template_version)) => template.version > template_version,
-            _ => false,
-        };
+        let update_available = self.update_available(template.as_ref());
 
         Ok(ResolvedAdminInstance {
             image,
@@ -580,6 +755,7 @@ impl ServerDatabaseInstance {
                 name: self.name,
                 is_locked: self.locked,
                 template_version: self.template_version,
+                pinned_template_version: self.pinned_template_version,
                 update_available: resolved.update_available,
                 image: resolved.image,
                 image_override: self.image,
@@ -637,6 +813,7 @@ impl IntoAdminApiObject for ServerDatabaseInstance {
                 name: self.name,
                 is_locked: self.locked,
                 template_version: self.template_version,
+                pinned_template_version: self.pinned_template_version,
                 update_available: resolved.update_available,
                 image: resolved.image,
                 image_override: self.image,
@@ -685,10 +862,7 @@ impl IntoApiObject for ServerDatabaseInstance {
             None => None,
         };
 
-        let update_available = match (&template, self.template_version) {
-            (Some(template), Some(template_version)) => template.version > template_version,
-            _ => false,
-        };
+        let update_available = self.update_available(template.as_ref());
 
         let api_object = finish_extendible!(
             ApiServerDatabaseInstance {
@@ -1036,6 +1210,18 @@ impl DeletableModel for ServerDatabaseInstance {
         self.run_delete_handlers(&options, state, transaction)
             .await?;
 
+        for snapshot in
+            super::server_database_instance_snapshot::ServerDatabaseInstanceSnapshot::all_by_server_database_instance_uuid(
+                &state.database,
+                self.uuid,
+            )
+            .await?
+        {
+            snapshot
+                .delete_with_transaction(state, (), transaction)
+                .await?;
+        }
+
         sqlx::query(
             r#"
             DELETE FROM server_database_instances
@@ -1082,6 +1268,238 @@ impl DeletableModel for ServerDatabaseInstance {
     }
 }
 
+pub(crate) fn database_agent_error(err: db_agent_api::client::ApiHttpError) -> anyhow::Error {
+    match err {
+        db_agent_api::client::ApiHttpError::Http(status, err) => {
+            crate::response::DisplayError::new(err.error.to_string())
+                .with_status(status)
+                .into()
+        }
+        err => err.into(),
+    }
+}
+
+async fn send_export_rows(
+    output: &super::server_database::DumpSender,
+    encoder: &mut super::server_database::ExportEncoder,
+    result: super::server_database::QueryResultSet,
+    first: bool,
+) -> Result<(), anyhow::Error> {
+    let mut buffer = Vec::new();
+    if first {
+        encoder.columns(result.columns, &mut buffer);
+    }
+    for row in &result.rows {
+        encoder.row(row, &mut buffer)?;
+    }
+
+    output
+        .send(Ok(buffer))
+        .await
+        .map_err(|_| anyhow::anyhow!("database export consumer has gone away"))
+}
+
+struct InstanceImportTarget<'a> {
+    client: &'a db_agent_api::client::DbAgentClient,
+    instance: uuid::Uuid,
+    database: uuid::Uuid,
+    schema: Option<compact_str::CompactString>,
+    table: compact_str::CompactString,
+}
+
+#[async_trait::async_trait]
+impl super::server_database::ImportTarget for InstanceImportTarget<'_> {
+    async fn insert(
+        &mut self,
+        rows: &[super::server_database::RowInsert],
+    ) -> Result<u64, anyhow::Error> {
+        let response = self
+            .client
+            .post_instances_instance_databases_database_explorer_rows_insert(
+                self.instance,
+                self.database,
+                &db_agent_api::instances_instance_databases_database_explorer_rows_insert::post::RequestBody {
+                    schema: self.schema.clone(),
+                    table: self.table.clone(),
+                    rows: rows.iter().cloned().map(Into::into).collect(),
+                },
+            )
+            .await
+            .map_err(database_agent_error)?;
+
+        Ok(response.affected)
+    }
+}
+
+impl ServerDatabaseInstance {
+    async fn agent_table(
+        &self,
+        client: &db_agent_api::client::DbAgentClient,
+        database: uuid::Uuid,
+        schema: Option<&str>,
+        table: &str,
+    ) -> Result<super::server_database::SchemaTable, anyhow::Error> {
+        let tables = client
+            .get_instances_instance_databases_database_explorer_schema(self.uuid, database)
+            .await
+            .map_err(database_agent_error)?
+            .tables;
+
+        tables
+            .into_iter()
+            .map(super::server_database::SchemaTable::from)
+            .find(|entry| {
+                entry.name == table && (schema.is_none() || entry.schema.as_deref() == schema)
+            })
+            .ok_or_else(|| {
+                crate::response::DisplayError::new(format!("table {table} does not exist"))
+                    .with_status(axum::http::StatusCode::NOT_FOUND)
+                    .into()
+            })
+    }
+
+    /// Streams a table or a query result of one of the databases of this instance into
+    /// `output`. Tables are paged through in primary key order, a failed export sends its error
+    /// as the last item.
+    pub async fn export_database(
+        &self,
+        database: &crate::database::Database,
+        database_uuid: uuid::Uuid,
+        source: &super::server_database::ExportSource,
+        format: super::server_database::ExportFormat,
+        output: super::server_database::DumpSender,
+    ) {
+        if let Err(err) = self
+            .export_database_rows(database, database_uuid, source, format, &output)
+            .await
+        {
+            output.send(Err(err)).await.ok();
+        }
+    }
+
+    async fn export_database_rows(
+        &self,
+        database: &crate::database::Database,
+        database_uuid: uuid::Uuid,
+        source: &super::server_database::ExportSource,
+        format: super::server_database::ExportFormat,
+        output: &super::server_database::DumpSender,
+    ) -> Result<(), anyhow::Error> {
+        use super::server_database::{
+            BROWSE_MAX_ROWS, ExportEncoder, ExportSource, QUERY_MAX_ROWS, QueryResultSet,
+            SqlDialect,
+        };
+
+        let mut encoder =
+            ExportEncoder::new(format, SqlDialect::from_agent_type(self.r#type), source)?;
+        let client = self.database_agent_host.api_client(database).await?;
+
+        match source {
+            ExportSource::Table { schema, table } => {
+                let definition = self
+                    .agent_table(&client, database_uuid, schema.as_deref(), table)
+                    .await?;
+                let order_by = definition
+                    .columns
+                    .iter()
+                    .find(|column| column.primary_key)
+                    .or_else(|| definition.columns.first())
+                    .map(|column| column.name.clone());
+
+                let mut offset = 0;
+                loop {
+                    let result: QueryResultSet = client
+                        .post_instances_instance_databases_database_explorer_rows(
+                            self.uuid,
+                            database_uuid,
+                            &db_agent_api::instances_instance_databases_database_explorer_rows::post::RequestBody {
+                                schema: schema.clone(),
+                                table: table.clone(),
+                                order_by: order_by.clone(),
+                                descending: false,
+                                limit: BROWSE_MAX_ROWS,
+                                offset,
+                                filters: Vec::new(),
+                            },
+                        )
+                        .await
+                        .map_err(database_agent_error)?
+                        .result
+                        .into();
+
+                    let rows = result.rows.len() as u64;
+                    send_export_rows(output, &mut encoder, result, offset == 0).await?;
+
+                    if rows < BROWSE_MAX_ROWS as u64 {
+                        break;
+                    }
+                    offset += rows;
+                }
+            }
+            ExportSource::Query { sql, .. } => {
+                let result = client
+                    .post_instances_instance_databases_database_explorer_query(
+                        self.uuid,
+                        database_uuid,
+                        &db_agent_api::instances_instance_databases_database_explorer_query::post::RequestBody {
+                            query: sql.as_str().into(),
+                            rows: QUERY_MAX_ROWS,
+                            read_only: true,
+                        },
+                    )
+                    .await
+                    .map_err(database_agent_error)?
+                    .results
+                    .into_iter()
+                    .next();
+
+                if let Some(result) = result {
+                    send_export_rows(output, &mut encoder, result.into(), true).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Imports CSV or JSON lines into a table of one of the databases of this instance.
+    pub async fn import_database(
+        &self,
+        database: &crate::database::Database,
+        database_uuid: uuid::Uuid,
+        options: &super::server_database::ImportOptions,
+        reader: impl tokio::io::AsyncBufRead + Unpin + Send,
+    ) -> Result<super::server_database::ImportSummary, anyhow::Error> {
+        use super::server_database::ValueKind;
+
+        let client = self.database_agent_host.api_client(database).await?;
+        let table = self
+            .agent_table(
+                &client,
+                database_uuid,
+                options.schema.as_deref(),
+                &options.table,
+            )
+            .await?;
+        let kinds: Vec<ValueKind> = table
+            .columns
+            .iter()
+            .map(|column| ValueKind::classify(column, &column.type_name))
+            .collect();
+
+        let mut target = InstanceImportTarget {
+            client: &client,
+            instance: self.uuid,
+            database: database_uuid,
+            schema: table.schema.clone(),
+            table: table.name.clone(),
+        };
+
+        super::server_database::import_rows(reader, options, &table.columns, &kinds, &mut target)
+            .await
+    }
+}
+
 #[derive(ToSchema, Serialize)]
 #[schema(title = "ServerDatabaseInstanceDatabase")]
 pub struct ApiServerDatabaseInstanceDatabase {
@@ -1177,6 +1595,7 @@ pub struct AdminApiServerServerDatabaseInstance {
     pub database_agent_template:
         Option<super::database_agent_template::AdminApiDatabaseAgentTemplate>,
     pub template_version: Option<i32>,
+    pub pinned_template_version: Option<i32>,
     pub update_available: bool,
 
     pub r#type: db_agent_api::DatabaseAgentType,
@@ -1217,6 +1636,7 @@ pub struct AdminApiServerDatabaseInstance {
     pub database_agent_template:
         Option<super::database_agent_template::AdminApiDatabaseAgentTemplate>,
     pub template_version: Option<i32>,
+    pub pinned_template_version: Option<i32>,
     pub update_available: bool,
 
     pub r#type: db_agent_api::DatabaseAgentType,
@@ -0,0 +1,400 @@
+use crate::{
+    models::{
+        server_database::{QuotaEnforcement, quota_warning},
+        server_database_instance::{ServerDatabaseInstance, database_agent_error},
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::LazyLock,
+};
+
+/// Quota state of a single database inside of a database agent instance, the
+/// databases themselves only exist on the agent.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerDatabaseInstanceDatabaseQuota {
+    pub server_database_instance_uuid: uuid::Uuid,
+    pub database_uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+
+    pub size: i64,
+    pub size_checked: chrono::NaiveDateTime,
+    pub quota_exceeded: bool,
+    pub quota_warning: Option<i16>,
+    /// Users that lost write access when the quota was exceeded.
+    pub revoked_users: Vec<uuid::Uuid>,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerDatabaseInstanceDatabaseQuota {
+    const NAME: &'static str = "server_database_instance_database_quota";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_database_instance_database_quotas.server_database_instance_uuid",
+                compact_str::format_compact!("{prefix}server_database_instance_uuid"),
+            ),
+            (
+                "server_database_instance_database_quotas.database_uuid",
+                compact_str::format_compact!("{prefix}database_uuid"),
+            ),
+            (
+                "server_database_instance_database_quotas.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "server_database_instance_database_quotas.size",
+                compact_str::format_compact!("{prefix}size"),
+            ),
+            (
+                "server_database_instance_database_quotas.size_checked",
+                compact_str::format_compact!("{prefix}size_checked"),
+            ),
+            (
+                "server_database_instance_database_quotas.quota_exceeded",
+                compact_str::format_compact!("{prefix}quota_exceeded"),
+            ),
+            (
+                "server_database_instance_database_quotas.quota_warning",
+                compact_str::format_compact!("{prefix}quota_warning"),
+            ),
+            (
+                "server_database_instance_database_quotas.revoked_users",
+                compact_str::format_compact!("{prefix}revoked_users"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            server_database_instance_uuid: row.try_get(
+                compact_str::format_compact!("{prefix}server_database_instance_uuid").as_str(),
+            )?,
+            database_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}database_uuid").as_str())?,
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            size: row.try_get(compact_str::format_compact!("{prefix}size").as_str())?,
+            size_checked: row
+                .try_get(compact_str::format_compact!("{prefix}size_checked").as_str())?,
+            quota_exceeded: row
+                .try_get(compact_str::format_compact!("{prefix}quota_exceeded").as_str())?,
+            quota_warning: row
+                .try_get(compact_str::format_compact!("{prefix}quota_warning").as_str())?,
+            revoked_users: row
+                .try_get(compact_str::format_compact!("{prefix}revoked_users").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerDatabaseInstanceDatabaseQuota {
+    pub async fn all_by_server_database_instance_uuid(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_database_instance_database_quotas
+            WHERE server_database_instance_database_quotas.server_database_instance_uuid = $1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_instance_uuid)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn exceeded_database_uuids(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+    ) -> Result<Vec<uuid::Uuid>, crate::database::DatabaseError> {
+        Ok(sqlx::query_scalar(
+            r#"
+            SELECT server_database_instance_database_quotas.database_uuid
+            FROM server_database_instance_database_quotas
+            WHERE server_database_instance_database_quotas.server_database_instance_uuid = $1
+                AND server_database_instance_database_quotas.quota_exceeded
+            "#,
+        )
+        .bind(server_database_instance_uuid)
+        .fetch_all(database.read())
+        .await?)
+    }
+
+    /// Fails when write access is requested on one of `database_uuids` while
+    /// it is over quota.
+    pub async fn ensure_writable(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+        database_uuids: &[uuid::Uuid],
+    ) -> Result<(), anyhow::Error> {
+        if database_uuids.is_empty() {
+            return Ok(());
+        }
+
+        let exceeded =
+            Self::exceeded_database_uuids(database, server_database_instance_uuid).await?;
+        if database_uuids
+            .iter()
+            .any(|database_uuid| exceeded.contains(database_uuid))
+        {
+            return Err(crate::response::DisplayError::new(
+                "write access cannot be granted on a database that is over its size quota",
+            )
+            .with_status(reqwest::StatusCode::EXPECTATION_FAILED)
+            .into());
+        }
+
+        Ok(())
+    }
+
+    async fn upsert(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+        stored_database: &db_agent_api::StoredDatabase,
+        size: i64,
+        quota_exceeded: bool,
+        quota_warning: Option<i16>,
+        revoked_users: &[uuid::Uuid],
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            INSERT INTO server_database_instance_database_quotas (
+                server_database_instance_uuid, database_uuid, name, size, size_checked,
+                quota_exceeded, quota_warning, revoked_users
+            )
+            VALUES ($1, $2, $3, $4, NOW(), $5, $6, $7)
+            ON CONFLICT (server_database_instance_uuid, database_uuid) DO UPDATE SET
+                name = EXCLUDED.name,
+                size = EXCLUDED.size,
+                size_checked = EXCLUDED.size_checked,
+                quota_exceeded = EXCLUDED.quota_exceeded,
+                quota_warning = EXCLUDED.quota_warning,
+                revoked_users = EXCLUDED.revoked_users
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_instance_uuid)
+        .bind(stored_database.uuid)
+        .bind(&stored_database.name)
+        .bind(size)
+        .bind(quota_exceeded)
+        .bind(quota_warning)
+        .bind(revoked_users)
+        .fetch_one(database.write())
+        .await?;
+
+        Self::map(None, &row)
+    }
+}
+
+impl ServerDatabaseInstance {
+    /// Returns all instances of servers that have a database size limit or
+    /// that still have databases marked as over quota, paired with the limit in
+    /// bytes.
+    pub async fn all_with_size_limit(
+        database: &crate::database::Database,
+    ) -> Result<Vec<(Self, Option<i64>)>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, servers.database_size_limit AS effective_size_limit
+            FROM server_database_instances
+            JOIN database_agent_hosts ON database_agent_hosts.uuid = server_database_instances.database_agent_host_uuid
+            JOIN servers ON servers.uuid = server_database_instances.server_uuid
+            WHERE servers.database_size_limit IS NOT NULL
+                OR EXISTS (
+                    SELECT 1
+                    FROM server_database_instance_database_quotas
+                    WHERE server_database_instance_database_quotas.server_database_instance_uuid = server_database_instances.uuid
+                        AND server_database_instance_database_quotas.quota_exceeded
+                )
+            "#,
+            Self::columns_sql(None)
+        )))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| -> Result<_, crate::database::DatabaseError> {
+                Ok((Self::map(None, &row)?, row.try_get("effective_size_limit")?))
+            })
+            .try_collect_vec()
+    }
+
+    /// Measures every database of the instance and compares it to
+    /// `size_limit`, downgrading users with write access to read only when a
+    /// database exceeds it and upgrading them again once it is below it.
+    pub async fn enforce_quotas(
+        &self,
+        database: &crate::database::Database,
+        size_limit: Option<i64>,
+    ) -> Result<Vec<(ServerDatabaseInstanceDatabaseQuota, QuotaEnforcement)>, anyhow::Error> {
+        let client = self.database_agent_host.api_client(database).await?;
+
+        let stored_databases = client
+            .get_instances_instance_databases(self.uuid)
+            .await
+            .map_err(database_agent_error)?
+            .databases;
+        let mut quotas: HashMap<uuid::Uuid, ServerDatabaseInstanceDatabaseQuota> =
+            ServerDatabaseInstanceDatabaseQuota::all_by_server_database_instance_uuid(
+                database, self.uuid,
+            )
+            .await?
+            .into_iter()
+            .map(|quota| (quota.database_uuid, quota))
+            .collect();
+        let mut stored_users = None;
+
+        let mut enforcements = Vec::with_capacity(stored_databases.len());
+
+        for stored_database in stored_databases {
+            let size = client
+                .get_instances_instance_databases_database_size(self.uuid, stored_database.uuid)
+                .await
+                .map_err(database_agent_error)?
+                .size;
+            let previous = quotas.remove(&stored_database.uuid);
+
+            let quota_exceeded = size_limit.is_some_and(|size_limit| size >= size_limit);
+            let quota_warning = quota_warning(size, size_limit);
+            let was_exceeded = previous.as_ref().is_some_and(|quota| quota.quota_exceeded);
+            let mut revoked_users = previous
+                .as_ref()
+                .map(|quota| quota.revoked_users.clone())
+                .unwrap_or_default();
+
+            let enforcement = if quota_exceeded && !was_exceeded {
+                let stored_users = match &stored_users {
+                    Some(stored_users) => stored_users,
+                    None => stored_users.insert(
+                        client
+                            .get_instances_instance_users(self.uuid)
+                            .await
+                            .map_err(database_agent_error)?
+                            .users,
+                    ),
+                };
+
+                for stored_user in stored_users {
+                    if !stored_user.databases.iter().any(|user_database| {
+                        user_database.database_uuid == stored_database.uuid
+                            && matches!(
+                                user_database.permission,
+                                db_agent_api::DatabasePermission::ReadWrite
+                            )
+                    }) {
+                        continue;
+                    }
+
+                    client
+                        .put_instances_instance_users_user_databases_database(
+                            self.uuid,
+                            stored_user.uuid,
+                            stored_database.uuid,
+                            &db_agent_api::instances_instance_users_user_databases_database::put::RequestBody {
+                                permission: db_agent_api::DatabasePermission::ReadOnly,
+                            },
+                        )
+                        .await
+                        .map_err(database_agent_error)?;
+                    revoked_users.push(stored_user.uuid);
+                }
+
+                QuotaEnforcement::Exceeded
+            } else if !quota_exceeded && was_exceeded {
+                for user_uuid in std::mem::take(&mut revoked_users) {
+                    match client
+                        .put_instances_instance_users_user_databases_database(
+                            self.uuid,
+                            user_uuid,
+                            stored_database.uuid,
+                            &db_agent_api::instances_instance_users_user_databases_database::put::RequestBody {
+                                permission: db_agent_api::DatabasePermission::ReadWrite,
+                            },
+                        )
+                        .await
+                    {
+                        Ok(_) => {}
+                        // the user was deleted while the database was over quota
+                        Err(db_agent_api::client::ApiHttpError::Http(
+                            reqwest::StatusCode::NOT_FOUND,
+                            _,
+                        )) => {}
+                        Err(err) => return Err(database_agent_error(err)),
+                    }
+                }
+
+                QuotaEnforcement::Restored
+            } else if !quota_exceeded
+                && let Some(threshold) = quota_warning
+                && previous
+                    .as_ref()
+                    .and_then(|quota| quota.quota_warning)
+                    .is_none_or(|warning| warning < threshold)
+            {
+                QuotaEnforcement::Warning(threshold)
+            } else {
+                QuotaEnforcement::Unchanged
+            };
+
+            let quota = ServerDatabaseInstanceDatabaseQuota::upsert(
+                database,
+                self.uuid,
+                &stored_database,
+                size,
+                quota_exceeded,
+                quota_warning,
+                &revoked_users,
+            )
+            .await?;
+
+            enforcements.push((quota, enforcement));
+        }
+
+        if !quotas.is_empty() {
+            sqlx::query(
+                r#"
+                DELETE FROM server_database_instance_database_quotas
+                WHERE server_database_instance_database_quotas.server_database_instance_uuid = $1
+                    AND server_database_instance_database_quotas.database_uuid = ANY($2)
+                "#,
+            )
+            .bind(self.uuid)
+            .bind(quotas.into_keys().collect::<Vec<_>>())
+            .execute(database.write())
+            .await?;
+        }
+
+        Ok(enforcements)
+    }
+}
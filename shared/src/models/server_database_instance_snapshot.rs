@@ -0,0 +1,821 @@
+use crate::{
+    models::{
+        InsertQueryBuilder,
+        backup_configuration::{BackupConfigsS3, BackupConfiguration},
+        server_backup::encryption::BackupEncryptionKey,
+        server_backup_database::ServerBackupDatabase,
+        server_database_instance::ServerDatabaseInstance,
+    },
+    prelude::*,
+};
+use compact_str::ToCompactString;
+use futures_util::{StreamExt, TryStreamExt};
+use garde::Validate;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Debug, ToSchema, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerDatabaseInstanceSnapshotStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+/// A single dump stored as part of a snapshot. Redis instances are dumped as a whole and have
+/// no `instance_database_uuid`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerDatabaseInstanceSnapshotFile {
+    pub instance_database_uuid: Option<uuid::Uuid>,
+    pub name: compact_str::CompactString,
+    pub upload_path: compact_str::CompactString,
+    pub bytes: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerDatabaseInstanceSnapshot {
+    pub uuid: uuid::Uuid,
+    pub server_database_instance_uuid: uuid::Uuid,
+    pub snapshot_policy_uuid: Option<uuid::Uuid>,
+    pub backup_configuration: Option<Fetchable<BackupConfiguration>>,
+
+    pub name: compact_str::CompactString,
+    pub encrypted: bool,
+
+    pub bytes: i64,
+    pub files: Vec<ServerDatabaseInstanceSnapshotFile>,
+    pub error: Option<compact_str::CompactString>,
+
+    pub completed: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerDatabaseInstanceSnapshot {
+    const NAME: &'static str = "server_database_instance_snapshot";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_database_instance_snapshots.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_database_instance_snapshots.server_database_instance_uuid",
+                compact_str::format_compact!("{prefix}server_database_instance_uuid"),
+            ),
+            (
+                "server_database_instance_snapshots.snapshot_policy_uuid",
+                compact_str::format_compact!("{prefix}snapshot_policy_uuid"),
+            ),
+            (
+                "server_database_instance_snapshots.backup_configuration_uuid",
+                compact_str::format_compact!("{prefix}backup_configuration_uuid"),
+            ),
+            (
+                "server_database_instance_snapshots.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "server_database_instance_snapshots.encrypted",
+                compact_str::format_compact!("{prefix}encrypted"),
+            ),
+            (
+                "server_database_instance_snapshots.bytes",
+                compact_str::format_compact!("{prefix}bytes"),
+            ),
+            (
+                "server_database_instance_snapshots.files",
+                compact_str::format_compact!("{prefix}files"),
+            ),
+            (
+                "server_database_instance_snapshots.error",
+                compact_str::format_compact!("{prefix}error"),
+            ),
+            (
+                "server_database_instance_snapshots.completed",
+                compact_str::format_compact!("{prefix}completed"),
+            ),
+            (
+                "server_database_instance_snapshots.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server_database_instance_uuid: row.try_get(
+                compact_str::format_compact!("{prefix}server_database_instance_uuid").as_str(),
+            )?,
+            snapshot_policy_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}snapshot_policy_uuid").as_str())?,
+            backup_configuration: BackupConfiguration::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}backup_configuration_uuid"),
+            ),
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            encrypted: row.try_get(compact_str::format_compact!("{prefix}encrypted").as_str())?,
+            bytes: row.try_get(compact_str::format_compact!("{prefix}bytes").as_str())?,
+            files: serde_json::from_value(row.try_get::<serde_json::Value, _>(
+                compact_str::format_compact!("{prefix}files").as_str(),
+            )?)?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            completed: row.try_get(compact_str::format_compact!("{prefix}completed").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerDatabaseInstanceSnapshot {
+    pub async fn by_server_database_instance_uuid_uuid(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_database_instance_snapshots
+            WHERE server_database_instance_snapshots.server_database_instance_uuid = $1
+                AND server_database_instance_snapshots.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_instance_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_server_database_instance_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_database_instance_snapshots
+            WHERE server_database_instance_snapshots.server_database_instance_uuid = $1
+                AND ($2 IS NULL OR server_database_instance_snapshots.name ILIKE '%' || $2 || '%')
+            ORDER BY server_database_instance_snapshots.created DESC
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_instance_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn all_by_server_database_instance_uuid(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_database_instance_snapshots
+            WHERE server_database_instance_snapshots.server_database_instance_uuid = $1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_instance_uuid)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Returns the finished snapshots of a policy that fall outside of its retention, oldest
+    /// last. Pending snapshots are never returned.
+    pub async fn all_expired_by_snapshot_policy_uuid(
+        database: &crate::database::Database,
+        snapshot_policy_uuid: uuid::Uuid,
+        retention_count: i32,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_database_instance_snapshots
+            WHERE server_database_instance_snapshots.snapshot_policy_uuid = $1
+                AND (server_database_instance_snapshots.completed IS NOT NULL OR server_database_instance_snapshots.error IS NOT NULL)
+            ORDER BY server_database_instance_snapshots.created DESC
+            OFFSET $2
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(snapshot_policy_uuid)
+        .bind(retention_count as i64)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn count_pending_by_server_database_instance_uuid(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM server_database_instance_snapshots
+            WHERE server_database_instance_snapshots.server_database_instance_uuid = $1
+                AND server_database_instance_snapshots.completed IS NULL
+                AND server_database_instance_snapshots.error IS NULL
+            "#,
+        )
+        .bind(server_database_instance_uuid)
+        .fetch_one(database.read())
+        .await
+    }
+
+    /// Fails snapshots that have been pending for longer than any export could reasonably take,
+    /// which happens when the panel restarts while a snapshot is being written.
+    pub async fn fail_interrupted(
+        database: &crate::database::Database,
+    ) -> Result<u64, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+            UPDATE server_database_instance_snapshots
+            SET error = 'snapshot was interrupted'
+            WHERE server_database_instance_snapshots.completed IS NULL
+                AND server_database_instance_snapshots.error IS NULL
+                AND server_database_instance_snapshots.created < NOW() - INTERVAL '12 hours'
+            "#,
+        )
+        .execute(database.write())
+        .await?
+        .rows_affected())
+    }
+
+    pub fn default_name() -> compact_str::CompactString {
+        let now = chrono::Local::now();
+
+        now.format("%Y-%m-%d %H:%M:%S %z").to_compact_string()
+    }
+
+    #[inline]
+    pub fn status(&self) -> ServerDatabaseInstanceSnapshotStatus {
+        if self.completed.is_some() {
+            ServerDatabaseInstanceSnapshotStatus::Completed
+        } else if self.error.is_some() {
+            ServerDatabaseInstanceSnapshotStatus::Failed
+        } else {
+            ServerDatabaseInstanceSnapshotStatus::Pending
+        }
+    }
+
+    async fn s3_configuration(
+        database: &crate::database::Database,
+        backup_configuration: &BackupConfiguration,
+    ) -> Result<BackupConfigsS3, anyhow::Error> {
+        let Some(mut s3_configuration) = backup_configuration.backup_configs.s3.clone() else {
+            return Err(crate::response::DisplayError::new(
+                "backup configuration does not have an S3 configuration to store snapshots in",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .into());
+        };
+        s3_configuration.decrypt(database).await?;
+
+        Ok(s3_configuration)
+    }
+
+    /// Creates a pending snapshot of the instance in the backup configuration of its server.
+    /// The snapshot has to be written with [Self::run] afterwards.
+    pub async fn create_for_instance(
+        state: &crate::State,
+        instance: &ServerDatabaseInstance,
+        snapshot_policy_uuid: Option<uuid::Uuid>,
+        name: compact_str::CompactString,
+    ) -> Result<Self, anyhow::Error> {
+        let server = instance.server.fetch_cached(&state.database).await?;
+        let backup_configuration = server
+            .backup_configuration(&state.database)
+            .await
+            .ok_or_else(|| {
+                crate::response::DisplayError::new(
+                    "no backup configuration available, unable to create snapshot",
+                )
+                .with_status(StatusCode::EXPECTATION_FAILED)
+            })?;
+
+        if backup_configuration.maintenance_enabled {
+            return Err(crate::response::DisplayError::new(
+                "cannot create snapshot while backup configuration is in maintenance mode",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .into());
+        }
+
+        let s3_configuration =
+            Self::s3_configuration(&state.database, &backup_configuration).await?;
+
+        Ok(Self::create(
+            state,
+            CreateServerDatabaseInstanceSnapshotOptions {
+                server_database_instance_uuid: instance.uuid,
+                snapshot_policy_uuid,
+                backup_configuration_uuid: backup_configuration.uuid,
+                name,
+                encrypted: s3_configuration.encryption_enabled,
+            },
+        )
+        .await?)
+    }
+
+    /// Exports every database of the instance into the snapshot's backup configuration and
+    /// records the outcome. A single failing export fails the whole snapshot, partially
+    /// written files are removed again.
+    pub async fn run(
+        &mut self,
+        state: &crate::State,
+        instance: &ServerDatabaseInstance,
+    ) -> Result<(), anyhow::Error> {
+        let written = self.write_files(state, instance).await;
+
+        match written {
+            Ok((files, bytes)) => {
+                let completed: chrono::NaiveDateTime = sqlx::query_scalar(
+                    r#"
+                    UPDATE server_database_instance_snapshots
+                    SET bytes = $2, files = $3, error = NULL, completed = NOW()
+                    WHERE server_database_instance_snapshots.uuid = $1
+                    RETURNING server_database_instance_snapshots.completed
+                    "#,
+                )
+                .bind(self.uuid)
+                .bind(bytes)
+                .bind(serde_json::to_value(&files)?)
+                .fetch_one(state.database.write())
+                .await?;
+
+                self.bytes = bytes;
+                self.files = files;
+                self.completed = Some(completed);
+
+                Ok(())
+            }
+            Err(err) => {
+                tracing::warn!(
+                    snapshot = %self.uuid,
+                    database_instance = %instance.uuid,
+                    "failed to write database instance snapshot: {:#?}",
+                    err
+                );
+
+                sqlx::query(
+                    r#"
+                    UPDATE server_database_instance_snapshots
+                    SET error = $2
+                    WHERE server_database_instance_snapshots.uuid = $1
+                    "#,
+                )
+                .bind(self.uuid)
+                .bind(err.to_string())
+                .execute(state.database.write())
+                .await?;
+
+                self.error = Some(err.to_string().into());
+
+                Err(err)
+            }
+        }
+    }
+
+    async fn write_files(
+        &self,
+        state: &crate::State,
+        instance: &ServerDatabaseInstance,
+    ) -> Result<(Vec<ServerDatabaseInstanceSnapshotFile>, i64), anyhow::Error> {
+        let Some(backup_configuration) = &self.backup_configuration else {
+            return Err(anyhow::anyhow!(
+                "snapshot does not have a backup configuration"
+            ));
+        };
+        let backup_configuration = backup_configuration.fetch_cached(&state.database).await?;
+        let s3_configuration =
+            Self::s3_configuration(&state.database, &backup_configuration).await?;
+
+        let encryption_key = if self.encrypted {
+            Some(BackupEncryptionKey::for_server(&state.database, instance.server.uuid).await?)
+        } else {
+            None
+        };
+
+        let client = instance
+            .database_agent_host
+            .api_client(&state.database)
+            .await?;
+
+        let mut files = Vec::new();
+        let mut bytes = 0;
+
+        let written = async {
+            for (instance_database_uuid, name) in
+                ServerBackupDatabase::instance_databases(state, instance).await?
+            {
+                let export = client
+                    .get_instances_instance_export(
+                        instance.uuid,
+                        &db_agent_api::instances_instance_export::get::Query {
+                            db: instance_database_uuid.map(|_| name.clone()),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+
+                let mut file_path = compact_str::format_compact!(
+                    "{}/database-instances/{}/snapshots/{}/{}.{}",
+                    instance.server.uuid,
+                    instance.uuid,
+                    self.uuid,
+                    files.len(),
+                    instance.r#type.dump_extension()
+                );
+                if self.encrypted {
+                    file_path.push_str(".enc");
+                }
+
+                let (upload_path, file_bytes) = ServerBackupDatabase::upload(
+                    s3_configuration.clone(),
+                    file_path,
+                    encryption_key.as_ref(),
+                    tokio_util::io::ReaderStream::new(export)
+                        .map(|chunk| {
+                            chunk
+                                .map(|chunk| chunk.to_vec())
+                                .map_err(Into::<anyhow::Error>::into)
+                        })
+                        .boxed(),
+                )
+                .await?;
+
+                bytes += file_bytes;
+                files.push(ServerDatabaseInstanceSnapshotFile {
+                    instance_database_uuid,
+                    name,
+                    upload_path,
+                    bytes: file_bytes,
+                });
+            }
+
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+
+        if let Err(err) = written {
+            Self::delete_files(s3_configuration, &files).await;
+
+            return Err(err);
+        }
+
+        Ok((files, bytes))
+    }
+
+    async fn delete_files(
+        s3_configuration: BackupConfigsS3,
+        files: &[ServerDatabaseInstanceSnapshotFile],
+    ) {
+        let (client, bucket) = s3_configuration.into_client();
+
+        for file in files {
+            if let Err(err) = client
+                .delete_object()
+                .bucket(&*bucket)
+                .key(&*file.upload_path)
+                .send()
+                .await
+            {
+                tracing::warn!(
+                    path = %file.upload_path,
+                    "failed to delete database instance snapshot file from S3: {:?}",
+                    err
+                );
+            }
+        }
+    }
+
+    /// Restores every database of the snapshot into the instance, replacing their contents.
+    /// Databases that no longer exist in the instance are recreated by the agent on import.
+    pub async fn restore(
+        &self,
+        state: &crate::State,
+        instance: &ServerDatabaseInstance,
+    ) -> Result<(), anyhow::Error> {
+        if self.status() != ServerDatabaseInstanceSnapshotStatus::Completed {
+            return Err(crate::response::DisplayError::new(
+                "only completed snapshots can be restored",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .into());
+        }
+
+        let Some(backup_configuration) = &self.backup_configuration else {
+            return Err(crate::response::DisplayError::new(
+                "snapshot backup configuration no longer exists",
+            )
+            .with_status(StatusCode::EXPECTATION_FAILED)
+            .into());
+        };
+        let backup_configuration = backup_configuration.fetch_cached(&state.database).await?;
+        let s3_configuration =
+            Self::s3_configuration(&state.database, &backup_configuration).await?;
+
+        let encryption_key = if self.encrypted {
+            Some(BackupEncryptionKey::for_server(&state.database, instance.server.uuid).await?)
+        } else {
+            None
+        };
+
+        let client = instance
+            .database_agent_host
+            .api_client(&state.database)
+            .await?;
+
+        for file in &self.files {
+            // databases may have been renamed since, so the current name is looked up
+            let db = match file.instance_database_uuid {
+                Some(instance_database_uuid) => Some(
+                    match client
+                        .get_instances_instance_databases_database(
+                            instance.uuid,
+                            instance_database_uuid,
+                        )
+                        .await
+                    {
+                        Ok(response) => response.database.name,
+                        Err(db_agent_api::client::ApiHttpError::Http(StatusCode::NOT_FOUND, _)) => {
+                            file.name.clone()
+                        }
+                        Err(err) => return Err(err.into()),
+                    },
+                ),
+                None => None,
+            };
+
+            let dump = ServerBackupDatabase::download_path(
+                state,
+                s3_configuration.clone(),
+                &file.upload_path,
+                encryption_key.as_ref(),
+            )
+            .await?;
+
+            client
+                .post_instances_instance_import(
+                    instance.uuid,
+                    db_agent_api::client::AsyncRequestReader::new(
+                        tokio_util::io::StreamReader::new(
+                            dump.map_ok(tokio_util::bytes::Bytes::from)
+                                .map_err(std::io::Error::other),
+                        ),
+                    ),
+                    &db_agent_api::instances_instance_import::post::Query {
+                        source_db: file.instance_database_uuid.map(|_| file.name.clone()),
+                        db,
+                        wipe: Some(true),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerDatabaseInstanceSnapshot {
+    type ApiObject = ApiServerDatabaseInstanceSnapshot;
+    type ExtraArgs<'a> = ();
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let status = self.status();
+        let api_object = ApiServerDatabaseInstanceSnapshot::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            ApiServerDatabaseInstanceSnapshot {
+                uuid: self.uuid,
+                snapshot_policy_uuid: self.snapshot_policy_uuid,
+                name: self.name,
+                status,
+                bytes: self.bytes,
+                databases: self.files.into_iter().map(|file| file.name).collect(),
+                completed: self.completed.map(|dt| dt.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(Validate)]
+pub struct CreateServerDatabaseInstanceSnapshotOptions {
+    #[garde(skip)]
+    pub server_database_instance_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub snapshot_policy_uuid: Option<uuid::Uuid>,
+    #[garde(skip)]
+    pub backup_configuration_uuid: uuid::Uuid,
+    #[garde(length(chars, min = 1, max = 255))]
+    pub name: compact_str::CompactString,
+    #[garde(skip)]
+    pub encrypted: bool,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerDatabaseInstanceSnapshot {
+    type CreateOptions<'a> = CreateServerDatabaseInstanceSnapshotOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerDatabaseInstanceSnapshot>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_database_instance_snapshots");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set(
+                "server_database_instance_uuid",
+                options.server_database_instance_uuid,
+            )
+            .set("snapshot_policy_uuid", options.snapshot_policy_uuid)
+            .set(
+                "backup_configuration_uuid",
+                options.backup_configuration_uuid,
+            )
+            .set("name", &options.name)
+            .set("encrypted", options.encrypted);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut snapshot = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut snapshot, &options, state, transaction).await?;
+
+        Ok(snapshot)
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for ServerDatabaseInstanceSnapshot {
+    type DeleteOptions = ();
+
+    fn get_delete_handlers() -> &'static LazyLock<DeleteHandlerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteHandlerList<ServerDatabaseInstanceSnapshot>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete_with_transaction(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), anyhow::Error> {
+        self.run_delete_handlers(&options, state, transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM server_database_instance_snapshots
+            WHERE server_database_instance_snapshots.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut **transaction)
+        .await?;
+
+        if !self.files.is_empty() {
+            match &self.backup_configuration {
+                Some(backup_configuration) => {
+                    match async {
+                        let backup_configuration =
+                            backup_configuration.fetch_cached(&state.database).await?;
+
+                        Self::s3_configuration(&state.database, &backup_configuration).await
+                    }
+                    .await
+                    {
+                        Ok(s3_configuration) => {
+                            Self::delete_files(s3_configuration, &self.files).await
+                        }
+                        Err(err) => {
+                            tracing::warn!(
+                                snapshot = %self.uuid,
+                                "unable to load S3 configuration to delete snapshot files: {:#?}",
+                                err
+                            );
+                        }
+                    }
+                }
+                None => {
+                    tracing::warn!(
+                        snapshot = %self.uuid,
+                        "snapshot backup configuration no longer exists, leaving its files behind"
+                    );
+                }
+            }
+        }
+
+        self.run_after_delete_handlers(&options, state, transaction)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerDatabaseInstanceSnapshot, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerDatabaseInstanceSnapshot")]
+pub struct ApiServerDatabaseInstanceSnapshot {
+    pub uuid: uuid::Uuid,
+    pub snapshot_policy_uuid: Option<uuid::Uuid>,
+    pub name: compact_str::CompactString,
+
+    pub status: ServerDatabaseInstanceSnapshotStatus,
+    pub bytes: i64,
+    pub databases: Vec<compact_str::CompactString>,
+
+    pub completed: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
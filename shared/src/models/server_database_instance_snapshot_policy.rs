@@ -0,0 +1,468 @@
+use crate::{
+    models::{InsertQueryBuilder, UpdateQueryBuilder},
+    prelude::*,
+};
+use compact_str::ToCompactString;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerDatabaseInstanceSnapshotPolicy {
+    pub uuid: uuid::Uuid,
+    pub server_database_instance_uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub enabled: bool,
+    pub cron: croner::Cron,
+    pub retention_count: i32,
+
+    pub last_run: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerDatabaseInstanceSnapshotPolicy {
+    const NAME: &'static str = "server_database_instance_snapshot_policy";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_database_instance_snapshot_policies.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_database_instance_snapshot_policies.server_database_instance_uuid",
+                compact_str::format_compact!("{prefix}server_database_instance_uuid"),
+            ),
+            (
+                "server_database_instance_snapshot_policies.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "server_database_instance_snapshot_policies.enabled",
+                compact_str::format_compact!("{prefix}enabled"),
+            ),
+            (
+                "server_database_instance_snapshot_policies.cron",
+                compact_str::format_compact!("{prefix}cron"),
+            ),
+            (
+                "server_database_instance_snapshot_policies.retention_count",
+                compact_str::format_compact!("{prefix}retention_count"),
+            ),
+            (
+                "server_database_instance_snapshot_policies.last_run",
+                compact_str::format_compact!("{prefix}last_run"),
+            ),
+            (
+                "server_database_instance_snapshot_policies.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        let cron: String = row.try_get(compact_str::format_compact!("{prefix}cron").as_str())?;
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server_database_instance_uuid: row.try_get(
+                compact_str::format_compact!("{prefix}server_database_instance_uuid").as_str(),
+            )?,
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            enabled: row.try_get(compact_str::format_compact!("{prefix}enabled").as_str())?,
+            cron: croner::Cron::from_str(&cron)
+                .map_err(|err| crate::database::DatabaseError::Any(anyhow::Error::new(err)))?,
+            retention_count: row
+                .try_get(compact_str::format_compact!("{prefix}retention_count").as_str())?,
+            last_run: row.try_get(compact_str::format_compact!("{prefix}last_run").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerDatabaseInstanceSnapshotPolicy {
+    pub async fn by_server_database_instance_uuid_uuid(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_database_instance_snapshot_policies
+            WHERE server_database_instance_snapshot_policies.server_database_instance_uuid = $1
+                AND server_database_instance_snapshot_policies.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_instance_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_server_database_instance_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_database_instance_snapshot_policies
+            WHERE server_database_instance_snapshot_policies.server_database_instance_uuid = $1
+                AND ($2 IS NULL OR server_database_instance_snapshot_policies.name ILIKE '%' || $2 || '%')
+            ORDER BY server_database_instance_snapshot_policies.created
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_instance_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn all_enabled(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_database_instance_snapshot_policies
+            WHERE server_database_instance_snapshot_policies.enabled
+            ORDER BY server_database_instance_snapshot_policies.created
+            "#,
+            Self::columns_sql(None)
+        )))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn count_by_server_database_instance_uuid(
+        database: &crate::database::Database,
+        server_database_instance_uuid: uuid::Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM server_database_instance_snapshot_policies
+            WHERE server_database_instance_snapshot_policies.server_database_instance_uuid = $1
+            "#,
+        )
+        .bind(server_database_instance_uuid)
+        .fetch_one(database.read())
+        .await
+    }
+
+    #[inline]
+    pub fn is_due(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.enabled
+            && self
+                .cron
+                .iter_after(self.last_run.unwrap_or(self.created).and_utc())
+                .next()
+                .is_some_and(|next| next <= now)
+    }
+
+    /// Marks the policy as run, returning `false` if another worker already claimed this run.
+    pub async fn claim_run(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<bool, sqlx::Error> {
+        let last_run: Option<chrono::NaiveDateTime> = sqlx::query_scalar(
+            r#"
+            UPDATE server_database_instance_snapshot_policies
+            SET last_run = NOW()
+            WHERE server_database_instance_snapshot_policies.uuid = $1
+                AND server_database_instance_snapshot_policies.last_run IS NOT DISTINCT FROM $2
+            RETURNING server_database_instance_snapshot_policies.last_run
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(self.last_run)
+        .fetch_optional(database.write())
+        .await?
+        .flatten();
+
+        match last_run {
+            Some(last_run) => {
+                self.last_run = Some(last_run);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerDatabaseInstanceSnapshotPolicy {
+    type ApiObject = ApiServerDatabaseInstanceSnapshotPolicy;
+    type ExtraArgs<'a> = ();
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiServerDatabaseInstanceSnapshotPolicy::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            ApiServerDatabaseInstanceSnapshotPolicy {
+                uuid: self.uuid,
+                name: self.name,
+                enabled: self.enabled,
+                cron: self.cron,
+                retention_count: self.retention_count,
+                last_run: self.last_run.map(|dt| dt.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerDatabaseInstanceSnapshotPolicyOptions {
+    #[garde(skip)]
+    pub server_database_instance_uuid: uuid::Uuid,
+
+    #[garde(length(chars, min = 1, max = 255))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub name: compact_str::CompactString,
+    #[garde(skip)]
+    pub enabled: bool,
+    #[garde(skip)]
+    #[schema(value_type = String, example = "0 0 0 * * *")]
+    pub cron: croner::Cron,
+    #[garde(range(min = 1, max = 100))]
+    #[schema(minimum = 1, maximum = 100)]
+    pub retention_count: i32,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerDatabaseInstanceSnapshotPolicy {
+    type CreateOptions<'a> = CreateServerDatabaseInstanceSnapshotPolicyOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<
+            CreateListenerList<ServerDatabaseInstanceSnapshotPolicy>,
+        > = LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder =
+            InsertQueryBuilder::new("server_database_instance_snapshot_policies");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set(
+                "server_database_instance_uuid",
+                options.server_database_instance_uuid,
+            )
+            .set("name", &options.name)
+            .set("enabled", options.enabled)
+            .set("cron", options.cron.to_compact_string())
+            .set("retention_count", options.retention_count);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut policy = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut policy, &options, state, transaction).await?;
+
+        Ok(policy)
+    }
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Default)]
+pub struct UpdateServerDatabaseInstanceSnapshotPolicyOptions {
+    #[garde(length(chars, min = 1, max = 255))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub name: Option<compact_str::CompactString>,
+    #[garde(skip)]
+    pub enabled: Option<bool>,
+    #[garde(skip)]
+    #[schema(value_type = Option<String>, example = "0 0 0 * * *")]
+    pub cron: Option<croner::Cron>,
+    #[garde(inner(range(min = 1, max = 100)))]
+    #[schema(minimum = 1, maximum = 100)]
+    pub retention_count: Option<i32>,
+}
+
+#[async_trait::async_trait]
+impl UpdatableModel for ServerDatabaseInstanceSnapshotPolicy {
+    type UpdateOptions = UpdateServerDatabaseInstanceSnapshotPolicyOptions;
+
+    fn get_update_handlers() -> &'static LazyLock<UpdateHandlerList<Self>> {
+        static UPDATE_LISTENERS: LazyLock<UpdateHandlerList<ServerDatabaseInstanceSnapshotPolicy>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &UPDATE_LISTENERS
+    }
+
+    async fn update_with_transaction(
+        &mut self,
+        state: &crate::State,
+        mut options: Self::UpdateOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder =
+            UpdateQueryBuilder::new("server_database_instance_snapshot_policies");
+
+        self.run_update_handlers(&mut options, &mut query_builder, state, transaction)
+            .await?;
+
+        query_builder
+            .set("name", options.name.as_ref())
+            .set("enabled", options.enabled)
+            .set(
+                "cron",
+                options.cron.as_ref().map(|cron| cron.to_compact_string()),
+            )
+            .set("retention_count", options.retention_count)
+            .where_eq("uuid", self.uuid);
+
+        query_builder.execute(&mut **transaction).await?;
+
+        if let Some(name) = options.name {
+            self.name = name;
+        }
+        if let Some(enabled) = options.enabled {
+            self.enabled = enabled;
+        }
+        if let Some(cron) = options.cron {
+            self.cron = cron;
+        }
+        if let Some(retention_count) = options.retention_count {
+            self.retention_count = retention_count;
+        }
+
+        self.run_after_update_handlers(state, transaction).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for ServerDatabaseInstanceSnapshotPolicy {
+    type DeleteOptions = ();
+
+    fn get_delete_handlers() -> &'static LazyLock<DeleteHandlerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteHandlerList<ServerDatabaseInstanceSnapshotPolicy>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete_with_transaction(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), anyhow::Error> {
+        self.run_delete_handlers(&options, state, transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM server_database_instance_snapshot_policies
+            WHERE server_database_instance_snapshot_policies.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut **transaction)
+        .await?;
+
+        self.run_after_delete_handlers(&options, state, transaction)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerDatabaseInstanceSnapshotPolicy, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerDatabaseInstanceSnapshotPolicy")]
+pub struct ApiServerDatabaseInstanceSnapshotPolicy {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub enabled: bool,
+    #[schema(value_type = String, example = "0 0 0 * * *")]
+    pub cron: croner::Cron,
+    pub retention_count: i32,
+
+    pub last_run: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,576 @@
+use crate::{
+    models::{InsertQueryBuilder, database_host::DatabaseHost, server_database::ServerDatabase},
+    prelude::*,
+};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use utoipa::ToSchema;
+
+#[derive(Debug, ToSchema, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerDatabaseMigrationStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerDatabaseMigration {
+    pub uuid: uuid::Uuid,
+    pub server_database_uuid: uuid::Uuid,
+    pub source_database_host_uuid: uuid::Uuid,
+    pub target_database_host_uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+
+    pub bytes: i64,
+    pub error: Option<compact_str::CompactString>,
+
+    pub started: Option<chrono::NaiveDateTime>,
+    /// Set right before the target database is created, leftovers on the target may only be
+    /// dropped when this is set.
+    pub provisioned: Option<chrono::NaiveDateTime>,
+    pub heartbeat: Option<chrono::NaiveDateTime>,
+    pub completed: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerDatabaseMigration {
+    const NAME: &'static str = "server_database_migration";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_database_migrations.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_database_migrations.server_database_uuid",
+                compact_str::format_compact!("{prefix}server_database_uuid"),
+            ),
+            (
+                "server_database_migrations.source_database_host_uuid",
+                compact_str::format_compact!("{prefix}source_database_host_uuid"),
+            ),
+            (
+                "server_database_migrations.target_database_host_uuid",
+                compact_str::format_compact!("{prefix}target_database_host_uuid"),
+            ),
+            (
+                "server_database_migrations.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "server_database_migrations.bytes",
+                compact_str::format_compact!("{prefix}bytes"),
+            ),
+            (
+                "server_database_migrations.error",
+                compact_str::format_compact!("{prefix}error"),
+            ),
+            (
+                "server_database_migrations.started",
+                compact_str::format_compact!("{prefix}started"),
+            ),
+            (
+                "server_database_migrations.provisioned",
+                compact_str::format_compact!("{prefix}provisioned"),
+            ),
+            (
+                "server_database_migrations.heartbeat",
+                compact_str::format_compact!("{prefix}heartbeat"),
+            ),
+            (
+                "server_database_migrations.completed",
+                compact_str::format_compact!("{prefix}completed"),
+            ),
+            (
+                "server_database_migrations.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server_database_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}server_database_uuid").as_str())?,
+            source_database_host_uuid: row.try_get(
+                compact_str::format_compact!("{prefix}source_database_host_uuid").as_str(),
+            )?,
+            target_database_host_uuid: row.try_get(
+                compact_str::format_compact!("{prefix}target_database_host_uuid").as_str(),
+            )?,
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            bytes: row.try_get(compact_str::format_compact!("{prefix}bytes").as_str())?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            started: row.try_get(compact_str::format_compact!("{prefix}started").as_str())?,
+            provisioned: row
+                .try_get(compact_str::format_compact!("{prefix}provisioned").as_str())?,
+            heartbeat: row.try_get(compact_str::format_compact!("{prefix}heartbeat").as_str())?,
+            completed: row.try_get(compact_str::format_compact!("{prefix}completed").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerDatabaseMigration {
+    /// How often a running migration records its progress.
+    const HEARTBEAT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+    /// Running migrations without a heartbeat for this long are considered interrupted.
+    const HEARTBEAT_TIMEOUT_SECONDS: i32 = 300;
+
+    pub async fn by_database_host_uuid_with_pagination(
+        database: &crate::database::Database,
+        database_host_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_database_migrations
+            WHERE (
+                server_database_migrations.source_database_host_uuid = $1
+                OR server_database_migrations.target_database_host_uuid = $1
+            ) AND ($2 IS NULL OR server_database_migrations.name ILIKE '%' || $2 || '%')
+            ORDER BY server_database_migrations.created DESC
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(database_host_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn active_server_database_uuids(
+        database: &crate::database::Database,
+    ) -> Result<Vec<uuid::Uuid>, crate::database::DatabaseError> {
+        Ok(sqlx::query_scalar(
+            r#"
+            SELECT server_database_migrations.server_database_uuid
+            FROM server_database_migrations
+            WHERE server_database_migrations.completed IS NULL
+                AND server_database_migrations.error IS NULL
+            "#,
+        )
+        .fetch_all(database.read())
+        .await?)
+    }
+
+    /// Fails running migrations whose process stopped sending heartbeats, so their databases
+    /// can be migrated again. Databases that were still on their source host get their write
+    /// access back and the partial copy on the target host is dropped.
+    pub async fn fail_interrupted(
+        database: &crate::database::Database,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            UPDATE server_database_migrations
+            SET error = 'migration was interrupted'
+            WHERE server_database_migrations.started IS NOT NULL
+                AND server_database_migrations.completed IS NULL
+                AND server_database_migrations.error IS NULL
+                AND server_database_migrations.heartbeat < NOW() - make_interval(secs => $1)
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(Self::HEARTBEAT_TIMEOUT_SECONDS)
+        .fetch_all(database.write())
+        .await?;
+
+        let migrations: Vec<Self> = rows
+            .into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()?;
+
+        for migration in &migrations {
+            if migration.provisioned.is_none() {
+                continue;
+            }
+
+            if let Err(err) = migration.clean_up(database).await {
+                tracing::warn!(
+                    migration = %migration.uuid,
+                    "failed to clean up interrupted database migration: {:?}",
+                    err
+                );
+            }
+        }
+
+        Ok(migrations.len() as u64)
+    }
+
+    async fn clean_up(&self, database: &crate::database::Database) -> Result<(), anyhow::Error> {
+        let Some(mut server_database) =
+            ServerDatabase::by_uuid_optional(database, self.server_database_uuid).await?
+        else {
+            return Ok(());
+        };
+        if server_database.database_host.uuid != self.source_database_host_uuid {
+            return Ok(());
+        }
+
+        let Some(target) =
+            DatabaseHost::by_uuid_optional(database, self.target_database_host_uuid).await?
+        else {
+            return Ok(());
+        };
+
+        server_database
+            .clean_up_failed_migration(database, &target)
+            .await;
+
+        Ok(())
+    }
+
+    /// Marks the target database of a migration as about to be created.
+    pub(crate) async fn mark_provisioned(
+        database: &crate::database::Database,
+        uuid: uuid::Uuid,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            UPDATE server_database_migrations
+            SET provisioned = NOW()
+            WHERE server_database_migrations.uuid = $1
+            "#,
+        )
+        .bind(uuid)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Whether an earlier migration of the database to the target host failed after it had
+    /// created the target database, meaning a database with its name there is a leftover.
+    pub(crate) async fn has_failed_provisioned(
+        database: &crate::database::Database,
+        server_database_uuid: uuid::Uuid,
+        target_database_host_uuid: uuid::Uuid,
+    ) -> Result<bool, crate::database::DatabaseError> {
+        Ok(sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM server_database_migrations
+                WHERE server_database_migrations.server_database_uuid = $1
+                    AND server_database_migrations.target_database_host_uuid = $2
+                    AND server_database_migrations.provisioned IS NOT NULL
+                    AND server_database_migrations.error IS NOT NULL
+            )
+            "#,
+        )
+        .bind(server_database_uuid)
+        .bind(target_database_host_uuid)
+        .fetch_one(database.read())
+        .await?)
+    }
+
+    /// Starts pending migrations, oldest first, keeping at most `limit` of them running at once.
+    pub async fn claim_pending(
+        database: &crate::database::Database,
+        limit: i64,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            UPDATE server_database_migrations
+            SET started = NOW(), heartbeat = NOW()
+            WHERE server_database_migrations.uuid IN (
+                SELECT server_database_migrations.uuid
+                FROM server_database_migrations
+                WHERE server_database_migrations.started IS NULL
+                    AND server_database_migrations.error IS NULL
+                ORDER BY server_database_migrations.created
+                LIMIT GREATEST(0, $1 - (
+                    SELECT COUNT(*) FROM server_database_migrations
+                    WHERE server_database_migrations.started IS NOT NULL
+                        AND server_database_migrations.completed IS NULL
+                        AND server_database_migrations.error IS NULL
+                ))
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(limit)
+        .fetch_all(database.write())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    #[inline]
+    pub fn status(&self) -> ServerDatabaseMigrationStatus {
+        if self.completed.is_some() {
+            ServerDatabaseMigrationStatus::Completed
+        } else if self.error.is_some() {
+            ServerDatabaseMigrationStatus::Failed
+        } else if self.started.is_some() {
+            ServerDatabaseMigrationStatus::Running
+        } else {
+            ServerDatabaseMigrationStatus::Pending
+        }
+    }
+
+    async fn record_progress(
+        &self,
+        database: &crate::database::Database,
+        bytes: u64,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            UPDATE server_database_migrations
+            SET bytes = $1, heartbeat = NOW()
+            WHERE server_database_migrations.uuid = $2
+            "#,
+        )
+        .bind(bytes as i64)
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn migrate(
+        &self,
+        state: &crate::State,
+        transferred: &AtomicU64,
+    ) -> Result<(), anyhow::Error> {
+        let mut server_database =
+            ServerDatabase::by_uuid_optional(&state.database, self.server_database_uuid)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("database no longer exists"))?;
+        if server_database.database_host.uuid != self.source_database_host_uuid {
+            return Err(anyhow::anyhow!(
+                "database is no longer on the source database host"
+            ));
+        }
+
+        let target =
+            DatabaseHost::by_uuid_optional(&state.database, self.target_database_host_uuid)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("target database host no longer exists"))?;
+
+        server_database
+            .migrate_to(&state.database, &target, self.uuid, transferred)
+            .await
+    }
+
+    /// Runs the migration, recording the amount of transferred bytes while it is in progress
+    /// and its outcome once it is done.
+    pub async fn run(&self, state: &crate::State) -> Result<(), anyhow::Error> {
+        let transferred = AtomicU64::new(0);
+
+        let mut interval = tokio::time::interval(Self::HEARTBEAT_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        let migrate = self.migrate(state, &transferred);
+        tokio::pin!(migrate);
+
+        let migrated = loop {
+            tokio::select! {
+                migrated = &mut migrate => break migrated,
+                _ = interval.tick() => {
+                    if let Err(err) = self
+                        .record_progress(&state.database, transferred.load(Ordering::Relaxed))
+                        .await
+                    {
+                        tracing::warn!(
+                            migration = %self.uuid,
+                            "failed to record database migration progress: {:?}",
+                            err
+                        );
+                    }
+                }
+            }
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE server_database_migrations
+            SET bytes = $1, heartbeat = NOW(), error = $2, completed = CASE WHEN $2 IS NULL THEN NOW() END
+            WHERE server_database_migrations.uuid = $3
+            "#,
+        )
+        .bind(transferred.load(Ordering::Relaxed) as i64)
+        .bind(migrated.as_ref().err().map(|err| err.to_string()))
+        .bind(self.uuid)
+        .execute(state.database.write())
+        .await?;
+
+        migrated
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoAdminApiObject for ServerDatabaseMigration {
+    type AdminApiObject = AdminApiServerDatabaseMigration;
+    type ExtraArgs<'a> = ();
+
+    async fn into_admin_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::AdminApiObject, crate::database::DatabaseError> {
+        let api_object = AdminApiServerDatabaseMigration::init_hooks(&self, state).await?;
+        let status = self.status();
+
+        let api_object = finish_extendible!(
+            AdminApiServerDatabaseMigration {
+                uuid: self.uuid,
+                server_database_uuid: self.server_database_uuid,
+                source_database_host_uuid: self.source_database_host_uuid,
+                target_database_host_uuid: self.target_database_host_uuid,
+                name: self.name,
+                status,
+                bytes: self.bytes,
+                error: self.error,
+                started: self.started.map(|started| started.and_utc()),
+                completed: self.completed.map(|completed| completed.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerDatabaseMigrationOptions {
+    #[garde(skip)]
+    pub server_database_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub source_database_host_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub target_database_host_uuid: uuid::Uuid,
+
+    #[garde(skip)]
+    pub name: compact_str::CompactString,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerDatabaseMigration {
+    type CreateOptions<'a> = CreateServerDatabaseMigrationOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerDatabaseMigration>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_database_migrations");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set("server_database_uuid", options.server_database_uuid)
+            .set(
+                "source_database_host_uuid",
+                options.source_database_host_uuid,
+            )
+            .set(
+                "target_database_host_uuid",
+                options.target_database_host_uuid,
+            )
+            .set("name", &options.name);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut migration = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut migration, &options, state, transaction).await?;
+
+        Ok(migration)
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerDatabaseMigration, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminServerDatabaseMigration")]
+pub struct AdminApiServerDatabaseMigration {
+    pub uuid: uuid::Uuid,
+    pub server_database_uuid: uuid::Uuid,
+    pub source_database_host_uuid: uuid::Uuid,
+    pub target_database_host_uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub status: ServerDatabaseMigrationStatus,
+
+    pub bytes: i64,
+    pub error: Option<compact_str::CompactString>,
+
+    pub started: Option<chrono::DateTime<chrono::Utc>>,
+    pub completed: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,328 @@
+use crate::{models::InsertQueryBuilder, prelude::*};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerDatabaseQueryHistory {
+    pub uuid: uuid::Uuid,
+
+    pub query: String,
+    pub read_only: bool,
+    pub error: Option<String>,
+
+    pub rows: i64,
+    pub rows_affected: i64,
+    pub duration_ms: i64,
+
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerDatabaseQueryHistory {
+    const NAME: &'static str = "server_database_query_history";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_database_query_history.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_database_query_history.query",
+                compact_str::format_compact!("{prefix}query"),
+            ),
+            (
+                "server_database_query_history.read_only",
+                compact_str::format_compact!("{prefix}read_only"),
+            ),
+            (
+                "server_database_query_history.error",
+                compact_str::format_compact!("{prefix}error"),
+            ),
+            (
+                "server_database_query_history.rows",
+                compact_str::format_compact!("{prefix}rows"),
+            ),
+            (
+                "server_database_query_history.rows_affected",
+                compact_str::format_compact!("{prefix}rows_affected"),
+            ),
+            (
+                "server_database_query_history.duration_ms",
+                compact_str::format_compact!("{prefix}duration_ms"),
+            ),
+            (
+                "server_database_query_history.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            query: row.try_get(compact_str::format_compact!("{prefix}query").as_str())?,
+            read_only: row.try_get(compact_str::format_compact!("{prefix}read_only").as_str())?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            rows: row.try_get(compact_str::format_compact!("{prefix}rows").as_str())?,
+            rows_affected: row
+                .try_get(compact_str::format_compact!("{prefix}rows_affected").as_str())?,
+            duration_ms: row
+                .try_get(compact_str::format_compact!("{prefix}duration_ms").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerDatabaseQueryHistory {
+    pub async fn by_server_database_uuid_user_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_database_uuid: uuid::Uuid,
+        user_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_database_query_history
+            WHERE server_database_query_history.server_database_uuid = $1
+                AND server_database_query_history.user_uuid = $2
+                AND ($3 IS NULL OR server_database_query_history.query ILIKE '%' || $3 || '%')
+            ORDER BY server_database_query_history.created DESC
+            LIMIT $4 OFFSET $5
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_uuid)
+        .bind(user_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn delete_by_server_database_uuid_user_uuid(
+        database: &crate::database::Database,
+        server_database_uuid: uuid::Uuid,
+        user_uuid: uuid::Uuid,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_database_query_history
+            WHERE server_database_query_history.server_database_uuid = $1
+                AND server_database_query_history.user_uuid = $2
+            "#,
+        )
+        .bind(server_database_uuid)
+        .bind(user_uuid)
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_older_than(
+        database: &crate::database::Database,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_database_query_history
+            WHERE server_database_query_history.created < $1
+            "#,
+        )
+        .bind(cutoff.naive_utc())
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn retain_latest_per_user(
+        database: &crate::database::Database,
+        keep_count: i64,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_database_query_history
+            WHERE ctid IN (
+                SELECT ctid
+                FROM (
+                    SELECT
+                        ctid,
+                        ROW_NUMBER() OVER (
+                            PARTITION BY server_database_query_history.server_database_uuid, server_database_query_history.user_uuid
+                            ORDER BY server_database_query_history.created DESC
+                        ) rn
+                    FROM server_database_query_history
+                ) sub
+                WHERE sub.rn > $1
+            )
+            "#,
+        )
+        .bind(keep_count)
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerDatabaseQueryHistory {
+    type ApiObject = ApiServerDatabaseQueryHistory;
+    type ExtraArgs<'a> = ();
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiServerDatabaseQueryHistory::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            ApiServerDatabaseQueryHistory {
+                uuid: self.uuid,
+                query: self.query,
+                read_only: self.read_only,
+                error: self.error,
+                rows: self.rows,
+                rows_affected: self.rows_affected,
+                duration_ms: self.duration_ms,
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerDatabaseQueryHistoryOptions {
+    #[garde(skip)]
+    pub server_database_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub user_uuid: uuid::Uuid,
+
+    #[garde(length(chars, min = 1, max = super::server_database::QUERY_MAX_LENGTH))]
+    pub query: String,
+    #[garde(skip)]
+    pub read_only: bool,
+    #[garde(skip)]
+    pub error: Option<String>,
+
+    #[garde(skip)]
+    pub rows: i64,
+    #[garde(skip)]
+    pub rows_affected: i64,
+    #[garde(skip)]
+    pub duration_ms: i64,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerDatabaseQueryHistory {
+    type CreateOptions<'a> = CreateServerDatabaseQueryHistoryOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerDatabaseQueryHistory>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_database_query_history");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set("server_database_uuid", options.server_database_uuid)
+            .set("user_uuid", options.user_uuid)
+            .set("query", &options.query)
+            .set("read_only", options.read_only)
+            .set("error", &options.error)
+            .set("rows", options.rows)
+            .set("rows_affected", options.rows_affected)
+            .set("duration_ms", options.duration_ms);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut query_history = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut query_history, &options, state, transaction).await?;
+
+        Ok(query_history)
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerDatabaseQueryHistory, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerDatabaseQueryHistory")]
+pub struct ApiServerDatabaseQueryHistory {
+    pub uuid: uuid::Uuid,
+
+    pub query: String,
+    pub read_only: bool,
+    pub error: Option<String>,
+
+    pub rows: i64,
+    pub rows_affected: i64,
+    pub duration_ms: i64,
+
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,385 @@
+use crate::{
+    models::{InsertQueryBuilder, UpdateQueryBuilder},
+    prelude::*,
+};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerDatabaseSavedQuery {
+    pub uuid: uuid::Uuid,
+    pub user: Fetchable<super::user::User>,
+
+    pub name: compact_str::CompactString,
+    pub query: String,
+    pub shared: bool,
+
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerDatabaseSavedQuery {
+    const NAME: &'static str = "server_database_saved_query";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_database_saved_queries.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_database_saved_queries.user_uuid",
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            (
+                "server_database_saved_queries.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "server_database_saved_queries.query",
+                compact_str::format_compact!("{prefix}query"),
+            ),
+            (
+                "server_database_saved_queries.shared",
+                compact_str::format_compact!("{prefix}shared"),
+            ),
+            (
+                "server_database_saved_queries.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            user: super::user::User::get_fetchable(
+                row.try_get(compact_str::format_compact!("{prefix}user_uuid").as_str())?,
+            ),
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            query: row.try_get(compact_str::format_compact!("{prefix}query").as_str())?,
+            shared: row.try_get(compact_str::format_compact!("{prefix}shared").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerDatabaseSavedQuery {
+    /// Only returns the saved query if it belongs to the user or has been shared.
+    pub async fn by_server_database_uuid_user_uuid_uuid(
+        database: &crate::database::Database,
+        server_database_uuid: uuid::Uuid,
+        user_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_database_saved_queries
+            WHERE server_database_saved_queries.server_database_uuid = $1
+                AND (server_database_saved_queries.user_uuid = $2 OR server_database_saved_queries.shared)
+                AND server_database_saved_queries.uuid = $3
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_uuid)
+        .bind(user_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_server_database_uuid_user_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_database_uuid: uuid::Uuid,
+        user_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_database_saved_queries
+            WHERE server_database_saved_queries.server_database_uuid = $1
+                AND (server_database_saved_queries.user_uuid = $2 OR server_database_saved_queries.shared)
+                AND ($3 IS NULL OR server_database_saved_queries.name ILIKE '%' || $3 || '%')
+            ORDER BY server_database_saved_queries.name
+            LIMIT $4 OFFSET $5
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_database_uuid)
+        .bind(user_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn count_by_server_database_uuid(
+        database: &crate::database::Database,
+        server_database_uuid: uuid::Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM server_database_saved_queries
+            WHERE server_database_saved_queries.server_database_uuid = $1
+            "#,
+        )
+        .bind(server_database_uuid)
+        .fetch_one(database.read())
+        .await
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerDatabaseSavedQuery {
+    type ApiObject = ApiServerDatabaseSavedQuery;
+    type ExtraArgs<'a> = &'a crate::storage::StorageUrlRetriever<'a>;
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        storage_url_retriever: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiServerDatabaseSavedQuery::init_hooks(&self, state).await?;
+
+        let user = self
+            .user
+            .fetch_cached(&state.database)
+            .await?
+            .into_api_object(state, storage_url_retriever)
+            .await?;
+
+        let api_object = finish_extendible!(
+            ApiServerDatabaseSavedQuery {
+                uuid: self.uuid,
+                user,
+                name: self.name,
+                query: self.query,
+                shared: self.shared,
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerDatabaseSavedQueryOptions {
+    #[garde(skip)]
+    pub server_database_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub user_uuid: uuid::Uuid,
+
+    #[garde(length(chars, min = 1, max = 63))]
+    #[schema(min_length = 1, max_length = 63)]
+    pub name: compact_str::CompactString,
+    #[garde(length(chars, min = 1, max = super::server_database::QUERY_MAX_LENGTH))]
+    #[schema(min_length = 1, max_length = 65535)]
+    pub query: String,
+    #[garde(skip)]
+    pub shared: bool,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerDatabaseSavedQuery {
+    type CreateOptions<'a> = CreateServerDatabaseSavedQueryOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerDatabaseSavedQuery>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_database_saved_queries");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set("server_database_uuid", options.server_database_uuid)
+            .set("user_uuid", options.user_uuid)
+            .set("name", &options.name)
+            .set("query", &options.query)
+            .set("shared", options.shared);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut saved_query = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut saved_query, &options, state, transaction).await?;
+
+        Ok(saved_query)
+    }
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Default)]
+pub struct UpdateServerDatabaseSavedQueryOptions {
+    #[garde(length(chars, min = 1, max = 63))]
+    #[schema(min_length = 1, max_length = 63, value_type = String)]
+    pub name: Option<compact_str::CompactString>,
+    #[garde(length(chars, min = 1, max = super::server_database::QUERY_MAX_LENGTH))]
+    #[schema(min_length = 1, max_length = 65535)]
+    pub query: Option<String>,
+    #[garde(skip)]
+    pub shared: Option<bool>,
+}
+
+#[async_trait::async_trait]
+impl UpdatableModel for ServerDatabaseSavedQuery {
+    type UpdateOptions = UpdateServerDatabaseSavedQueryOptions;
+
+    fn get_update_handlers() -> &'static LazyLock<UpdateHandlerList<Self>> {
+        static UPDATE_LISTENERS: LazyLock<UpdateHandlerList<ServerDatabaseSavedQuery>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &UPDATE_LISTENERS
+    }
+
+    async fn update_with_transaction(
+        &mut self,
+        state: &crate::State,
+        mut options: Self::UpdateOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = UpdateQueryBuilder::new("server_database_saved_queries");
+
+        self.run_update_handlers(&mut options, &mut query_builder, state, transaction)
+            .await?;
+
+        query_builder
+            .set("name", options.name.as_ref())
+            .set("query", options.query.as_ref())
+            .set("shared", options.shared)
+            .where_eq("uuid", self.uuid);
+
+        query_builder.execute(&mut **transaction).await?;
+
+        if let Some(name) = options.name {
+            self.name = name;
+        }
+        if let Some(query) = options.query {
+            self.query = query;
+        }
+        if let Some(shared) = options.shared {
+            self.shared = shared;
+        }
+
+        self.run_after_update_handlers(state, transaction).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for ServerDatabaseSavedQuery {
+    type DeleteOptions = ();
+
+    fn get_delete_handlers() -> &'static LazyLock<DeleteHandlerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteHandlerList<ServerDatabaseSavedQuery>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete_with_transaction(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), anyhow::Error> {
+        self.run_delete_handlers(&options, state, transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM server_database_saved_queries
+            WHERE server_database_saved_queries.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut **transaction)
+        .await?;
+
+        self.run_after_delete_handlers(&options, state, transaction)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerDatabaseSavedQuery, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerDatabaseSavedQuery")]
+pub struct ApiServerDatabaseSavedQuery {
+    pub uuid: uuid::Uuid,
+    pub user: super::user::ApiUser,
+
+    pub name: compact_str::CompactString,
+    pub query: String,
+    pub shared: bool,
+
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,394 @@
+use crate::{models::InsertQueryBuilder, prelude::*};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Debug, ToSchema, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerDeploymentStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerDeployment {
+    pub uuid: uuid::Uuid,
+    pub server_deployment_source_uuid: uuid::Uuid,
+    pub user: Option<Fetchable<super::user::User>>,
+
+    pub webhook: bool,
+    pub commit_hash: Option<compact_str::CompactString>,
+    pub commit_message: Option<String>,
+    pub files: i32,
+    pub error: Option<String>,
+
+    pub completed: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerDeployment {
+    const NAME: &'static str = "server_deployment";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_deployments.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_deployments.server_deployment_source_uuid",
+                compact_str::format_compact!("{prefix}server_deployment_source_uuid"),
+            ),
+            (
+                "server_deployments.user_uuid",
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            (
+                "server_deployments.webhook",
+                compact_str::format_compact!("{prefix}webhook"),
+            ),
+            (
+                "server_deployments.commit_hash",
+                compact_str::format_compact!("{prefix}commit_hash"),
+            ),
+            (
+                "server_deployments.commit_message",
+                compact_str::format_compact!("{prefix}commit_message"),
+            ),
+            (
+                "server_deployments.files",
+                compact_str::format_compact!("{prefix}files"),
+            ),
+            (
+                "server_deployments.error",
+                compact_str::format_compact!("{prefix}error"),
+            ),
+            (
+                "server_deployments.completed",
+                compact_str::format_compact!("{prefix}completed"),
+            ),
+            (
+                "server_deployments.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server_deployment_source_uuid: row.try_get(
+                compact_str::format_compact!("{prefix}server_deployment_source_uuid").as_str(),
+            )?,
+            user: super::user::User::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            webhook: row.try_get(compact_str::format_compact!("{prefix}webhook").as_str())?,
+            commit_hash: row
+                .try_get(compact_str::format_compact!("{prefix}commit_hash").as_str())?,
+            commit_message: row
+                .try_get(compact_str::format_compact!("{prefix}commit_message").as_str())?,
+            files: row.try_get(compact_str::format_compact!("{prefix}files").as_str())?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            completed: row.try_get(compact_str::format_compact!("{prefix}completed").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerDeployment {
+    pub async fn by_server_deployment_source_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_deployment_source_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_deployments
+            WHERE server_deployments.server_deployment_source_uuid = $1
+            ORDER BY server_deployments.created DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_deployment_source_uuid)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn running_by_server_deployment_source_uuid(
+        database: &crate::database::Database,
+        server_deployment_source_uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_deployments
+            WHERE server_deployments.server_deployment_source_uuid = $1
+                AND server_deployments.completed IS NULL
+            ORDER BY server_deployments.created DESC
+            LIMIT 1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_deployment_source_uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn finish(
+        &mut self,
+        database: &crate::database::Database,
+        commit_hash: Option<compact_str::CompactString>,
+        commit_message: Option<String>,
+        files: i32,
+        error: Option<String>,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            UPDATE server_deployments
+            SET commit_hash = $2, commit_message = $3, files = $4, error = $5, completed = NOW()
+            WHERE server_deployments.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(&commit_hash)
+        .bind(&commit_message)
+        .bind(files)
+        .bind(&error)
+        .execute(database.write())
+        .await?;
+
+        self.commit_hash = commit_hash;
+        self.commit_message = commit_message;
+        self.files = files;
+        self.error = error;
+        self.completed = Some(chrono::Utc::now().naive_utc());
+
+        Ok(())
+    }
+
+    /// Deployments run inside the panel process, so a restart leaves them
+    /// without a completion time forever unless they are failed here.
+    pub async fn fail_stale(
+        database: &crate::database::Database,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE server_deployments
+            SET error = 'deployment was interrupted', completed = NOW()
+            WHERE server_deployments.completed IS NULL AND server_deployments.created < $1
+            "#,
+        )
+        .bind(cutoff.naive_utc())
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn retain_latest_per_source(
+        database: &crate::database::Database,
+        keep_count: i64,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_deployments
+            WHERE ctid IN (
+                SELECT ctid
+                FROM (
+                    SELECT
+                        ctid,
+                        ROW_NUMBER() OVER (
+                            PARTITION BY server_deployments.server_deployment_source_uuid
+                            ORDER BY server_deployments.created DESC
+                        ) rn
+                    FROM server_deployments
+                ) sub
+                WHERE sub.rn > $1
+            )
+            "#,
+        )
+        .bind(keep_count)
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[inline]
+    pub fn status(&self) -> ServerDeploymentStatus {
+        if self.error.is_some() {
+            ServerDeploymentStatus::Failed
+        } else if self.completed.is_some() {
+            ServerDeploymentStatus::Succeeded
+        } else {
+            ServerDeploymentStatus::Running
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerDeployment {
+    type ApiObject = ApiServerDeployment;
+    type ExtraArgs<'a> = &'a crate::storage::StorageUrlRetriever<'a>;
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        storage_url_retriever: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiServerDeployment::init_hooks(&self, state).await?;
+
+        let user = if let Some(user) = &self.user {
+            Some(
+                user.fetch_cached(&state.database)
+                    .await?
+                    .into_api_object(state, storage_url_retriever)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        let api_object = finish_extendible!(
+            ApiServerDeployment {
+                uuid: self.uuid,
+                user,
+                status: self.status(),
+                webhook: self.webhook,
+                commit_hash: self.commit_hash,
+                commit_message: self.commit_message,
+                files: self.files,
+                error: self.error,
+                completed: self.completed.map(|dt| dt.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerDeploymentOptions {
+    #[garde(skip)]
+    pub server_deployment_source_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub user_uuid: Option<uuid::Uuid>,
+    #[garde(skip)]
+    pub webhook: bool,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerDeployment {
+    type CreateOptions<'a> = CreateServerDeploymentOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerDeployment>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_deployments");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set(
+                "server_deployment_source_uuid",
+                options.server_deployment_source_uuid,
+            )
+            .set("user_uuid", options.user_uuid)
+            .set("webhook", options.webhook);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut deployment = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut deployment, &options, state, transaction).await?;
+
+        Ok(deployment)
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerDeployment, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerDeployment")]
+pub struct ApiServerDeployment {
+    pub uuid: uuid::Uuid,
+    pub user: Option<super::user::ApiUser>,
+
+    pub status: ServerDeploymentStatus,
+    pub webhook: bool,
+    pub commit_hash: Option<compact_str::CompactString>,
+    pub commit_message: Option<String>,
+    pub files: i32,
+    pub error: Option<String>,
+
+    pub completed: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
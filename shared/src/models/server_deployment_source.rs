@@ -0,0 +1,1054 @@
+use super::egg_repository::{EggRepositoryCredentials, validate_git_repository};
+use crate::{
+    models::{InsertQueryBuilder, UpdateQueryBuilder},
+    prelude::*,
+};
+use compact_str::ToCompactString;
+use garde::Validate;
+use rand::distr::SampleString;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow, prelude::Type};
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use utoipa::ToSchema;
+
+pub const WEBHOOK_TOKEN_PREFIX: &str = "c7wh_";
+pub const MAX_DEPLOYMENT_FILES: usize = 10_000;
+/// Maximum size of a cloned repository, including its git metadata.
+pub const MAX_DEPLOYMENT_BYTES: u64 = 1024 * 1024 * 1024;
+/// How long the pre-deploy power action may take before the deployment fails.
+const PRE_DEPLOY_POWER_ACTION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+fn validate_deployment_path(
+    path: &compact_str::CompactString,
+    _context: &(),
+) -> Result<(), garde::Error> {
+    if Path::new(path.as_str())
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+    {
+        return Err(garde::Error::new(
+            "path must not contain parent directories",
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(
+    type_name = "server_deployment_power_action",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+pub enum ServerDeploymentPowerAction {
+    Start,
+    Stop,
+    Restart,
+    Kill,
+}
+
+impl ServerDeploymentPowerAction {
+    /// Whether the server has reached the state this action leads to.
+    #[inline]
+    fn is_completed(self, state: wings_api::ServerState) -> bool {
+        match self {
+            Self::Stop | Self::Kill => matches!(state, wings_api::ServerState::Offline),
+            Self::Start | Self::Restart => matches!(state, wings_api::ServerState::Running),
+        }
+    }
+}
+
+impl From<ServerDeploymentPowerAction> for wings_api::ServerPowerAction {
+    fn from(value: ServerDeploymentPowerAction) -> Self {
+        match value {
+            ServerDeploymentPowerAction::Start => Self::Start,
+            ServerDeploymentPowerAction::Stop => Self::Stop,
+            ServerDeploymentPowerAction::Restart => Self::Restart,
+            ServerDeploymentPowerAction::Kill => Self::Kill,
+        }
+    }
+}
+
+/// Total size of the files below `path`, without following symlinks.
+fn directory_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => directory_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+struct CheckedOutTree {
+    _temp_dir: tempfile::TempDir,
+    filesystem: crate::cap::CapFilesystem,
+    base: PathBuf,
+    files: Vec<PathBuf>,
+    commit_hash: compact_str::CompactString,
+    commit_message: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerDeploymentSource {
+    pub uuid: uuid::Uuid,
+    pub server_uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub git_repository: compact_str::CompactString,
+    pub branch: Option<compact_str::CompactString>,
+    pub subdirectory: Option<compact_str::CompactString>,
+    pub target_path: compact_str::CompactString,
+    pub credentials: EggRepositoryCredentials,
+
+    pub pre_deploy_power_action: Option<ServerDeploymentPowerAction>,
+    pub post_deploy_power_action: Option<ServerDeploymentPowerAction>,
+
+    pub last_deployed: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerDeploymentSource {
+    const NAME: &'static str = "server_deployment_source";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_deployment_sources.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_deployment_sources.server_uuid",
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            (
+                "server_deployment_sources.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "server_deployment_sources.git_repository",
+                compact_str::format_compact!("{prefix}git_repository"),
+            ),
+            (
+                "server_deployment_sources.branch",
+                compact_str::format_compact!("{prefix}branch"),
+            ),
+            (
+                "server_deployment_sources.subdirectory",
+                compact_str::format_compact!("{prefix}subdirectory"),
+            ),
+            (
+                "server_deployment_sources.target_path",
+                compact_str::format_compact!("{prefix}target_path"),
+            ),
+            (
+                "server_deployment_sources.credentials",
+                compact_str::format_compact!("{prefix}credentials"),
+            ),
+            (
+                "server_deployment_sources.pre_deploy_power_action",
+                compact_str::format_compact!("{prefix}pre_deploy_power_action"),
+            ),
+            (
+                "server_deployment_sources.post_deploy_power_action",
+                compact_str::format_compact!("{prefix}post_deploy_power_action"),
+            ),
+            (
+                "server_deployment_sources.last_deployed",
+                compact_str::format_compact!("{prefix}last_deployed"),
+            ),
+            (
+                "server_deployment_sources.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}server_uuid").as_str())?,
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            git_repository: row
+                .try_get(compact_str::format_compact!("{prefix}git_repository").as_str())?,
+            branch: row.try_get(compact_str::format_compact!("{prefix}branch").as_str())?,
+            subdirectory: row
+                .try_get(compact_str::format_compact!("{prefix}subdirectory").as_str())?,
+            target_path: row
+                .try_get(compact_str::format_compact!("{prefix}target_path").as_str())?,
+            credentials: serde_json::from_value(
+                row.try_get(compact_str::format_compact!("{prefix}credentials").as_str())?,
+            )?,
+            pre_deploy_power_action: row.try_get(
+                compact_str::format_compact!("{prefix}pre_deploy_power_action").as_str(),
+            )?,
+            post_deploy_power_action: row.try_get(
+                compact_str::format_compact!("{prefix}post_deploy_power_action").as_str(),
+            )?,
+            last_deployed: row
+                .try_get(compact_str::format_compact!("{prefix}last_deployed").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerDeploymentSource {
+    pub async fn by_server_uuid_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_deployment_sources
+            WHERE server_deployment_sources.server_uuid = $1 AND server_deployment_sources.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_server_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_deployment_sources
+            WHERE server_deployment_sources.server_uuid = $1
+                AND ($2 IS NULL OR server_deployment_sources.name ILIKE '%' || $2 || '%')
+            ORDER BY server_deployment_sources.created
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn count_by_server_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM server_deployment_sources
+            WHERE server_deployment_sources.server_uuid = $1
+            "#,
+        )
+        .bind(server_uuid)
+        .fetch_one(database.read())
+        .await
+    }
+
+    pub async fn verify_webhook_token(
+        &self,
+        database: &crate::database::Database,
+        token: &str,
+    ) -> Result<bool, crate::database::DatabaseError> {
+        let valid: Option<bool> = sqlx::query_scalar(
+            r#"
+            SELECT server_deployment_sources.webhook_token = crypt($2, server_deployment_sources.webhook_token)
+            FROM server_deployment_sources
+            WHERE server_deployment_sources.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(token)
+        .fetch_optional(database.read())
+        .await?;
+
+        Ok(valid.unwrap_or(false))
+    }
+
+    pub async fn rotate_webhook_token(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<String, crate::database::DatabaseError> {
+        let token = format!(
+            "{WEBHOOK_TOKEN_PREFIX}{}",
+            rand::distr::Alphanumeric.sample_string(&mut rand::rng(), 43)
+        );
+
+        sqlx::query(
+            r#"
+            UPDATE server_deployment_sources
+            SET webhook_token = crypt($2, gen_salt('bf', 12))
+            WHERE server_deployment_sources.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(&token)
+        .execute(database.write())
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Whether a push to `reference` (as sent by git hosting webhooks, e.g.
+    /// `refs/heads/main`) should trigger this source.
+    pub fn matches_reference(&self, reference: &str) -> bool {
+        match &self.branch {
+            Some(branch) => {
+                reference == branch || reference.strip_prefix("refs/heads/") == Some(branch)
+            }
+            None => true,
+        }
+    }
+
+    /// Records a new deployment and runs it in the background, the returned
+    /// deployment is still running.
+    pub async fn deploy(
+        &self,
+        state: &crate::State,
+        user_uuid: Option<uuid::Uuid>,
+        webhook: bool,
+    ) -> Result<super::server_deployment::ServerDeployment, crate::database::DatabaseError> {
+        let deployment = super::server_deployment::ServerDeployment::create(
+            state,
+            super::server_deployment::CreateServerDeploymentOptions {
+                server_deployment_source_uuid: self.uuid,
+                user_uuid,
+                webhook,
+            },
+        )
+        .await?;
+
+        tokio::spawn({
+            let state = Arc::clone(state);
+            let source = self.clone();
+            let mut deployment = deployment.clone();
+
+            async move {
+                let result = source.run(&state, user_uuid).await;
+
+                let result = match result {
+                    Ok(tree) => {
+                        deployment
+                            .finish(
+                                &state.database,
+                                Some(tree.0),
+                                Some(tree.1),
+                                tree.2 as i32,
+                                None,
+                            )
+                            .await
+                    }
+                    Err(err) => {
+                        tracing::warn!(
+                            source = %source.uuid,
+                            deployment = %deployment.uuid,
+                            "deployment failed: {:#?}",
+                            err
+                        );
+
+                        deployment
+                            .finish(&state.database, None, None, 0, Some(err.to_string()))
+                            .await
+                    }
+                };
+                if let Err(err) = result {
+                    tracing::error!(
+                        deployment = %deployment.uuid,
+                        "failed to record deployment result: {:#?}",
+                        err
+                    );
+                }
+
+                if let Err(err) = sqlx::query(
+                    r#"
+                    UPDATE server_deployment_sources
+                    SET last_deployed = NOW()
+                    WHERE server_deployment_sources.uuid = $1
+                    "#,
+                )
+                .bind(source.uuid)
+                .execute(state.database.write())
+                .await
+                {
+                    tracing::error!(
+                        source = %source.uuid,
+                        "failed to update deployment source: {:#?}",
+                        err
+                    );
+                }
+            }
+        });
+
+        Ok(deployment)
+    }
+
+    async fn run(
+        &self,
+        state: &crate::State,
+        user_uuid: Option<uuid::Uuid>,
+    ) -> Result<(compact_str::CompactString, String, usize), anyhow::Error> {
+        let server = super::server::Server::by_uuid(&state.database, self.server_uuid).await?;
+        let client = server
+            .node
+            .fetch_cached(&state.database)
+            .await?
+            .api_client(&state.database)
+            .await?;
+
+        let tree = self.checkout(state).await?;
+
+        if let Some(action) = self.pre_deploy_power_action {
+            client
+                .post_servers_server_power(
+                    server.uuid,
+                    &wings_api::servers_server_power::post::RequestBody {
+                        action: action.into(),
+                        wait_seconds: None,
+                    },
+                )
+                .await?;
+
+            // wings only queues the action, files may not be written while the server still runs
+            let started = std::time::Instant::now();
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+                let state = client.get_servers_server(server.uuid).await?.state;
+                if action.is_completed(state) {
+                    break;
+                }
+
+                if started.elapsed() >= PRE_DEPLOY_POWER_ACTION_TIMEOUT {
+                    return Err(crate::response::DisplayError::new(format!(
+                        "server did not finish its pre-deploy power action within {} seconds",
+                        PRE_DEPLOY_POWER_ACTION_TIMEOUT.as_secs()
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        let target_path = Path::new(self.target_path.as_str());
+        for file in &tree.files {
+            let reader = tree.filesystem.async_open(tree.base.join(file)).await?;
+
+            client
+                .post_servers_server_files_write(
+                    server.uuid,
+                    wings_api::client::AsyncRequestReader::new(reader),
+                    &wings_api::servers_server_files_write::post::Query {
+                        file: Some(target_path.join(file).to_string_lossy().into()),
+                        user: user_uuid,
+                        ..Default::default()
+                    },
+                )
+                .await?;
+        }
+
+        if let Some(action) = self.post_deploy_power_action {
+            client
+                .post_servers_server_power(
+                    server.uuid,
+                    &wings_api::servers_server_power::post::RequestBody {
+                        action: action.into(),
+                        wait_seconds: None,
+                    },
+                )
+                .await?;
+        }
+
+        Ok((tree.commit_hash, tree.commit_message, tree.files.len()))
+    }
+
+    async fn checkout(&self, state: &crate::State) -> Result<CheckedOutTree, anyhow::Error> {
+        let git_repository = self.git_repository.clone();
+        let branch = self.branch.clone();
+        let subdirectory = self.subdirectory.clone();
+
+        let mut credentials = self.credentials.clone();
+        credentials.decrypt(&state.database).await?;
+
+        let url = gix::url::parse(git_repository.as_str())?;
+        crate::git::resolve_addresses(&state.env, &url).await?;
+
+        let env = Arc::clone(&state.env);
+
+        tokio::task::spawn_blocking(move || -> Result<CheckedOutTree, anyhow::Error> {
+            let temp_dir = tempfile::tempdir()?;
+
+            let mut prepare_fetch = gix::clone::PrepareFetch::new(
+                url.clone(),
+                temp_dir.path(),
+                gix::create::Kind::WithWorktree,
+                Default::default(),
+                gix::open::Options::default().config_overrides(["credential.helper="]),
+            )?
+            .with_shallow(gix::remote::fetch::Shallow::DepthAtRemote(
+                std::num::NonZeroU32::MIN,
+            ))
+            .configure_connection(
+                crate::git::GitCredentials::from(credentials)
+                    .into_connection_configurator(env, url),
+            );
+
+            if let Some(branch) = &branch {
+                prepare_fetch = prepare_fetch.with_ref_name(Some(branch.as_str()))?;
+            }
+
+            // the size of a fetch is not known upfront, so the clone is watched while it grows
+            let interrupt = AtomicBool::new(false);
+            let exceeded = AtomicBool::new(false);
+            let finished = AtomicBool::new(false);
+            let cloned = std::thread::scope(|scope| {
+                scope.spawn(|| {
+                    while !finished.load(Ordering::Relaxed) {
+                        if directory_size(temp_dir.path()) > MAX_DEPLOYMENT_BYTES {
+                            exceeded.store(true, Ordering::Relaxed);
+                            interrupt.store(true, Ordering::Relaxed);
+                            break;
+                        }
+
+                        std::thread::sleep(std::time::Duration::from_millis(250));
+                    }
+                });
+
+                let cloned = (|| -> Result<_, anyhow::Error> {
+                    let (mut prepare_checkout, _) =
+                        prepare_fetch.fetch_then_checkout(gix::progress::Discard, &interrupt)?;
+                    let (repository, _) =
+                        prepare_checkout.main_worktree(gix::progress::Discard, &interrupt)?;
+
+                    Ok((prepare_checkout, repository))
+                })();
+                finished.store(true, Ordering::Relaxed);
+
+                cloned
+            });
+
+            if exceeded.load(Ordering::Relaxed) {
+                return Err(crate::response::DisplayError::new(format!(
+                    "repository is larger than {MAX_DEPLOYMENT_BYTES} bytes"
+                ))
+                .into());
+            }
+            let (prepare_checkout, repository) = cloned?;
+
+            let commit = repository.head_commit()?;
+            let commit_hash = commit.id.to_compact_string();
+            let commit_message = commit.message()?.summary().to_string();
+
+            tracing::info!(
+                "cloned deployment source {} at {} to temporary directory",
+                git_repository,
+                commit_hash
+            );
+
+            let filesystem = crate::cap::CapFilesystem::new(temp_dir.path().to_path_buf())?;
+            let subdirectory = subdirectory.as_deref().unwrap_or(".");
+
+            if !filesystem.metadata(subdirectory).is_ok_and(|m| m.is_dir()) {
+                return Err(crate::response::DisplayError::new(format!(
+                    "subdirectory {subdirectory} does not exist in the repository"
+                ))
+                .into());
+            }
+
+            let base = filesystem.relative_path(Path::new(subdirectory));
+
+            let mut files = Vec::new();
+            let mut total_size = 0;
+            let mut walker = filesystem.walk_dir(subdirectory)?;
+            while let Some(entry) = walker.next_entry() {
+                let (is_dir, entry) = entry?;
+                if is_dir {
+                    continue;
+                }
+
+                let Ok(relative) = entry.strip_prefix(&base) else {
+                    continue;
+                };
+                if relative
+                    .components()
+                    .next()
+                    .is_some_and(|component| component.as_os_str() == ".git")
+                {
+                    continue;
+                }
+
+                let Ok(metadata) = filesystem.metadata(&entry) else {
+                    continue;
+                };
+                if !metadata.is_file() {
+                    continue;
+                }
+
+                total_size += metadata.len();
+                if total_size > MAX_DEPLOYMENT_BYTES {
+                    return Err(crate::response::DisplayError::new(format!(
+                        "repository is larger than {MAX_DEPLOYMENT_BYTES} bytes"
+                    ))
+                    .into());
+                }
+
+                if files.len() >= MAX_DEPLOYMENT_FILES {
+                    return Err(crate::response::DisplayError::new(format!(
+                        "repository contains more than {MAX_DEPLOYMENT_FILES} files"
+                    ))
+                    .into());
+                }
+
+                files.push(relative.to_path_buf());
+            }
+
+            drop(prepare_checkout);
+
+            Ok(CheckedOutTree {
+                _temp_dir: temp_dir,
+                filesystem,
+                base,
+                files,
+                commit_hash,
+                commit_message,
+            })
+        })
+        .await?
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerDeploymentSource {
+    type ApiObject = ApiServerDeploymentSource;
+    type ExtraArgs<'a> = ();
+
+    async fn into_api_object<'a>(
+        mut self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiServerDeploymentSource::init_hooks(&self, state).await?;
+
+        self.credentials.censor();
+
+        let settings = state.settings.get().await?;
+        let webhook_url = format!(
+            "{}/api/deployment-sources/{}/webhook",
+            settings.app.url.trim_end_matches('/'),
+            self.uuid
+        );
+        drop(settings);
+
+        let api_object = finish_extendible!(
+            ApiServerDeploymentSource {
+                uuid: self.uuid,
+                name: self.name,
+                git_repository: self.git_repository,
+                branch: self.branch,
+                subdirectory: self.subdirectory,
+                target_path: self.target_path,
+                credentials: self.credentials,
+                pre_deploy_power_action: self.pre_deploy_power_action,
+                post_deploy_power_action: self.post_deploy_power_action,
+                webhook_url,
+                last_deployed: self.last_deployed.map(|dt| dt.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerDeploymentSourceOptions {
+    #[garde(skip)]
+    pub server_uuid: uuid::Uuid,
+
+    #[garde(length(chars, min = 1, max = 255))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub name: compact_str::CompactString,
+    #[garde(custom(validate_git_repository))]
+    #[schema(example = "https://github.com/example/repo.git", format = "uri")]
+    pub git_repository: compact_str::CompactString,
+    #[garde(length(chars, min = 1, max = 255))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub branch: Option<compact_str::CompactString>,
+    #[garde(inner(length(chars, min = 1, max = 1024), custom(validate_deployment_path)))]
+    #[schema(min_length = 1, max_length = 1024)]
+    pub subdirectory: Option<compact_str::CompactString>,
+    #[garde(length(chars, min = 1, max = 1024), custom(validate_deployment_path))]
+    #[schema(min_length = 1, max_length = 1024)]
+    pub target_path: compact_str::CompactString,
+    #[garde(dive)]
+    pub credentials: Option<EggRepositoryCredentials>,
+
+    #[garde(skip)]
+    pub pre_deploy_power_action: Option<ServerDeploymentPowerAction>,
+    #[garde(skip)]
+    pub post_deploy_power_action: Option<ServerDeploymentPowerAction>,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerDeploymentSource {
+    type CreateOptions<'a> = CreateServerDeploymentSourceOptions;
+    type CreateResult = (String, Self);
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerDeploymentSource>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self::CreateResult, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let webhook_token = format!(
+            "{WEBHOOK_TOKEN_PREFIX}{}",
+            rand::distr::Alphanumeric.sample_string(&mut rand::rng(), 43)
+        );
+
+        let mut query_builder = InsertQueryBuilder::new("server_deployment_sources");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        let credentials = options
+            .credentials
+            .get_or_insert(EggRepositoryCredentials::None);
+        credentials.validate_scheme_for_url(&options.git_repository)?;
+        credentials.validate_key_material()?;
+        credentials.encrypt(&state.database).await?;
+
+        query_builder
+            .set("server_uuid", options.server_uuid)
+            .set("name", &options.name)
+            .set("git_repository", &options.git_repository)
+            .set("branch", &options.branch)
+            .set("subdirectory", &options.subdirectory)
+            .set("target_path", &options.target_path)
+            .set("credentials", serde_json::to_value(&credentials)?)
+            .set("pre_deploy_power_action", options.pre_deploy_power_action)
+            .set("post_deploy_power_action", options.post_deploy_power_action)
+            .set_expr(
+                "webhook_token",
+                "crypt($1, gen_salt('bf', 12))",
+                vec![webhook_token.as_str()],
+            );
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let deployment_source = Self::map(None, &row)?;
+
+        let mut result = (webhook_token, deployment_source);
+
+        Self::run_after_create_handlers(&mut result, &options, state, transaction).await?;
+
+        Ok(result)
+    }
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Clone, Default)]
+pub struct UpdateServerDeploymentSourceOptions {
+    #[garde(length(chars, min = 1, max = 255))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub name: Option<compact_str::CompactString>,
+    #[garde(inner(custom(validate_git_repository)))]
+    #[schema(example = "https://github.com/example/repo.git", format = "uri")]
+    pub git_repository: Option<compact_str::CompactString>,
+    #[garde(inner(inner(length(chars, min = 1, max = 255))))]
+    #[schema(min_length = 1, max_length = 255)]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    pub branch: Option<Option<compact_str::CompactString>>,
+    #[garde(inner(inner(length(chars, min = 1, max = 1024), custom(validate_deployment_path))))]
+    #[schema(min_length = 1, max_length = 1024)]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    pub subdirectory: Option<Option<compact_str::CompactString>>,
+    #[garde(inner(length(chars, min = 1, max = 1024), custom(validate_deployment_path)))]
+    #[schema(min_length = 1, max_length = 1024)]
+    pub target_path: Option<compact_str::CompactString>,
+    #[garde(dive)]
+    pub credentials: Option<EggRepositoryCredentials>,
+
+    #[garde(skip)]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    pub pre_deploy_power_action: Option<Option<ServerDeploymentPowerAction>>,
+    #[garde(skip)]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    pub post_deploy_power_action: Option<Option<ServerDeploymentPowerAction>>,
+}
+
+#[async_trait::async_trait]
+impl UpdatableModel for ServerDeploymentSource {
+    type UpdateOptions = UpdateServerDeploymentSourceOptions;
+
+    fn get_update_handlers() -> &'static LazyLock<UpdateHandlerList<Self>> {
+        static UPDATE_LISTENERS: LazyLock<UpdateHandlerList<ServerDeploymentSource>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &UPDATE_LISTENERS
+    }
+
+    async fn update_with_transaction(
+        &mut self,
+        state: &crate::State,
+        mut options: Self::UpdateOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = UpdateQueryBuilder::new("server_deployment_sources");
+
+        self.run_update_handlers(&mut options, &mut query_builder, state, transaction)
+            .await?;
+
+        let git_repository = options
+            .git_repository
+            .as_deref()
+            .unwrap_or(&self.git_repository);
+
+        match &mut options.credentials {
+            Some(credentials) => {
+                credentials.validate_scheme_for_url(git_repository)?;
+                credentials.validate_key_material()?;
+                credentials.encrypt(&state.database).await?;
+            }
+            None => self.credentials.validate_scheme_for_url(git_repository)?,
+        }
+
+        query_builder
+            .set("name", options.name.as_ref())
+            .set("git_repository", options.git_repository.as_ref())
+            .set("branch", options.branch.as_ref().map(|b| b.as_ref()))
+            .set(
+                "subdirectory",
+                options.subdirectory.as_ref().map(|s| s.as_ref()),
+            )
+            .set("target_path", options.target_path.as_ref())
+            .set(
+                "credentials",
+                options
+                    .credentials
+                    .as_ref()
+                    .map(serde_json::to_value)
+                    .transpose()?,
+            )
+            .set("pre_deploy_power_action", options.pre_deploy_power_action)
+            .set("post_deploy_power_action", options.post_deploy_power_action)
+            .where_eq("uuid", self.uuid);
+
+        query_builder.execute(&mut **transaction).await?;
+
+        if let Some(name) = options.name {
+            self.name = name;
+        }
+        if let Some(git_repository) = options.git_repository {
+            self.git_repository = git_repository;
+        }
+        if let Some(branch) = options.branch {
+            self.branch = branch;
+        }
+        if let Some(subdirectory) = options.subdirectory {
+            self.subdirectory = subdirectory;
+        }
+        if let Some(target_path) = options.target_path {
+            self.target_path = target_path;
+        }
+        if let Some(credentials) = options.credentials {
+            self.credentials = credentials;
+        }
+        if let Some(pre_deploy_power_action) = options.pre_deploy_power_action {
+            self.pre_deploy_power_action = pre_deploy_power_action;
+        }
+        if let Some(post_deploy_power_action) = options.post_deploy_power_action {
+            self.post_deploy_power_action = post_deploy_power_action;
+        }
+
+        self.run_after_update_handlers(state, transaction).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ByUuid for ServerDeploymentSource {
+    async fn by_uuid(
+        database: &crate::database::Database,
+        uuid: uuid::Uuid,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_deployment_sources
+            WHERE server_deployment_sources.uuid = $1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(uuid)
+        .fetch_one(database.read())
+        .await?;
+
+        Self::map(None, &row)
+    }
+
+    async fn by_uuid_with_transaction(
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        uuid: uuid::Uuid,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_deployment_sources
+            WHERE server_deployment_sources.uuid = $1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(uuid)
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        Self::map(None, &row)
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for ServerDeploymentSource {
+    type DeleteOptions = ();
+
+    fn get_delete_handlers() -> &'static LazyLock<DeleteHandlerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteHandlerList<ServerDeploymentSource>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete_with_transaction(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), anyhow::Error> {
+        self.run_delete_handlers(&options, state, transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM server_deployment_sources
+            WHERE server_deployment_sources.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut **transaction)
+        .await?;
+
+        self.run_after_delete_handlers(&options, state, transaction)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerDeploymentSource, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerDeploymentSource")]
+pub struct ApiServerDeploymentSource {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub git_repository: compact_str::CompactString,
+    pub branch: Option<compact_str::CompactString>,
+    pub subdirectory: Option<compact_str::CompactString>,
+    pub target_path: compact_str::CompactString,
+    pub credentials: EggRepositoryCredentials,
+
+    pub pre_deploy_power_action: Option<ServerDeploymentPowerAction>,
+    pub post_deploy_power_action: Option<ServerDeploymentPowerAction>,
+
+    #[schema(format = "uri")]
+    pub webhook_url: String,
+
+    pub last_deployed: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
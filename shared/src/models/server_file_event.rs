@@ -0,0 +1,414 @@
+use crate::{models::InsertQueryBuilder, prelude::*};
+use compact_str::ToCompactString;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, Type, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(
+    type_name = "server_file_event_action",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+pub enum ServerFileEventAction {
+    Create,
+    Write,
+    Rename,
+    Delete,
+    Chmod,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(
+    type_name = "server_file_event_source",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+pub enum ServerFileEventSource {
+    Panel,
+    Sftp,
+}
+
+#[derive(ToSchema, Validate, Deserialize)]
+pub struct ServerFileEventFilters {
+    #[garde(skip)]
+    pub user: Option<uuid::Uuid>,
+    #[garde(skip)]
+    pub action: Option<ServerFileEventAction>,
+    #[garde(skip)]
+    pub source: Option<ServerFileEventSource>,
+    #[garde(length(chars, min = 1, max = 1024))]
+    #[schema(min_length = 1, max_length = 1024)]
+    pub path: Option<compact_str::CompactString>,
+
+    #[garde(skip)]
+    pub start: Option<chrono::DateTime<chrono::Utc>>,
+    #[garde(skip)]
+    pub end: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ServerFileEvent {
+    pub uuid: uuid::Uuid,
+    pub user: Option<super::user::User>,
+
+    pub action: ServerFileEventAction,
+    pub source: ServerFileEventSource,
+    pub path: compact_str::CompactString,
+    pub new_path: Option<compact_str::CompactString>,
+    pub mode: Option<i32>,
+    pub ip: Option<sqlx::types::ipnetwork::IpNetwork>,
+
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerFileEvent {
+    const NAME: &'static str = "server_file_event";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        let mut columns = BTreeMap::from([
+            (
+                "server_file_events.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_file_events.action",
+                compact_str::format_compact!("{prefix}action"),
+            ),
+            (
+                "server_file_events.source",
+                compact_str::format_compact!("{prefix}source"),
+            ),
+            (
+                "server_file_events.path",
+                compact_str::format_compact!("{prefix}path"),
+            ),
+            (
+                "server_file_events.new_path",
+                compact_str::format_compact!("{prefix}new_path"),
+            ),
+            (
+                "server_file_events.mode",
+                compact_str::format_compact!("{prefix}mode"),
+            ),
+            (
+                "server_file_events.ip",
+                compact_str::format_compact!("{prefix}ip"),
+            ),
+            (
+                "server_file_events.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ]);
+
+        columns.extend(super::user::User::base_columns(Some("user_")));
+
+        columns
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            user: if row
+                .try_get::<uuid::Uuid, _>("user_uuid".to_string().as_str())
+                .is_ok()
+            {
+                Some(super::user::User::map(Some("user_"), row)?)
+            } else {
+                None
+            },
+            action: row.try_get(compact_str::format_compact!("{prefix}action").as_str())?,
+            source: row.try_get(compact_str::format_compact!("{prefix}source").as_str())?,
+            path: row.try_get(compact_str::format_compact!("{prefix}path").as_str())?,
+            new_path: row.try_get(compact_str::format_compact!("{prefix}new_path").as_str())?,
+            mode: row.try_get(compact_str::format_compact!("{prefix}mode").as_str())?,
+            ip: row.try_get(compact_str::format_compact!("{prefix}ip").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerFileEvent {
+    /// Brings paths reported by wings and entered by users into the same `/a/b` form,
+    /// so filtering on a directory matches regardless of leading or trailing slashes.
+    pub fn normalize_path(path: &str) -> compact_str::CompactString {
+        compact_str::format_compact!("/{}", path.trim_matches('/'))
+    }
+
+    /// Events on `filters.path` itself or anything inside it, including renames into
+    /// or out of it, newest first.
+    pub async fn by_server_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        filters: &ServerFileEventFilters,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let path = filters
+            .path
+            .as_deref()
+            .map(Self::normalize_path)
+            .filter(|path| path != "/");
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_file_events
+            LEFT JOIN users ON users.uuid = server_file_events.user_uuid
+            LEFT JOIN roles ON roles.uuid = users.role_uuid
+            WHERE server_file_events.server_uuid = $1
+                AND ($2 IS NULL OR server_file_events.user_uuid = $2)
+                AND ($3 IS NULL OR server_file_events.action = $3)
+                AND ($4 IS NULL OR server_file_events.source = $4)
+                AND (
+                    $5 IS NULL
+                    OR server_file_events.path = $5
+                    OR starts_with(server_file_events.path, $5 || '/')
+                    OR server_file_events.new_path = $5
+                    OR starts_with(server_file_events.new_path, $5 || '/')
+                )
+                AND ($6 IS NULL OR server_file_events.created >= $6)
+                AND ($7 IS NULL OR server_file_events.created <= $7)
+                AND ($8 IS NULL OR server_file_events.path ILIKE '%' || $8 || '%' OR server_file_events.new_path ILIKE '%' || $8 || '%' OR users.username ILIKE '%' || $8 || '%')
+            ORDER BY server_file_events.created DESC
+            LIMIT $9 OFFSET $10
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(filters.user)
+        .bind(filters.action)
+        .bind(filters.source)
+        .bind(path.as_deref())
+        .bind(filters.start.map(|dt| dt.naive_utc()))
+        .bind(filters.end.map(|dt| dt.naive_utc()))
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn delete_older_than(
+        database: &crate::database::Database,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_file_events
+            WHERE server_file_events.created < $1
+            "#,
+        )
+        .bind(cutoff.naive_utc())
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn retain_latest_events_per_server(
+        database: &crate::database::Database,
+        keep_count: i64,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_file_events
+            WHERE server_file_events.uuid IN (
+                SELECT uuid
+                FROM (
+                    SELECT
+                        uuid,
+                        ROW_NUMBER() OVER (PARTITION BY server_file_events.server_uuid ORDER BY server_file_events.created DESC) rn
+                    FROM server_file_events
+                ) sub
+                WHERE sub.rn > $1
+            )
+            "#,
+        )
+        .bind(keep_count)
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerFileEvent {
+    type ApiObject = ApiServerFileEvent;
+    type ExtraArgs<'a> = (&'a crate::storage::StorageUrlRetriever<'a>, bool);
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        (storage_url_retriever, show_ip): Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiServerFileEvent::init_hooks(&self, state).await?;
+
+        let user = if let Some(user) = self.user {
+            Some(user.into_api_object(state, storage_url_retriever).await?)
+        } else {
+            None
+        };
+
+        let api_object = finish_extendible!(
+            ApiServerFileEvent {
+                uuid: self.uuid,
+                user,
+                action: self.action,
+                source: self.source,
+                path: self.path,
+                new_path: self.new_path,
+                mode: self
+                    .mode
+                    .map(|mode| compact_str::format_compact!("{:o}", mode)),
+                ip: if show_ip {
+                    self.ip.map(|ip| ip.ip().to_compact_string())
+                } else {
+                    None
+                },
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerFileEventOptions {
+    #[garde(skip)]
+    pub server_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub user_uuid: Option<uuid::Uuid>,
+    #[garde(skip)]
+    pub action: ServerFileEventAction,
+    #[garde(skip)]
+    pub source: ServerFileEventSource,
+    #[garde(length(min = 1))]
+    #[schema(min_length = 1)]
+    pub path: compact_str::CompactString,
+    #[garde(length(min = 1))]
+    #[schema(min_length = 1)]
+    pub new_path: Option<compact_str::CompactString>,
+    #[garde(range(min = 0, max = 0o7777))]
+    #[schema(minimum = 0, maximum = 0o7777)]
+    pub mode: Option<i32>,
+    #[garde(skip)]
+    #[schema(value_type = Option<String>)]
+    pub ip: Option<sqlx::types::ipnetwork::IpNetwork>,
+
+    #[garde(skip)]
+    pub created: Option<chrono::NaiveDateTime>,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerFileEvent {
+    type CreateOptions<'a> = CreateServerFileEventOptions;
+    type CreateResult = ();
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerFileEvent>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self::CreateResult, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_file_events");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set("server_uuid", options.server_uuid)
+            .set("user_uuid", options.user_uuid)
+            .set("action", options.action)
+            .set("source", options.source)
+            .set("path", Self::normalize_path(&options.path))
+            .set(
+                "new_path",
+                options.new_path.as_deref().map(Self::normalize_path),
+            )
+            .set("mode", options.mode)
+            .set("ip", options.ip);
+
+        if let Some(created) = options.created {
+            query_builder.set("created", created);
+        }
+
+        query_builder.execute(&mut **transaction).await?;
+
+        let mut result = ();
+
+        Self::run_after_create_handlers(&mut result, &options, state, transaction).await?;
+
+        Ok(result)
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerFileEvent, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerFileEvent")]
+pub struct ApiServerFileEvent {
+    pub uuid: uuid::Uuid,
+    pub user: Option<super::user::ApiUser>,
+
+    pub action: ServerFileEventAction,
+    pub source: ServerFileEventSource,
+    pub path: compact_str::CompactString,
+    pub new_path: Option<compact_str::CompactString>,
+    pub mode: Option<compact_str::CompactString>,
+    pub ip: Option<compact_str::CompactString>,
+
+    pub created: chrono::DateTime<chrono::Utc>,
+}
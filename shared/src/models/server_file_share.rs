@@ -0,0 +1,543 @@
+use crate::{jwt::BasePayload, models::InsertQueryBuilder, prelude::*};
+use compact_str::ToCompactString;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize)]
+pub struct ServerFileShareJwt {
+    #[serde(flatten)]
+    pub base: BasePayload,
+
+    pub file_share_uuid: uuid::Uuid,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerFileShare {
+    pub uuid: uuid::Uuid,
+    pub server_uuid: uuid::Uuid,
+    pub user: Option<Fetchable<super::user::User>>,
+
+    pub path: compact_str::CompactString,
+    pub directory: bool,
+    pub has_password: bool,
+
+    pub max_downloads: Option<i32>,
+    pub downloads: i32,
+
+    pub expires: chrono::NaiveDateTime,
+    pub last_download: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerFileShare {
+    const NAME: &'static str = "server_file_share";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_file_shares.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_file_shares.server_uuid",
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            (
+                "server_file_shares.user_uuid",
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            (
+                "server_file_shares.path",
+                compact_str::format_compact!("{prefix}path"),
+            ),
+            (
+                "server_file_shares.directory",
+                compact_str::format_compact!("{prefix}directory"),
+            ),
+            (
+                "(server_file_shares.password IS NOT NULL)",
+                compact_str::format_compact!("{prefix}has_password"),
+            ),
+            (
+                "server_file_shares.max_downloads",
+                compact_str::format_compact!("{prefix}max_downloads"),
+            ),
+            (
+                "server_file_shares.downloads",
+                compact_str::format_compact!("{prefix}downloads"),
+            ),
+            (
+                "server_file_shares.expires",
+                compact_str::format_compact!("{prefix}expires"),
+            ),
+            (
+                "server_file_shares.last_download",
+                compact_str::format_compact!("{prefix}last_download"),
+            ),
+            (
+                "server_file_shares.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}server_uuid").as_str())?,
+            user: super::user::User::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            path: row.try_get(compact_str::format_compact!("{prefix}path").as_str())?,
+            directory: row.try_get(compact_str::format_compact!("{prefix}directory").as_str())?,
+            has_password: row
+                .try_get(compact_str::format_compact!("{prefix}has_password").as_str())?,
+            max_downloads: row
+                .try_get(compact_str::format_compact!("{prefix}max_downloads").as_str())?,
+            downloads: row.try_get(compact_str::format_compact!("{prefix}downloads").as_str())?,
+            expires: row.try_get(compact_str::format_compact!("{prefix}expires").as_str())?,
+            last_download: row
+                .try_get(compact_str::format_compact!("{prefix}last_download").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerFileShare {
+    pub async fn by_server_uuid_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_file_shares
+            WHERE server_file_shares.server_uuid = $1 AND server_file_shares.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_server_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_file_shares
+            WHERE server_file_shares.server_uuid = $1
+                AND ($2 IS NULL OR server_file_shares.path ILIKE '%' || $2 || '%')
+            ORDER BY server_file_shares.created DESC
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn count_by_server_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM server_file_shares
+            WHERE server_file_shares.server_uuid = $1
+            "#,
+        )
+        .bind(server_uuid)
+        .fetch_one(database.read())
+        .await
+    }
+
+    pub async fn delete_expired(
+        database: &crate::database::Database,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_file_shares
+            WHERE server_file_shares.expires < NOW()
+            "#,
+        )
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn verify_password(
+        &self,
+        database: &crate::database::Database,
+        password: Option<&str>,
+    ) -> Result<bool, crate::database::DatabaseError> {
+        if !self.has_password {
+            return Ok(true);
+        }
+
+        let Some(password) = password else {
+            return Ok(false);
+        };
+
+        let valid: Option<bool> = sqlx::query_scalar(
+            r#"
+            SELECT server_file_shares.password = crypt($2, server_file_shares.password)
+            FROM server_file_shares
+            WHERE server_file_shares.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(password)
+        .fetch_optional(database.read())
+        .await?;
+
+        Ok(valid.unwrap_or(false))
+    }
+
+    /// Atomically consumes one download from the share, returning `false` if the
+    /// share expired or its download limit was reached in the meantime.
+    pub async fn claim_download(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<bool, crate::database::DatabaseError> {
+        let downloads: Option<i32> = sqlx::query_scalar(
+            r#"
+            UPDATE server_file_shares
+            SET downloads = server_file_shares.downloads + 1, last_download = NOW()
+            WHERE server_file_shares.uuid = $1
+                AND server_file_shares.expires > NOW()
+                AND (
+                    server_file_shares.max_downloads IS NULL
+                    OR server_file_shares.downloads < server_file_shares.max_downloads
+                )
+            RETURNING server_file_shares.downloads
+            "#,
+        )
+        .bind(self.uuid)
+        .fetch_optional(database.write())
+        .await?;
+
+        match downloads {
+            Some(downloads) => {
+                self.downloads = downloads;
+                self.last_download = Some(chrono::Utc::now().naive_utc());
+
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.expires < chrono::Utc::now().naive_utc()
+            || self
+                .max_downloads
+                .is_some_and(|max_downloads| self.downloads >= max_downloads)
+    }
+
+    pub fn create_token(&self, jwt: &crate::jwt::Jwt) -> Result<String, anyhow::Error> {
+        Ok(jwt.create(&ServerFileShareJwt {
+            base: BasePayload {
+                scope: "file-share".into(),
+                issuer: "panel".into(),
+                subject: None,
+                audience: Vec::new(),
+                expiration_time: Some(self.expires.and_utc().timestamp()),
+                not_before: None,
+                issued_at: Some(self.created.and_utc().timestamp()),
+                jwt_id: self.uuid.to_compact_string(),
+            },
+            file_share_uuid: self.uuid,
+        })?)
+    }
+}
+
+#[async_trait::async_trait]
+impl ByUuid for ServerFileShare {
+    async fn by_uuid(
+        database: &crate::database::Database,
+        uuid: uuid::Uuid,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_file_shares
+            WHERE server_file_shares.uuid = $1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(uuid)
+        .fetch_one(database.read())
+        .await?;
+
+        Self::map(None, &row)
+    }
+
+    async fn by_uuid_with_transaction(
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        uuid: uuid::Uuid,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_file_shares
+            WHERE server_file_shares.uuid = $1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(uuid)
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        Self::map(None, &row)
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerFileShare {
+    type ApiObject = ApiServerFileShare;
+    type ExtraArgs<'a> = &'a crate::storage::StorageUrlRetriever<'a>;
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        storage_url_retriever: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiServerFileShare::init_hooks(&self, state).await?;
+
+        let settings = state.settings.get().await?;
+        let url = format!(
+            "{}/api/file-shares/{}",
+            settings.app.url.trim_end_matches('/'),
+            self.create_token(&state.jwt)?
+        );
+        drop(settings);
+
+        let user = if let Some(user) = &self.user {
+            Some(
+                user.fetch_cached(&state.database)
+                    .await?
+                    .into_api_object(state, storage_url_retriever)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        let api_object = finish_extendible!(
+            ApiServerFileShare {
+                uuid: self.uuid,
+                user,
+                url,
+                exhausted: self.is_exhausted(),
+                path: self.path,
+                directory: self.directory,
+                has_password: self.has_password,
+                max_downloads: self.max_downloads,
+                downloads: self.downloads,
+                expires: self.expires.and_utc(),
+                last_download: self.last_download.map(|dt| dt.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerFileShareOptions {
+    #[garde(skip)]
+    pub server_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub user_uuid: Option<uuid::Uuid>,
+
+    #[garde(length(chars, min = 1))]
+    #[schema(min_length = 1)]
+    pub path: compact_str::CompactString,
+    #[garde(skip)]
+    pub directory: bool,
+    #[garde(length(chars, min = 1, max = 512))]
+    #[schema(min_length = 1, max_length = 512)]
+    pub password: Option<compact_str::CompactString>,
+
+    #[garde(range(min = 1))]
+    #[schema(minimum = 1)]
+    pub max_downloads: Option<i32>,
+    #[garde(skip)]
+    pub expires: chrono::NaiveDateTime,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerFileShare {
+    type CreateOptions<'a> = CreateServerFileShareOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerFileShare>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_file_shares");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set("server_uuid", options.server_uuid)
+            .set("user_uuid", options.user_uuid)
+            .set("path", &options.path)
+            .set("directory", options.directory)
+            .set("max_downloads", options.max_downloads)
+            .set("expires", options.expires);
+
+        if let Some(password) = &options.password {
+            query_builder.set_expr(
+                "password",
+                "crypt($1, gen_salt('bf', 12))",
+                vec![password.as_str()],
+            );
+        }
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut file_share = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut file_share, &options, state, transaction).await?;
+
+        Ok(file_share)
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for ServerFileShare {
+    type DeleteOptions = ();
+
+    fn get_delete_handlers() -> &'static LazyLock<DeleteHandlerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteHandlerList<ServerFileShare>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete_with_transaction(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), anyhow::Error> {
+        self.run_delete_handlers(&options, state, transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM server_file_shares
+            WHERE server_file_shares.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut **transaction)
+        .await?;
+
+        self.run_after_delete_handlers(&options, state, transaction)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerFileShare, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerFileShare")]
+pub struct ApiServerFileShare {
+    pub uuid: uuid::Uuid,
+    pub user: Option<super::user::ApiUser>,
+
+    #[schema(format = "uri")]
+    pub url: String,
+    pub exhausted: bool,
+
+    pub path: compact_str::CompactString,
+    pub directory: bool,
+    pub has_password: bool,
+
+    pub max_downloads: Option<i32>,
+    pub downloads: i32,
+
+    pub expires: chrono::DateTime<chrono::Utc>,
+    pub last_download: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
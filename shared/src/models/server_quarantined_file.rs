@@ -0,0 +1,522 @@
+use crate::{jwt::BasePayload, models::InsertQueryBuilder, prelude::*};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use sqlx::{Row, Type, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "file_scan_source", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FileScanSource {
+    Upload,
+    Pull,
+    Sftp,
+}
+
+/// Token of an upload that goes through the panel to be scanned before it reaches wings.
+#[derive(Serialize, Deserialize)]
+pub struct ScannedFileUploadJwt {
+    #[serde(flatten)]
+    pub base: BasePayload,
+
+    pub server_uuid: uuid::Uuid,
+    pub user_uuid: uuid::Uuid,
+
+    pub ignored_files: Option<Vec<compact_str::CompactString>>,
+}
+
+#[derive(ToSchema, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileScanVerdict {
+    Clean,
+    Infected,
+    Unscanned,
+}
+
+#[derive(Serialize)]
+pub struct FileScanReport {
+    pub file: compact_str::CompactString,
+    pub source: FileScanSource,
+    pub policy: super::location::FileScanPolicy,
+    pub verdict: FileScanVerdict,
+    pub signature: Option<compact_str::CompactString>,
+    pub quarantined: Option<uuid::Uuid>,
+}
+
+impl FileScanReport {
+    pub fn unscanned(
+        file: impl Into<compact_str::CompactString>,
+        source: FileScanSource,
+        policy: super::location::FileScanPolicy,
+    ) -> Self {
+        Self {
+            file: file.into(),
+            source,
+            policy,
+            verdict: FileScanVerdict::Unscanned,
+            signature: None,
+            quarantined: None,
+        }
+    }
+
+    #[inline]
+    pub fn is_quarantined(&self) -> bool {
+        self.quarantined.is_some()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerQuarantinedFile {
+    pub uuid: uuid::Uuid,
+    pub server_uuid: uuid::Uuid,
+    pub user: Option<Fetchable<super::user::User>>,
+
+    pub source: FileScanSource,
+    pub path: compact_str::CompactString,
+    pub size: i64,
+    pub signature: compact_str::CompactString,
+    pub sha256: Option<compact_str::CompactString>,
+
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerQuarantinedFile {
+    const NAME: &'static str = "server_quarantined_file";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_quarantined_files.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_quarantined_files.server_uuid",
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            (
+                "server_quarantined_files.user_uuid",
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            (
+                "server_quarantined_files.source",
+                compact_str::format_compact!("{prefix}source"),
+            ),
+            (
+                "server_quarantined_files.path",
+                compact_str::format_compact!("{prefix}path"),
+            ),
+            (
+                "server_quarantined_files.size",
+                compact_str::format_compact!("{prefix}size"),
+            ),
+            (
+                "server_quarantined_files.signature",
+                compact_str::format_compact!("{prefix}signature"),
+            ),
+            (
+                "server_quarantined_files.sha256",
+                compact_str::format_compact!("{prefix}sha256"),
+            ),
+            (
+                "server_quarantined_files.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}server_uuid").as_str())?,
+            user: super::user::User::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            source: row.try_get(compact_str::format_compact!("{prefix}source").as_str())?,
+            path: row.try_get(compact_str::format_compact!("{prefix}path").as_str())?,
+            size: row.try_get(compact_str::format_compact!("{prefix}size").as_str())?,
+            signature: row.try_get(compact_str::format_compact!("{prefix}signature").as_str())?,
+            sha256: row.try_get(compact_str::format_compact!("{prefix}sha256").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerQuarantinedFile {
+    /// Returns the scan policy of `node` along with the clamd address and the maximum size of
+    /// a scanned file, or `None` if files on the node are not scanned.
+    pub async fn scan_policy(
+        state: &crate::State,
+        node: &super::node::Node,
+    ) -> Result<
+        Option<(
+            super::location::FileScanPolicy,
+            compact_str::CompactString,
+            u64,
+        )>,
+        anyhow::Error,
+    > {
+        let policy = node.effective_file_scan_policy();
+        if policy == super::location::FileScanPolicy::Off {
+            return Ok(None);
+        }
+
+        let settings = state.settings.get().await?;
+        let Some(address) = settings.server.file_scan_clamd_address.clone() else {
+            return Ok(None);
+        };
+        let max_size = settings.server.max_file_scan_size;
+        drop(settings);
+
+        Ok(Some((policy, address, max_size)))
+    }
+
+    /// Scans `data` with clamd according to the scan policy of `node`.
+    ///
+    /// Returns `None` if the node does not scan files. Infected files are recorded by their
+    /// hash when the policy blocks them, their contents are never kept, the caller is
+    /// responsible for keeping them off the server.
+    pub async fn scan(
+        state: &crate::State,
+        node: &super::node::Node,
+        server_uuid: uuid::Uuid,
+        user_uuid: Option<uuid::Uuid>,
+        source: FileScanSource,
+        file: &str,
+        data: &[u8],
+    ) -> Result<Option<FileScanReport>, anyhow::Error> {
+        let Some((policy, address, _)) = Self::scan_policy(state, node).await? else {
+            return Ok(None);
+        };
+
+        let mut report = FileScanReport::unscanned(file, source, policy);
+
+        match crate::clamd::scan(&address, data).await {
+            Ok(crate::clamd::ScanVerdict::Clean) => report.verdict = FileScanVerdict::Clean,
+            Ok(crate::clamd::ScanVerdict::Infected(signature)) => {
+                report.verdict = FileScanVerdict::Infected;
+                report.signature = Some(signature);
+            }
+            Err(err) if policy == super::location::FileScanPolicy::Block => return Err(err),
+            Err(err) => {
+                tracing::warn!(
+                    server = %server_uuid,
+                    "failed to scan file with clamd: {:?}",
+                    err
+                );
+
+                return Ok(Some(report));
+            }
+        }
+
+        if let Some(signature) = &report.signature
+            && policy == super::location::FileScanPolicy::Block
+        {
+            let uuid = uuid::Uuid::new_v4();
+
+            let options = CreateServerQuarantinedFileOptions {
+                uuid,
+                server_uuid,
+                user_uuid,
+                source,
+                path: file.into(),
+                size: data.len() as i64,
+                signature: signature.chars().take(255).collect(),
+                sha256: hex::encode(sha2::Sha256::digest(data)).into(),
+            };
+            Self::create(state, options).await?;
+
+            report.quarantined = Some(uuid);
+        }
+
+        Ok(Some(report))
+    }
+
+    pub async fn by_server_uuid_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_quarantined_files
+            WHERE server_quarantined_files.server_uuid = $1 AND server_quarantined_files.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_server_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_quarantined_files
+            WHERE server_quarantined_files.server_uuid = $1
+                AND ($2 IS NULL OR server_quarantined_files.path ILIKE '%' || $2 || '%')
+            ORDER BY server_quarantined_files.created DESC
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn all_older_than(
+        database: &crate::database::Database,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_quarantined_files
+            WHERE server_quarantined_files.created < $1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(cutoff.naive_utc())
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerQuarantinedFile {
+    type ApiObject = ApiServerQuarantinedFile;
+    type ExtraArgs<'a> = &'a crate::storage::StorageUrlRetriever<'a>;
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        storage_url_retriever: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiServerQuarantinedFile::init_hooks(&self, state).await?;
+
+        let user = if let Some(user) = self.user {
+            Some(
+                user.fetch_cached(&state.database)
+                    .await?
+                    .into_api_object(state, storage_url_retriever)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        let api_object = finish_extendible!(
+            ApiServerQuarantinedFile {
+                uuid: self.uuid,
+                user,
+                source: self.source,
+                path: self.path,
+                size: self.size,
+                signature: self.signature,
+                sha256: self.sha256,
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerQuarantinedFileOptions {
+    #[garde(skip)]
+    pub uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub server_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub user_uuid: Option<uuid::Uuid>,
+
+    #[garde(skip)]
+    pub source: FileScanSource,
+    #[garde(length(chars, min = 1))]
+    #[schema(min_length = 1)]
+    pub path: compact_str::CompactString,
+    #[garde(skip)]
+    pub size: i64,
+    #[garde(length(chars, min = 1, max = 255))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub signature: compact_str::CompactString,
+    #[garde(length(min = 64, max = 64))]
+    #[schema(min_length = 64, max_length = 64)]
+    pub sha256: compact_str::CompactString,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerQuarantinedFile {
+    type CreateOptions<'a> = CreateServerQuarantinedFileOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerQuarantinedFile>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_quarantined_files");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set("uuid", options.uuid)
+            .set("server_uuid", options.server_uuid)
+            .set("user_uuid", options.user_uuid)
+            .set("source", options.source)
+            .set("path", &options.path)
+            .set("size", options.size)
+            .set("signature", &options.signature)
+            .set("sha256", &options.sha256);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut quarantined_file = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut quarantined_file, &options, state, transaction)
+            .await?;
+
+        Ok(quarantined_file)
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for ServerQuarantinedFile {
+    type DeleteOptions = ();
+
+    fn get_delete_handlers() -> &'static LazyLock<DeleteHandlerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteHandlerList<ServerQuarantinedFile>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete_with_transaction(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), anyhow::Error> {
+        self.run_delete_handlers(&options, state, transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM server_quarantined_files
+            WHERE server_quarantined_files.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut **transaction)
+        .await?;
+
+        self.run_after_delete_handlers(&options, state, transaction)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+    ) -> Result<(), anyhow::Error> {
+        let mut transaction = state.database.write().begin().await?;
+        self.delete_with_transaction(state, options, &mut transaction)
+            .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerQuarantinedFile, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerQuarantinedFile")]
+pub struct ApiServerQuarantinedFile {
+    pub uuid: uuid::Uuid,
+    pub user: Option<super::user::ApiUser>,
+
+    pub source: FileScanSource,
+    pub path: compact_str::CompactString,
+    pub size: i64,
+    pub signature: compact_str::CompactString,
+    pub sha256: Option<compact_str::CompactString>,
+
+    pub created: chrono::DateTime<chrono::Utc>,
+}
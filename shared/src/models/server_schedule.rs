@@ -144,6 +144,27 @@ impl ServerSchedule {
         row.try_map(|row| Self::map(None, &row))
     }
 
+    pub async fn by_server_uuid_name(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        name: &str,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_schedules
+            WHERE server_schedules.server_uuid = $1 AND server_schedules.name = $2
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(name)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
     pub async fn by_server_uuid_with_pagination(
         database: &crate::database::Database,
         server_uuid: uuid::Uuid,
@@ -0,0 +1,340 @@
+use crate::{models::InsertQueryBuilder, prelude::*};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerTrashedFile {
+    pub uuid: uuid::Uuid,
+    pub server_uuid: uuid::Uuid,
+    pub user: Option<Fetchable<super::user::User>>,
+
+    pub path: compact_str::CompactString,
+    pub directory: bool,
+    pub size: i64,
+
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for ServerTrashedFile {
+    const NAME: &'static str = "server_trashed_file";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_trashed_files.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_trashed_files.server_uuid",
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            (
+                "server_trashed_files.user_uuid",
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            (
+                "server_trashed_files.path",
+                compact_str::format_compact!("{prefix}path"),
+            ),
+            (
+                "server_trashed_files.directory",
+                compact_str::format_compact!("{prefix}directory"),
+            ),
+            (
+                "server_trashed_files.size",
+                compact_str::format_compact!("{prefix}size"),
+            ),
+            (
+                "server_trashed_files.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server_uuid: row
+                .try_get(compact_str::format_compact!("{prefix}server_uuid").as_str())?,
+            user: super::user::User::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            path: row.try_get(compact_str::format_compact!("{prefix}path").as_str())?,
+            directory: row.try_get(compact_str::format_compact!("{prefix}directory").as_str())?,
+            size: row.try_get(compact_str::format_compact!("{prefix}size").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl ServerTrashedFile {
+    pub const UNSUPPORTED_ERROR: &'static str =
+        "the node of this server does not support the file trash";
+
+    pub async fn by_server_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_trashed_files
+            WHERE server_trashed_files.server_uuid = $1
+                AND ($2 IS NULL OR server_trashed_files.path ILIKE '%' || $2 || '%')
+            ORDER BY server_trashed_files.created DESC
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn by_server_uuid_uuids(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        uuids: &[uuid::Uuid],
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_trashed_files
+            WHERE server_trashed_files.server_uuid = $1 AND server_trashed_files.uuid = ANY($2)
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .bind(uuids)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn all_by_server_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_trashed_files
+            WHERE server_trashed_files.server_uuid = $1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(server_uuid)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn all_older_than(
+        database: &crate::database::Database,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM server_trashed_files
+            WHERE server_trashed_files.created < $1
+            ORDER BY server_trashed_files.server_uuid
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(cutoff.naive_utc())
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn delete_by_uuids(
+        database: &crate::database::Database,
+        uuids: &[uuid::Uuid],
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_trashed_files
+            WHERE server_trashed_files.uuid = ANY($1)
+            "#,
+        )
+        .bind(uuids)
+        .execute(database.write())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for ServerTrashedFile {
+    type ApiObject = ApiServerTrashedFile;
+    type ExtraArgs<'a> = &'a crate::storage::StorageUrlRetriever<'a>;
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        storage_url_retriever: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiServerTrashedFile::init_hooks(&self, state).await?;
+
+        let user = if let Some(user) = self.user {
+            Some(
+                user.fetch_cached(&state.database)
+                    .await?
+                    .into_api_object(state, storage_url_retriever)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        let api_object = finish_extendible!(
+            ApiServerTrashedFile {
+                uuid: self.uuid,
+                user,
+                path: self.path,
+                directory: self.directory,
+                size: self.size,
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateServerTrashedFileOptions {
+    #[garde(skip)]
+    pub uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub server_uuid: uuid::Uuid,
+    #[garde(skip)]
+    pub user_uuid: Option<uuid::Uuid>,
+
+    #[garde(length(chars, min = 1))]
+    #[schema(min_length = 1)]
+    pub path: compact_str::CompactString,
+    #[garde(skip)]
+    pub directory: bool,
+    #[garde(skip)]
+    pub size: i64,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for ServerTrashedFile {
+    type CreateOptions<'a> = CreateServerTrashedFileOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<ServerTrashedFile>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("server_trashed_files");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set("uuid", options.uuid)
+            .set("server_uuid", options.server_uuid)
+            .set("user_uuid", options.user_uuid)
+            .set("path", &options.path)
+            .set("directory", options.directory)
+            .set("size", options.size);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut trashed_file = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut trashed_file, &options, state, transaction).await?;
+
+        Ok(trashed_file)
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(ServerTrashedFile, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerTrashedFile")]
+pub struct ApiServerTrashedFile {
+    pub uuid: uuid::Uuid,
+    pub user: Option<super::user::ApiUser>,
+
+    pub path: compact_str::CompactString,
+    pub directory: bool,
+    pub size: i64,
+
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,471 @@
+use crate::{
+    models::{InsertQueryBuilder, UpdateQueryBuilder},
+    prelude::*,
+};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SshCertificateAuthority {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub description: Option<compact_str::CompactString>,
+
+    pub fingerprint: compact_str::CompactString,
+    pub enabled: bool,
+
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for SshCertificateAuthority {
+    const NAME: &'static str = "ssh_certificate_authority";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "ssh_certificate_authorities.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "ssh_certificate_authorities.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "ssh_certificate_authorities.description",
+                compact_str::format_compact!("{prefix}description"),
+            ),
+            (
+                "ssh_certificate_authorities.fingerprint",
+                compact_str::format_compact!("{prefix}fingerprint"),
+            ),
+            (
+                "ssh_certificate_authorities.enabled",
+                compact_str::format_compact!("{prefix}enabled"),
+            ),
+            (
+                "ssh_certificate_authorities.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            description: row
+                .try_get(compact_str::format_compact!("{prefix}description").as_str())?,
+            fingerprint: row
+                .try_get(compact_str::format_compact!("{prefix}fingerprint").as_str())?,
+            enabled: row.try_get(compact_str::format_compact!("{prefix}enabled").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl SshCertificateAuthority {
+    pub async fn by_fingerprint_enabled(
+        database: &crate::database::Database,
+        fingerprint: &str,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM ssh_certificate_authorities
+            WHERE ssh_certificate_authorities.fingerprint = $1 AND ssh_certificate_authorities.enabled
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(fingerprint)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn all_with_pagination(
+        database: &crate::database::Database,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM ssh_certificate_authorities
+            WHERE ($1 IS NULL OR ssh_certificate_authorities.name ILIKE '%' || $1 || '%')
+            ORDER BY ssh_certificate_authorities.created
+            LIMIT $2 OFFSET $3
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    /// Checks an OpenSSH user certificate signed by this authority for a login as `username`.
+    ///
+    /// Returns a human readable reason when the certificate must be rejected.
+    pub fn verify_certificate(
+        &self,
+        certificate: &russh::keys::Certificate,
+        username: &str,
+        ip: Option<std::net::IpAddr>,
+    ) -> Result<(), &'static str> {
+        if certificate.cert_type() != russh::keys::ssh_key::certificate::CertType::User {
+            return Err("certificate is not a user certificate");
+        }
+
+        let fingerprint = certificate
+            .signature_key()
+            .fingerprint(russh::keys::HashAlg::Sha256);
+        if fingerprint.to_string() != self.fingerprint {
+            return Err("certificate is not signed by this authority");
+        }
+
+        // also covers the signature and the valid_after / valid_before window
+        if certificate
+            .validate_at(chrono::Utc::now().timestamp() as u64, [&fingerprint])
+            .is_err()
+        {
+            return Err("certificate is invalid or expired");
+        }
+
+        if !certificate
+            .valid_principals()
+            .iter()
+            .any(|principal| principal.eq_ignore_ascii_case(username))
+        {
+            return Err("certificate is not valid for this user");
+        }
+
+        for (option, value) in certificate.critical_options().iter() {
+            match option.as_str() {
+                "source-address" => {
+                    let Some(ip) = ip else {
+                        return Err("certificate requires a source address");
+                    };
+
+                    let allowed = value.split(',').any(|network| {
+                        network
+                            .trim()
+                            .parse::<sqlx::types::ipnetwork::IpNetwork>()
+                            .is_ok_and(|network| network.contains(ip))
+                    });
+                    if !allowed {
+                        return Err("certificate is not valid from this source address");
+                    }
+                }
+                "force-command" if value == "internal-sftp" => {}
+                _ => return Err("certificate has unsupported critical options"),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoAdminApiObject for SshCertificateAuthority {
+    type AdminApiObject = AdminApiSshCertificateAuthority;
+    type ExtraArgs<'a> = ();
+
+    async fn into_admin_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::AdminApiObject, crate::database::DatabaseError> {
+        let api_object = AdminApiSshCertificateAuthority::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            AdminApiSshCertificateAuthority {
+                uuid: self.uuid,
+                name: self.name,
+                description: self.description,
+                fingerprint: self.fingerprint,
+                enabled: self.enabled,
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[async_trait::async_trait]
+impl ByUuid for SshCertificateAuthority {
+    async fn by_uuid(
+        database: &crate::database::Database,
+        uuid: uuid::Uuid,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM ssh_certificate_authorities
+            WHERE ssh_certificate_authorities.uuid = $1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(uuid)
+        .fetch_one(database.read())
+        .await?;
+
+        Self::map(None, &row)
+    }
+
+    async fn by_uuid_with_transaction(
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        uuid: uuid::Uuid,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM ssh_certificate_authorities
+            WHERE ssh_certificate_authorities.uuid = $1
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(uuid)
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        Self::map(None, &row)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateSshCertificateAuthorityOptions {
+    #[garde(length(chars, min = 1, max = 255))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub name: compact_str::CompactString,
+    #[garde(length(chars, min = 1, max = 1024))]
+    #[schema(min_length = 1, max_length = 1024)]
+    pub description: Option<compact_str::CompactString>,
+
+    #[garde(skip)]
+    #[schema(value_type = String)]
+    #[serde(deserialize_with = "crate::deserialize::deserialize_public_key")]
+    pub public_key: russh::keys::PublicKey,
+    #[garde(skip)]
+    pub enabled: bool,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for SshCertificateAuthority {
+    type CreateOptions<'a> = CreateSshCertificateAuthorityOptions;
+    type CreateResult = Self;
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<SshCertificateAuthority>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = InsertQueryBuilder::new("ssh_certificate_authorities");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set("name", &options.name)
+            .set("description", &options.description)
+            .set(
+                "fingerprint",
+                options
+                    .public_key
+                    .fingerprint(russh::keys::HashAlg::Sha256)
+                    .to_string(),
+            )
+            .set(
+                "public_key",
+                options.public_key.to_bytes().map_err(anyhow::Error::from)?,
+            )
+            .set("enabled", options.enabled);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let mut certificate_authority = Self::map(None, &row)?;
+
+        Self::run_after_create_handlers(&mut certificate_authority, &options, state, transaction)
+            .await?;
+
+        Ok(certificate_authority)
+    }
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Clone, Default)]
+pub struct UpdateSshCertificateAuthorityOptions {
+    #[garde(length(chars, min = 1, max = 255))]
+    #[schema(min_length = 1, max_length = 255)]
+    pub name: Option<compact_str::CompactString>,
+    #[garde(length(chars, min = 1, max = 1024))]
+    #[schema(min_length = 1, max_length = 1024)]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    pub description: Option<Option<compact_str::CompactString>>,
+    #[garde(skip)]
+    pub enabled: Option<bool>,
+}
+
+#[async_trait::async_trait]
+impl UpdatableModel for SshCertificateAuthority {
+    type UpdateOptions = UpdateSshCertificateAuthorityOptions;
+
+    fn get_update_handlers() -> &'static LazyLock<UpdateHandlerList<Self>> {
+        static UPDATE_LISTENERS: LazyLock<UpdateHandlerList<SshCertificateAuthority>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &UPDATE_LISTENERS
+    }
+
+    async fn update_with_transaction(
+        &mut self,
+        state: &crate::State,
+        mut options: Self::UpdateOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = UpdateQueryBuilder::new("ssh_certificate_authorities");
+
+        self.run_update_handlers(&mut options, &mut query_builder, state, transaction)
+            .await?;
+
+        query_builder
+            .set("name", options.name.as_ref())
+            .set(
+                "description",
+                options.description.as_ref().map(|d| d.as_ref()),
+            )
+            .set("enabled", options.enabled)
+            .where_eq("uuid", self.uuid);
+
+        query_builder.execute(&mut **transaction).await?;
+
+        if let Some(name) = options.name {
+            self.name = name;
+        }
+        if let Some(description) = options.description {
+            self.description = description;
+        }
+        if let Some(enabled) = options.enabled {
+            self.enabled = enabled;
+        }
+
+        self.run_after_update_handlers(state, transaction).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for SshCertificateAuthority {
+    type DeleteOptions = ();
+
+    fn get_delete_handlers() -> &'static LazyLock<DeleteHandlerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteHandlerList<SshCertificateAuthority>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete_with_transaction(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), anyhow::Error> {
+        self.run_delete_handlers(&options, state, transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM ssh_certificate_authorities
+            WHERE ssh_certificate_authorities.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut **transaction)
+        .await?;
+
+        self.run_after_delete_handlers(&options, state, transaction)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(SshCertificateAuthority, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "SshCertificateAuthority")]
+pub struct AdminApiSshCertificateAuthority {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub description: Option<compact_str::CompactString>,
+
+    pub fingerprint: compact_str::CompactString,
+    pub enabled: bool,
+
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -26,6 +26,7 @@ pub struct SystemBackupPolicy {
     pub retention_count: Option<i32>,
     pub retention_days: Option<i32>,
     pub parallelism: i32,
+    pub include_databases: bool,
 
     pub triggered: Option<chrono::NaiveDateTime>,
     pub created: chrono::NaiveDateTime,
@@ -88,6 +89,10 @@ impl BaseModel for SystemBackupPolicy {
                 "system_backup_policies.parallelism",
                 compact_str::format_compact!("{prefix}parallelism"),
             ),
+            (
+                "system_backup_policies.include_databases",
+                compact_str::format_compact!("{prefix}include_databases"),
+            ),
             (
                 "system_backup_policies.triggered",
                 compact_str::format_compact!("{prefix}triggered"),
@@ -124,6 +129,8 @@ impl BaseModel for SystemBackupPolicy {
                 .try_get(compact_str::format_compact!("{prefix}retention_days").as_str())?,
             parallelism: row
                 .try_get(compact_str::format_compact!("{prefix}parallelism").as_str())?,
+            include_databases: row
+                .try_get(compact_str::format_compact!("{prefix}include_databases").as_str())?,
             triggered: row.try_get(compact_str::format_compact!("{prefix}triggered").as_str())?,
             created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
             extension_data: Self::map_extensions(prefix, row)?,
@@ -388,6 +395,7 @@ impl IntoAdminApiObject for SystemBackupPolicy {
                 retention_count: self.retention_count,
                 retention_days: self.retention_days,
                 parallelism: self.parallelism,
+                include_databases: self.include_databases,
                 triggered: self.triggered.map(|dt| dt.and_utc()),
                 total_nodes,
                 total_locations,
@@ -468,6 +476,9 @@ pub struct CreateSystemBackupPolicyOptions {
     #[garde(range(min = 1, max = 100))]
     #[schema(minimum = 1, maximum = 100)]
     pub parallelism: i32,
+    #[garde(skip)]
+    #[serde(default)]
+    pub include_databases: bool,
 }
 
 #[async_trait::async_trait]
@@ -515,7 +526,8 @@ impl CreatableModel for SystemBackupPolicy {
             .set("cron", options.cron.to_compact_string())
             .set("retention_count", options.retention_count)
             .set("retention_days", options.retention_days)
-            .set("parallelism", options.parallelism);
+            .set("parallelism", options.parallelism)
+            .set("include_databases", options.include_databases);
 
         let row = query_builder
             .returning(&Self::columns_sql(None))
@@ -573,6 +585,8 @@ pub struct UpdateSystemBackupPolicyOptions {
     #[garde(inner(range(min = 1, max = 100)))]
     #[schema(minimum = 1, maximum = 100)]
     pub parallelism: Option<i32>,
+    #[garde(skip)]
+    pub include_databases: Option<bool>,
 }
 
 #[async_trait::async_trait]
@@ -628,6 +642,7 @@ impl UpdatableModel for SystemBackupPolicy {
             .set("retention_count", options.retention_count)
             .set("retention_days", options.retention_days)
             .set("parallelism", options.parallelism)
+            .set("include_databases", options.include_databases)
             .where_eq("uuid", self.uuid);
 
         query_builder.execute(&mut **transaction).await?;
@@ -657,6 +672,9 @@ impl UpdatableModel for SystemBackupPolicy {
         if let Some(parallelism) = options.parallelism {
             self.parallelism = parallelism;
         }
+        if let Some(include_databases) = options.include_databases {
+            self.include_databases = include_databases;
+        }
 
         self.run_after_update_handlers(state, transaction).await?;
 
@@ -717,6 +735,7 @@ pub struct AdminApiSystemBackupPolicy {
     pub retention_count: Option<i32>,
     pub retention_days: Option<i32>,
     pub parallelism: i32,
+    pub include_databases: bool,
 
     pub triggered: Option<chrono::DateTime<chrono::Utc>>,
 
@@ -55,6 +55,7 @@ pub struct User {
     pub start_on_grouped_servers: bool,
 
     pub has_password: bool,
+    pub disable_sftp_password_login: bool,
 
     pub created: chrono::NaiveDateTime,
 
@@ -139,6 +140,10 @@ impl BaseModel for User {
                 "(users.password IS NOT NULL)",
                 compact_str::format_compact!("{prefix}has_password"),
             ),
+            (
+                "users.disable_sftp_password_login",
+                compact_str::format_compact!("{prefix}disable_sftp_password_login"),
+            ),
             (
                 "users.created",
                 compact_str::format_compact!("{prefix}created"),
@@ -190,6 +195,9 @@ impl BaseModel for User {
             )?,
             has_password: row
                 .try_get(compact_str::format_compact!("{prefix}has_password").as_str())?,
+            disable_sftp_password_login: row.try_get(
+                compact_str::format_compact!("{prefix}disable_sftp_password_login").as_str(),
+            )?,
             created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
             extension_data: Self::map_extensions(prefix, row)?,
         })
@@ -487,6 +495,42 @@ impl User {
         row.try_map(|row| Self::map(None, &row))
     }
 
+    pub async fn by_username_sftp_password(
+        database: &crate::database::Database,
+        username: &str,
+        password: &str,
+    ) -> Result<Option<(Self, super::user_sftp_password::UserSftpPassword)>, anyhow::Error> {
+        let Some(password_start) = password.get(0..16) else {
+            return Ok(None);
+        };
+
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, {}
+            FROM users
+            LEFT JOIN roles ON roles.uuid = users.role_uuid
+            JOIN user_sftp_passwords ON user_sftp_passwords.user_uuid = users.uuid
+            WHERE lower(users.username) = lower($1)
+                AND user_sftp_passwords.password_start = $2
+                AND user_sftp_passwords.password = crypt($3, user_sftp_passwords.password)
+            "#,
+            Self::columns_sql(None),
+            super::user_sftp_password::UserSftpPassword::columns_sql(Some("sftp_password_"))
+        )))
+        .bind(username)
+        .bind(password_start)
+        .bind(password)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| {
+            Ok::<_, anyhow::Error>((
+                Self::map(None, &row)?,
+                super::user_sftp_password::UserSftpPassword::map(Some("sftp_password_"), &row)?,
+            ))
+        })
+    }
+
     pub async fn by_role_uuid_with_pagination(
         database: &crate::database::Database,
         role_uuid: uuid::Uuid,
@@ -728,6 +772,7 @@ impl User {
                 toast_position: self.toast_position,
                 start_on_grouped_servers: self.start_on_grouped_servers,
                 has_password: self.has_password,
+                disable_sftp_password_login: self.disable_sftp_password_login,
                 created: self.created.and_utc(),
             },
             api_object,
@@ -812,6 +857,7 @@ impl IntoAdminApiObject for User {
                 toast_position: self.toast_position,
                 start_on_grouped_servers: self.start_on_grouped_servers,
                 has_password: self.has_password,
+                disable_sftp_password_login: self.disable_sftp_password_login,
                 created: self.created.and_utc(),
             },
             api_object,
@@ -1021,6 +1067,8 @@ pub struct UpdateUserOptions {
     pub toast_position: Option<UserToastPosition>,
     #[garde(skip)]
     pub start_on_grouped_servers: Option<bool>,
+    #[garde(skip)]
+    pub disable_sftp_password_login: Option<bool>,
 
     #[garde(skip)]
     pub admin: Option<bool>,
@@ -1088,6 +1136,10 @@ impl UpdatableModel for User {
             .set("language", options.language.as_ref())
             .set("toast_position", options.toast_position.as_ref())
             .set("start_on_grouped_servers", options.start_on_grouped_servers)
+            .set(
+                "disable_sftp_password_login",
+                options.disable_sftp_password_login,
+            )
             .where_eq("uuid", self.uuid);
 
         query_builder.execute(&mut **transaction).await?;
@@ -1116,6 +1168,9 @@ impl UpdatableModel for User {
         if let Some(start_on_grouped_servers) = options.start_on_grouped_servers {
             self.start_on_grouped_servers = start_on_grouped_servers;
         }
+        if let Some(disable_sftp_password_login) = options.disable_sftp_password_login {
+            self.disable_sftp_password_login = disable_sftp_password_login;
+        }
         if let Some(admin) = options.admin {
             self.admin = admin;
         }
@@ -1280,6 +1335,7 @@ pub struct ApiFullUser {
     pub start_on_grouped_servers: bool,
 
     pub has_password: bool,
+    pub disable_sftp_password_login: bool,
 
     pub created: chrono::DateTime<chrono::Utc>,
 }
@@ -1314,6 +1370,7 @@ pub struct AdminApiUser {
     pub start_on_grouped_servers: bool,
 
     pub has_password: bool,
+    pub disable_sftp_password_login: bool,
 
     pub created: chrono::DateTime<chrono::Utc>,
 }
@@ -0,0 +1,443 @@
+use crate::{
+    models::{InsertQueryBuilder, UpdateQueryBuilder},
+    prelude::*,
+};
+use garde::Validate;
+use rand::distr::SampleString;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+pub const SFTP_PASSWORD_PREFIX: &str = "c7sf_";
+
+const READ_ONLY_PERMISSIONS: &[&str] = &["files.read", "files.read-content", "files.sftp"];
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UserSftpPassword {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub password_start: compact_str::CompactString,
+
+    pub servers: Vec<uuid::Uuid>,
+    pub read_only: bool,
+
+    pub last_used: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+
+    extension_data: super::ModelExtensionData,
+}
+
+impl BaseModel for UserSftpPassword {
+    const NAME: &'static str = "user_sftp_password";
+
+    fn get_extension_list() -> &'static super::ModelExtensionList {
+        static EXTENSIONS: LazyLock<super::ModelExtensionList> =
+            LazyLock::new(|| parking_lot::RwLock::new(Vec::new()));
+
+        &EXTENSIONS
+    }
+
+    fn get_extension_data(&self) -> &super::ModelExtensionData {
+        &self.extension_data
+    }
+
+    #[inline]
+    fn base_columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "user_sftp_passwords.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "user_sftp_passwords.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "user_sftp_passwords.password_start",
+                compact_str::format_compact!("{prefix}password_start"),
+            ),
+            (
+                "user_sftp_passwords.servers",
+                compact_str::format_compact!("{prefix}servers"),
+            ),
+            (
+                "user_sftp_passwords.read_only",
+                compact_str::format_compact!("{prefix}read_only"),
+            ),
+            (
+                "user_sftp_passwords.last_used",
+                compact_str::format_compact!("{prefix}last_used"),
+            ),
+            (
+                "user_sftp_passwords.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            password_start: row
+                .try_get(compact_str::format_compact!("{prefix}password_start").as_str())?,
+            servers: row.try_get(compact_str::format_compact!("{prefix}servers").as_str())?,
+            read_only: row.try_get(compact_str::format_compact!("{prefix}read_only").as_str())?,
+            last_used: row.try_get(compact_str::format_compact!("{prefix}last_used").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+            extension_data: Self::map_extensions(prefix, row)?,
+        })
+    }
+}
+
+impl UserSftpPassword {
+    pub async fn by_user_uuid_uuid(
+        database: &crate::database::Database,
+        user_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}
+            FROM user_sftp_passwords
+            WHERE user_sftp_passwords.user_uuid = $1 AND user_sftp_passwords.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(user_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_user_uuid_with_pagination(
+        database: &crate::database::Database,
+        user_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(sqlx::AssertSqlSafe(format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM user_sftp_passwords
+            WHERE user_sftp_passwords.user_uuid = $1 AND ($2 IS NULL OR user_sftp_passwords.name ILIKE '%' || $2 || '%')
+            ORDER BY user_sftp_passwords.created
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        )))
+        .bind(user_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn count_by_user_uuid(
+        database: &crate::database::Database,
+        user_uuid: uuid::Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM user_sftp_passwords
+            WHERE user_sftp_passwords.user_uuid = $1
+            "#,
+        )
+        .bind(user_uuid)
+        .fetch_one(database.read())
+        .await
+    }
+
+    pub async fn update_last_used(&self, database: &Arc<crate::database::Database>) {
+        let uuid = self.uuid;
+        let now = chrono::Utc::now().naive_utc();
+
+        database
+            .batch_action("update_user_sftp_password", uuid, {
+                let database = database.clone();
+
+                async move {
+                    sqlx::query(
+                        "UPDATE user_sftp_passwords
+                        SET last_used = $2
+                        WHERE user_sftp_passwords.uuid = $1",
+                    )
+                    .bind(uuid)
+                    .bind(now)
+                    .execute(database.write())
+                    .await?;
+
+                    Ok(())
+                }
+            })
+            .await;
+    }
+
+    #[inline]
+    pub fn allows_server(&self, server_uuid: uuid::Uuid) -> bool {
+        self.servers.is_empty() || self.servers.contains(&server_uuid)
+    }
+
+    /// Strips every file permission that could modify the server when this password is read-only.
+    pub fn restrict_permissions(&self, permissions: &mut Vec<&str>) {
+        if !self.read_only {
+            return;
+        }
+
+        let has_star = permissions.contains(&"*");
+        permissions.retain(|permission| {
+            *permission != "*"
+                && (!permission.starts_with("files.")
+                    || READ_ONLY_PERMISSIONS.iter().any(|p| p == permission))
+        });
+
+        if has_star {
+            for permission in READ_ONLY_PERMISSIONS {
+                if !permissions.contains(permission) {
+                    permissions.push(*permission);
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl IntoApiObject for UserSftpPassword {
+    type ApiObject = ApiUserSftpPassword;
+    type ExtraArgs<'a> = ();
+
+    async fn into_api_object<'a>(
+        self,
+        state: &crate::State,
+        _args: Self::ExtraArgs<'a>,
+    ) -> Result<Self::ApiObject, crate::database::DatabaseError> {
+        let api_object = ApiUserSftpPassword::init_hooks(&self, state).await?;
+
+        let api_object = finish_extendible!(
+            ApiUserSftpPassword {
+                uuid: self.uuid,
+                name: self.name,
+                password_start: self.password_start,
+                servers: self.servers,
+                read_only: self.read_only,
+                last_used: self.last_used.map(|dt| dt.and_utc()),
+                created: self.created.and_utc(),
+            },
+            api_object,
+            state
+        )?;
+
+        Ok(api_object)
+    }
+}
+
+#[derive(ToSchema, Deserialize, Validate)]
+pub struct CreateUserSftpPasswordOptions {
+    #[garde(skip)]
+    pub user_uuid: uuid::Uuid,
+
+    #[garde(length(chars, min = 3, max = 31))]
+    #[schema(min_length = 3, max_length = 31)]
+    pub name: compact_str::CompactString,
+
+    #[garde(length(max = 100))]
+    #[schema(max_items = 100)]
+    pub servers: Vec<uuid::Uuid>,
+    #[garde(skip)]
+    pub read_only: bool,
+}
+
+#[async_trait::async_trait]
+impl CreatableModel for UserSftpPassword {
+    type CreateOptions<'a> = CreateUserSftpPasswordOptions;
+    type CreateResult = (String, Self);
+
+    fn get_create_handlers() -> &'static LazyLock<CreateListenerList<Self>> {
+        static CREATE_LISTENERS: LazyLock<CreateListenerList<UserSftpPassword>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &CREATE_LISTENERS
+    }
+
+    async fn create_with_transaction(
+        state: &crate::State,
+        mut options: Self::CreateOptions<'_>,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Self::CreateResult, crate::database::DatabaseError> {
+        options.validate()?;
+
+        let password = format!(
+            "{SFTP_PASSWORD_PREFIX}{}",
+            rand::distr::Alphanumeric.sample_string(&mut rand::rng(), 43)
+        );
+
+        let mut query_builder = InsertQueryBuilder::new("user_sftp_passwords");
+
+        Self::run_create_handlers(&mut options, &mut query_builder, state, transaction).await?;
+
+        query_builder
+            .set("user_uuid", options.user_uuid)
+            .set("name", &options.name)
+            .set("password_start", &password[0..16])
+            .set_expr("password", "crypt($1, gen_salt('bf', 12))", vec![&password])
+            .set("servers", &options.servers)
+            .set("read_only", options.read_only);
+
+        let row = query_builder
+            .returning(&Self::columns_sql(None))
+            .fetch_one(&mut **transaction)
+            .await?;
+        let sftp_password = Self::map(None, &row)?;
+
+        let mut result = (password, sftp_password);
+
+        Self::run_after_create_handlers(&mut result, &options, state, transaction).await?;
+
+        Ok(result)
+    }
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Default)]
+pub struct UpdateUserSftpPasswordOptions {
+    #[garde(length(chars, min = 3, max = 31))]
+    #[schema(min_length = 3, max_length = 31)]
+    pub name: Option<compact_str::CompactString>,
+
+    #[garde(length(max = 100))]
+    #[schema(max_items = 100)]
+    pub servers: Option<Vec<uuid::Uuid>>,
+    #[garde(skip)]
+    pub read_only: Option<bool>,
+}
+
+#[async_trait::async_trait]
+impl UpdatableModel for UserSftpPassword {
+    type UpdateOptions = UpdateUserSftpPasswordOptions;
+
+    fn get_update_handlers() -> &'static LazyLock<UpdateHandlerList<Self>> {
+        static UPDATE_LISTENERS: LazyLock<UpdateHandlerList<UserSftpPassword>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &UPDATE_LISTENERS
+    }
+
+    async fn update_with_transaction(
+        &mut self,
+        state: &crate::State,
+        mut options: Self::UpdateOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), crate::database::DatabaseError> {
+        options.validate()?;
+
+        let mut query_builder = UpdateQueryBuilder::new("user_sftp_passwords");
+
+        self.run_update_handlers(&mut options, &mut query_builder, state, transaction)
+            .await?;
+
+        query_builder
+            .set("name", options.name.as_ref())
+            .set("servers", options.servers.as_ref())
+            .set("read_only", options.read_only)
+            .where_eq("uuid", self.uuid);
+
+        query_builder.execute(&mut **transaction).await?;
+
+        if let Some(name) = options.name {
+            self.name = name;
+        }
+        if let Some(servers) = options.servers {
+            self.servers = servers;
+        }
+        if let Some(read_only) = options.read_only {
+            self.read_only = read_only;
+        }
+
+        self.run_after_update_handlers(state, transaction).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for UserSftpPassword {
+    type DeleteOptions = ();
+
+    fn get_delete_handlers() -> &'static LazyLock<DeleteHandlerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteHandlerList<UserSftpPassword>> =
+            LazyLock::new(|| Arc::new(ModelHandlerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete_with_transaction(
+        &self,
+        state: &crate::State,
+        options: Self::DeleteOptions,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(), anyhow::Error> {
+        self.run_delete_handlers(&options, state, transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM user_sftp_passwords
+            WHERE user_sftp_passwords.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut **transaction)
+        .await?;
+
+        self.run_after_delete_handlers(&options, state, transaction)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[schema_extension_derive::extendible]
+#[init_args(UserSftpPassword, crate::State)]
+#[hook_args(crate::State)]
+#[derive(ToSchema, Serialize)]
+#[schema(title = "UserSftpPassword")]
+pub struct ApiUserSftpPassword {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub password_start: compact_str::CompactString,
+
+    pub servers: Vec<uuid::Uuid>,
+    pub read_only: bool,
+
+    pub last_used: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
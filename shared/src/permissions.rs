@@ -154,6 +154,18 @@ pub(crate) static BASE_USER_PERMISSIONS: LazyLock<IndexMap<&'static str, Permiss
                     ]),
                 },
             ),
+            (
+                "sftp-passwords",
+                PermissionGroup {
+                    description: "Permissions that control the ability to manage SFTP app passwords on an account.",
+                    permissions: IndexMap::from([
+                        ("create", "Allows generating new SFTP app passwords."),
+                        ("read", "Allows viewing SFTP app passwords."),
+                        ("update", "Allows modifying SFTP app passwords."),
+                        ("delete", "Allows revoking SFTP app passwords."),
+                    ]),
+                },
+            ),
             (
                 "oauth-links",
                 PermissionGroup {
@@ -562,6 +574,21 @@ pub(crate) static BASE_ADMIN_PERMISSIONS: LazyLock<IndexMap<&'static str, Permis
                     ]),
                 },
             ),
+            (
+                "ssh-certificate-authorities",
+                PermissionGroup {
+                    description: "Permissions that control the ability to manage trusted SSH certificate authorities for SFTP logins.",
+                    permissions: IndexMap::from([
+                        (
+                            "create",
+                            "Allows registering new SSH certificate authorities.",
+                        ),
+                        ("read", "Allows viewing SSH certificate authorities."),
+                        ("update", "Allows modifying SSH certificate authorities."),
+                        ("delete", "Allows deleting SSH certificate authorities."),
+                    ]),
+                },
+            ),
             (
                 "activity",
                 PermissionGroup {
@@ -651,6 +678,10 @@ pub(crate) static BASE_SERVER_PERMISSIONS: LazyLock<IndexMap<&'static str, Permi
                             "archive",
                             "Allows archiving the contents of a directory and decompressing files.",
                         ),
+                        (
+                            "share",
+                            "Allows creating and revoking public download links for files and directories.",
+                        ),
                         ("sftp", "Allows connecting via SFTP to manage files."),
                         (
                             "query-raw",
@@ -697,6 +728,28 @@ pub(crate) static BASE_SERVER_PERMISSIONS: LazyLock<IndexMap<&'static str, Permi
                     ]),
                 },
             ),
+            (
+                "deployments",
+                PermissionGroup {
+                    description: "Permissions that control the ability to deploy files from git repositories to this server.",
+                    permissions: IndexMap::from([
+                        ("create", "Allows creating new deployment sources."),
+                        (
+                            "read",
+                            "Allows viewing deployment sources and their deployment history.",
+                        ),
+                        (
+                            "update",
+                            "Allows updating deployment sources and rotating their webhook tokens.",
+                        ),
+                        ("delete", "Allows deleting deployment sources."),
+                        (
+                            "deploy",
+                            "Allows manually triggering a deployment, which overwrites files in the target directory.",
+                        ),
+                    ]),
+                },
+            ),
             (
                 "allocations",
                 PermissionGroup {
@@ -785,6 +838,10 @@ pub(crate) static BASE_SERVER_PERMISSIONS: LazyLock<IndexMap<&'static str, Permi
                             "delete-structure",
                             "Allows deleting tables and columns through the panel, permanently destroying any data they contain.",
                         ),
+                        (
+                            "access-tokens",
+                            "Allows creating, viewing and revoking time-limited read-only credentials for a database, for use by external tools.",
+                        ),
                     ]),
                 },
             ),
@@ -829,6 +886,14 @@ pub(crate) static BASE_SERVER_PERMISSIONS: LazyLock<IndexMap<&'static str, Permi
                         ),
                         ("import", "Allows importing data into database instances."),
                         ("export", "Allows exporting data from database instances."),
+                        (
+                            "snapshots",
+                            "Allows viewing, creating and deleting snapshots of database instances and managing their snapshot policies.",
+                        ),
+                        (
+                            "restore-snapshots",
+                            "Allows restoring database instances from snapshots, replacing the data they currently hold.",
+                        ),
                         (
                             "query",
                             "Allows browsing an instance database's tables and reading their rows through the panel.",
@@ -849,6 +914,10 @@ pub(crate) static BASE_SERVER_PERMISSIONS: LazyLock<IndexMap<&'static str, Permi
                             "delete-structure",
                             "Allows deleting tables and columns through the panel, permanently destroying any data they contain.",
                         ),
+                        (
+                            "access-tokens",
+                            "Allows creating, viewing and revoking time-limited read-only users scoped to a single instance database, for use by external tools.",
+                        ),
                     ]),
                 },
             ),
@@ -882,6 +951,10 @@ pub(crate) static BASE_SERVER_PERMISSIONS: LazyLock<IndexMap<&'static str, Permi
                             "Allows changing the server's auto-start settings.",
                         ),
                         ("install", "Allows triggering a reinstall of the server."),
+                        (
+                            "console-archive",
+                            "Allows enabling or disabling console log archiving for the server.",
+                        ),
                         (
                             "cancel-install",
                             "Allows canceling the server's installation process.",
@@ -899,6 +972,10 @@ pub(crate) static BASE_SERVER_PERMISSIONS: LazyLock<IndexMap<&'static str, Permi
                             "read-ip",
                             "Allows viewing IP addresses associated with activity logs.",
                         ),
+                        (
+                            "read-files",
+                            "Allows viewing the server's file change audit log, including changes made over SFTP.",
+                        ),
                     ]),
                 },
             ),
@@ -14,6 +14,8 @@ pub struct AppSettingsActivity {
     pub user_log_retention_count: Option<u64>,
     pub server_log_retention_days: u16,
     pub server_log_retention_count: Option<u64>,
+    pub server_file_log_retention_days: u16,
+    pub server_file_log_retention_count: Option<u64>,
 
     pub server_log_admin_activity: bool,
     pub server_log_schedule_activity: bool,
@@ -53,6 +55,15 @@ impl SettingsSerializeExt for AppSettingsActivity {
                 self.server_log_retention_count
                     .map_or("".into(), |c| c.to_compact_string()),
             )
+            .write_raw_setting(
+                "server_file_log_retention_days",
+                self.server_file_log_retention_days.to_compact_string(),
+            )
+            .write_raw_setting(
+                "server_file_log_retention_count",
+                self.server_file_log_retention_count
+                    .map_or("".into(), |c| c.to_compact_string()),
+            )
             .write_raw_setting(
                 "server_log_admin_activity",
                 self.server_log_admin_activity.to_compact_string(),
@@ -94,6 +105,13 @@ impl SettingsDeserializeExt for AppSettingsActivityDeserializer {
             server_log_retention_count: deserializer
                 .take_raw_setting("server_log_retention_count")
                 .and_then(|s| s.parse().ok()),
+            server_file_log_retention_days: deserializer
+                .take_raw_setting("server_file_log_retention_days")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
+            server_file_log_retention_count: deserializer
+                .take_raw_setting("server_file_log_retention_count")
+                .and_then(|s| s.parse().ok()),
             server_log_admin_activity: deserializer
                 .take_raw_setting("server_log_admin_activity")
                 .map(|s| s == "true")
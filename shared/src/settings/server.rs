@@ -1,3 +1,5 @@
+use crate::prelude::StringExt;
+
 use super::{
     ExtensionSettings, SettingsDeserializeExt, SettingsDeserializer, SettingsSerializeExt,
     SettingsSerializer,
@@ -16,6 +18,20 @@ pub struct AppSettingsServer {
     pub max_backup_group_count: u64,
     pub max_database_instance_database_count: u64,
     pub max_database_instance_user_count: u64,
+    pub max_database_saved_query_count: u64,
+    pub max_database_instance_snapshot_policy_count: u64,
+    pub max_file_share_count: u64,
+    pub max_deployment_source_count: u64,
+
+    pub database_query_history_retention_days: u16,
+    pub database_query_history_retention_count: u64,
+    pub file_trash_retention_days: u16,
+    pub console_log_archive_retention_days: u16,
+    pub max_console_log_archive_size: u64,
+
+    pub file_scan_clamd_address: Option<compact_str::CompactString>,
+    pub max_file_scan_size: u64,
+    pub file_quarantine_retention_days: u16,
 
     pub allow_overwriting_custom_docker_image: bool,
     pub allow_viewing_installation_logs: bool,
@@ -66,6 +82,57 @@ impl SettingsSerializeExt for AppSettingsServer {
                 "max_database_instance_user_count",
                 self.max_database_instance_user_count.to_compact_string(),
             )
+            .write_raw_setting(
+                "max_database_saved_query_count",
+                self.max_database_saved_query_count.to_compact_string(),
+            )
+            .write_raw_setting(
+                "max_database_instance_snapshot_policy_count",
+                self.max_database_instance_snapshot_policy_count
+                    .to_compact_string(),
+            )
+            .write_raw_setting(
+                "max_file_share_count",
+                self.max_file_share_count.to_compact_string(),
+            )
+            .write_raw_setting(
+                "max_deployment_source_count",
+                self.max_deployment_source_count.to_compact_string(),
+            )
+            .write_raw_setting(
+                "database_query_history_retention_days",
+                self.database_query_history_retention_days
+                    .to_compact_string(),
+            )
+            .write_raw_setting(
+                "database_query_history_retention_count",
+                self.database_query_history_retention_count
+                    .to_compact_string(),
+            )
+            .write_raw_setting(
+                "file_trash_retention_days",
+                self.file_trash_retention_days.to_compact_string(),
+            )
+            .write_raw_setting(
+                "console_log_archive_retention_days",
+                self.console_log_archive_retention_days.to_compact_string(),
+            )
+            .write_raw_setting(
+                "max_console_log_archive_size",
+                self.max_console_log_archive_size.to_compact_string(),
+            )
+            .write_raw_setting(
+                "file_scan_clamd_address",
+                self.file_scan_clamd_address.as_deref().unwrap_or(""),
+            )
+            .write_raw_setting(
+                "max_file_scan_size",
+                self.max_file_scan_size.to_compact_string(),
+            )
+            .write_raw_setting(
+                "file_quarantine_retention_days",
+                self.file_quarantine_retention_days.to_compact_string(),
+            )
             .write_raw_setting(
                 "allow_overwriting_custom_docker_image",
                 self.allow_overwriting_custom_docker_image
@@ -129,6 +196,53 @@ impl SettingsDeserializeExt for AppSettingsServerDeserializer {
                 .take_raw_setting("max_database_instance_user_count")
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(10),
+            max_database_saved_query_count: deserializer
+                .take_raw_setting("max_database_saved_query_count")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(50),
+            max_database_instance_snapshot_policy_count: deserializer
+                .take_raw_setting("max_database_instance_snapshot_policy_count")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(5),
+            max_file_share_count: deserializer
+                .take_raw_setting("max_file_share_count")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(25),
+            max_deployment_source_count: deserializer
+                .take_raw_setting("max_deployment_source_count")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(5),
+            database_query_history_retention_days: deserializer
+                .take_raw_setting("database_query_history_retention_days")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
+            database_query_history_retention_count: deserializer
+                .take_raw_setting("database_query_history_retention_count")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(100),
+            file_trash_retention_days: deserializer
+                .take_raw_setting("file_trash_retention_days")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0),
+            console_log_archive_retention_days: deserializer
+                .take_raw_setting("console_log_archive_retention_days")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
+            max_console_log_archive_size: deserializer
+                .take_raw_setting("max_console_log_archive_size")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(64 * 1024 * 1024),
+            file_scan_clamd_address: deserializer
+                .take_raw_setting("file_scan_clamd_address")
+                .and_then(|s| s.into_optional()),
+            max_file_scan_size: deserializer
+                .take_raw_setting("max_file_scan_size")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(100 * 1024 * 1024),
+            file_quarantine_retention_days: deserializer
+                .take_raw_setting("file_quarantine_retention_days")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
             allow_overwriting_custom_docker_image: deserializer
                 .take_raw_setting("allow_overwriting_custom_docker_image")
                 .map(|s| s == "true")
@@ -13,6 +13,7 @@ pub struct AppSettingsUser {
     pub max_command_snippet_count: u64,
     pub max_security_key_count: u64,
     pub max_ssh_key_count: u64,
+    pub max_sftp_password_count: u64,
 
     pub allow_changing_language: bool,
 
@@ -46,6 +47,10 @@ impl SettingsSerializeExt for AppSettingsUser {
                 "max_ssh_key_count",
                 self.max_ssh_key_count.to_compact_string(),
             )
+            .write_raw_setting(
+                "max_sftp_password_count",
+                self.max_sftp_password_count.to_compact_string(),
+            )
             .write_raw_setting(
                 "allow_changing_language",
                 self.allow_changing_language.to_compact_string(),
@@ -83,6 +88,10 @@ impl SettingsDeserializeExt for AppSettingsUserDeserializer {
                 .take_raw_setting("max_ssh_key_count")
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(50),
+            max_sftp_password_count: deserializer
+                .take_raw_setting("max_sftp_password_count")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(25),
             allow_changing_language: deserializer
                 .take_raw_setting("allow_changing_language")
                 .map(|s| s == "true")